@@ -0,0 +1,148 @@
+use std::sync::Mutex;
+
+/// State of a subscription's circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Events are handled normally.
+    Closed,
+    /// Too many consecutive failures; the consumer is cooling down.
+    Open,
+    /// The cooldown has passed. The next failure re-opens the circuit
+    /// straight away and the next success closes it.
+    HalfOpen,
+}
+
+/// Stops a consumer from hammering its retry queue when every event fails.
+///
+/// Handlers report each outcome; once `threshold` retryable failures
+/// happen in a row the circuit opens and the consumer loop pauses for a
+/// cooldown before trying again. A threshold of 0 disables the breaker.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    threshold: u32,
+    inner: Mutex<BreakerState>,
+}
+
+#[derive(Debug)]
+struct BreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+}
+
+impl CircuitBreaker {
+    /// Create a closed breaker that trips after `threshold` consecutive failures.
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            threshold,
+            inner: Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+            }),
+        }
+    }
+
+    /// Current state of the circuit.
+    pub fn state(&self) -> CircuitState {
+        self.lock().state
+    }
+
+    /// Number of retryable failures since the last success.
+    pub fn consecutive_failures(&self) -> u32 {
+        self.lock().consecutive_failures
+    }
+
+    /// Record a successfully handled event, closing the circuit.
+    pub fn record_success(&self) {
+        let mut inner = self.lock();
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+    }
+
+    /// Record a retryable failure.
+    ///
+    /// Returns true when this failure opened the circuit.
+    pub fn record_failure(&self) -> bool {
+        let mut inner = self.lock();
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+
+        if self.threshold == 0 || inner.state == CircuitState::Open {
+            return false;
+        }
+        if inner.state == CircuitState::HalfOpen || inner.consecutive_failures >= self.threshold {
+            inner.state = CircuitState::Open;
+            return true;
+        }
+        false
+    }
+
+    /// Move an open circuit to half-open once its cooldown is over.
+    pub fn end_cooldown(&self) {
+        let mut inner = self.lock();
+        if inner.state == CircuitState::Open {
+            inner.state = CircuitState::HalfOpen;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        // The state is always left consistent, so a poisoned lock is still usable
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consecutive_failures_trip_the_breaker() {
+        let breaker = CircuitBreaker::new(3);
+
+        assert!(!breaker.record_failure());
+        assert!(!breaker.record_failure());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.record_failure());
+        assert_eq!(breaker.state(), CircuitState::Open);
+        // Already open: further failures don't trip it again
+        assert!(!breaker.record_failure());
+        assert_eq!(breaker.consecutive_failures(), 4);
+    }
+
+    #[test]
+    fn test_success_resets_the_failure_count() {
+        let breaker = CircuitBreaker::new(3);
+
+        breaker.record_failure();
+        breaker.record_failure();
+        breaker.record_success();
+        assert_eq!(breaker.consecutive_failures(), 0);
+
+        assert!(!breaker.record_failure());
+        assert!(!breaker.record_failure());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_retrips_on_first_failure() {
+        let breaker = CircuitBreaker::new(2);
+        breaker.record_failure();
+        breaker.record_failure();
+        breaker.end_cooldown();
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        assert!(breaker.record_failure());
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        breaker.end_cooldown();
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_zero_threshold_never_trips() {
+        let breaker = CircuitBreaker::new(0);
+        for _ in 0..100 {
+            assert!(!breaker.record_failure());
+        }
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use std::sync::Mutex;
+
+/// Source of the current time.
+///
+/// Anything that stamps or compares timestamps (envelope creation, age
+/// checks, service windows) asks a `Clock` instead of calling
+/// `Utc::now()` directly, so tests can swap in a `MockClock` and drive
+/// time forward without sleeping.
+pub trait Clock: Send + Sync {
+    /// The current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Clock backed by the system's wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock that only moves when told to.
+///
+/// # Example
+/// ```
+/// use common::clock::{Clock, MockClock};
+///
+/// let clock = MockClock::new(chrono::Utc::now());
+/// let start = clock.now();
+/// clock.advance(chrono::Duration::minutes(10));
+/// assert_eq!(clock.now() - start, chrono::Duration::minutes(10));
+/// ```
+#[derive(Debug)]
+pub struct MockClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl MockClock {
+    /// Create a clock frozen at `now`.
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self { now: Mutex::new(now) }
+    }
+
+    /// Jump to `now`.
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.lock() = now;
+    }
+
+    /// Move the clock forward by `by` (backwards if negative).
+    pub fn advance(&self, by: chrono::Duration) {
+        *self.lock() += by;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, DateTime<Utc>> {
+        // A timestamp can't be left half-written, so a poisoned lock is still usable
+        self.now.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.lock()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_only_moves_when_told() {
+        let start = Utc::now();
+        let clock = MockClock::new(start);
+        assert_eq!(clock.now(), start);
+
+        clock.advance(chrono::Duration::seconds(90));
+        assert_eq!(clock.now(), start + chrono::Duration::seconds(90));
+
+        clock.set(start);
+        assert_eq!(clock.now(), start);
+    }
+}
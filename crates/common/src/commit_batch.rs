@@ -0,0 +1,207 @@
+use crate::message_bus::SubscriptionConfig;
+use rdkafka::{error::KafkaResult, Offset, TopicPartitionList};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Offsets of handled messages waiting to be committed together.
+///
+/// Committing after every message costs a broker round trip each time. With
+/// batching enabled, the consumer loop records each handled message here and
+/// commits once `max_pending` have built up or `interval` has passed since
+/// the last commit, whichever comes first.
+///
+/// Kafka commits the next offset to read, so a message handled at offset N
+/// is committed as N + 1. Only the highest offset per partition is kept.
+/// Commits are cumulative, so once a message is held back (see
+/// `hold_back`) its partition is never committed past it.
+#[derive(Debug)]
+pub struct CommitBatch {
+    max_pending: usize,
+    interval: Option<Duration>,
+    offsets: HashMap<(String, i32), i64>,
+    /// Lowest offset per partition that was left unhandled
+    held: HashMap<(String, i32), i64>,
+    pending: usize,
+    last_commit: Instant,
+}
+
+impl CommitBatch {
+    /// Create an empty batch, due after `max_pending` messages (0 disables
+    /// that trigger) or once `interval` has passed.
+    pub fn new(max_pending: usize, interval: Option<Duration>) -> Self {
+        Self {
+            max_pending,
+            interval,
+            offsets: HashMap::new(),
+            held: HashMap::new(),
+            pending: 0,
+            last_commit: Instant::now(),
+        }
+    }
+
+    /// The batch for a subscription, or `None` when it commits every message.
+    pub fn from_config(config: &SubscriptionConfig) -> Option<Self> {
+        let interval = config.commit_interval();
+        (config.commit_batch_size > 0 || interval.is_some())
+            .then(|| Self::new(config.commit_batch_size, interval))
+    }
+
+    /// Record a handled message.
+    pub fn record(&mut self, topic: &str, partition: i32, offset: i64) {
+        let key = (topic.to_string(), partition);
+        let next = match self.held.get(&key) {
+            Some(&held) => (offset + 1).min(held),
+            None => offset + 1,
+        };
+        let committed = self.offsets.entry(key).or_insert(next);
+        *committed = (*committed).max(next);
+        self.pending += 1;
+    }
+
+    /// Record a message that wasn't handled and must be read again.
+    ///
+    /// Later messages on the partition are still counted, but its commits
+    /// stop at this offset so a restart redelivers it.
+    pub fn hold_back(&mut self, topic: &str, partition: i32, offset: i64) {
+        let held = self.held.entry((topic.to_string(), partition)).or_insert(offset);
+        *held = (*held).min(offset);
+    }
+
+    /// Handled messages recorded since the last commit.
+    pub fn pending(&self) -> usize {
+        self.pending
+    }
+
+    /// Offset that would be committed for a partition.
+    pub fn next_offset(&self, topic: &str, partition: i32) -> Option<i64> {
+        self.offsets.get(&(topic.to_string(), partition)).copied()
+    }
+
+    /// When the pending offsets are due by time, if anything is pending.
+    pub fn deadline(&self) -> Option<Instant> {
+        if self.pending == 0 {
+            return None;
+        }
+        self.interval.map(|interval| self.last_commit + interval)
+    }
+
+    /// Whether the pending offsets should be committed now.
+    pub fn is_due(&self, now: Instant) -> bool {
+        if self.pending == 0 {
+            return false;
+        }
+        (self.max_pending > 0 && self.pending >= self.max_pending)
+            || self.deadline().is_some_and(|deadline| now >= deadline)
+    }
+
+    /// Empty the batch, returning the offsets to commit.
+    ///
+    /// Returns `None` when nothing is pending.
+    pub fn take(&mut self) -> KafkaResult<Option<TopicPartitionList>> {
+        self.last_commit = Instant::now();
+        if self.pending == 0 {
+            return Ok(None);
+        }
+
+        let mut list = TopicPartitionList::new();
+        for ((topic, partition), offset) in self.offsets.drain() {
+            list.add_partition_offset(&topic, partition, Offset::Offset(offset))?;
+        }
+        self.pending = 0;
+        Ok(Some(list))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highest_offset_per_partition_is_committed() {
+        let mut batch = CommitBatch::new(10, None);
+        batch.record("orders", 0, 4);
+        batch.record("orders", 0, 6);
+        batch.record("orders", 0, 5);
+        batch.record("orders", 1, 0);
+
+        assert_eq!(batch.pending(), 4);
+        assert_eq!(batch.next_offset("orders", 0), Some(7));
+        assert_eq!(batch.next_offset("orders", 1), Some(1));
+        assert_eq!(batch.next_offset("orders", 2), None);
+
+        let list = batch.take().unwrap().expect("Should have offsets");
+        assert_eq!(list.find_partition("orders", 0).unwrap().offset(), Offset::Offset(7));
+        assert_eq!(list.find_partition("orders", 1).unwrap().offset(), Offset::Offset(1));
+        assert_eq!(list.count(), 2);
+
+        assert_eq!(batch.pending(), 0);
+        assert!(batch.take().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_held_back_message_is_never_committed_past() {
+        let mut batch = CommitBatch::new(10, None);
+        batch.record("orders", 0, 3);
+        batch.hold_back("orders", 0, 4);
+        batch.record("orders", 0, 5);
+        batch.record("orders", 1, 5);
+
+        assert_eq!(batch.next_offset("orders", 0), Some(4));
+        assert_eq!(batch.next_offset("orders", 1), Some(6));
+
+        // Still capped in later batches
+        batch.take().unwrap();
+        batch.record("orders", 0, 9);
+        assert_eq!(batch.next_offset("orders", 0), Some(4));
+    }
+
+    #[test]
+    fn test_due_after_max_pending_messages() {
+        let mut batch = CommitBatch::new(3, None);
+        let now = Instant::now();
+        assert!(!batch.is_due(now));
+
+        batch.record("orders", 0, 0);
+        batch.record("orders", 0, 1);
+        assert!(!batch.is_due(now));
+        batch.record("orders", 0, 2);
+        assert!(batch.is_due(now));
+
+        batch.take().unwrap();
+        assert!(!batch.is_due(now));
+        assert_eq!(batch.deadline(), None);
+    }
+
+    #[test]
+    fn test_due_once_interval_has_passed() {
+        let mut batch = CommitBatch::new(0, Some(Duration::from_secs(5)));
+        let start = Instant::now();
+
+        // Nothing pending, nothing due, however long it's been
+        assert_eq!(batch.deadline(), None);
+        assert!(!batch.is_due(start + Duration::from_secs(60)));
+
+        batch.record("orders", 0, 0);
+        let deadline = batch.deadline().expect("Should have a deadline");
+        assert!(deadline <= start + Duration::from_secs(5) + Duration::from_millis(100));
+        assert!(!batch.is_due(deadline - Duration::from_millis(1)));
+        assert!(batch.is_due(deadline));
+
+        // The size trigger is off, so a large backlog alone isn't due
+        for offset in 1..1000 {
+            batch.record("orders", 0, offset);
+        }
+        assert!(!batch.is_due(deadline - Duration::from_millis(1)));
+    }
+
+    #[test]
+    fn test_from_config_only_batches_when_enabled() {
+        assert!(CommitBatch::from_config(&SubscriptionConfig::default()).is_none());
+
+        let config = SubscriptionConfig::builder().commit_batch_size(50).build();
+        assert!(CommitBatch::from_config(&config).is_some());
+
+        let config = SubscriptionConfig::builder().commit_interval_ms(1000).build();
+        assert!(CommitBatch::from_config(&config).is_some());
+    }
+}
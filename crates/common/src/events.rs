@@ -1,4 +1,3 @@
-use crate::message_bus::Event;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -15,15 +14,13 @@ pub struct MessageReceived {
     pub metadata: HashMap<String, String>,
 }
 
-impl Event for MessageReceived {
-    const TOPIC: &'static str = "conversation.messages";
-    const VERSION: &'static str = "1.0";
-    /// Partitioning by `from_phone` allows us to group messages from
-    /// the same sender together.
-    fn partition_key(&self) -> Option<String> {
-        Some(self.from_phone.clone())
-    }
-}
+// Partitioning by `from_phone` allows us to group messages from
+// the same sender together.
+crate::impl_event!(MessageReceived {
+    topic: "conversation.messages",
+    version: "1.0",
+    partition_by: from_phone,
+});
 
 /// Represents when a user interacts with buttons or lists.
 /// This way we can handle interactive responses in a structured way.
@@ -36,15 +33,13 @@ pub struct InteractionReceived {
     pub received_at: chrono::DateTime<chrono::Utc>,
 }
 
-impl Event for InteractionReceived {
-    const TOPIC: &'static str = "conversation.interactions";
-    const VERSION: &'static str = "1.0";
-    /// Partitioning by `from_phone` allows us to group interactions
-    /// from the same sender together.
-    fn partition_key(&self) -> Option<String> {
-        Some(self.from_phone.clone())
-    }
-}
+// Partitioning by `from_phone` allows us to group interactions
+// from the same sender together.
+crate::impl_event!(InteractionReceived {
+    topic: "conversation.interactions",
+    version: "1.0",
+    partition_by: from_phone,
+});
 
 /// Represents when the AI or any service has something to say
 /// back to the user.
@@ -62,16 +57,13 @@ pub struct ResponseReady {
     pub priority: ResponsePriority,
 }
 
-impl Event for ResponseReady {
-    const TOPIC: &'static str = "conversation.responses";
-    const VERSION: &'static str = "1.0";
-    /// Partitioning by `to_phone` allows us to group responses
-    /// to the same recipient together.
-    fn partition_key(&self) -> Option<String> {
-        Some(self.to_phone.clone())
-    }
-    
-}
+// Partitioning by `to_phone` allows us to group responses
+// to the same recipient together.
+crate::impl_event!(ResponseReady {
+    topic: "conversation.responses",
+    version: "1.0",
+    partition_by: to_phone,
+});
 
 /// Represents when a message fails to process after all retries.
 /// TODO: This should trigger human intervention or alerting.
@@ -85,19 +77,17 @@ pub struct MessageFailed {
     pub failed_at: chrono::DateTime<chrono::Utc>,
 }
 
-impl Event for MessageFailed {
-    const TOPIC: &'static str = "conversation.messages.failed";
-    const VERSION: &'static str = "1.0";
-    /// Partitioning by `phone` allows us to group failures
-    /// for the same recipient together.
-    fn partition_key(&self) -> Option<String> {
-        Some(self.phone.clone())
-    }
-}
+// Partitioning by `phone` allows us to group failures
+// for the same recipient together.
+crate::impl_event!(MessageFailed {
+    topic: "conversation.messages.failed",
+    version: "1.0",
+    partition_by: phone,
+});
 
 // ====> Supporting types for the events <=====
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MessageType {
     Text,
     Image,
@@ -124,6 +114,10 @@ pub enum MessageContent {
         longitude: f64,
         name: Option<String>,
         address: Option<String>,
+        /// Set for live location updates, which keep arriving while the
+        /// sender shares their position
+        #[serde(default)]
+        is_live: bool,
     },
     Contact {
         name: String,
@@ -136,6 +130,7 @@ pub enum MessageContent {
 pub enum InteractionType {
     ButtonReply,
     ListReply,
+    FlowReply,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -149,6 +144,10 @@ pub enum InteractionSelection {
         title: String,
         description: Option<String>,
     },
+    /// Fields submitted through a WhatsApp Flow
+    Flow {
+        response: serde_json::Value,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -213,4 +212,6 @@ pub enum FailureType {
     ExternalServiceError,
     ValidationError,
     UnknownError,
+    /// Account-level error reported by WhatsApp, not tied to one message
+    AccountError,
 }
@@ -0,0 +1,87 @@
+//! Geographic helpers shared by the services.
+
+/// Mean Earth radius used for great-circle distances, in kilometers.
+pub const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Great-circle distance between two coordinates in kilometers.
+///
+/// Uses the Haversine formula on a spherical Earth, which is accurate to
+/// within about 0.5% anywhere on the globe.
+///
+/// # Example
+/// ```
+/// use common::geo::haversine_km;
+///
+/// // Times Square to the Empire State Building
+/// let distance = haversine_km(40.7580, -73.9855, 40.7484, -73.9857);
+/// assert!((distance - 1.07).abs() < 0.01);
+/// ```
+pub fn haversine_km(lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> f64 {
+    let lat1_rad = lat1.to_radians();
+    let lat2_rad = lat2.to_radians();
+    let delta_lat = (lat2 - lat1).to_radians();
+    let delta_lng = (lng2 - lng1).to_radians();
+
+    let a = (delta_lat / 2.0).sin().powi(2) +
+            lat1_rad.cos() * lat2_rad.cos() * (delta_lng / 2.0).sin().powi(2);
+    let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
+
+    EARTH_RADIUS_KM * c
+}
+
+/// Units a distance in kilometers can be converted to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DistanceUnit {
+    /// Kilometers (what `haversine_km` returns)
+    #[default]
+    Kilometers,
+    /// Meters
+    Meters,
+    /// Statute miles
+    Miles,
+}
+
+impl DistanceUnit {
+    /// Convert a distance in kilometers into this unit.
+    pub fn from_km(self, km: f64) -> f64 {
+        match self {
+            Self::Kilometers => km,
+            Self::Meters => km * 1000.0,
+            Self::Miles => km / 1.609_344,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_points_are_zero_apart() {
+        assert!(haversine_km(40.7580, -73.9855, 40.7580, -73.9855) < 0.001);
+        assert_eq!(haversine_km(0.0, 0.0, 0.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_pole_to_pole_is_half_the_circumference() {
+        let distance = haversine_km(90.0, 0.0, -90.0, 0.0);
+        assert!((distance - 20015.0).abs() < 100.0);
+        // Longitude doesn't matter at the poles
+        assert!((haversine_km(90.0, 45.0, -90.0, -120.0) - distance).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_distance_is_symmetric() {
+        let there = haversine_km(40.7580, -73.9855, 51.5074, -0.1278);
+        let back = haversine_km(51.5074, -0.1278, 40.7580, -73.9855);
+        assert!((there - back).abs() < 1e-9);
+        assert!((there - 5570.0).abs() < 20.0);
+    }
+
+    #[test]
+    fn test_distance_unit_conversion() {
+        assert_eq!(DistanceUnit::Kilometers.from_km(2.5), 2.5);
+        assert_eq!(DistanceUnit::Meters.from_km(2.5), 2500.0);
+        assert!((DistanceUnit::Miles.from_km(1.609_344) - 1.0).abs() < 1e-12);
+    }
+}
@@ -1,19 +1,37 @@
 use crate::message_bus::{
+    BatchPublishError,
     Event, 
     EventBus,
     EventBusError,
     EventEnvelope,
     ProcessingResult,
+    PublishReceipt,
     SubscriptionConfig,
+    VersionMismatchAction,
 };
+use crate::circuit_breaker::{CircuitBreaker, CircuitState};
+use crate::commit_batch::CommitBatch;
+use crate::clock::{Clock, SystemClock};
+use crate::metrics::Metrics;
+use crate::redact::{loggable_phone, REDACT_PII_DEFAULT};
+use crate::serialization::{SerializationFormat, Serializer};
 use rdkafka::{
+    admin::{AdminClient, AdminOptions, NewTopic, TopicReplication},
+    client::DefaultClientContext,
     config::ClientConfig,
-    consumer::{StreamConsumer, Consumer},
+    consumer::{CommitMode, Consumer, StreamConsumer},
+    error::{KafkaError as RdKafkaError, KafkaResult, RDKafkaErrorCode},
+    message::{BorrowedMessage, Header, Headers, OwnedHeaders, ToBytes},
     producer::{FutureProducer, FutureRecord, Producer},
     util::Timeout,
+    Offset,
+    TopicPartitionList,
     Message,
 };
-use futures::future::join_all;
+use futures::{
+    future::join_all,
+    stream::{self, StreamExt},
+};
 use serde::{
     Serialize, 
     de::DeserializeOwned
@@ -21,12 +39,42 @@ use serde::{
 use std::{
     collections::HashMap,
     error::Error,
-    sync::Arc,
-    time::Duration,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
 };
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
+/// Kafka record header carrying the envelope's event type
+pub const HEADER_EVENT_TYPE: &str = "event-type";
+/// Kafka record header carrying the envelope's schema version
+pub const HEADER_EVENT_VERSION: &str = "event-version";
+/// Kafka record header carrying the envelope's unique event ID
+pub const HEADER_EVENT_ID: &str = "event-id";
+/// Kafka record header naming the payload's serialization format
+pub const HEADER_CONTENT_FORMAT: &str = "content-format";
+/// Kafka record header added to quarantined records with the version the consumer expected
+pub const HEADER_EXPECTED_VERSION: &str = "expected-version";
+/// Kafka record header on scheduled records: when to deliver, in Unix milliseconds
+pub const HEADER_DELIVER_AT: &str = "deliver-at";
+/// Kafka record header on scheduled records: the topic to deliver to
+pub const HEADER_DELIVER_TO: &str = "deliver-to";
+
+/// How long the scheduler waits before retrying a record it failed to forward
+const SCHEDULER_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// How long a subscription waits before retrying a failed oversized diversion
+const OVERSIZED_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Compression codecs accepted by librdkafka's `compression.type`
+const SUPPORTED_COMPRESSION_TYPES: [&str; 5] = ["none", "gzip", "snappy", "lz4", "zstd"];
+
+/// Metadata keys added when an event is sent to the dead letter queue
+const DLQ_METADATA_KEYS: [&str; 5] = ["dlq_reason", "original_topic", "final_attempt_count", "dlq_timestamp", "dlq_age_ms"];
+
 /// Kafka-based implementation of the EventBus trait
 ///
 /// This provides a complete publish-subscribe system using Kafka
@@ -37,13 +85,105 @@ pub struct KafkaEventBus {
     producer: Arc<FutureProducer>,
     /// Coniguration for Kafka connections
     config: KafkaConfig,
-    /// Active consumers (tracked for graceful shutdown)
-    consumers : Arc<RwLock<HashMap<String, Arc<StreamConsumer>>>>,
+    /// Active consumers keyed by consumer group (tracked for graceful shutdown)
+    consumers : Arc<RwLock<HashMap<String, ConsumerHandle>>>,
+    /// Hooks for publish/consume/retry/DLQ metrics
+    metrics: Arc<dyn Metrics>,
+    /// Time source for envelope timestamps and age checks
+    clock: Arc<dyn Clock>,
+    /// When a subscription last handled an event, keyed by topic
+    last_processed: Arc<RwLock<HashMap<String, chrono::DateTime<chrono::Utc>>>>,
     /// Shutdown signal for coordinating consumer shutdown
     shutdown_signal: Arc<tokio::sync::watch::Sender<bool>>,
     shutdown_receiver: tokio::sync::watch::Receiver<bool>,
 }
 
+/// Dead letter record whose event data was too large to keep
+#[derive(Serialize)]
+struct TruncatedDeadLetter<'a> {
+    event_id: &'a str,
+    timestamp: chrono::DateTime<chrono::Utc>,
+    event_type: &'a str,
+    version: &'a str,
+    metadata: &'a HashMap<String, String>,
+    attempt_count: u32,
+    max_attempts: u32,
+    correlation_id: &'a str,
+    dlq_truncated: &'a str,
+}
+
+/// A dead-lettered event read back by `peek_dead_letters`
+///
+/// The DLQ bookkeeping fields are parsed out of the envelope metadata and
+/// are `None` when a record lacks them, e.g. one written by another tool.
+#[derive(Debug, Clone)]
+pub struct DeadLetterRecord<T: Event> {
+    /// The event as it was dead-lettered, DLQ metadata included
+    pub envelope: EventEnvelope<T>,
+    /// Why the event was dead-lettered, e.g. `max_retries_exceeded`
+    pub dlq_reason: Option<String>,
+    /// Topic the event was consumed from before it failed
+    pub original_topic: Option<String>,
+    /// Attempts made before the event was dead-lettered
+    pub final_attempt_count: Option<u32>,
+    /// DLQ partition the record was read from
+    pub partition: i32,
+    /// Offset of the record within its DLQ partition
+    pub offset: i64,
+}
+
+impl<T: Event> DeadLetterRecord<T> {
+    /// Wrap a dead-lettered envelope, parsing its DLQ metadata
+    pub fn from_envelope(envelope: EventEnvelope<T>, partition: i32, offset: i64) -> Self {
+        let dlq_reason = envelope.metadata.get("dlq_reason").cloned();
+        let original_topic = envelope.metadata.get("original_topic").cloned();
+        let final_attempt_count = envelope.metadata
+            .get("final_attempt_count")
+            .and_then(|count| count.parse().ok());
+        
+        Self {
+            envelope,
+            dlq_reason,
+            original_topic,
+            final_attempt_count,
+            partition,
+            offset,
+        }
+    }
+}
+
+/// Handle to a running subscription's consumer loop
+struct ConsumerHandle {
+    /// The consumer driving this subscription
+    consumer: Arc<StreamConsumer>,
+    /// Stops only this subscription's loop
+    stop_signal: tokio::sync::watch::Sender<bool>,
+    /// Set while the subscription is paused; the loop holds back deliveries
+    paused: Arc<AtomicBool>,
+    /// Trips when the handler keeps failing, pausing the loop for a cooldown
+    breaker: Arc<CircuitBreaker>,
+    /// Topic the loop consumes
+    topic: String,
+    /// When the loop started, standing in for `last_processed` until the
+    /// first event is handled
+    started_at: chrono::DateTime<chrono::Utc>,
+    /// How long the subscription may go without handling an event before
+    /// `health` reports it stale
+    stale_after: Option<Duration>,
+    /// The spawned consumer loop
+    task: tokio::task::JoinHandle<()>,
+}
+
+/// Result of `KafkaEventBus::health`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthStatus {
+    /// Connected, and every subscription is handling events
+    Healthy,
+    /// Connected, but some consumer groups have an open (or half-open)
+    /// circuit breaker or haven't handled an event within their `stale_after_ms`
+    Degraded { open_circuits: Vec<String>, stale_subscriptions: Vec<String> },
+}
+
 /// Configuration for connecting to Kafka cluster
 #[derive(Debug, Clone)]
 pub struct KafkaConfig {
@@ -53,10 +193,86 @@ pub struct KafkaConfig {
     pub timeout_ms: u64,
     /// Base consumer group ID (will be suffixed for different subscriptions)
     pub consumer_group_id: String,
+    /// How a subscription's full group ID is built
+    ///
+    /// `{base}` is replaced with `consumer_group_id` and `{group}` with the
+    /// subscription's group name, so `prod.{base}.{group}` namespaces groups
+    /// per tenant on a shared cluster. Defaults to [`DEFAULT_GROUP_ID_TEMPLATE`].
+    pub group_id_template: String,
     /// Security configuration
     pub security_protocol: String,
+    /// Wire format used for published event payloads
+    pub serialization_format: SerializationFormat,
+    /// Producer compression codec (none, gzip, snappy, lz4 or zstd)
+    pub compression_type: String,
+    /// Maximum producer batch size in bytes
+    pub batch_size: usize,
+    /// How long the producer waits to fill a batch before sending
+    pub linger_ms: u64,
+    /// Acknowledgements required from brokers ("all", "1" or "0")
+    pub acks: String,
+    /// Whether the producer guards against duplicate writes on retry
+    pub enable_idempotence: bool,
+    /// Producer transactional ID, enabling `begin_transaction` and
+    /// `process_transactionally`
+    ///
+    /// Must be stable across restarts of the same instance and unique
+    /// between instances, so a restarted producer fences off its old
+    /// self. Needs `enable_idempotence`. `None` (the default) leaves the
+    /// producer non-transactional.
+    pub transactional_id: Option<String>,
+    /// Reject events without a partition key instead of keying them by event ID
+    ///
+    /// Keying by event ID scatters events across partitions, so strict mode
+    /// catches event types that should be ordered but forgot to implement
+    /// `Event::partition_key()`.
+    pub require_partition_key: bool,
+    /// Allow `KafkaEventBus::ensure_topics` to create missing topics
+    ///
+    /// Off by default since many managed clusters forbid clients from
+    /// creating topics.
+    pub auto_create_topics: bool,
+    /// Partition count for topics created by `ensure_topics`
+    pub topic_partitions: i32,
+    /// Replication factor for topics created by `ensure_topics`
+    pub topic_replication_factor: i32,
+    /// Maximum concurrent sends while publishing a batch
+    ///
+    /// Bounds how much of a large batch sits in the producer queue at once,
+    /// so it doesn't overflow `queue.buffering.max.kbytes`. 0 sends the whole
+    /// batch at once.
+    pub max_in_flight: usize,
+    /// Largest dead letter record payload, in bytes
+    ///
+    /// Dead-lettered events that would serialize larger than this have their
+    /// data replaced by a truncated `dlq_truncated` string, so the record
+    /// stays under the broker's `message.max.bytes`. 0 disables the cap.
+    pub dlq_max_payload_bytes: usize,
+    /// Mask phone numbers and record keys in logs (see `redact_phone`)
+    pub redact_pii: bool,
+    /// Smallest response, in bytes, a consumer fetch waits for
+    ///
+    /// Subscriptions can override this and the two settings below, e.g. a
+    /// latency-sensitive topic with `fetch_min_bytes` 1.
+    pub fetch_min_bytes: u32,
+    /// Longest a consumer fetch waits for `fetch_min_bytes` to build up
+    pub fetch_wait_max_ms: u32,
+    /// Most data returned per partition in one consumer fetch, in bytes
+    pub max_partition_fetch_bytes: u32,
 }
 
+/// Group ID template matching the original `{base}-{group}` naming
+pub const DEFAULT_GROUP_ID_TEMPLATE: &str = "{base}-{group}";
+
+/// Default `KafkaConfig::fetch_min_bytes`
+pub const DEFAULT_FETCH_MIN_BYTES: u32 = 1024;
+
+/// Default `KafkaConfig::fetch_wait_max_ms`
+pub const DEFAULT_FETCH_WAIT_MAX_MS: u32 = 500;
+
+/// Default `KafkaConfig::max_partition_fetch_bytes` (1MB)
+pub const DEFAULT_MAX_PARTITION_FETCH_BYTES: u32 = 1_048_576;
+
 impl KafkaConfig {
     /// Create configuration from environment variables
     /// 
@@ -64,7 +280,25 @@ impl KafkaConfig {
     /// - KAFKA_BOOTSTRAP_SERVERS: Comma-separated list of broker addresses
     /// - KAFKA_TIMEOUT_MS: Operation timeout in milliseconds (optional, default: 5000)
     /// - KAFKA_CONSUMER_GROUP_ID: Base consumer group identifier
+    /// - KAFKA_GROUP_ID_TEMPLATE: Full group ID format using `{base}` and `{group}` (optional, default: {base}-{group})
     /// - KAFKA_SECURITY_PROTOCOL: Security protocol (optional, default: PLAINTEXT)
+    /// - KAFKA_SERIALIZATION_FORMAT: Payload format, "json" or "msgpack" (optional, default: json)
+    /// - KAFKA_COMPRESSION_TYPE: Producer compression codec (optional, default: zstd)
+    /// - KAFKA_BATCH_SIZE: Producer batch size in bytes (optional, default: 65536)
+    /// - KAFKA_LINGER_MS: Producer batching delay in milliseconds (optional, default: 5)
+    /// - KAFKA_ACKS: Required broker acknowledgements (optional, default: all)
+    /// - KAFKA_ENABLE_IDEMPOTENCE: Idempotent producer (optional, default: true)
+    /// - KAFKA_TRANSACTIONAL_ID: Producer transactional ID (optional, default: unset)
+    /// - KAFKA_REQUIRE_PARTITION_KEY: Reject events without a partition key (optional, default: false)
+    /// - KAFKA_AUTO_CREATE_TOPICS: Let `ensure_topics` create missing topics (optional, default: false)
+    /// - KAFKA_TOPIC_PARTITIONS: Partitions for created topics (optional, default: 3)
+    /// - KAFKA_TOPIC_REPLICATION_FACTOR: Replication factor for created topics (optional, default: 1)
+    /// - KAFKA_MAX_IN_FLIGHT: Concurrent sends per batch, 0 for unbounded (optional, default: 0)
+    /// - KAFKA_DLQ_MAX_PAYLOAD_BYTES: Dead letter payload cap, 0 to disable (optional, default: 900000)
+    /// - KAFKA_REDACT_PII: Mask phone numbers in logs (optional, default: true in release builds)
+    /// - KAFKA_FETCH_MIN_BYTES: Minimum consumer fetch size in bytes (optional, default: 1024)
+    /// - KAFKA_FETCH_WAIT_MAX_MS: Longest consumer fetch wait in milliseconds (optional, default: 500)
+    /// - KAFKA_MAX_PARTITION_FETCH_BYTES: Consumer fetch size per partition in bytes (optional, default: 1048576)
     pub fn from_env() -> Result<Self, EventBusError> {
         dotenv::dotenv().ok();
         
@@ -77,6 +311,10 @@ impl KafkaConfig {
             .map_err(|_| EventBusError::ConfigError(
                 "KAFKA_CONSUMER_GROUP_ID environment variable must be set".to_string()
             ))?;
+
+        let group_id_template = std::env::var("KAFKA_GROUP_ID_TEMPLATE")
+            .unwrap_or_else(|_| DEFAULT_GROUP_ID_TEMPLATE.to_string());
+        Self::validate_group_id_template(&group_id_template)?;
             
         let timeout_ms = std::env::var("KAFKA_TIMEOUT_MS")
             .unwrap_or_else(|_| "5000".to_string())
@@ -87,14 +325,149 @@ impl KafkaConfig {
             
         let security_protocol = std::env::var("KAFKA_SECURITY_PROTOCOL")
             .unwrap_or_else(|_| "PLAINTEXT".to_string());
+
+        let serialization_format = match std::env::var("KAFKA_SERIALIZATION_FORMAT") {
+            Ok(name) => SerializationFormat::from_name(&name)
+                .ok_or_else(|| EventBusError::ConfigError(
+                    format!("KAFKA_SERIALIZATION_FORMAT '{}' is not supported (use json or msgpack)", name)
+                ))?,
+            Err(_) => SerializationFormat::default(),
+        };
+
+        let compression_type = std::env::var("KAFKA_COMPRESSION_TYPE")
+            .unwrap_or_else(|_| "zstd".to_string())
+            .to_ascii_lowercase();
+        Self::validate_compression_type(&compression_type)?;
+
+        let batch_size = Self::env_or("KAFKA_BATCH_SIZE", 65536)?;
+        let linger_ms = Self::env_or("KAFKA_LINGER_MS", 5)?;
+        let acks = std::env::var("KAFKA_ACKS")
+            .unwrap_or_else(|_| "all".to_string());
+        let enable_idempotence = Self::env_or("KAFKA_ENABLE_IDEMPOTENCE", true)?;
+        let transactional_id = std::env::var("KAFKA_TRANSACTIONAL_ID")
+            .ok()
+            .filter(|id| !id.is_empty());
+        let require_partition_key = Self::env_or("KAFKA_REQUIRE_PARTITION_KEY", false)?;
+        let auto_create_topics = Self::env_or("KAFKA_AUTO_CREATE_TOPICS", false)?;
+        let topic_partitions = Self::env_or("KAFKA_TOPIC_PARTITIONS", 3)?;
+        let topic_replication_factor = Self::env_or("KAFKA_TOPIC_REPLICATION_FACTOR", 1)?;
+        let max_in_flight = Self::env_or("KAFKA_MAX_IN_FLIGHT", 0)?;
+        let dlq_max_payload_bytes = Self::env_or("KAFKA_DLQ_MAX_PAYLOAD_BYTES", 900_000)?;
+        let redact_pii = Self::env_or("KAFKA_REDACT_PII", REDACT_PII_DEFAULT)?;
+        let fetch_min_bytes = Self::env_or("KAFKA_FETCH_MIN_BYTES", DEFAULT_FETCH_MIN_BYTES)?;
+        let fetch_wait_max_ms = Self::env_or("KAFKA_FETCH_WAIT_MAX_MS", DEFAULT_FETCH_WAIT_MAX_MS)?;
+        let max_partition_fetch_bytes = Self::env_or("KAFKA_MAX_PARTITION_FETCH_BYTES", DEFAULT_MAX_PARTITION_FETCH_BYTES)?;
         
         Ok(Self {
             bootstrap_servers,
             timeout_ms,
             consumer_group_id,
+            group_id_template,
             security_protocol,
+            serialization_format,
+            compression_type,
+            batch_size,
+            linger_ms,
+            acks,
+            enable_idempotence,
+            transactional_id,
+            require_partition_key,
+            auto_create_topics,
+            topic_partitions,
+            topic_replication_factor,
+            max_in_flight,
+            dlq_max_payload_bytes,
+            redact_pii,
+            fetch_min_bytes,
+            fetch_wait_max_ms,
+            max_partition_fetch_bytes,
         })
     }
+
+    /// Check that a compression codec is one librdkafka understands
+    pub fn validate_compression_type(compression_type: &str) -> Result<(), EventBusError> {
+        if SUPPORTED_COMPRESSION_TYPES.contains(&compression_type) {
+            Ok(())
+        } else {
+            Err(EventBusError::ConfigError(format!(
+                "KAFKA_COMPRESSION_TYPE '{}' is not supported (expected one of: {})",
+                compression_type,
+                SUPPORTED_COMPRESSION_TYPES.join(", ")
+            )))
+        }
+    }
+
+    /// Check that transactions, if enabled, have an idempotent producer
+    pub fn validate_transactions(&self) -> Result<(), EventBusError> {
+        if self.transactional_id.is_some() && !self.enable_idempotence {
+            return Err(EventBusError::ConfigError(
+                "KAFKA_TRANSACTIONAL_ID requires KAFKA_ENABLE_IDEMPOTENCE to be true".to_string()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that a group ID template keeps subscriptions apart
+    ///
+    /// Without `{group}` every subscription would share one consumer group
+    /// and split each other's partitions.
+    pub fn validate_group_id_template(template: &str) -> Result<(), EventBusError> {
+        if template.contains("{group}") {
+            Ok(())
+        } else {
+            Err(EventBusError::ConfigError(format!(
+                "KAFKA_GROUP_ID_TEMPLATE '{}' must contain {{group}}",
+                template
+            )))
+        }
+    }
+
+    /// Full consumer group ID for a subscription's group name
+    pub fn group_id(&self, consumer_group: &str) -> String {
+        self.group_id_template
+            .replace("{base}", &self.consumer_group_id)
+            .replace("{group}", consumer_group)
+    }
+
+    /// Parse an optional environment variable, falling back to `default`
+    fn env_or<T: std::str::FromStr>(name: &str, default: T) -> Result<T, EventBusError> {
+        match std::env::var(name) {
+            Ok(value) => value.parse().map_err(|_| EventBusError::ConfigError(
+                format!("{} has an invalid value: {}", name, value)
+            )),
+            Err(_) => Ok(default),
+        }
+    }
+}
+
+impl Default for KafkaConfig {
+    /// The same settings `from_env` falls back to, against a local broker
+    fn default() -> Self {
+        Self {
+            bootstrap_servers: "localhost:9092".to_string(),
+            timeout_ms: 5000,
+            consumer_group_id: "default-group".to_string(),
+            group_id_template: DEFAULT_GROUP_ID_TEMPLATE.to_string(),
+            security_protocol: "PLAINTEXT".to_string(),
+            serialization_format: SerializationFormat::default(),
+            compression_type: "zstd".to_string(),
+            batch_size: 65536,
+            linger_ms: 5,
+            acks: "all".to_string(),
+            enable_idempotence: true,
+            transactional_id: None,
+            require_partition_key: false,
+            auto_create_topics: false,
+            topic_partitions: 3,
+            topic_replication_factor: 1,
+            max_in_flight: 0,
+            dlq_max_payload_bytes: 900_000,
+            redact_pii: REDACT_PII_DEFAULT,
+            fetch_min_bytes: DEFAULT_FETCH_MIN_BYTES,
+            fetch_wait_max_ms: DEFAULT_FETCH_WAIT_MAX_MS,
+            max_partition_fetch_bytes: DEFAULT_MAX_PARTITION_FETCH_BYTES,
+        }
+    }
 }
 
 impl KafkaEventBus {
@@ -104,31 +477,51 @@ impl KafkaEventBus {
     /// infraestructure needed to manage consumers (for subscribing). The
     /// producer is created immediately, while consumers are created 
     /// on-demand for each subscription.
-    pub async fn new(config: KafkaConfig) -> Result<Self, EventBusError> {
+    ///
+    /// `metrics` receives publish, consume, retry and DLQ events; pass
+    /// `Arc::new(NoopMetrics)` when metrics aren't needed.
+    ///
+    /// With `transactional_id` set, the producer's transactions are
+    /// initialized here, so unlike a plain bus this needs a reachable broker.
+    pub async fn new(config: KafkaConfig, metrics: Arc<dyn Metrics>) -> Result<Self, EventBusError> {
         info!("🔧 Initializing Kafka event bus with brokers: {}", config.bootstrap_servers);
+        KafkaConfig::validate_compression_type(&config.compression_type)?;
+        config.validate_transactions()?;
         
         // Create the producer with optimized settings
-        let producer: FutureProducer = ClientConfig::new()
+        let mut producer_config = ClientConfig::new();
+        producer_config
             // Connection settings
             .set("bootstrap.servers", &config.bootstrap_servers)
             .set("security.protocol", &config.security_protocol)
             
             // Reliability settings - ensure messages are safely delivered
-            .set("acks", "all")                    // Wait for all replicas to acknowledge
-            .set("enable.idempotence", "true")     // Prevent duplicate messages
+            .set("acks", &config.acks)             // Replica acknowledgements to wait for
+            .set("enable.idempotence", config.enable_idempotence.to_string()) // Prevent duplicate messages
             .set("retries", "10")                  // Retry failed sends
             .set("retry.backoff.ms", "1000")       // Wait between retries
             
             // Performance optimizations
-            .set("compression.type", "zstd")       // Compress messages
-            .set("batch.size", "65536")            // Batch up to 64KB
-            .set("linger.ms", "5")                 // Wait up to 5ms to batch
-            .set("queue.buffering.max.kbytes", "32768")  // 32MB buffer
-            
+            .set("compression.type", &config.compression_type) // Compress messages
+            .set("batch.size", config.batch_size.to_string())  // Batch size in bytes
+            .set("linger.ms", config.linger_ms.to_string())    // Time to wait to fill a batch
+            .set("queue.buffering.max.kbytes", "32768");  // 32MB buffer
+        
+        if let Some(transactional_id) = &config.transactional_id {
+            producer_config.set("transactional.id", transactional_id);
+        }
+        
+        let producer: Arc<FutureProducer> = Arc::new(producer_config
             .create()
             .map_err(|e| EventBusError::ConnectionError(
                 format!("Failed to create Kafka producer: {}", e)
-            ))?;
+            ))?);
+        
+        if config.transactional_id.is_some() {
+            let timeout = Duration::from_millis(config.timeout_ms);
+            Self::run_transaction_call(producer.clone(), "initialize", move |producer| producer.init_transactions(timeout)).await?;
+            info!("🧾 Kafka producer transactions initialized");
+        }
         
         // Create shutdown coordination
         let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
@@ -136,20 +529,468 @@ impl KafkaEventBus {
         info!("✅ Kafka event bus initialized successfully");
         
         Ok(Self {
-            producer: Arc::new(producer),
+            producer,
             config,
             consumers: Arc::new(RwLock::new(HashMap::new())),
+            metrics,
+            clock: Arc::new(SystemClock),
+            last_processed: Arc::new(RwLock::new(HashMap::new())),
             shutdown_signal: Arc::new(shutdown_tx),
             shutdown_receiver: shutdown_rx,
         })
     }
 
+    /// Use `clock` instead of the system clock for envelope timestamps,
+    /// `max_age_ms` checks, DLQ metadata and delayed delivery
+    ///
+    /// Meant for tests that need to control time, e.g. with a `MockClock`.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Create `T::TOPIC`, its `T::TOPIC_SUFFIXES` sub-topics and its `.retry`,
+    /// `.dlq` and `.scheduled` topics if they're missing
+    ///
+    /// Retry, DLQ and delayed publishing use those topics, so on clusters that
+    /// don't auto-create topics the first failed event would otherwise be
+    /// lost. Meant to be called once at startup. Does nothing unless
+    /// `auto_create_topics` is enabled; topics that already exist are left
+    /// untouched, so calling this repeatedly is safe.
+    pub async fn ensure_topics<T: Event>(&self) -> Result<(), EventBusError> {
+        if !self.config.auto_create_topics {
+            debug!("⏭️ Skipping topic creation for {}: auto_create_topics is disabled", T::TOPIC);
+            return Ok(());
+        }
+        
+        let admin: AdminClient<DefaultClientContext> = ClientConfig::new()
+            .set("bootstrap.servers", &self.config.bootstrap_servers)
+            .set("security.protocol", &self.config.security_protocol)
+            .create()
+            .map_err(|e| EventBusError::ConnectionError(
+                format!("Failed to create Kafka admin client: {}", e)
+            ))?;
+        
+        let names: Vec<String> = [
+            T::TOPIC.to_string(),
+            format!("{}.retry", T::TOPIC),
+            format!("{}.dlq", T::TOPIC),
+            format!("{}.scheduled", T::TOPIC),
+        ]
+        .into_iter()
+        .chain(T::TOPIC_SUFFIXES.iter().map(|suffix| format!("{}.{}", T::TOPIC, suffix)))
+        .collect();
+        let new_topics: Vec<NewTopic> = names.iter()
+            .map(|name| NewTopic::new(
+                name,
+                self.config.topic_partitions,
+                TopicReplication::Fixed(self.config.topic_replication_factor),
+            ))
+            .collect();
+        let options = AdminOptions::new()
+            .operation_timeout(Some(Duration::from_millis(self.config.timeout_ms)));
+        
+        let results = admin.create_topics(&new_topics, &options).await
+            .map_err(|e| EventBusError::ConnectionError(
+                format!("Failed to create topics for {}: {}", T::TOPIC, e)
+            ))?;
+        
+        for result in results {
+            match result {
+                Ok(topic) => info!("🆕 Created topic {}", topic),
+                Err((topic, RDKafkaErrorCode::TopicAlreadyExists)) => {
+                    debug!("✔️ Topic {} already exists", topic);
+                }
+                Err((topic, code)) => {
+                    return Err(EventBusError::ConfigError(
+                        format!("Failed to create topic {}: {}", topic, code)
+                    ));
+                }
+            }
+        }
+        
+        Ok(())
+    }
+
+    /// Publish a batch, failing with the first event's error if any fail
+    ///
+    /// The pre-`BatchPublishError` behaviour, for callers that only care
+    /// whether the whole batch went out. Events are still all attempted.
+    pub async fn publish_batch_all_or_nothing<T>(&self, events: Vec<T>) -> Result<(), EventBusError>
+    where
+        T: Event,
+    {
+        match self.publish_batch(events).await {
+            Err(EventBusError::BatchPublishFailed(batch)) => match batch.into_first_failure() {
+                Some(first) => Err(first),
+                None => Ok(()),
+            },
+            result => result,
+        }
+    }
+
+    /// Run futures with at most `max_in_flight` of them pending at once
+    ///
+    /// Results are returned in input order. A limit of 0 runs them all
+    /// concurrently.
+    async fn run_bounded<F>(futures: impl IntoIterator<Item = F>, max_in_flight: usize) -> Vec<F::Output>
+    where
+        F: std::future::Future,
+    {
+        if max_in_flight == 0 {
+            return join_all(futures).await;
+        }
+        
+        let mut results: Vec<(usize, F::Output)> = stream::iter(futures.into_iter().enumerate())
+            .map(|(index, future)| async move { (index, future.await) })
+            .buffer_unordered(max_in_flight)
+            .collect()
+            .await;
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// Full consumer group ID for a subscription's group name
+    fn consumer_group_id(&self, consumer_group: &str) -> String {
+        self.config.group_id(consumer_group)
+    }
+
+    /// Stop a single subscription without affecting the others
+    ///
+    /// `consumer_group` is the name given in that subscription's
+    /// `SubscriptionConfig`. The consumer loop is signalled to stop and
+    /// removed from the bus; this waits up to the configured timeout for the
+    /// loop to finish handling any in-flight message.
+    pub async fn unsubscribe(&self, consumer_group: &str) -> Result<(), EventBusError> {
+        let group_id = self.consumer_group_id(consumer_group);
+        
+        let handle = self.consumers.write().await
+            .remove(&group_id)
+            .ok_or_else(|| EventBusError::SubscriptionFailed(
+                format!("No active subscription for consumer group {}", group_id)
+            ))?;
+        
+        info!("🛑 Unsubscribing consumer group {}", group_id);
+        let _ = handle.stop_signal.send(true);
+        
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        match tokio::time::timeout(timeout, handle.task).await {
+            Ok(_) => {
+                // Release the partition assignment so the group rebalances now
+                handle.consumer.unsubscribe();
+                info!("✅ Consumer group {} unsubscribed", group_id);
+                Ok(())
+            }
+            Err(_) => Err(EventBusError::ConsumerError(
+                format!("Consumer group {} did not stop within {}ms", group_id, self.config.timeout_ms)
+            )),
+        }
+    }
+
+    /// Temporarily stop delivering events to a subscription
+    ///
+    /// Fetching is paused on the consumer's assigned partitions, but the
+    /// consumer stays in its group, so no rebalance is triggered. Events
+    /// already buffered when the pause takes effect are rewound rather than
+    /// handed to the handler, and are delivered after `resume`.
+    pub async fn pause(&self, consumer_group: &str) -> Result<(), EventBusError> {
+        let group_id = self.consumer_group_id(consumer_group);
+        let consumers = self.consumers.read().await;
+        let handle = consumers.get(&group_id)
+            .ok_or_else(|| EventBusError::SubscriptionFailed(
+                format!("No active subscription for consumer group {}", group_id)
+            ))?;
+        
+        handle.paused.store(true, Ordering::SeqCst);
+        
+        let assignment = handle.consumer.assignment()
+            .map_err(|e| EventBusError::ConsumerError(format!("Failed to read assignment: {}", e)))?;
+        handle.consumer.pause(&assignment)
+            .map_err(|e| EventBusError::ConsumerError(format!("Failed to pause consumer group {}: {}", group_id, e)))?;
+        
+        info!("⏸️ Paused consumer group {} ({} partitions)", group_id, assignment.count());
+        Ok(())
+    }
+
+    /// Resume delivery to a subscription paused with `pause`
+    pub async fn resume(&self, consumer_group: &str) -> Result<(), EventBusError> {
+        let group_id = self.consumer_group_id(consumer_group);
+        let consumers = self.consumers.read().await;
+        let handle = consumers.get(&group_id)
+            .ok_or_else(|| EventBusError::SubscriptionFailed(
+                format!("No active subscription for consumer group {}", group_id)
+            ))?;
+        
+        let assignment = handle.consumer.assignment()
+            .map_err(|e| EventBusError::ConsumerError(format!("Failed to read assignment: {}", e)))?;
+        handle.consumer.resume(&assignment)
+            .map_err(|e| EventBusError::ConsumerError(format!("Failed to resume consumer group {}: {}", group_id, e)))?;
+        
+        handle.paused.store(false, Ordering::SeqCst);
+        
+        info!("▶️ Resumed consumer group {} ({} partitions)", group_id, assignment.count());
+        Ok(())
+    }
+
+    /// Move a consumer group on `topic` back (or forward) to `timestamp`
+    ///
+    /// Each partition is repositioned to its first event written at or
+    /// after `timestamp`, or to its end if there is none, e.g. to reprocess
+    /// the last two hours after fixing a handler bug. With an active
+    /// subscription for `consumer_group`, its consumer seeks the partitions
+    /// of `topic` it has assigned and carries on from there. Otherwise the
+    /// offsets are committed for the group, and the next subscription
+    /// starts from them.
+    ///
+    /// Returns the number of partitions repositioned.
+    pub async fn seek_to_timestamp(
+        &self,
+        consumer_group: &str,
+        topic: &str,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize, EventBusError> {
+        let group_id = self.consumer_group_id(consumer_group);
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        let timestamp_ms = timestamp.timestamp_millis();
+        let topic_name = topic.to_string();
+        
+        let active = self.consumers.read().await
+            .get(&group_id)
+            .map(|handle| handle.consumer.clone());
+        let seek = match active {
+            Some(consumer) => tokio::task::spawn_blocking(move || {
+                let assignment = consumer.assignment()
+                    .map_err(|e| EventBusError::ConsumerError(format!("Failed to read assignment: {}", e)))?;
+                let partitions: Vec<i32> = assignment.elements_for_topic(&topic_name)
+                    .iter()
+                    .map(|elem| elem.partition())
+                    .collect();
+                if partitions.is_empty() {
+                    return Err(EventBusError::ConsumerError(
+                        format!("No partitions of {} are assigned to this consumer yet", topic_name)
+                    ));
+                }
+                
+                let offsets = Self::offsets_at_timestamp(&consumer, &topic_name, &partitions, timestamp_ms, timeout)?;
+                for elem in offsets.elements() {
+                    consumer.seek(&topic_name, elem.partition(), elem.offset(), timeout)
+                        .map_err(|e| EventBusError::ConsumerError(
+                            format!("Failed to seek {}/{}: {}", topic_name, elem.partition(), e)
+                        ))?;
+                }
+                Ok(offsets.count())
+            }).await,
+            None => {
+                let consumer = self.create_consumer(&group_id, None)?;
+                tokio::task::spawn_blocking(move || {
+                    let partitions = Self::topic_partitions(&consumer, &topic_name, timeout)?;
+                    let offsets = Self::offsets_at_timestamp(&consumer, &topic_name, &partitions, timestamp_ms, timeout)?;
+                    consumer.commit(&offsets, CommitMode::Sync)
+                        .map_err(|e| EventBusError::ConsumerError(format!("Failed to commit seek offsets: {}", e)))?;
+                    Ok(offsets.count())
+                }).await
+            }
+        };
+        let repositioned = seek
+            .map_err(|e| EventBusError::ConsumerError(format!("Seek task failed: {}", e)))??;
+        
+        info!(topic, consumer_group = %group_id, %timestamp, repositioned, "⏪ Consumer group repositioned");
+        Ok(repositioned)
+    }
+
+    /// Whether a subscription is currently paused
+    pub async fn is_paused(&self, consumer_group: &str) -> bool {
+        let group_id = self.consumer_group_id(consumer_group);
+        self.consumers.read().await
+            .get(&group_id)
+            .is_some_and(|handle| handle.paused.load(Ordering::SeqCst))
+    }
+
+    /// State of a subscription's circuit breaker, if it's active
+    pub async fn circuit_state(&self, consumer_group: &str) -> Option<CircuitState> {
+        let group_id = self.consumer_group_id(consumer_group);
+        self.consumers.read().await
+            .get(&group_id)
+            .map(|handle| handle.breaker.state())
+    }
+
+    /// Check the connection and report whether any subscription is degraded
+    ///
+    /// Connection problems are errors, as in `health_check`. A reachable
+    /// cluster is `Degraded` while any subscription's circuit breaker isn't
+    /// closed, meaning its handler has been failing, or while a subscription
+    /// with `stale_after_ms` set hasn't handled an event for that long.
+    pub async fn health(&self) -> Result<HealthStatus, EventBusError> {
+        self.check_connection().await?;
+        
+        let now = self.clock.now();
+        let last_processed = self.last_processed.read().await;
+        let consumers = self.consumers.read().await;
+        
+        let mut open_circuits: Vec<String> = consumers
+            .iter()
+            .filter(|(_, handle)| handle.breaker.state() != CircuitState::Closed)
+            .map(|(group_id, _)| group_id.clone())
+            .collect();
+        let mut stale_subscriptions: Vec<String> = consumers
+            .iter()
+            .filter(|(_, handle)| {
+                let Some(stale_after) = handle.stale_after else {
+                    return false;
+                };
+                let last = last_processed.get(&handle.topic).copied()
+                    .map_or(handle.started_at, |last| last.max(handle.started_at));
+                (now - last).to_std().is_ok_and(|idle| idle > stale_after)
+            })
+            .map(|(group_id, _)| group_id.clone())
+            .collect();
+        
+        if open_circuits.is_empty() && stale_subscriptions.is_empty() {
+            Ok(HealthStatus::Healthy)
+        } else {
+            open_circuits.sort();
+            stale_subscriptions.sort();
+            Ok(HealthStatus::Degraded { open_circuits, stale_subscriptions })
+        }
+    }
+
+    /// When a subscription to `topic` last handled an event
+    ///
+    /// Updated whenever a handler returns `Success` or `Skip`. `None` until
+    /// the first one, including for topics nobody subscribed to.
+    pub async fn last_processed(&self, topic: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.last_processed.read().await.get(topic).copied()
+    }
+
+    /// Fetch cluster metadata to confirm the brokers are reachable
+    async fn check_connection(&self) -> Result<(), EventBusError> {
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        let metadata_future = tokio::task::spawn_blocking({
+            let producer = self.producer.clone();
+            move || {
+                producer.client().fetch_metadata(None, timeout)
+            }
+        });
+        
+        match tokio::time::timeout(Duration::from_secs(10), metadata_future).await {
+            Ok(Ok(Ok(_metadata))) => Ok(()),
+            Ok(Ok(Err(e))) => {
+                error!("❌ Kafka health check failed: {}", e);
+                Err(EventBusError::ConnectionError(format!("Health check failed: {}", e)))
+            }
+            Ok(Err(e)) => {
+                error!("❌ Kafka health check task failed: {}", e);
+                Err(EventBusError::ConnectionError(format!("Health check task error: {}", e)))
+            }
+            Err(_) => {
+                error!("❌ Kafka health check timed out");
+                Err(EventBusError::ConnectionError("Health check timeout".to_string()))
+            }
+        }
+    }
+
+    /// Hold back a message received while its subscription is paused
+    ///
+    /// Messages can still arrive after a pause (already fetched, or from
+    /// partitions assigned by a later rebalance). The partition is paused
+    /// and rewound to the message so it's redelivered once resumed.
+    fn hold_paused_message(consumer: &StreamConsumer, message: &BorrowedMessage<'_>) {
+        let mut partition = TopicPartitionList::new();
+        partition.add_partition(message.topic(), message.partition());
+        if let Err(e) = consumer.pause(&partition) {
+            error!("❌ Failed to pause partition {}/{}: {}", message.topic(), message.partition(), e);
+        }
+        
+        Self::rewind_to_message(consumer, message);
+    }
+
+    /// Rewind a message's partition so the message is delivered again
+    fn rewind_to_message(consumer: &StreamConsumer, message: &BorrowedMessage<'_>) {
+        if let Err(e) = consumer.seek(
+            message.topic(),
+            message.partition(),
+            Offset::Offset(message.offset()),
+            Duration::from_secs(1),
+        ) {
+            error!("❌ Failed to rewind partition {}/{}: {}", message.topic(), message.partition(), e);
+        }
+    }
+
+    /// Commit a handled message, or add it to the subscription's commit batch
+    ///
+    /// Without a batch the message is committed straight away. With one, it
+    /// is only committed once the batch is due.
+    fn commit_handled(
+        consumer: &StreamConsumer,
+        commits: &mut Option<CommitBatch>,
+        message: &BorrowedMessage<'_>,
+    ) -> KafkaResult<()> {
+        let Some(batch) = commits else {
+            return consumer.commit_message(message, CommitMode::Async);
+        };
+        batch.record(message.topic(), message.partition(), message.offset());
+        if batch.is_due(std::time::Instant::now()) {
+            return Self::flush_commits(consumer, batch, CommitMode::Async);
+        }
+        Ok(())
+    }
+
+    /// Commit every offset pending in `batch`
+    fn flush_commits(consumer: &StreamConsumer, batch: &mut CommitBatch, mode: CommitMode) -> KafkaResult<()> {
+        let pending = batch.pending();
+        match batch.take()? {
+            Some(offsets) => {
+                debug!(pending, partitions = offsets.count(), "💾 Committing batched offsets");
+                consumer.commit(&offsets, mode)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Resolve when `commits` is due by time; never, if there's no deadline
+    async fn commit_deadline(commits: Option<&CommitBatch>) {
+        match commits.and_then(CommitBatch::deadline) {
+            Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+            None => std::future::pending().await,
+        }
+    }
+
     /// Create a new Kafka consumer with the specified configuration
     ///
     /// This sets up a consumer with optimized settings for reliable message
     /// processing in a microservices architecture.
-    fn create_consumer(&self, consumer_group: &str) -> Result<StreamConsumer, EventBusError> {
-        let consumer: StreamConsumer = ClientConfig::new()
+    fn create_consumer(
+        &self,
+        consumer_group: &str,
+        subscription: Option<&SubscriptionConfig>,
+    ) -> Result<StreamConsumer, EventBusError> {
+        let consumer: StreamConsumer = self.consumer_config(consumer_group, subscription)
+            .create()
+            .map_err(|e|
+                EventBusError::ConsumerError(
+                    format!("Failed to create Kafka consumer: {}", e)
+                )
+            )?;
+        Ok(consumer)            
+    }
+
+    /// Client settings for a consumer in `consumer_group`
+    ///
+    /// Fetch sizes come from `subscription` where it overrides them and
+    /// from the bus config otherwise.
+    fn consumer_config(&self, consumer_group: &str, subscription: Option<&SubscriptionConfig>) -> ClientConfig {
+        let fetch_min_bytes = subscription
+            .and_then(|s| s.fetch_min_bytes)
+            .unwrap_or(self.config.fetch_min_bytes);
+        let fetch_wait_max_ms = subscription
+            .and_then(|s| s.fetch_wait_max_ms)
+            .unwrap_or(self.config.fetch_wait_max_ms);
+        let max_partition_fetch_bytes = subscription
+            .and_then(|s| s.max_partition_fetch_bytes)
+            .unwrap_or(self.config.max_partition_fetch_bytes);
+
+        let mut config = ClientConfig::new();
+        config
             // Connection settings
             .set("bootstrap.servers", &self.config.bootstrap_servers)
             .set("security.protocol", &self.config.security_protocol)
@@ -163,17 +1004,10 @@ impl KafkaEventBus {
             .set("max.poll.interval.ms", "300000")  // 5 min. max poll interval
             
             // Performance settings
-            .set("fetch.min.bytes", "1024")         // Minimum bytes to fetch
-            .set("fetch.wait.max.ms", "500")        // Wait up to 500ms for more data
-            .set("max.partition.fetch.bytes", "1048576") // 1MB per partition
-
-            .create()
-            .map_err(|e|
-                EventBusError::ConsumerError(
-                    format!("Failed to create Kafka consumer: {}", e)
-                )
-            )?;
-        Ok(consumer)            
+            .set("fetch.min.bytes", fetch_min_bytes.to_string())
+            .set("fetch.wait.max.ms", fetch_wait_max_ms.to_string())
+            .set("max.partition.fetch.bytes", max_partition_fetch_bytes.to_string());
+        config
     }
 
     /// Publish an event with retry logic and dead letter queue support
@@ -183,35 +1017,54 @@ impl KafkaEventBus {
     /// - Serializes the event to JSON.
     /// - Sends to appropriate Kafka topic.
     /// - Handles failures with retries and dead letter queue logic.
-    async fn publish_envelope<T>(&self, envelope: EventEnvelope<T>) -> Result<(), EventBusError>
+    ///
+    /// When `key_override` is set it replaces the key derived from the event.
+    /// Returns where the record was written.
+    async fn publish_envelope<T>(
+        &self,
+        envelope: EventEnvelope<T>,
+        key_override: Option<String>,
+    ) -> Result<PublishReceipt, EventBusError>
         where 
             T: Event + Serialize + DeserializeOwned + Send + 'static,
     {
-        let topic = T::TOPIC;
-        let key = envelope.data
-            .partition_key()
-            .unwrap_or(envelope.event_id.clone());
+        let topic = envelope.data.routing_topic();
+        self.publish_envelope_to(envelope, &topic, key_override).await
+    }
+
+    /// Like `publish_envelope`, but to `topic` instead of the event's routing topic
+    async fn publish_envelope_to<T>(
+        &self,
+        envelope: EventEnvelope<T>,
+        topic: &str,
+        key_override: Option<String>,
+    ) -> Result<PublishReceipt, EventBusError>
+        where 
+            T: Event + Serialize + DeserializeOwned + Send + 'static,
+    {
+        let key = Self::record_key(&envelope, key_override, self.config.require_partition_key)?;
 
-        debug!("📤 Publishing event {} to topic {}", envelope.event_id, topic);
+        debug!(event_id = %envelope.event_id, topic, "📤 Publishing event");
 
-        let payload = serde_json::to_string(&envelope)
-            .map_err(|e| EventBusError::SerializationError(
-                    format!("Failed to serialize event: {}", e)
-            ))?;
+        let format = self.config.serialization_format;
+        let payload = format.serialize(&envelope)?;
 
-        let record = FutureRecord::to(&topic)
+        let record = FutureRecord::to(topic)
             .key(&key)
-            .payload(&payload);
-
-        let timeout = Timeout::After(Duration::from_millis(self.config.timeout_ms));
+            .payload(&payload)
+            .headers(Self::envelope_headers(&envelope, format));
 
-        match self.producer.send(record, timeout).await {
-            Ok(delivery) => {
-                debug!("✅ Event {} published successfully: {:?}", envelope.event_id, delivery);
-                Ok(())
+        match self.send_record(topic, record).await {
+            Ok((partition, offset)) => {
+                debug!(event_id = %envelope.event_id, topic, "✅ Event published");
+                Ok(PublishReceipt {
+                    topic: topic.to_string(),
+                    partition,
+                    offset,
+                })
             }
-            Err((kafka_error, _)) => {
-                error!("❌ Failed to publish event {}: {}", envelope.event_id, kafka_error);
+            Err(kafka_error) => {
+                error!(event_id = %envelope.event_id, topic, error = %kafka_error, "❌ Failed to publish event");
                 Err(EventBusError::PublishFailed(
                     format!("Kafka send error: {}", kafka_error)
                 ))
@@ -219,438 +1072,3742 @@ impl KafkaEventBus {
         }
     }
 
-    /// Process a single event envelope with the provided handler
-    /// 
-    /// This implements the core event processing logic including retry
-    /// and dead letter queue handling based on the processing result.
-    async fn process_event_envelope<T, F>(
-        &self,
-        envelope: EventEnvelope<T>,
-        handler: &F,
-    ) -> Result<bool, EventBusError>
+    /// Send a record with the shared producer, recording publish metrics
+    ///
+    /// Returns the partition and offset from the delivery report.
+    async fn send_record<K, P>(&self, topic: &str, record: FutureRecord<'_, K, P>) -> Result<(i32, i64), RdKafkaError>
+    where
+        K: ToBytes + ?Sized,
+        P: ToBytes + ?Sized,
+    {
+        let timeout = Timeout::After(Duration::from_millis(self.config.timeout_ms));
+        let started = Instant::now();
+        let result = self.producer.send(record, timeout).await;
+        self.metrics.record_publish(topic, started.elapsed(), result.is_ok());
+
+        result
+            .inspect(|(partition, offset)| debug!(topic, partition, offset, "📬 Record delivered"))
+            .map_err(|(kafka_error, _)| kafka_error)
+    }
+
+    /// Resolve the Kafka record key for an envelope
+    ///
+    /// An explicit override wins, then the event's partition key, and
+    /// finally the event ID so that every record carries some key. With
+    /// `require_partition_key` set, a missing or empty key is an error instead.
+    fn record_key<T>(
+        envelope: &EventEnvelope<T>,
+        key_override: Option<String>,
+        require_partition_key: bool,
+    ) -> Result<String, EventBusError>
+    where
+        T: Event,
+    {
+        match key_override.or_else(|| envelope.partition_key()) {
+            Some(key) if !key.is_empty() => Ok(key),
+            _ if require_partition_key => Err(EventBusError::PublishFailed(format!(
+                "{} event {} has no partition key and partition keys are required",
+                envelope.event_type, envelope.event_id
+            ))),
+            _ => Ok(envelope.event_id.clone()),
+        }
+    }
+
+    /// Build the routing headers attached to every record we produce
+    ///
+    /// These let consumers route by type or reject incompatible schema
+    /// versions without deserializing the full payload. With the `otel`
+    /// feature the current trace context is attached as well.
+    fn envelope_headers<T>(envelope: &EventEnvelope<T>, format: SerializationFormat) -> OwnedHeaders
+    where
+        T: Event,
+    {
+        let headers = OwnedHeaders::new()
+            .insert(Header { key: HEADER_EVENT_TYPE, value: Some(envelope.event_type.as_str()) })
+            .insert(Header { key: HEADER_EVENT_VERSION, value: Some(envelope.version.as_str()) })
+            .insert(Header { key: HEADER_EVENT_ID, value: Some(envelope.event_id.as_str()) })
+            .insert(Header { key: HEADER_CONTENT_FORMAT, value: Some(format.format()) });
+        
+        #[cfg(feature = "otel")]
+        let headers = crate::trace_context::inject_current_context(headers);
+        
+        headers
+    }
+
+    /// Determine how an incoming record's payload was serialized
+    ///
+    /// Records without a `content-format` header predate pluggable
+    /// serialization and are always JSON.
+    fn payload_format<H: Headers>(headers: Option<&H>) -> Result<SerializationFormat, EventBusError> {
+        match headers.and_then(|headers| Self::header_value(headers, HEADER_CONTENT_FORMAT)) {
+            Some(name) => SerializationFormat::from_name(&name)
+                .ok_or_else(|| EventBusError::SerializationError(format!("Unsupported content format: {}", name))),
+            None => Ok(SerializationFormat::Json),
+        }
+    }
+
+    /// Deserialize an envelope using the format named in the record headers
+    fn decode_envelope<T, H>(headers: Option<&H>, payload: &[u8]) -> Result<EventEnvelope<T>, EventBusError>
+    where
+        T: Event,
+        H: Headers,
+    {
+        Self::payload_format(headers)?.deserialize(payload)
+    }
+
+    /// Read a UTF-8 header value by name
+    fn header_value<H: Headers>(headers: &H, name: &str) -> Option<String> {
+        headers
+            .iter()
+            .find(|header| header.key == name)
+            .and_then(|header| header.value)
+            .and_then(|value| std::str::from_utf8(value).ok())
+            .map(str::to_string)
+    }
+
+    /// Find the schema version of an incoming record
+    ///
+    /// Reads the `event-version` header when present, otherwise probes the
+    /// payload for the envelope's `version` field without deserializing
+    /// the event data itself.
+    fn incoming_version<H: Headers>(headers: Option<&H>, payload: &[u8]) -> Option<String> {
+        #[derive(serde::Deserialize)]
+        struct VersionProbe {
+            version: String,
+        }
+        
+        headers
+            .and_then(|headers| Self::header_value(headers, HEADER_EVENT_VERSION))
+            .or_else(|| {
+                Self::payload_format(headers)
+                    .and_then(|format| format.deserialize::<VersionProbe>(payload))
+                    .ok()
+                    .map(|probe| probe.version)
+            })
+    }
+
+    /// Run a synchronous handler on the blocking thread pool
+    ///
+    /// Dropping or aborting the returned future doesn't stop the call, so a
+    /// handler that outlives its timeout still runs to completion.
+    async fn run_blocking<T, F>(handler: Arc<F>, envelope: EventEnvelope<T>) -> Result<ProcessingResult, Box<dyn Error + Send + Sync>>
+    where
+        T: Event,
+        F: Fn(EventEnvelope<T>) -> Result<ProcessingResult, Box<dyn Error + Send + Sync>> + Send + Sync + 'static,
+    {
+        tokio::task::spawn_blocking(move || handler(envelope))
+            .await
+            .unwrap_or_else(|join_error| Err(Box::new(join_error)))
+    }
+
+    /// Process a single event envelope with the provided handler
+    /// 
+    /// This implements the core event processing logic including retry
+    /// and dead letter queue handling based on the processing result.
+    /// 
+    /// The handler's future runs on its own task, so a panic is reported as
+    /// a handler error instead of ending the consumer loop. One that runs
+    /// longer than `handler_timeout` is cancelled and treated as a retryable
+    /// failure so the partition keeps moving. Synchronous handlers given to
+    /// `subscribe` run on the blocking thread pool, where the call itself
+    /// can't be cancelled and finishes in the background.
+    /// 
+    /// Events older than `max_age` skip the handler and go straight to the
+    /// dead letter queue.
+    async fn process_event_envelope<T, F, Fut>(
+        &self,
+        envelope: EventEnvelope<T>,
+        subscribed_topic: &str,
+        handler: &F,
+        handler_timeout: Duration,
+        max_age: Option<Duration>,
+        breaker: &CircuitBreaker,
+    ) -> Result<bool, EventBusError>
     where
         T: Event,
-        F: Fn(EventEnvelope<T>) -> Result<ProcessingResult, Box<dyn Error + Send + Sync>>,
+        F: Fn(EventEnvelope<T>) -> Fut,
+        Fut: Future<Output = Result<ProcessingResult, Box<dyn Error + Send + Sync>>> + Send + 'static,
     {
         let event_id = envelope.event_id.clone();
-        let topic = T::TOPIC;
+        let correlation_id = envelope.correlation_id.clone();
+        // The sub-topic the event came from, so retries, dead letters and
+        // metrics keep its priority routing
+        let topic = subscribed_topic;
         
-        debug!("🔄 Processing event {} from topic {}", event_id, topic);
+        debug!(%event_id, %correlation_id, topic, "🔄 Processing event");
+        self.metrics.record_consume(topic);
+        
+        if let Some(max_age) = max_age {
+            let age = envelope.age_at(self.clock.now());
+            if age > max_age {
+                warn!(
+                    %event_id,
+                    %correlation_id,
+                    age_ms = age.as_millis() as u64,
+                    max_age_ms = max_age.as_millis() as u64,
+                    "⌛ Event is too old to process, sending to DLQ",
+                );
+                let mut envelope = envelope;
+                envelope.add_metadata("dlq_reason".to_string(), "max_age_exceeded".to_string());
+                envelope.add_metadata("dlq_age_ms".to_string(), age.as_millis().to_string());
+                self.send_to_dead_letter_queue(envelope, topic).await?;
+                return Ok(true); // Commit the offset
+            }
+        }
         
         // Call the user's handler function
-        match handler(envelope.clone()) {
+        let mut task = tokio::spawn(handler(envelope.clone()));
+        let outcome = tokio::time::timeout(handler_timeout, &mut task).await;
+        
+        let result = match outcome {
+            Ok(Ok(result)) => result,
+            Ok(Err(join_error)) => Err(Box::new(join_error) as Box<dyn Error + Send + Sync>),
+            Err(_) => {
+                task.abort();
+                warn!(
+                    %event_id,
+                    %correlation_id,
+                    timeout_ms = handler_timeout.as_millis() as u64,
+                    "⏱️ Handler timed out",
+                );
+                Ok(ProcessingResult::retry(format!("handler timed out after {}ms", handler_timeout.as_millis())))
+            }
+        };
+        
+        match result {
             Ok(ProcessingResult::Success) => {
-                debug!("✅ Event {} processed successfully", event_id);
+                debug!(%event_id, %correlation_id, "✅ Event processed");
+                breaker.record_success();
+                self.mark_processed(subscribed_topic).await;
+                Ok(true) // Commit the offset
+            }
+            Ok(ProcessingResult::Skip(reason)) => {
+                debug!(%event_id, %correlation_id, reason = %reason, "⏭️ Event skipped by handler");
+                self.metrics.record_skip(topic);
+                // The handler ran fine, it just had nothing to do
+                breaker.record_success();
+                self.mark_processed(subscribed_topic).await;
                 Ok(true) // Commit the offset
             }
             Ok(ProcessingResult::RetryableError(error_msg)) => {
-                warn!("🔄 Event {} failed with retryable error: {}", event_id, error_msg);
+                warn!(%event_id, %correlation_id, error = %error_msg, "🔄 Event failed with retryable error");
+                Self::record_breaker_failure(breaker, topic);
                 
                 // Check if we should retry or send to DLQ
                 if envelope.should_dead_letter() {
-                    error!("💀 Event {} exceeded retry limit, sending to DLQ", event_id);
-                    self.send_to_dead_letter_queue(envelope).await?;
+                    error!(%event_id, %correlation_id, attempt = envelope.attempt_count, "💀 Event exceeded retry limit, sending to DLQ");
+                    self.send_to_dead_letter_queue(envelope, topic).await?;
                 } else {
-                    info!("⏰ Event {} will be retried (attempt {})", event_id, envelope.attempt_count + 1);
-                    self.send_to_retry_queue(envelope).await?;
+                    info!(%event_id, %correlation_id, attempt = envelope.attempt_count + 1, "⏰ Event will be retried");
+                    self.send_to_retry_queue(envelope, topic).await?;
                 }
                 Ok(true) // Commit the offset (we've handled the error)
             }
             Ok(ProcessingResult::PermanentError(error_msg)) => {
-                error!("💀 Event {} failed with permanent error: {}", event_id, error_msg);
-                self.send_to_dead_letter_queue(envelope).await?;
+                error!(%event_id, %correlation_id, error = %error_msg, "💀 Event failed with permanent error");
+                self.send_to_dead_letter_queue(envelope, topic).await?;
                 Ok(true) // Commit the offset
             }
             Err(handler_error) => {
-                error!("❌ Handler threw exception for event {}: {}", event_id, handler_error);
+                error!(%event_id, %correlation_id, error = %handler_error, "❌ Handler threw exception");
+                Self::record_breaker_failure(breaker, topic);
                 // Treat handler exceptions as retryable errors
                 if envelope.should_dead_letter() {
-                    self.send_to_dead_letter_queue(envelope).await?;
+                    self.send_to_dead_letter_queue(envelope, topic).await?;
                 } else {
-                    self.send_to_retry_queue(envelope).await?;
+                    self.send_to_retry_queue(envelope, topic).await?;
                 }
                 Ok(true) // Commit the offset
             }
         }
     }
 
+    /// Record that a subscription to `topic` just handled an event
+    async fn mark_processed(&self, topic: &str) {
+        let now = self.clock.now();
+        self.last_processed.write().await.insert(topic.to_string(), now);
+    }
+    
+    /// Count a retryable failure against a subscription's circuit breaker
+    fn record_breaker_failure(breaker: &CircuitBreaker, topic: &str) {
+        if breaker.record_failure() {
+            error!(
+                topic,
+                consecutive_failures = breaker.consecutive_failures(),
+                "🔌 Circuit breaker opened after consecutive handler failures",
+            );
+        }
+    }
+
     /// Send a failed event to the retry queue for delayed reprocessing
-    async fn send_to_retry_queue<T>(&self, mut envelope: EventEnvelope<T>) -> Result<(), EventBusError>
+    ///
+    /// `source_topic` is the topic the event was consumed from, recorded as
+    /// its `original_topic`.
+    async fn send_to_retry_queue<T>(&self, mut envelope: EventEnvelope<T>, source_topic: &str) -> Result<(), EventBusError>
     where
         T: Event,
     {
         let retry_topic = format!("{}.retry", T::TOPIC);
+        self.metrics.record_retry(source_topic);
         envelope.increment_attempt();
         
         // Add retry metadata
         envelope.add_metadata("retry_reason".to_string(), "retryable_error".to_string());
-        envelope.add_metadata("original_topic".to_string(), T::TOPIC.to_string());
+        envelope.add_metadata("original_topic".to_string(), source_topic.to_string());
         envelope.add_metadata("retry_attempt".to_string(), envelope.attempt_count.to_string());
         
         let key = envelope.partition_key().unwrap_or(envelope.event_id.clone());
-        let payload = serde_json::to_string(&envelope)
-            .map_err(|e| EventBusError::SerializationError(format!("Failed to serialize retry event: {}", e)))?;
+        let format = self.config.serialization_format;
+        let payload = format.serialize(&envelope)?;
         
         let record = FutureRecord::to(&retry_topic)
             .key(&key)
-            .payload(&payload);
+            .payload(&payload)
+            .headers(Self::envelope_headers(&envelope, format));
         
         let timeout = Timeout::After(Duration::from_millis(self.config.timeout_ms));
         
         match self.producer.send(record, timeout).await {
             Ok(_) => {
-                info!("📮 Event {} sent to retry queue {}", envelope.event_id, retry_topic);
+                info!(
+                    event_id = %envelope.event_id,
+                    correlation_id = %envelope.correlation_id,
+                    topic = %retry_topic,
+                    "📮 Event sent to retry queue",
+                );
                 Ok(())
             }
             Err((kafka_error, _)) => {
-                error!("❌ Failed to send event {} to retry queue: {}", envelope.event_id, kafka_error);
+                error!(
+                    event_id = %envelope.event_id,
+                    correlation_id = %envelope.correlation_id,
+                    topic = %retry_topic,
+                    error = %kafka_error,
+                    "❌ Failed to send event to retry queue",
+                );
                 Err(EventBusError::PublishFailed(format!("Retry queue send error: {}", kafka_error)))
             }
         }
     }
     
+    /// Route a record with an incompatible schema version to `{topic}.version-mismatch`
+    ///
+    /// The record is forwarded verbatim (key, payload and headers) since it
+    /// can't be deserialized into the consumer's event type. An extra header
+    /// records which version the consumer expected.
+    async fn send_to_version_mismatch_queue<T>(&self, message: &BorrowedMessage<'_>) -> Result<(), EventBusError>
+    where
+        T: Event,
+    {
+        let mismatch_topic = format!("{}.version-mismatch", T::TOPIC);
+        
+        let headers = message
+            .headers()
+            .map(|headers| headers.detach())
+            .unwrap_or_default()
+            .insert(Header { key: HEADER_EXPECTED_VERSION, value: Some(T::VERSION) });
+        
+        let mut record: FutureRecord<'_, [u8], [u8]> = FutureRecord::to(&mismatch_topic)
+            .headers(headers);
+        if let Some(key) = message.key() {
+            record = record.key(key);
+        }
+        if let Some(payload) = message.payload() {
+            record = record.payload(payload);
+        }
+        
+        let timeout = Timeout::After(Duration::from_millis(self.config.timeout_ms));
+        
+        match self.producer.send(record, timeout).await {
+            Ok(_) => {
+                warn!(topic = %mismatch_topic, "🧪 Incompatible event quarantined");
+                Ok(())
+            }
+            Err((kafka_error, _)) => {
+                error!(topic = %mismatch_topic, error = %kafka_error, "❌ Failed to quarantine incompatible event");
+                Err(EventBusError::PublishFailed(format!("Version mismatch queue send error: {}", kafka_error)))
+            }
+        }
+    }
+    
+    /// Route a record over the subscription's `max_payload_bytes` to `{topic}.oversized`
+    ///
+    /// The record is forwarded verbatim (key, payload and headers) without
+    /// ever being deserialized, so a huge payload can't blow up memory on
+    /// the consumer.
+    async fn send_to_oversized_queue<T>(&self, message: &BorrowedMessage<'_>) -> Result<(), EventBusError>
+    where
+        T: Event,
+    {
+        let oversized_topic = format!("{}.oversized", T::TOPIC);
+        
+        let headers = message
+            .headers()
+            .map(|headers| headers.detach())
+            .unwrap_or_default();
+        
+        let mut record: FutureRecord<'_, [u8], [u8]> = FutureRecord::to(&oversized_topic)
+            .headers(headers);
+        if let Some(key) = message.key() {
+            record = record.key(key);
+        }
+        if let Some(payload) = message.payload() {
+            record = record.payload(payload);
+        }
+        
+        match self.send_record(&oversized_topic, record).await {
+            Ok(_) => {
+                warn!(topic = %oversized_topic, "🐘 Oversized record diverted");
+                Ok(())
+            }
+            Err(kafka_error) => {
+                error!(topic = %oversized_topic, error = %kafka_error, "❌ Failed to divert oversized record");
+                Err(EventBusError::PublishFailed(format!("Oversized queue send error: {}", kafka_error)))
+            }
+        }
+    }
+    
     /// Send a failed event to the dead letter queue for investigation
-    async fn send_to_dead_letter_queue<T>(&self, mut envelope: EventEnvelope<T>) -> Result<(), EventBusError>
+    ///
+    /// `source_topic` is the topic the event was consumed from, recorded as
+    /// its `original_topic` so a replay goes back to the same sub-topic.
+    async fn send_to_dead_letter_queue<T>(&self, mut envelope: EventEnvelope<T>, source_topic: &str) -> Result<(), EventBusError>
     where
         T: Event,
     {
         let dlq_topic = format!("{}.dlq", T::TOPIC);
+        self.metrics.record_dlq(source_topic);
         
-        // Add DLQ metadata
-        envelope.add_metadata("dlq_reason".to_string(), "max_retries_exceeded".to_string());
-        envelope.add_metadata("original_topic".to_string(), T::TOPIC.to_string());
+        // Add DLQ metadata, keeping a more specific reason set by the caller
+        envelope.metadata
+            .entry("dlq_reason".to_string())
+            .or_insert_with(|| "max_retries_exceeded".to_string());
+        envelope.add_metadata("original_topic".to_string(), source_topic.to_string());
         envelope.add_metadata("final_attempt_count".to_string(), envelope.attempt_count.to_string());
-        envelope.add_metadata("dlq_timestamp".to_string(), chrono::Utc::now().to_rfc3339());
+        envelope.add_metadata("dlq_timestamp".to_string(), self.clock.now().to_rfc3339());
         
         let key = envelope.partition_key().unwrap_or(envelope.event_id.clone());
-        let payload = serde_json::to_string(&envelope)
-            .map_err(|e| EventBusError::SerializationError(format!("Failed to serialize DLQ event: {}", e)))?;
+        let format = self.config.serialization_format;
+        let mut payload = format.serialize(&envelope)?;
+        
+        let max_bytes = self.config.dlq_max_payload_bytes;
+        if max_bytes > 0 && payload.len() > max_bytes {
+            warn!(
+                event_id = %envelope.event_id,
+                correlation_id = %envelope.correlation_id,
+                size = payload.len(),
+                max_bytes,
+                "✂️ Dead letter payload too large, truncating event data",
+            );
+            payload = Self::truncated_dead_letter(&envelope, format, payload.len(), max_bytes)?;
+        }
         
         let record = FutureRecord::to(&dlq_topic)
             .key(&key)
-            .payload(&payload);
+            .payload(&payload)
+            .headers(Self::envelope_headers(&envelope, format));
         
         let timeout = Timeout::After(Duration::from_millis(self.config.timeout_ms));
         
         match self.producer.send(record, timeout).await {
             Ok(_) => {
-                warn!("💀 Event {} sent to dead letter queue {}", envelope.event_id, dlq_topic);
+                warn!(
+                    event_id = %envelope.event_id,
+                    correlation_id = %envelope.correlation_id,
+                    topic = %dlq_topic,
+                    "💀 Event sent to dead letter queue",
+                );
                 Ok(())
             }
             Err((kafka_error, _)) => {
-                error!("❌ Failed to send event {} to DLQ: {}", envelope.event_id, kafka_error);
+                error!(
+                    event_id = %envelope.event_id,
+                    correlation_id = %envelope.correlation_id,
+                    topic = %dlq_topic,
+                    error = %kafka_error,
+                    "❌ Failed to send event to DLQ",
+                );
                 Err(EventBusError::PublishFailed(format!("DLQ send error: {}", kafka_error)))
             }
         }
     }
 
-}
-
 
-#[allow(async_fn_in_trait)]
-impl EventBus for KafkaEventBus {
-    type Error = EventBusError;
-    
-    /// Publish a single event to the appropriate Kafka topic
-    async fn publish<T>(&self, event: T) -> Result<(), Self::Error>
+    /// Serialize a dead letter record with the event data cut down to fit `max_bytes`
+    ///
+    /// The envelope fields and metadata are kept as-is; `data` is replaced by
+    /// `dlq_truncated`, a prefix of the data's JSON text, and the original
+    /// payload size is recorded in the `dlq_original_bytes` metadata. These
+    /// records no longer decode as `EventEnvelope<T>`, so `replay_dead_letters`
+    /// skips them.
+    fn truncated_dead_letter<T>(
+        envelope: &EventEnvelope<T>,
+        format: SerializationFormat,
+        original_bytes: usize,
+        max_bytes: usize,
+    ) -> Result<Vec<u8>, EventBusError>
     where
         T: Event,
     {
-        let envelope = EventEnvelope::new(event);
-        self.publish_envelope(envelope).await
+        let mut metadata = envelope.metadata.clone();
+        metadata.insert("dlq_original_bytes".to_string(), original_bytes.to_string());
+        
+        let data = serde_json::to_string(&envelope.data)
+            .map_err(|e| EventBusError::SerializationError(format!("JSON encode failed: {}", e)))?;
+        let mut keep = data.len().min(max_bytes);
+        
+        loop {
+            while !data.is_char_boundary(keep) {
+                keep -= 1;
+            }
+            
+            let record = TruncatedDeadLetter {
+                event_id: &envelope.event_id,
+                timestamp: envelope.timestamp,
+                event_type: &envelope.event_type,
+                version: &envelope.version,
+                metadata: &metadata,
+                attempt_count: envelope.attempt_count,
+                max_attempts: envelope.max_attempts,
+                correlation_id: &envelope.correlation_id,
+                dlq_truncated: &data[..keep],
+            };
+            let payload = format.serialize(&record)?;
+            
+            if payload.len() <= max_bytes {
+                return Ok(payload);
+            }
+            if keep == 0 {
+                return Err(EventBusError::PublishFailed(format!(
+                    "Dead letter envelope needs {} bytes even without event data (limit {})",
+                    payload.len(),
+                    max_bytes
+                )));
+            }
+            // Escaping can make the data grow when embedded, so shrink by the overshoot and retry
+            keep = keep.saturating_sub(payload.len() - max_bytes);
+        }
     }
-    
-    /// Publish multiple events efficiently as a batch
-    async fn publish_batch<T>(&self, events: Vec<T>) -> Result<(), Self::Error>
+
+    /// Replay events from the dead letter queue back onto their original topic
+    ///
+    /// Consumes up to `limit` events from `{topic}.dlq`, strips the DLQ
+    /// bookkeeping metadata, resets the attempt counter and republishes each
+    /// one to the topic it was consumed from (its `original_topic`, falling
+    /// back to the event's routing topic). Offsets are committed under a dedicated replay
+    /// consumer group, so successive calls continue where the last one
+    /// stopped. Only events present when the call starts are considered:
+    /// reading stops at each partition's high-water mark, so events that
+    /// fail again and land back in the DLQ aren't replayed in a loop.
+    ///
+    /// Returns the number of events replayed.
+    pub async fn replay_dead_letters<T>(&self, limit: usize) -> Result<usize, EventBusError>
     where
         T: Event,
     {
-        if events.is_empty() {
-            return Ok(());
-        }
+        let dlq_topic = format!("{}.dlq", T::TOPIC);
+        let consumer_group = self.consumer_group_id("dlq-replay");
+        let timeout = Duration::from_millis(self.config.timeout_ms);
         
-        info!("📦 Publishing batch of {} events", events.len());
+        info!("♻️ Replaying up to {} events from {}", limit, dlq_topic);
         
-        // Convert all events to envelopes and publish them
-        let mut publish_futures = Vec::new();
-        for event in events {
-            let envelope = EventEnvelope::new(event);
-            publish_futures.push(self.publish_envelope(envelope));
+        if limit == 0 {
+            return Ok(0);
         }
         
-        // Wait for all publishes to complete
-        let results = join_all(publish_futures).await;
+        let consumer = Arc::new(self.create_consumer(&consumer_group, None)?);
         
-        // Check if any failed
-        for (i, result) in results.into_iter().enumerate() {
-            if let Err(e) = result {
-                error!("❌ Event {} in batch failed to publish: {}", i, e);
-                return Err(e);
+        // Metadata and offset lookups are blocking librdkafka calls
+        let mut pending = tokio::task::spawn_blocking({
+            let consumer = consumer.clone();
+            let dlq_topic = dlq_topic.clone();
+            move || Self::assign_pending_range(&consumer, &dlq_topic, timeout)
+        })
+        .await
+        .map_err(|e| EventBusError::ConsumerError(format!("DLQ replay setup task failed: {}", e)))??;
+        
+        let mut replayed = 0;
+        
+        while replayed < limit && !pending.is_empty() {
+            let message = match tokio::time::timeout(timeout, consumer.recv()).await {
+                Ok(Ok(message)) => message,
+                Ok(Err(e)) => {
+                    return Err(EventBusError::ConsumerError(format!("Failed to read from {}: {}", dlq_topic, e)));
+                }
+                Err(_) => {
+                    warn!("⏱️ Timed out waiting for DLQ events, stopping replay early");
+                    break;
+                }
+            };
+            
+            let partition = message.partition();
+            let offset = message.offset();
+            
+            let Some(&high_watermark) = pending.get(&partition) else {
+                continue;
+            };
+            if offset >= high_watermark {
+                // Arrived after the replay started
+                pending.remove(&partition);
+                continue;
+            }
+            
+            match message.payload().map(|payload| Self::decode_envelope::<T, _>(message.headers(), payload)) {
+                Some(Ok(envelope)) => {
+                    let target = Self::replay_topic(&envelope);
+                    let envelope = Self::prepare_for_replay(envelope);
+                    debug!("♻️ Replaying event {} to {}", envelope.event_id, target);
+                    self.publish_envelope_to(envelope, &target, None).await?;
+                    replayed += 1;
+                }
+                Some(Err(e)) => {
+                    error!("❌ Skipping undeserializable DLQ event at {}/{}: {}", partition, offset, e);
+                }
+                None => {
+                    warn!("📭 Skipping empty DLQ event at {}/{}", partition, offset);
+                }
+            }
+            
+            consumer.commit_message(&message, rdkafka::consumer::CommitMode::Sync)
+                .map_err(|e| EventBusError::ConsumerError(format!("Failed to commit DLQ offset: {}", e)))?;
+            
+            if offset + 1 >= high_watermark {
+                pending.remove(&partition);
             }
         }
         
-        info!("✅ Batch publishing completed successfully");
-        Ok(())
+        info!("✅ Replayed {} events from {}", replayed, dlq_topic);
+        Ok(replayed)
     }
     
-    /// Subscribe to events with a single-event handler
-    async fn subscribe<T, F>(&self, config: SubscriptionConfig, handler: F) -> Result<(), Self::Error>
+    /// Read dead-lettered events without replaying or committing them
+    ///
+    /// Returns up to `limit` records from `{topic}.dlq`, starting where the
+    /// next `replay_dead_letters` call would, so ops can check what a replay
+    /// is about to republish. Nothing is committed, so peeking twice returns
+    /// the same records. Records that no longer decode as `EventEnvelope<T>`
+    /// (such as truncated oversized ones) are logged and left out.
+    pub async fn peek_dead_letters<T>(&self, limit: usize) -> Result<Vec<DeadLetterRecord<T>>, EventBusError>
     where
         T: Event,
-        F: Fn(EventEnvelope<T>) -> Result<ProcessingResult, Box<dyn Error + Send + Sync>> 
-            + Send 
-            + Sync 
-            + 'static,
     {
-        let topic = T::TOPIC;
-        let consumer_group = format!("{}-{}", self.config.consumer_group_id, config.consumer_group);
-        
-        info!("🎯 Starting subscription to topic {} with consumer group {}", topic, consumer_group);
-        
-        // Create consumer
-        let consumer = Arc::new(self.create_consumer(&consumer_group)?);
-        
-        // Subscribe to the topic
-        consumer.subscribe(&[topic])
-            .map_err(|e| EventBusError::SubscriptionFailed(format!("Failed to subscribe to topic {}: {}", topic, e)))?;
+        let dlq_topic = format!("{}.dlq", T::TOPIC);
+        // Only reads the replay group's offsets, never moves them
+        let consumer_group = self.consumer_group_id("dlq-replay");
+        let timeout = Duration::from_millis(self.config.timeout_ms);
         
-        // Store consumer reference for shutdown coordination
-        {
-            let mut consumers = self.consumers.write().await;
-            consumers.insert(consumer_group.clone(), consumer.clone());
+        let mut records = Vec::new();
+        if limit == 0 {
+            return Ok(records);
         }
         
-        // Clone necessary references for the async task
-        let event_bus = Arc::new(self.clone());
-        let shutdown_rx = self.shutdown_receiver.clone();
+        let consumer = Arc::new(self.create_consumer(&consumer_group, None)?);
         
-        // Spawn the consumer loop
-        tokio::spawn(async move {
-            info!("🔄 Consumer loop starting for topic {}", topic);
-            
-            loop {
-                // Check for shutdown signal
-                if shutdown_rx.has_changed().unwrap_or(false) && *shutdown_rx.borrow() {
-                    info!("🛑 Shutdown signal received for consumer {}", consumer_group);
+        let mut pending = tokio::task::spawn_blocking({
+            let consumer = consumer.clone();
+            let dlq_topic = dlq_topic.clone();
+            move || Self::assign_pending_range(&consumer, &dlq_topic, timeout)
+        })
+        .await
+        .map_err(|e| EventBusError::ConsumerError(format!("DLQ peek setup task failed: {}", e)))??;
+        
+        while records.len() < limit && !pending.is_empty() {
+            let message = match tokio::time::timeout(timeout, consumer.recv()).await {
+                Ok(Ok(message)) => message,
+                Ok(Err(e)) => {
+                    return Err(EventBusError::ConsumerError(format!("Failed to read from {}: {}", dlq_topic, e)));
+                }
+                Err(_) => {
+                    warn!("⏱️ Timed out waiting for DLQ events, returning what was read");
                     break;
                 }
-                
-                // Poll for messages
-                match consumer.recv().await {
-                    Ok(message) => {
-                        // Extract message payload
-                        let payload = match message.payload() {
-                            Some(p) => p,
-                            None => {
-                                warn!("📭 Received empty message, skipping");
-                                continue;
-                            }
-                        };
-                        
-                        // Deserialize event envelope
-                        let envelope: EventEnvelope<T> = match serde_json::from_slice(payload) {
-                            Ok(env) => env,
-                            Err(e) => {
-                                error!("❌ Failed to deserialize message: {}", e);
-                                // Commit the offset to skip this bad message
-                                if let Err(commit_err) = consumer.commit_message(&message, rdkafka::consumer::CommitMode::Async) {
-                                    error!("❌ Failed to commit offset for bad message: {}", commit_err);
-                                }
-                                continue;
-                            }
-                        };
-                        
-                        // Process the event
-                        match event_bus.process_event_envelope(envelope, &handler).await {
-                            Ok(should_commit) => {
-                                if should_commit {
-                                    // Commit the offset to mark this message as processed
-                                    if let Err(e) = consumer.commit_message(&message, rdkafka::consumer::CommitMode::Async) {
-                                        error!("❌ Failed to commit offset: {}", e);
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                error!("❌ Failed to process event: {}", e);
-                                // Still commit to avoid reprocessing the same message
-                                if let Err(commit_err) = consumer.commit_message(&message, rdkafka::consumer::CommitMode::Async) {
-                                    error!("❌ Failed to commit offset after processing error: {}", commit_err);
-                                }
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        error!("❌ Error receiving message: {}", e);
-                        // Sleep briefly to avoid tight loop on persistent errors
-                        tokio::time::sleep(Duration::from_millis(1000)).await;
-                    }
+            };
+            
+            let partition = message.partition();
+            let offset = message.offset();
+            
+            let Some(&high_watermark) = pending.get(&partition) else {
+                continue;
+            };
+            if offset >= high_watermark {
+                pending.remove(&partition);
+                continue;
+            }
+            
+            match message.payload().map(|payload| Self::decode_envelope::<T, _>(message.headers(), payload)) {
+                Some(Ok(envelope)) => records.push(DeadLetterRecord::from_envelope(envelope, partition, offset)),
+                Some(Err(e)) => {
+                    warn!("⚠️ Leaving out undeserializable DLQ event at {}/{}: {}", partition, offset, e);
+                }
+                None => {
+                    warn!("📭 Leaving out empty DLQ event at {}/{}", partition, offset);
                 }
             }
             
-            info!("🏁 Consumer loop ended for topic {}", topic);
-        });
+            if offset + 1 >= high_watermark {
+                pending.remove(&partition);
+            }
+        }
         
-        info!("✅ Subscription started successfully for topic {}", topic);
-        Ok(())
+        debug!("🔍 Peeked at {} events in {}", records.len(), dlq_topic);
+        Ok(records)
     }
     
-    /// Subscribe with batch processing (placeholder - would implement similar to single event)
-    async fn subscribe_batch<T, F>(&self, _config: SubscriptionConfig, _handler: F) -> Result<(), Self::Error>
-    where
-        T: Event,
-        F: Fn(Vec<EventEnvelope<T>>) -> Result<Vec<ProcessingResult>, Box<dyn Error + Send + Sync>> 
-            + Send 
-            + Sync 
-            + 'static,
-    {
-        // TODO: Implement batch processing
-        Err(EventBusError::SubscriptionFailed("Batch subscription not yet implemented".to_string()))
+    /// List the partition IDs of `topic_name`
+    fn topic_partitions(
+        consumer: &StreamConsumer,
+        topic_name: &str,
+        timeout: Duration,
+    ) -> Result<Vec<i32>, EventBusError> {
+        let metadata = consumer.fetch_metadata(Some(topic_name), timeout)
+            .map_err(|e| EventBusError::ConsumerError(format!("Failed to fetch metadata for {}: {}", topic_name, e)))?;
+        
+        let partitions: Vec<i32> = metadata.topics()
+            .iter()
+            .filter(|topic| topic.name() == topic_name)
+            .flat_map(|topic| topic.partitions().iter().map(|partition| partition.id()))
+            .collect();
+        
+        if partitions.is_empty() {
+            return Err(EventBusError::TopicNotFound(topic_name.to_string()));
+        }
+        Ok(partitions)
     }
     
-    /// Check if the Kafka connection is healthy
-    async fn health_check(&self) -> Result<(), Self::Error> {
-        debug!("🏥 Performing Kafka health check");
+    /// Find the offset of the first event at or after `timestamp_ms` in
+    /// each of `partitions`
+    ///
+    /// Partitions with nothing that recent get their high-water mark, so
+    /// reading from there only sees events written later.
+    fn offsets_at_timestamp(
+        consumer: &StreamConsumer,
+        topic_name: &str,
+        partitions: &[i32],
+        timestamp_ms: i64,
+        timeout: Duration,
+    ) -> Result<TopicPartitionList, EventBusError> {
+        let mut query = TopicPartitionList::new();
+        for &partition in partitions {
+            query.add_partition_offset(topic_name, partition, Offset::Offset(timestamp_ms))
+                .map_err(|e| EventBusError::ConsumerError(format!("Invalid seek timestamp: {}", e)))?;
+        }
+        let found = consumer.offsets_for_times(query, timeout)
+            .map_err(|e| EventBusError::ConsumerError(format!("Failed to look up offsets for {}: {}", topic_name, e)))?;
         
-        let timeout = Duration::from_millis(self.config.timeout_ms);
-        let metadata_future = tokio::task::spawn_blocking({
-            let producer = self.producer.clone();
-            move || {
-                producer.client().fetch_metadata(None, timeout)
+        let mut offsets = TopicPartitionList::new();
+        for elem in found.elements() {
+            elem.error().map_err(|e| EventBusError::ConsumerError(
+                format!("Failed to look up offset for {}/{}: {}", topic_name, elem.partition(), e)
+            ))?;
+            let offset = match elem.offset() {
+                Offset::Offset(offset) => offset,
+                _ => consumer.fetch_watermarks(topic_name, elem.partition(), timeout)
+                    .map_err(|e| EventBusError::ConsumerError(format!("Failed to fetch watermarks: {}", e)))?
+                    .1,
+            };
+            offsets.add_partition_offset(topic_name, elem.partition(), Offset::Offset(offset))
+                .map_err(|e| EventBusError::ConsumerError(format!("Invalid seek offset: {}", e)))?;
+        }
+        Ok(offsets)
+    }
+    
+    /// Assign every partition of `topic_name` to a consumer, starting at
+    /// the consumer group's committed offsets
+    ///
+    /// Returns the high-water mark of each partition that still has
+    /// unread events. Partitions already drained are left out. Used to read
+    /// a bounded batch for DLQ replay, peeking and transactional processing.
+    fn assign_pending_range(
+        consumer: &StreamConsumer,
+        topic_name: &str,
+        timeout: Duration,
+    ) -> Result<HashMap<i32, i64>, EventBusError> {
+        let partitions = Self::topic_partitions(consumer, topic_name, timeout)?;
+        
+        let mut assignment = TopicPartitionList::new();
+        for partition in &partitions {
+            assignment.add_partition(topic_name, *partition);
+        }
+        let committed = consumer.committed_offsets(assignment, timeout)
+            .map_err(|e| EventBusError::ConsumerError(format!("Failed to fetch committed offsets: {}", e)))?;
+        
+        let mut pending = HashMap::new();
+        let mut assignment = TopicPartitionList::new();
+        for partition in partitions {
+            let (low, high) = consumer.fetch_watermarks(topic_name, partition, timeout)
+                .map_err(|e| EventBusError::ConsumerError(format!("Failed to fetch watermarks: {}", e)))?;
+            
+            let start = match committed.find_partition(topic_name, partition).map(|elem| elem.offset()) {
+                Some(Offset::Offset(offset)) => offset.max(low),
+                _ => low,
+            };
+            
+            if start < high {
+                assignment.add_partition_offset(topic_name, partition, Offset::Offset(start))
+                    .map_err(|e| EventBusError::ConsumerError(format!("Invalid replay offset: {}", e)))?;
+                pending.insert(partition, high);
+            }
+        }
+        
+        consumer.assign(&assignment)
+            .map_err(|e| EventBusError::ConsumerError(format!("Failed to assign DLQ partitions: {}", e)))?;
+        
+        Ok(pending)
+    }
+    
+    /// Topic a dead-lettered event is replayed to
+    ///
+    /// Its recorded `original_topic` when that's `T::TOPIC` or one of its
+    /// sub-topics, otherwise wherever the event routes itself.
+    fn replay_topic<T>(envelope: &EventEnvelope<T>) -> String
+    where
+        T: Event,
+    {
+        envelope.metadata.get("original_topic")
+            .filter(|topic| {
+                topic.as_str() == T::TOPIC
+                    || topic.strip_prefix(T::TOPIC).is_some_and(|rest| rest.starts_with('.'))
+            })
+            .cloned()
+            .unwrap_or_else(|| envelope.data.routing_topic())
+    }
+
+    /// Reset a dead-lettered envelope so it can be processed from scratch
+    fn prepare_for_replay<T>(mut envelope: EventEnvelope<T>) -> EventEnvelope<T>
+    where
+        T: Event,
+    {
+        for key in DLQ_METADATA_KEYS {
+            envelope.metadata.remove(key);
+        }
+        envelope.attempt_count = 0;
+        envelope
+    }
+
+    /// Start a Kafka transaction on the shared producer
+    ///
+    /// Everything published through this bus until `commit_transaction` or
+    /// `abort_transaction` becomes visible to `read_committed` consumers
+    /// (librdkafka's default) all at once, or not at all. The bus has one
+    /// producer, so there is at most one open transaction at a time, and
+    /// while transactions are enabled every publish must happen inside one.
+    /// Requires `KafkaConfig::transactional_id`.
+    pub fn begin_transaction(&self) -> Result<(), EventBusError> {
+        self.require_transactions()?;
+        self.producer.begin_transaction()
+            .map_err(|e| EventBusError::PublishFailed(format!("Failed to begin transaction: {}", e)))?;
+        debug!("🧾 Transaction started");
+        Ok(())
+    }
+    
+    /// Commit the open transaction, making its events visible
+    ///
+    /// Waits for every event published in the transaction to be delivered.
+    /// If this fails the transaction should be aborted.
+    pub async fn commit_transaction(&self) -> Result<(), EventBusError> {
+        self.require_transactions()?;
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        Self::run_transaction_call(self.producer.clone(), "commit", move |producer| producer.commit_transaction(timeout)).await?;
+        debug!("✅ Transaction committed");
+        Ok(())
+    }
+    
+    /// Abort the open transaction, discarding the events published in it
+    pub async fn abort_transaction(&self) -> Result<(), EventBusError> {
+        self.require_transactions()?;
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        Self::run_transaction_call(self.producer.clone(), "abort", move |producer| producer.abort_transaction(timeout)).await?;
+        warn!("↩️ Transaction aborted");
+        Ok(())
+    }
+    
+    /// Consume, handle and commit events from `T::TOPIC` in one transaction
+    ///
+    /// Reads up to `limit` events present when the call starts, resuming
+    /// from `consumer_group`'s committed offsets, and passes each to
+    /// `handler`. Whatever the handler publishes through this bus is written
+    /// in the same transaction as the consumed offsets, so the outputs and
+    /// the progress through `T::TOPIC` become visible together exactly once.
+    /// If the handler fails for any event the whole transaction is aborted
+    /// and the error returned; the next call reads the same events again.
+    /// Events that can't be decoded are logged and skipped.
+    ///
+    /// Returns the number of events handled.
+    pub async fn process_transactionally<T, F, Fut>(
+        &self,
+        consumer_group: &str,
+        limit: usize,
+        handler: F,
+    ) -> Result<usize, EventBusError>
+    where
+        T: Event,
+        F: Fn(EventEnvelope<T>) -> Fut,
+        Fut: Future<Output = Result<(), Box<dyn Error + Send + Sync>>>,
+    {
+        self.require_transactions()?;
+        if limit == 0 {
+            return Ok(0);
+        }
+        
+        let consumer_group = self.consumer_group_id(consumer_group);
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        let consumer = Arc::new(self.create_consumer(&consumer_group, None)?);
+        
+        let pending = tokio::task::spawn_blocking({
+            let consumer = consumer.clone();
+            move || Self::assign_pending_range(&consumer, T::TOPIC, timeout)
+        })
+        .await
+        .map_err(|e| EventBusError::ConsumerError(format!("Transactional consumer setup task failed: {}", e)))??;
+        
+        self.begin_transaction()?;
+        let result = match self.handle_in_transaction(&consumer, pending, limit, &handler).await {
+            Ok(handled) => self.commit_transaction().await.map(|()| handled),
+            Err(e) => Err(e),
+        };
+        
+        match result {
+            Ok(handled) => {
+                info!(topic = T::TOPIC, consumer_group, handled, "🧾 Transactional batch committed");
+                Ok(handled)
+            }
+            Err(e) => {
+                error!(topic = T::TOPIC, consumer_group, error = %e, "❌ Transactional batch failed, aborting");
+                if let Err(abort_error) = self.abort_transaction().await {
+                    error!(topic = T::TOPIC, consumer_group, error = %abort_error, "❌ Failed to abort transaction");
+                }
+                Err(e)
+            }
+        }
+    }
+    
+    /// Handle events from an assigned consumer and add their offsets to the
+    /// open transaction
+    async fn handle_in_transaction<T, F, Fut>(
+        &self,
+        consumer: &Arc<StreamConsumer>,
+        mut pending: HashMap<i32, i64>,
+        limit: usize,
+        handler: &F,
+    ) -> Result<usize, EventBusError>
+    where
+        T: Event,
+        F: Fn(EventEnvelope<T>) -> Fut,
+        Fut: Future<Output = Result<(), Box<dyn Error + Send + Sync>>>,
+    {
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        let mut handled = 0;
+        
+        while handled < limit && !pending.is_empty() {
+            let message = match tokio::time::timeout(timeout, consumer.recv()).await {
+                Ok(Ok(message)) => message,
+                Ok(Err(e)) => {
+                    return Err(EventBusError::ConsumerError(format!("Failed to read from {}: {}", T::TOPIC, e)));
+                }
+                Err(_) => {
+                    warn!("⏱️ Timed out waiting for events, committing what was handled");
+                    break;
+                }
+            };
+            
+            let partition = message.partition();
+            let offset = message.offset();
+            
+            let Some(&high_watermark) = pending.get(&partition) else {
+                continue;
+            };
+            if offset >= high_watermark {
+                pending.remove(&partition);
+                continue;
+            }
+            
+            match message.payload().map(|payload| Self::decode_envelope::<T, _>(message.headers(), payload)) {
+                Some(Ok(envelope)) => {
+                    let event_id = envelope.event_id.clone();
+                    handler(envelope).await.map_err(|e| EventBusError::ConsumerError(
+                        format!("Handler failed for event {} at {}/{}: {}", event_id, partition, offset, e)
+                    ))?;
+                    handled += 1;
+                }
+                Some(Err(e)) => {
+                    error!("❌ Skipping undeserializable event at {}/{}: {}", partition, offset, e);
+                }
+                None => {
+                    warn!("📭 Skipping empty event at {}/{}", partition, offset);
+                }
+            }
+            
+            if offset + 1 >= high_watermark {
+                pending.remove(&partition);
+            }
+        }
+        
+        // The consumer position also steps over transaction markers, which
+        // never reach the handler
+        let positions = consumer.position()
+            .map_err(|e| EventBusError::ConsumerError(format!("Failed to read consumer position: {}", e)))?;
+        let mut offsets = TopicPartitionList::new();
+        for elem in positions.elements_for_topic(T::TOPIC) {
+            if let Offset::Offset(next_offset) = elem.offset() {
+                offsets.add_partition_offset(T::TOPIC, elem.partition(), Offset::Offset(next_offset))
+                    .map_err(|e| EventBusError::ConsumerError(format!("Invalid transaction offset: {}", e)))?;
+            }
+        }
+        if offsets.count() == 0 {
+            return Ok(handled);
+        }
+        
+        let group_metadata = consumer.group_metadata()
+            .ok_or_else(|| EventBusError::ConsumerError("Consumer has no group metadata".to_string()))?;
+        Self::run_transaction_call(self.producer.clone(), "send offsets to", move |producer| {
+            producer.send_offsets_to_transaction(&offsets, &group_metadata, timeout)
+        }).await?;
+        
+        Ok(handled)
+    }
+    
+    /// Error unless the producer was configured with a transactional ID
+    fn require_transactions(&self) -> Result<(), EventBusError> {
+        if self.config.transactional_id.is_none() {
+            return Err(EventBusError::ConfigError(
+                "Transactions need KafkaConfig::transactional_id to be set".to_string()
+            ));
+        }
+        Ok(())
+    }
+    
+    /// Run a blocking producer transaction call on the blocking thread pool
+    async fn run_transaction_call<F>(
+        producer: Arc<FutureProducer>,
+        operation: &str,
+        call: F,
+    ) -> Result<(), EventBusError>
+    where
+        F: FnOnce(&FutureProducer) -> KafkaResult<()> + Send + 'static,
+    {
+        tokio::task::spawn_blocking(move || call(&producer))
+            .await
+            .map_err(|e| EventBusError::PublishFailed(format!("Transaction task failed: {}", e)))?
+            .map_err(|e| EventBusError::PublishFailed(format!("Failed to {} transaction: {}", operation, e)))
+    }
+
+    /// Start forwarding due events from `{topic}.scheduled` to `T::TOPIC`
+    ///
+    /// Events published with `publish_delayed` sit in the scheduled topic
+    /// until a scheduler picks them up, so at least one service must call
+    /// this for each delayed event type. The scheduler joins the
+    /// `{topic}-scheduler` consumer group, so running it in several
+    /// instances splits the work rather than duplicating it. Stop it with
+    /// `unsubscribe("{topic}-scheduler")` or `shutdown()`.
+    ///
+    /// Events are never forwarded early. They can be late, though:
+    /// - The scheduler notices a record up to `KafkaConfig::fetch_wait_max_ms`
+    ///   (500ms by default) after it's written, so very short delays round
+    ///   up to that.
+    /// - Records are released in order within a partition. While the record
+    ///   at the head of a partition isn't due, the partition is paused, so
+    ///   a short delay queued behind a longer one (same partition key, or
+    ///   hash collision) waits for the longer one.
+    /// - Delivery relies on the scheduler's clock (see `with_clock`); clock
+    ///   skew between publisher and scheduler shifts delivery by the same
+    ///   amount. Held records are re-checked at least once a second, so a
+    ///   clock that jumps ahead is noticed within that time.
+    pub async fn start_scheduler<T: Event>(&self) -> Result<(), EventBusError> {
+        let scheduled_topic = format!("{}.scheduled", T::TOPIC);
+        let consumer_group = self.consumer_group_id(&format!("{}-scheduler", T::TOPIC));
+        
+        info!("⏰ Starting scheduler for {} with consumer group {}", scheduled_topic, consumer_group);
+        
+        let consumer = Arc::new(self.create_consumer(&consumer_group, None)?);
+        consumer.subscribe(&[scheduled_topic.as_str()])
+            .map_err(|e| EventBusError::SubscriptionFailed(format!("Failed to subscribe to topic {}: {}", scheduled_topic, e)))?;
+        
+        let event_bus = self.clone();
+        let mut shutdown_rx = self.shutdown_receiver.clone();
+        let (stop_tx, mut stop_rx) = tokio::sync::watch::channel(false);
+        let loop_consumer = consumer.clone();
+        let loop_group = consumer_group.clone();
+        
+        let task = tokio::spawn(async move {
+            let consumer = loop_consumer;
+            let consumer_group = loop_group;
+            // Partitions held back until their head record is due, with when that is
+            let mut held: HashMap<i32, i64> = HashMap::new();
+            
+            loop {
+                let next_due = held.values().min().copied();
+                let wait = next_due
+                    .map(|deliver_at| Self::until_due(event_bus.clock.as_ref(), deliver_at).min(SCHEDULER_RETRY_DELAY))
+                    .unwrap_or(Duration::MAX);
+                
+                let received = tokio::select! {
+                    changed = shutdown_rx.changed() => {
+                        if changed.is_err() || *shutdown_rx.borrow() {
+                            info!("🛑 Shutdown signal received for scheduler {}", consumer_group);
+                            break;
+                        }
+                        continue;
+                    }
+                    _ = stop_rx.changed() => {
+                        info!("🛑 Unsubscribe requested for scheduler {}", consumer_group);
+                        break;
+                    }
+                    _ = tokio::time::sleep(wait), if next_due.is_some() => {
+                        Self::release_due_partitions(&consumer, &mut held, event_bus.clock.as_ref());
+                        continue;
+                    }
+                    received = consumer.recv() => received,
+                };
+                
+                let message = match received {
+                    Ok(message) => message,
+                    Err(e) => {
+                        error!(topic = %consumer_group, error = %e, "❌ Error receiving scheduled record");
+                        tokio::time::sleep(SCHEDULER_RETRY_DELAY).await;
+                        continue;
+                    }
+                };
+                
+                // Already fetched before its partition was held; it's redelivered on resume
+                if held.contains_key(&message.partition()) {
+                    continue;
+                }
+                
+                let deliver_at = message.headers()
+                    .and_then(|headers| Self::header_value(headers, HEADER_DELIVER_AT))
+                    .and_then(|value| value.parse::<i64>().ok());
+                let Some(deliver_at) = deliver_at else {
+                    warn!(
+                        partition = message.partition(),
+                        offset = message.offset(),
+                        "⚠️ Scheduled record has no valid deliver-at header, forwarding now",
+                    );
+                    event_bus.forward_scheduled::<T>(&consumer, &message, &mut held).await;
+                    continue;
+                };
+                
+                if Self::until_due(event_bus.clock.as_ref(), deliver_at).is_zero() {
+                    event_bus.forward_scheduled::<T>(&consumer, &message, &mut held).await;
+                } else {
+                    debug!(
+                        partition = message.partition(),
+                        offset = message.offset(),
+                        deliver_at,
+                        "⏳ Holding scheduled record until due",
+                    );
+                    Self::hold_paused_message(&consumer, &message);
+                    held.insert(message.partition(), deliver_at);
+                }
+            }
+            
+            info!("🏁 Scheduler ended for {}", consumer_group);
+        });
+        
+        let mut consumers = self.consumers.write().await;
+        consumers.insert(consumer_group, ConsumerHandle {
+            consumer,
+            stop_signal: stop_tx,
+            paused: Arc::new(AtomicBool::new(false)),
+            breaker: Arc::new(CircuitBreaker::new(0)),
+            topic: scheduled_topic,
+            started_at: self.clock.now(),
+            stale_after: None,
+            task,
+        });
+        
+        Ok(())
+    }
+    
+    /// Time left on `clock` until a Unix millisecond timestamp, zero once it has passed
+    fn until_due(clock: &dyn Clock, deliver_at_ms: i64) -> Duration {
+        let remaining = deliver_at_ms - clock.now().timestamp_millis();
+        Duration::from_millis(remaining.max(0) as u64)
+    }
+    
+    /// Resume held partitions whose head record is now due
+    ///
+    /// The partitions were rewound when paused, so the due record is the
+    /// next one received.
+    fn release_due_partitions(consumer: &StreamConsumer, held: &mut HashMap<i32, i64>, clock: &dyn Clock) {
+        let Some(topic) = consumer.subscription().ok()
+            .and_then(|subscription| subscription.elements().first().map(|element| element.topic().to_string()))
+        else {
+            return;
+        };
+        
+        held.retain(|&partition, &mut deliver_at| {
+            if !Self::until_due(clock, deliver_at).is_zero() {
+                return true;
+            }
+            let mut partitions = TopicPartitionList::new();
+            partitions.add_partition(&topic, partition);
+            if let Err(e) = consumer.resume(&partitions) {
+                error!("❌ Failed to resume scheduled partition {}/{}: {}", topic, partition, e);
+            }
+            false
+        });
+    }
+    
+    /// Republish a due scheduled record to its topic and commit it
+    ///
+    /// The record goes to the topic in its `deliver-to` header, or `T::TOPIC`
+    /// for records scheduled before that header existed. It's copied
+    /// verbatim apart from the scheduling headers. If
+    /// the send fails the partition is held back briefly and the record is
+    /// tried again, so nothing is committed until it has been forwarded.
+    async fn forward_scheduled<T: Event>(
+        &self,
+        consumer: &StreamConsumer,
+        message: &BorrowedMessage<'_>,
+        held: &mut HashMap<i32, i64>,
+    ) {
+        let topic = message.headers()
+            .and_then(|headers| Self::header_value(headers, HEADER_DELIVER_TO))
+            .unwrap_or_else(|| T::TOPIC.to_string());
+        let topic = topic.as_str();
+        
+        let mut headers = OwnedHeaders::new();
+        if let Some(original) = message.headers() {
+            for header in original.iter().filter(|header| header.key != HEADER_DELIVER_AT && header.key != HEADER_DELIVER_TO) {
+                headers = headers.insert(Header { key: header.key, value: header.value });
+            }
+        }
+        
+        let mut record: FutureRecord<'_, [u8], [u8]> = FutureRecord::to(topic).headers(headers);
+        if let Some(key) = message.key() {
+            record = record.key(key);
+        }
+        if let Some(payload) = message.payload() {
+            record = record.payload(payload);
+        }
+        
+        match self.send_record(topic, record).await {
+            Ok(_) => {
+                debug!(topic, partition = message.partition(), offset = message.offset(), "📬 Scheduled event delivered");
+                if let Err(e) = consumer.commit_message(message, rdkafka::consumer::CommitMode::Async) {
+                    error!("❌ Failed to commit scheduled record: {}", e);
+                }
+            }
+            Err(kafka_error) => {
+                error!(topic, error = %kafka_error, "❌ Failed to forward scheduled event, will retry");
+                Self::hold_paused_message(consumer, message);
+                let retry_at = self.clock.now().timestamp_millis() + SCHEDULER_RETRY_DELAY.as_millis() as i64;
+                held.insert(message.partition(), retry_at);
+            }
+        }
+    }
+
+}
+
+
+#[allow(async_fn_in_trait)]
+impl EventBus for KafkaEventBus {
+    type Error = EventBusError;
+    
+    /// Publish a single event to the appropriate Kafka topic
+    async fn publish<T>(&self, event: T) -> Result<(), Self::Error>
+    where
+        T: Event,
+    {
+        self.publish_with_receipt(event).await?;
+        Ok(())
+    }
+    
+    /// Publish a single event, returning the partition and offset it was written to
+    async fn publish_with_receipt<T>(&self, event: T) -> Result<PublishReceipt, Self::Error>
+    where
+        T: Event,
+    {
+        let envelope = EventEnvelope::with_clock(event, self.clock.as_ref());
+        self.publish_envelope(envelope, None).await
+    }
+    
+    /// Publish a single event, overriding its partition key
+    async fn publish_with_key<T>(&self, event: T, key: String) -> Result<(), Self::Error>
+    where
+        T: Event,
+    {
+        let envelope = EventEnvelope::with_clock(event, self.clock.as_ref());
+        self.publish_envelope(envelope, Some(key)).await?;
+        Ok(())
+    }
+    
+    /// Publish an event to `{topic}.scheduled` for delivery after `delay`
+    ///
+    /// The record carries `deliver-at` and `deliver-to` headers and is moved
+    /// to the event's routing topic by `start_scheduler`, which must be running somewhere. See there for
+    /// how precise delivery is. A zero delay publishes directly.
+    async fn publish_delayed<T>(&self, event: T, delay: Duration) -> Result<(), Self::Error>
+    where
+        T: Event,
+    {
+        if delay.is_zero() {
+            return self.publish(event).await;
+        }
+        
+        let envelope = EventEnvelope::with_clock(event, self.clock.as_ref());
+        let scheduled_topic = format!("{}.scheduled", T::TOPIC);
+        let key = Self::record_key(&envelope, None, self.config.require_partition_key)?;
+        let deliver_at = (self.clock.now().timestamp_millis() + delay.as_millis() as i64).to_string();
+        
+        let format = self.config.serialization_format;
+        let payload = format.serialize(&envelope)?;
+        let deliver_to = envelope.data.routing_topic();
+        let headers = Self::envelope_headers(&envelope, format)
+            .insert(Header { key: HEADER_DELIVER_AT, value: Some(deliver_at.as_str()) })
+            .insert(Header { key: HEADER_DELIVER_TO, value: Some(deliver_to.as_str()) });
+        
+        let record = FutureRecord::to(&scheduled_topic)
+            .key(&key)
+            .payload(&payload)
+            .headers(headers);
+        
+        match self.send_record(&scheduled_topic, record).await {
+            Ok(_) => {
+                debug!(event_id = %envelope.event_id, topic = %scheduled_topic, deliver_at, "⏰ Event scheduled");
+                Ok(())
+            }
+            Err(kafka_error) => {
+                error!(event_id = %envelope.event_id, topic = %scheduled_topic, error = %kafka_error, "❌ Failed to schedule event");
+                Err(EventBusError::PublishFailed(format!("Kafka send error: {}", kafka_error)))
+            }
+        }
+    }
+    
+    /// Publish raw bytes to a topic through the shared producer
+    async fn publish_raw(&self, topic: &str, key: Option<String>, payload: Vec<u8>) -> Result<(), Self::Error> {
+        debug!(topic, bytes = payload.len(), "📤 Publishing raw record");
+        
+        let mut record: FutureRecord<'_, String, Vec<u8>> = FutureRecord::to(topic).payload(&payload);
+        if let Some(key) = &key {
+            record = record.key(key);
+        }
+        
+        self.send_record(topic, record).await.map(|_| ()).map_err(|kafka_error| {
+            error!(topic, error = %kafka_error, "❌ Failed to publish raw record");
+            EventBusError::PublishFailed(format!("Kafka send error: {}", kafka_error))
+        })
+    }
+    
+    /// Publish multiple events efficiently as a batch
+    async fn publish_batch<T>(&self, events: Vec<T>) -> Result<(), Self::Error>
+    where
+        T: Event,
+    {
+        if events.is_empty() {
+            return Ok(());
+        }
+        
+        info!(topic = T::TOPIC, count = events.len(), "📦 Publishing batch");
+        
+        // Convert all events to envelopes and publish them
+        let publish_futures = events
+            .into_iter()
+            .map(|event| self.publish_envelope(EventEnvelope::with_clock(event, self.clock.as_ref()), None));
+        
+        // Wait for all publishes to complete
+        let results = Self::run_bounded(publish_futures, self.config.max_in_flight).await;
+        
+        // Report every failure, not just the first
+        for (i, result) in results.iter().enumerate() {
+            if let Err(e) = result {
+                error!(topic = T::TOPIC, index = i, error = %e, "❌ Event in batch failed to publish");
+            }
+        }
+        
+        BatchPublishError::check(results.into_iter().map(|result| result.map(|_| ())))?;
+        info!(topic = T::TOPIC, "✅ Batch published");
+        Ok(())
+    }
+    
+    /// Subscribe to events with a single-event handler
+    ///
+    /// The handler runs on the blocking thread pool, so it may block
+    /// without stalling the consumer loop. A blocking call can't be
+    /// interrupted: when it passes `handler_timeout_ms` the event goes to
+    /// the retry queue while the call keeps running in the background, so
+    /// its side effects can happen again on the retried delivery.
+    async fn subscribe<T, F>(&self, config: SubscriptionConfig, handler: F) -> Result<(), Self::Error>
+    where
+        T: Event,
+        F: Fn(EventEnvelope<T>) -> Result<ProcessingResult, Box<dyn Error + Send + Sync>> 
+            + Send 
+            + Sync 
+            + 'static,
+    {
+        let handler = Arc::new(handler);
+        self.subscribe_async(config, move |envelope: EventEnvelope<T>| Self::run_blocking(handler.clone(), envelope)).await
+    }
+    
+    /// Subscribe to events with an async single-event handler
+    async fn subscribe_async<T, F, Fut>(&self, config: SubscriptionConfig, handler: F) -> Result<(), Self::Error>
+    where
+        T: Event,
+        F: Fn(EventEnvelope<T>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<ProcessingResult, Box<dyn Error + Send + Sync>>> + Send + 'static,
+    {
+        let subscribed_topic = config.topic::<T>();
+        let consumer_group = self.consumer_group_id(&config.consumer_group);
+        
+        info!("🎯 Starting subscription to topic {} with consumer group {}", subscribed_topic, consumer_group);
+        
+        // Create consumer
+        let consumer = Arc::new(self.create_consumer(&consumer_group, Some(&config))?);
+        
+        // Subscribe to the topic
+        let topic = T::TOPIC;
+        consumer.subscribe(&[subscribed_topic.as_str()])
+            .map_err(|e| EventBusError::SubscriptionFailed(format!("Failed to subscribe to topic {}: {}", subscribed_topic, e)))?;
+        
+        // Clone necessary references for the async task
+        let event_bus = Arc::new(self.clone());
+        let version_policy = config.version_mismatch_policy.clone();
+        let handler_timeout = Duration::from_millis(config.handler_timeout_ms);
+        let max_age = config.max_age();
+        let max_payload_bytes = config.max_payload_bytes;
+        let redact_pii = self.config.redact_pii;
+        let cooldown = Duration::from_millis(config.circuit_breaker_cooldown_ms);
+        let breaker = Arc::new(CircuitBreaker::new(config.circuit_breaker_threshold));
+        let loop_breaker = breaker.clone();
+        let mut shutdown_rx = self.shutdown_receiver.clone();
+        let (stop_tx, mut stop_rx) = tokio::sync::watch::channel(false);
+        let paused = Arc::new(AtomicBool::new(false));
+        let loop_paused = paused.clone();
+        let loop_consumer = consumer.clone();
+        let loop_group = consumer_group.clone();
+        let loop_topic = subscribed_topic.clone();
+        let mut commits = CommitBatch::from_config(&config);
+        
+        // Spawn the consumer loop
+        let task = tokio::spawn(async move {
+            let consumer = loop_consumer;
+            let consumer_group = loop_group;
+            let breaker = loop_breaker;
+            
+            info!("🔄 Consumer loop starting for topic {}", topic);
+            
+            loop {
+                // Stop reading while the circuit is open so a failing
+                // downstream isn't flooded with retries
+                if breaker.state() == CircuitState::Open {
+                    warn!(topic, cooldown_ms = cooldown.as_millis() as u64, "🔌 Circuit open, pausing consumer {}", consumer_group);
+                    // Don't sit on handled offsets for the whole cooldown
+                    if let Some(batch) = commits.as_mut()
+                        && let Err(e) = KafkaEventBus::flush_commits(&consumer, batch, CommitMode::Async)
+                    {
+                        error!(topic, error = %e, "❌ Failed to commit batched offsets");
+                    }
+                    tokio::select! {
+                        changed = shutdown_rx.changed() => {
+                            if changed.is_err() || *shutdown_rx.borrow() {
+                                info!("🛑 Shutdown signal received for consumer {}", consumer_group);
+                                break;
+                            }
+                        }
+                        _ = stop_rx.changed() => {
+                            info!("🛑 Unsubscribe requested for consumer {}", consumer_group);
+                            break;
+                        }
+                        _ = tokio::time::sleep(cooldown) => {}
+                    }
+                    breaker.end_cooldown();
+                    info!(topic, "🔌 Circuit half-open, resuming consumer {}", consumer_group);
+                }
+                
+
+                // Wait for the next message, or a bus-wide / per-consumer stop signal
+                let received = tokio::select! {
+                    changed = shutdown_rx.changed() => {
+                        if changed.is_err() || *shutdown_rx.borrow() {
+                            info!("🛑 Shutdown signal received for consumer {}", consumer_group);
+                            break;
+                        }
+                        continue;
+                    }
+                    _ = stop_rx.changed() => {
+                        info!("🛑 Unsubscribe requested for consumer {}", consumer_group);
+                        break;
+                    }
+                    _ = KafkaEventBus::commit_deadline(commits.as_ref()) => {
+                        if let Some(batch) = commits.as_mut()
+                            && let Err(e) = KafkaEventBus::flush_commits(&consumer, batch, CommitMode::Async)
+                        {
+                            error!(topic, error = %e, "❌ Failed to commit batched offsets");
+                        }
+                        continue;
+                    }
+                    received = consumer.recv() => received,
+                };
+                
+                match received {
+                    Ok(message) => {
+                        if loop_paused.load(Ordering::SeqCst) {
+                            debug!(
+                                topic,
+                                partition = message.partition(),
+                                offset = message.offset(),
+                                "⏸️ Holding back message while paused",
+                            );
+                            KafkaEventBus::hold_paused_message(&consumer, &message);
+                            continue;
+                        }
+                        
+                        // Extract message payload. A null payload is a tombstone
+                        // (a deletion marker on compacted topics) and carries no
+                        // event, so it's committed and skipped rather than re-read
+                        // after every restart.
+                        let payload = match message.payload() {
+                            Some(p) => p,
+                            None => {
+                                debug!(
+                                    topic,
+                                    partition = message.partition(),
+                                    offset = message.offset(),
+                                    key = ?message.key().map(|key| loggable_phone(&String::from_utf8_lossy(key), redact_pii).into_owned()),
+                                    "🪦 Skipping tombstone",
+                                );
+                                if let Err(commit_err) = KafkaEventBus::commit_handled(&consumer, &mut commits, &message) {
+                                    error!("❌ Failed to commit offset for tombstone: {}", commit_err);
+                                }
+                                continue;
+                            }
+                        };
+                        
+                        // Set aside anything over the size limit before any of it is
+                        // parsed, even the version probe. It's only committed once the
+                        // copy is on the oversized topic; until then the partition is
+                        // rewound and the diversion retried after a pause.
+                        if max_payload_bytes > 0 && payload.len() > max_payload_bytes {
+                            warn!(
+                                topic,
+                                partition = message.partition(),
+                                offset = message.offset(),
+                                size = payload.len(),
+                                max_bytes = max_payload_bytes,
+                                "🐘 Payload over the size limit, diverting unparsed",
+                            );
+                            if let Err(e) = event_bus.send_to_oversized_queue::<T>(&message).await {
+                                error!(topic, offset = message.offset(), error = %e, "❌ Failed to divert oversized record, will retry");
+                                KafkaEventBus::rewind_to_message(&consumer, &message);
+                                tokio::time::sleep(OVERSIZED_RETRY_DELAY).await;
+                                continue;
+                            }
+                            if let Err(commit_err) = KafkaEventBus::commit_handled(&consumer, &mut commits, &message) {
+                                error!("❌ Failed to commit offset for oversized message: {}", commit_err);
+                            }
+                            continue;
+                        }
+                        
+                        // Determine the schema version before paying for full deserialization.
+                        // Routing headers are preferred; records produced before headers were
+                        // introduced fall back to probing the payload's `version` field.
+                        let headers = message.headers();
+                        if let Some(headers) = headers {
+                            debug!(
+                                topic,
+                                event_type = ?KafkaEventBus::header_value(headers, HEADER_EVENT_TYPE),
+                                event_id = ?KafkaEventBus::header_value(headers, HEADER_EVENT_ID),
+                                "📨 Received event",
+                            );
+                        }
+                        
+                        if let Some(version) = KafkaEventBus::incoming_version(headers, payload) {
+                            match version_policy.decide(&version, T::VERSION) {
+                                VersionMismatchAction::Process => {}
+                                VersionMismatchAction::Skip => {
+                                    warn!(
+                                        topic,
+                                        %version,
+                                        expected_version = T::VERSION,
+                                        "⚠️ Skipping event with incompatible version",
+                                    );
+                                    if let Err(commit_err) = KafkaEventBus::commit_handled(&consumer, &mut commits, &message) {
+                                        error!("❌ Failed to commit offset for skipped message: {}", commit_err);
+                                    }
+                                    continue;
+                                }
+                                VersionMismatchAction::Quarantine => {
+                                    warn!(
+                                        topic,
+                                        %version,
+                                        expected_version = T::VERSION,
+                                        "⚠️ Quarantining event with incompatible version",
+                                    );
+                                    match event_bus.send_to_version_mismatch_queue::<T>(&message).await {
+                                        Ok(()) => {
+                                            if let Err(commit_err) = KafkaEventBus::commit_handled(&consumer, &mut commits, &message) {
+                                                error!("❌ Failed to commit offset for quarantined message: {}", commit_err);
+                                            }
+                                        }
+                                        Err(e) => {
+                                            // Leave the offset uncommitted so the event isn't lost
+                                            error!("❌ Failed to quarantine incompatible event: {}", e);
+                                            if let Some(batch) = commits.as_mut() {
+                                                batch.hold_back(message.topic(), message.partition(), message.offset());
+                                            }
+                                        }
+                                    }
+                                    continue;
+                                }
+                            }
+                        }
+                        
+                        // Deserialize event envelope
+                        let envelope: EventEnvelope<T> = match KafkaEventBus::decode_envelope(headers, payload) {
+                            Ok(env) => env,
+                            Err(e) => {
+                                error!(
+                                    topic,
+                                    partition = message.partition(),
+                                    offset = message.offset(),
+                                    error = %e,
+                                    "❌ Failed to deserialize message",
+                                );
+                                // Commit the offset to skip this bad message
+                                if let Err(commit_err) = KafkaEventBus::commit_handled(&consumer, &mut commits, &message) {
+                                    error!("❌ Failed to commit offset for bad message: {}", commit_err);
+                                }
+                                continue;
+                            }
+                        };
+                        
+                        // Process the event
+                        let correlation_id = envelope.correlation_id.clone();
+                        let processing = event_bus.process_event_envelope(envelope, &loop_topic, &handler, handler_timeout, max_age, &breaker);
+                        // Continue the producer's trace while handling this event
+                        #[cfg(feature = "otel")]
+                        let processing = tracing::Instrument::instrument(
+                            processing,
+                            crate::trace_context::consumer_span(topic, headers),
+                        );
+                        match processing.await {
+                            Ok(should_commit) => {
+                                if should_commit {
+                                    // Commit the offset to mark this message as processed
+                                    if let Err(e) = KafkaEventBus::commit_handled(&consumer, &mut commits, &message) {
+                                        error!("❌ Failed to commit offset: {}", e);
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                error!(topic, %correlation_id, error = %e, "❌ Failed to process event");
+                                // Still commit to avoid reprocessing the same message
+                                if let Err(commit_err) = KafkaEventBus::commit_handled(&consumer, &mut commits, &message) {
+                                    error!("❌ Failed to commit offset after processing error: {}", commit_err);
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        error!(topic, error = %e, "❌ Error receiving message");
+                        // Sleep briefly to avoid tight loop on persistent errors
+                        tokio::time::sleep(Duration::from_millis(1000)).await;
+                    }
+                }
+            }
+            
+            // Commit whatever was handled since the last batch went out
+            if let Some(batch) = commits.as_mut()
+                && batch.pending() > 0
+            {
+                info!(topic, pending = batch.pending(), "💾 Flushing batched offsets before stopping");
+                if let Err(e) = KafkaEventBus::flush_commits(&consumer, batch, CommitMode::Sync) {
+                    error!(topic, error = %e, "❌ Failed to commit batched offsets");
+                }
+            }
+            
+            info!("🏁 Consumer loop ended for topic {}", topic);
+        });
+        
+        // Store consumer handle for unsubscribe and shutdown coordination.
+        // Replacing an existing handle drops its stop signal, ending that loop.
+        {
+            let mut consumers = self.consumers.write().await;
+            consumers.insert(consumer_group, ConsumerHandle {
+                consumer,
+                stop_signal: stop_tx,
+                paused,
+                breaker,
+                topic: subscribed_topic.clone(),
+                started_at: self.clock.now(),
+                stale_after: config.stale_after(),
+                task,
+            });
+        }
+        
+        info!("✅ Subscription started successfully for topic {}", subscribed_topic);
+        Ok(())
+    }
+    
+    /// Subscribe with batch processing (placeholder - would implement similar to single event)
+    async fn subscribe_batch<T, F>(&self, _config: SubscriptionConfig, _handler: F) -> Result<(), Self::Error>
+    where
+        T: Event,
+        F: Fn(Vec<EventEnvelope<T>>) -> Result<Vec<ProcessingResult>, Box<dyn Error + Send + Sync>> 
+            + Send 
+            + Sync 
+            + 'static,
+    {
+        // TODO: Implement batch processing
+        Err(EventBusError::SubscriptionFailed("Batch subscription not yet implemented".to_string()))
+    }
+    
+    /// Check if the Kafka connection is healthy
+    ///
+    /// A degraded bus (see `KafkaEventBus::health`) still passes, since the
+    /// connection itself is fine; it's logged as a warning.
+    async fn health_check(&self) -> Result<(), Self::Error> {
+        debug!("🏥 Performing Kafka health check");
+        
+        match self.health().await? {
+            HealthStatus::Healthy => debug!("✅ Kafka health check passed"),
+            HealthStatus::Degraded { open_circuits, stale_subscriptions } => {
+                warn!(?open_circuits, ?stale_subscriptions, "⚠️ Kafka health check passed but consumers are degraded");
+            }
+        }
+        Ok(())
+    }
+    
+    /// Gracefully shutdown the event bus
+    async fn shutdown(&self) -> Result<(), Self::Error> {
+        info!("🛑 Initiating graceful shutdown of Kafka event bus");
+        
+        // Signal all consumers to stop
+        let _ = self.shutdown_signal.send(true);
+        
+        // Wait for consumers to finish processing
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        
+        // Clear consumer references
+        {
+            let mut consumers = self.consumers.write().await;
+            consumers.clear();
+        }
+        
+        info!("✅ Kafka event bus shutdown completed");
+        Ok(())
+    }
+}
+
+impl Clone for KafkaEventBus {
+    fn clone(&self) -> Self {
+        Self {
+            producer: self.producer.clone(),
+            config: self.config.clone(),
+            consumers: self.consumers.clone(),
+            metrics: self.metrics.clone(),
+            clock: self.clock.clone(),
+            last_processed: self.last_processed.clone(),
+            shutdown_signal: self.shutdown_signal.clone(),
+            shutdown_receiver: self.shutdown_receiver.clone(),
+        }
+    }
+}
+
+impl Drop for KafkaEventBus {
+    /// Ensure clean shutdown of Kafka producer
+    /// 
+    /// This flushes any pending messages before the producer is dropped.
+    fn drop(&mut self) {
+        debug!("🧹 Cleaning up Kafka event bus");
+        // The producer will flush automatically when dropped, but we could add
+        // explicit flush logic here if needed for more control
+    }
+}
+
+// Add some additional error types for Kafka-specific operations
+#[derive(Debug)]
+pub enum KafkaError {
+    ConnectionFailed(String),
+    TopicNotFound(String),
+    ProducerError(String),
+    ConsumerError(String),
+}
+
+impl std::fmt::Display for KafkaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KafkaError::ConnectionFailed(msg) => write!(f, "Kafka connection failed: {}", msg),
+            KafkaError::TopicNotFound(msg) => write!(f, "Kafka topic not found: {}", msg),
+            KafkaError::ProducerError(msg) => write!(f, "Kafka producer error: {}", msg),
+            KafkaError::ConsumerError(msg) => write!(f, "Kafka consumer error: {}", msg),
+        }
+    }
+}
+
+impl Error for KafkaError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{MessageReceived, MessageType, MessageContent};
+    use crate::metrics::NoopMetrics;
+    use std::sync::Mutex;
+    
+    /// Metrics double that records which hooks fired
+    #[derive(Default)]
+    struct RecordingMetrics {
+        calls: Mutex<Vec<String>>,
+    }
+    
+    impl RecordingMetrics {
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+    
+    impl Metrics for RecordingMetrics {
+        fn record_publish(&self, topic: &str, _duration: Duration, success: bool) {
+            self.calls.lock().unwrap().push(format!("publish:{}:{}", topic, success));
+        }
+        
+        fn record_consume(&self, topic: &str) {
+            self.calls.lock().unwrap().push(format!("consume:{}", topic));
+        }
+        
+        fn record_retry(&self, topic: &str) {
+            self.calls.lock().unwrap().push(format!("retry:{}", topic));
+        }
+        
+        fn record_dlq(&self, topic: &str) {
+            self.calls.lock().unwrap().push(format!("dlq:{}", topic));
+        }
+        
+        fn record_skip(&self, topic: &str) {
+            self.calls.lock().unwrap().push(format!("skip:{}", topic));
+        }
+    }
+    
+    /// Config pointing at an unreachable broker; clients connect lazily
+    fn offline_config(consumer_group_id: &str) -> KafkaConfig {
+        KafkaConfig {
+            bootstrap_servers: "127.0.0.1:1".to_string(),
+            timeout_ms: 3000,
+            consumer_group_id: consumer_group_id.to_string(),
+            topic_partitions: 1,
+            redact_pii: false,
+            ..KafkaConfig::default()
+        }
+    }
+    
+    /// Log events captured by `CaptureLayer`, as field name to rendered value
+    type CapturedLogs = Arc<Mutex<Vec<HashMap<String, String>>>>;
+    
+    /// Tracing layer recording the fields of every log event
+    struct CaptureLayer(CapturedLogs);
+    
+    struct FieldRecorder<'a>(&'a mut HashMap<String, String>);
+    
+    impl tracing::field::Visit for FieldRecorder<'_> {
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+        
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+    
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for CaptureLayer {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: tracing_subscriber::layer::Context<'_, S>) {
+            let mut fields = HashMap::new();
+            event.record(&mut FieldRecorder(&mut fields));
+            self.0.lock().unwrap().push(fields);
+        }
+    }
+    
+    /// Serializes tests that modify process environment variables
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+    
+    #[tokio::test]
+    async fn test_kafka_config_from_env() {
+        let _env = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        
+        // Set test environment variables
+        unsafe {
+            std::env::remove_var("KAFKA_BOOTSTRAP_SERVERS");
+            std::env::set_var("KAFKA_BOOTSTRAP_SERVERS", "localhost:9092");
+            std::env::remove_var("KAFKA_CONSUMER_GROUP_ID");
+            std::env::set_var("KAFKA_CONSUMER_GROUP_ID", "test-group");
+            std::env::remove_var("KAFKA_TIMEOUT_MS");
+            std::env::set_var("KAFKA_TIMEOUT_MS", "3000");
+        }
+        
+        let config = KafkaConfig::from_env().expect("Should create config from env");
+        
+        assert_eq!(config.bootstrap_servers, "localhost:9092");
+        assert_eq!(config.consumer_group_id, "test-group");
+        assert_eq!(config.timeout_ms, 3000);
+    }
+    
+    #[test]
+    fn test_kafka_config_producer_tuning_from_env() {
+        let _env = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let tuning_vars = [
+            "KAFKA_COMPRESSION_TYPE",
+            "KAFKA_BATCH_SIZE",
+            "KAFKA_LINGER_MS",
+            "KAFKA_ACKS",
+            "KAFKA_ENABLE_IDEMPOTENCE",
+        ];
+        
+        unsafe {
+            std::env::set_var("KAFKA_BOOTSTRAP_SERVERS", "localhost:9092");
+            std::env::set_var("KAFKA_CONSUMER_GROUP_ID", "test-group");
+            for var in tuning_vars {
+                std::env::remove_var(var);
+            }
+        }
+        
+        // Defaults match the previously hard-coded producer settings
+        let config = KafkaConfig::from_env().expect("Should create config from env");
+        assert_eq!(config.compression_type, "zstd");
+        assert_eq!(config.batch_size, 65536);
+        assert_eq!(config.linger_ms, 5);
+        assert_eq!(config.acks, "all");
+        assert!(config.enable_idempotence);
+        
+        unsafe {
+            std::env::set_var("KAFKA_COMPRESSION_TYPE", "LZ4");
+            std::env::set_var("KAFKA_BATCH_SIZE", "131072");
+            std::env::set_var("KAFKA_LINGER_MS", "20");
+            std::env::set_var("KAFKA_ACKS", "1");
+            std::env::set_var("KAFKA_ENABLE_IDEMPOTENCE", "false");
+        }
+        let config = KafkaConfig::from_env().expect("Should accept overrides");
+        assert_eq!(config.compression_type, "lz4");
+        assert_eq!(config.batch_size, 131072);
+        assert_eq!(config.linger_ms, 20);
+        assert_eq!(config.acks, "1");
+        assert!(!config.enable_idempotence);
+        
+        unsafe { std::env::set_var("KAFKA_COMPRESSION_TYPE", "brotli"); }
+        assert!(matches!(KafkaConfig::from_env(), Err(EventBusError::ConfigError(_))));
+        
+        unsafe {
+            std::env::set_var("KAFKA_COMPRESSION_TYPE", "gzip");
+            std::env::set_var("KAFKA_LINGER_MS", "soon");
+        }
+        assert!(matches!(KafkaConfig::from_env(), Err(EventBusError::ConfigError(_))));
+        
+        unsafe {
+            for var in tuning_vars {
+                std::env::remove_var(var);
+            }
+        }
+    }
+    
+    #[test]
+    fn test_group_id_default_template() {
+        let config = offline_config("my-ai-service");
+        
+        assert_eq!(config.group_id("responses"), "my-ai-service-responses");
+        assert_eq!(config.group_id("dlq-replay"), "my-ai-service-dlq-replay");
+    }
+    
+    #[test]
+    fn test_group_id_custom_template() {
+        let mut config = offline_config("billing");
+        config.group_id_template = "prod.{base}.{group}".to_string();
+        
+        assert_eq!(config.group_id("responses"), "prod.billing.responses");
+    }
+    
+    #[test]
+    fn test_group_id_template_from_env() {
+        let _env = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        unsafe {
+            std::env::set_var("KAFKA_BOOTSTRAP_SERVERS", "localhost:9092");
+            std::env::set_var("KAFKA_CONSUMER_GROUP_ID", "test-group");
+            std::env::remove_var("KAFKA_GROUP_ID_TEMPLATE");
+        }
+        let config = KafkaConfig::from_env().expect("Should create config from env");
+        assert_eq!(config.group_id_template, DEFAULT_GROUP_ID_TEMPLATE);
+        
+        unsafe { std::env::set_var("KAFKA_GROUP_ID_TEMPLATE", "staging.{base}.{group}"); }
+        let config = KafkaConfig::from_env().expect("Should accept a custom template");
+        assert_eq!(config.group_id("orders"), "staging.test-group.orders");
+        
+        // Every subscription would land in the same group
+        unsafe { std::env::set_var("KAFKA_GROUP_ID_TEMPLATE", "staging.{base}"); }
+        assert!(matches!(KafkaConfig::from_env(), Err(EventBusError::ConfigError(_))));
+        
+        unsafe { std::env::remove_var("KAFKA_GROUP_ID_TEMPLATE"); }
+    }
+    
+    #[tokio::test]
+    async fn test_consumer_fetch_settings_overridable_per_subscription() {
+        let mut config = offline_config("fetch-test");
+        config.max_partition_fetch_bytes = 2_097_152;
+        let bus = KafkaEventBus::new(config, Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        // Internal consumers and plain subscriptions use the bus settings
+        for subscription in [None, Some(&SubscriptionConfig::default())] {
+            let consumer = bus.consumer_config("fetch-test-group", subscription);
+            assert_eq!(consumer.get("fetch.min.bytes"), Some("1024"));
+            assert_eq!(consumer.get("fetch.wait.max.ms"), Some("500"));
+            assert_eq!(consumer.get("max.partition.fetch.bytes"), Some("2097152"));
+        }
+        
+        // A latency-sensitive subscription overrides only what it sets
+        let low_latency = SubscriptionConfig::builder()
+            .fetch_min_bytes(1)
+            .fetch_wait_max_ms(10)
+            .build();
+        let consumer = bus.consumer_config("fetch-test-group", Some(&low_latency));
+        assert_eq!(consumer.get("fetch.min.bytes"), Some("1"));
+        assert_eq!(consumer.get("fetch.wait.max.ms"), Some("10"));
+        assert_eq!(consumer.get("max.partition.fetch.bytes"), Some("2097152"));
+        assert_eq!(consumer.get("group.id"), Some("fetch-test-group"));
+    }
+    
+    #[test]
+    fn test_consumer_fetch_settings_from_env() {
+        let _env = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let fetch_vars = ["KAFKA_FETCH_MIN_BYTES", "KAFKA_FETCH_WAIT_MAX_MS", "KAFKA_MAX_PARTITION_FETCH_BYTES"];
+        unsafe {
+            std::env::set_var("KAFKA_BOOTSTRAP_SERVERS", "localhost:9092");
+            std::env::set_var("KAFKA_CONSUMER_GROUP_ID", "test-group");
+            for var in fetch_vars {
+                std::env::remove_var(var);
+            }
+        }
+        
+        // Defaults match the previously hard-coded consumer settings
+        let config = KafkaConfig::from_env().expect("Should create config from env");
+        assert_eq!(config.fetch_min_bytes, 1024);
+        assert_eq!(config.fetch_wait_max_ms, 500);
+        assert_eq!(config.max_partition_fetch_bytes, 1_048_576);
+        
+        unsafe {
+            std::env::set_var("KAFKA_FETCH_MIN_BYTES", "1");
+            std::env::set_var("KAFKA_FETCH_WAIT_MAX_MS", "100");
+            std::env::set_var("KAFKA_MAX_PARTITION_FETCH_BYTES", "4194304");
+        }
+        let config = KafkaConfig::from_env().expect("Should accept overrides");
+        assert_eq!(config.fetch_min_bytes, 1);
+        assert_eq!(config.fetch_wait_max_ms, 100);
+        assert_eq!(config.max_partition_fetch_bytes, 4_194_304);
+        
+        unsafe { std::env::set_var("KAFKA_FETCH_WAIT_MAX_MS", "-1"); }
+        assert!(matches!(KafkaConfig::from_env(), Err(EventBusError::ConfigError(_))));
+        
+        unsafe {
+            for var in fetch_vars {
+                std::env::remove_var(var);
+            }
+        }
+    }
+    
+    #[test]
+    fn test_event_serialization() {
+        let message = MessageReceived {
+            message_id: "test-123".to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Hello, world!".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        };
+        
+        let envelope = EventEnvelope::new(message);
+        
+        // Test that we can serialize and deserialize the envelope
+        let json = serde_json::to_string(&envelope).expect("Should serialize");
+        let deserialized: EventEnvelope<MessageReceived> = 
+            serde_json::from_str(&json).expect("Should deserialize");
+        
+        assert_eq!(deserialized.data.message_id, "test-123");
+        assert_eq!(deserialized.version, "1.0");
+    }
+    
+    #[test]
+    fn test_record_key_override() {
+        let message = MessageReceived {
+            message_id: "test-123".to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Hello, world!".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        };
+        let envelope = EventEnvelope::new(message);
+        
+        // Without an override the event's partition key is used
+        let default_key = KafkaEventBus::record_key(&envelope, None, false).unwrap();
+        assert_eq!(default_key, "+1234567890");
+        
+        // An override replaces the derived key on the record
+        let override_key = KafkaEventBus::record_key(&envelope, Some("thread-42".to_string()), false).unwrap();
+        let record: FutureRecord<'_, String, String> = FutureRecord::to(MessageReceived::TOPIC)
+            .key(&override_key);
+        assert_eq!(record.key, Some(&"thread-42".to_string()));
+    }
+    
+    /// Event type that doesn't implement `partition_key`
+    #[derive(Debug, Clone, Serialize, serde::Deserialize)]
+    struct UnkeyedEvent {
+        note: String,
+    }
+    
+    impl Event for UnkeyedEvent {
+        const TOPIC: &'static str = "test.unkeyed";
+        const VERSION: &'static str = "1.0";
+    }
+    
+    #[tokio::test]
+    async fn test_require_partition_key_rejects_unkeyed_events() {
+        let envelope = EventEnvelope::new(UnkeyedEvent { note: "hi".to_string() });
+        
+        // The default falls back to the event ID
+        let key = KafkaEventBus::record_key(&envelope, None, false).expect("Should permit unkeyed event");
+        assert_eq!(key, envelope.event_id);
+        
+        // Strict mode rejects missing and empty keys but accepts an override
+        assert!(KafkaEventBus::record_key(&envelope, None, true).is_err());
+        assert!(KafkaEventBus::record_key(&envelope, Some(String::new()), true).is_err());
+        assert!(KafkaEventBus::record_key(&envelope, Some("thread-42".to_string()), true).is_ok());
+        
+        // Publishing fails before anything reaches the producer
+        let mut config = offline_config("strict-keys-test");
+        config.require_partition_key = true;
+        let bus = KafkaEventBus::new(config, Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        let result = bus.publish(UnkeyedEvent { note: "hi".to_string() }).await;
+        assert!(matches!(result, Err(EventBusError::PublishFailed(_))));
+    }
+    
+    #[test]
+    fn test_envelope_headers_round_trip() {
+        let message = MessageReceived {
+            message_id: "test-123".to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Hello, world!".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        };
+        let envelope = EventEnvelope::new(message);
+        
+        let headers = KafkaEventBus::envelope_headers(&envelope, SerializationFormat::Json);
+        assert_eq!(headers.count(), 4);
+        assert_eq!(
+            KafkaEventBus::header_value(&headers, HEADER_EVENT_TYPE).as_deref(),
+            Some("MessageReceived")
+        );
+        assert_eq!(
+            KafkaEventBus::header_value(&headers, HEADER_EVENT_VERSION).as_deref(),
+            Some(MessageReceived::VERSION)
+        );
+        assert_eq!(
+            KafkaEventBus::header_value(&headers, HEADER_EVENT_ID),
+            Some(envelope.event_id.clone())
+        );
+        assert_eq!(
+            KafkaEventBus::header_value(&headers, HEADER_CONTENT_FORMAT).as_deref(),
+            Some("json")
+        );
+        assert_eq!(KafkaEventBus::header_value(&headers, "missing"), None);
+    }
+    
+    #[test]
+    fn test_decode_envelope_uses_content_format_header() {
+        let message = MessageReceived {
+            message_id: "test-123".to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Hello, world!".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        };
+        let envelope = EventEnvelope::new(message);
+        
+        // MessagePack records are decoded according to their header
+        let format = SerializationFormat::MessagePack;
+        let payload = format.serialize(&envelope).unwrap();
+        let headers = KafkaEventBus::envelope_headers(&envelope, format);
+        let decoded: EventEnvelope<MessageReceived> =
+            KafkaEventBus::decode_envelope(Some(&headers), &payload).expect("Should decode MessagePack");
+        assert_eq!(decoded.event_id, envelope.event_id);
+        
+        // The version probe honours the content format when no version header is present
+        let version = KafkaEventBus::incoming_version(Some(&OwnedHeaders::new()
+            .insert(Header { key: HEADER_CONTENT_FORMAT, value: Some("msgpack") })), &payload);
+        assert_eq!(version.as_deref(), Some(MessageReceived::VERSION));
+        
+        // Header-less records are legacy JSON
+        let json = serde_json::to_vec(&envelope).unwrap();
+        assert!(KafkaEventBus::decode_envelope::<MessageReceived, OwnedHeaders>(None, &json).is_ok());
+        
+        // Unknown formats are rejected rather than guessed
+        let unknown = OwnedHeaders::new()
+            .insert(Header { key: HEADER_CONTENT_FORMAT, value: Some("avro") });
+        assert!(matches!(
+            KafkaEventBus::decode_envelope::<MessageReceived, _>(Some(&unknown), &json),
+            Err(EventBusError::SerializationError(_))
+        ));
+    }
+    
+    #[test]
+    fn test_v2_envelope_consumed_by_v1_handler() {
+        let message = MessageReceived {
+            message_id: "test-123".to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Hello, world!".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        };
+        let mut envelope = EventEnvelope::new(message);
+        envelope.version = "2.0".to_string();
+        let payload = serde_json::to_vec(&envelope).unwrap();
+        let policy = SubscriptionConfig::default().version_mismatch_policy;
+        
+        // Header takes precedence over the payload
+        let headers = KafkaEventBus::envelope_headers(&envelope, SerializationFormat::Json);
+        let version = KafkaEventBus::incoming_version(Some(&headers), &payload).unwrap();
+        assert_eq!(version, "2.0");
+        assert_eq!(policy.decide(&version, MessageReceived::VERSION), VersionMismatchAction::Quarantine);
+        
+        // Legacy records without headers are probed from the payload
+        let version = KafkaEventBus::incoming_version::<OwnedHeaders>(None, &payload).unwrap();
+        assert_eq!(version, "2.0");
+        assert_eq!(policy.decide(&version, MessageReceived::VERSION), VersionMismatchAction::Quarantine);
+        
+        // A forward-compatible minor bump is still handed to the v1 handler
+        envelope.version = "1.1".to_string();
+        let payload = serde_json::to_vec(&envelope).unwrap();
+        let version = KafkaEventBus::incoming_version::<OwnedHeaders>(None, &payload).unwrap();
+        assert_eq!(policy.decide(&version, MessageReceived::VERSION), VersionMismatchAction::Process);
+        assert!(serde_json::from_slice::<EventEnvelope<MessageReceived>>(&payload).is_ok());
+        
+        // Garbage payloads have no detectable version
+        assert_eq!(KafkaEventBus::incoming_version::<OwnedHeaders>(None, b"not json"), None);
+    }
+    
+    #[test]
+    fn test_prepare_for_replay_resets_dlq_state() {
+        let message = MessageReceived {
+            message_id: "test-123".to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Hello, world!".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        };
+        let mut envelope = EventEnvelope::new(message);
+        envelope.add_metadata("correlation_id".to_string(), "abc".to_string());
+        for _ in 0..3 {
+            envelope.increment_attempt();
+        }
+        envelope.add_metadata("dlq_reason".to_string(), "max_retries_exceeded".to_string());
+        envelope.add_metadata("original_topic".to_string(), MessageReceived::TOPIC.to_string());
+        envelope.add_metadata("final_attempt_count".to_string(), "3".to_string());
+        envelope.add_metadata("dlq_timestamp".to_string(), chrono::Utc::now().to_rfc3339());
+        assert!(envelope.should_dead_letter());
+        
+        // Simulate the trip through the DLQ topic
+        let dlq_payload = serde_json::to_vec(&envelope).unwrap();
+        let from_dlq: EventEnvelope<MessageReceived> = serde_json::from_slice(&dlq_payload).unwrap();
+        
+        let replayed = KafkaEventBus::prepare_for_replay(from_dlq);
+        assert_eq!(replayed.attempt_count, 0);
+        assert!(!replayed.should_dead_letter());
+        assert_eq!(replayed.event_id, envelope.event_id);
+        assert_eq!(replayed.metadata.len(), 1);
+        assert_eq!(replayed.metadata.get("correlation_id").map(String::as_str), Some("abc"));
+    }
+    
+    #[tokio::test]
+    async fn test_peek_dead_letters_parses_dlq_metadata() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        let dlq_topic = format!("{}.dlq", MessageReceived::TOPIC);
+        cluster.create_topic(&dlq_topic, 1, 1).expect("Should create DLQ topic");
+        
+        let mut config = offline_config("dlq-peek-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let bus = KafkaEventBus::new(config, Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        let failed = |message_id: &str| EventEnvelope::new(MessageReceived {
+            message_id: message_id.to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Hello, world!".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        });
+        let mut retried = failed("retried-123");
+        for _ in 0..3 {
+            retried.increment_attempt();
+        }
+        let mut stale = failed("stale-456");
+        stale.add_metadata("dlq_reason".to_string(), "max_age_exceeded".to_string());
+        bus.send_to_dead_letter_queue(retried.clone(), MessageReceived::TOPIC).await.expect("Should dead-letter");
+        bus.send_to_dead_letter_queue(stale.clone(), MessageReceived::TOPIC).await.expect("Should dead-letter");
+        
+        let records = bus.peek_dead_letters::<MessageReceived>(10).await.expect("Should peek");
+        assert_eq!(records.len(), 2);
+        
+        assert_eq!(records[0].envelope.event_id, retried.event_id);
+        assert_eq!(records[0].dlq_reason.as_deref(), Some("max_retries_exceeded"));
+        assert_eq!(records[0].original_topic.as_deref(), Some(MessageReceived::TOPIC));
+        assert_eq!(records[0].final_attempt_count, Some(3));
+        assert_eq!((records[0].partition, records[0].offset), (0, 0));
+        
+        assert_eq!(records[1].envelope.data.message_id, "stale-456");
+        assert_eq!(records[1].dlq_reason.as_deref(), Some("max_age_exceeded"));
+        assert_eq!(records[1].final_attempt_count, Some(0));
+        assert_eq!(records[1].offset, 1);
+        
+        // Nothing was committed, so a second peek starts from the same place
+        let again = bus.peek_dead_letters::<MessageReceived>(1).await.expect("Should peek again");
+        assert_eq!(again.len(), 1);
+        assert_eq!(again[0].envelope.event_id, retried.event_id);
+        assert!(bus.peek_dead_letters::<MessageReceived>(0).await.expect("Should peek nothing").is_empty());
+    }
+    
+    #[test]
+    fn test_dead_letter_record_tolerates_missing_metadata() {
+        let mut envelope = EventEnvelope::new(UnkeyedEvent { note: "hi".to_string() });
+        envelope.add_metadata("final_attempt_count".to_string(), "many".to_string());
+        
+        let record = DeadLetterRecord::from_envelope(envelope, 2, 40);
+        assert_eq!(record.dlq_reason, None);
+        assert_eq!(record.original_topic, None);
+        assert_eq!(record.final_attempt_count, None);
+        assert_eq!((record.partition, record.offset), (2, 40));
+    }
+    
+    #[tokio::test]
+    async fn test_unsubscribe_stops_only_target_consumer() {
+        // Clients connect lazily, so no broker is needed to manage subscriptions
+        let bus = KafkaEventBus::new(offline_config("unsubscribe-test"), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        for group in ["referrals", "messages"] {
+            let config = SubscriptionConfig {
+                consumer_group: group.to_string(),
+                ..Default::default()
+            };
+            bus.subscribe::<MessageReceived, _>(config, |_| Ok(ProcessingResult::Success))
+                .await
+                .expect("Should subscribe");
+        }
+        
+        bus.unsubscribe("referrals").await.expect("Should stop the referral consumer");
+        
+        {
+            let consumers = bus.consumers.read().await;
+            assert!(!consumers.contains_key("unsubscribe-test-referrals"));
+            let remaining = consumers.get("unsubscribe-test-messages").expect("Other consumer should remain");
+            assert!(!remaining.task.is_finished());
+        }
+        
+        // Unsubscribing twice is an error
+        assert!(bus.unsubscribe("referrals").await.is_err());
+        
+        bus.unsubscribe("messages").await.expect("Should stop the message consumer");
+        assert!(bus.consumers.read().await.is_empty());
+    }
+    
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_batched_offsets_flushed_on_unsubscribe() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        cluster.create_topic(MessageReceived::TOPIC, 1, 1).expect("Should create topic");
+        
+        let mut config = offline_config("commit-batch-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let bus = KafkaEventBus::new(config.clone(), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        for i in 0..3 {
+            bus.publish(MessageReceived {
+                message_id: format!("batched-{}", i),
+                from_phone: "+1234567890".to_string(),
+                message_type: MessageType::Text,
+                content: MessageContent::Text {
+                    body: "Hello, world!".to_string(),
+                },
+                received_at: chrono::Utc::now(),
+                metadata: std::collections::HashMap::new(),
+            }).await.expect("Should publish");
+        }
+        
+        // Far more than will arrive, so nothing is committed until the flush
+        let (handled_tx, mut handled_rx) = tokio::sync::mpsc::unbounded_channel();
+        let subscription = SubscriptionConfig::builder()
+            .consumer_group("batched")
+            .commit_batch_size(100)
+            .build();
+        bus.subscribe_async::<MessageReceived, _, _>(subscription, move |envelope| {
+            let handled_tx = handled_tx.clone();
+            async move {
+                let _ = handled_tx.send(envelope.data.message_id);
+                Ok(ProcessingResult::Success)
+            }
+        })
+        .await
+        .expect("Should subscribe");
+        
+        for _ in 0..3 {
+            tokio::time::timeout(Duration::from_secs(10), handled_rx.recv())
+                .await
+                .expect("Should handle before timeout")
+                .expect("Channel should stay open");
+        }
+        
+        let committed = || {
+            let reader: StreamConsumer = ClientConfig::new()
+                .set("bootstrap.servers", &config.bootstrap_servers)
+                .set("group.id", "commit-batch-test-batched")
+                .create()
+                .expect("Should create consumer");
+            let mut partition = TopicPartitionList::new();
+            partition.add_partition(MessageReceived::TOPIC, 0);
+            reader
+                .committed_offsets(partition, Duration::from_secs(5))
+                .expect("Should fetch committed offsets")
+                .find_partition(MessageReceived::TOPIC, 0)
+                .expect("Should list the partition")
+                .offset()
+        };
+        assert_eq!(committed(), Offset::Invalid);
+        
+        bus.unsubscribe("batched").await.expect("Should stop the consumer");
+        assert_eq!(committed(), Offset::Offset(3));
+    }
+    
+    #[tokio::test]
+    async fn test_pause_and_resume_subscription() {
+        let bus = KafkaEventBus::new(offline_config("pause-test"), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        let handled = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = handled.clone();
+        let config = SubscriptionConfig {
+            consumer_group: "responses".to_string(),
+            ..Default::default()
+        };
+        bus.subscribe::<MessageReceived, _>(config, move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(ProcessingResult::Success)
+        })
+        .await
+        .expect("Should subscribe");
+        
+        assert!(!bus.is_paused("responses").await);
+        bus.pause("responses").await.expect("Should pause");
+        assert!(bus.is_paused("responses").await);
+        
+        // The loop keeps running (so the group membership is kept) but
+        // doesn't hand anything to the handler
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        {
+            let consumers = bus.consumers.read().await;
+            assert!(!consumers["pause-test-responses"].task.is_finished());
+        }
+        assert_eq!(handled.load(Ordering::SeqCst), 0);
+        
+        bus.resume("responses").await.expect("Should resume");
+        assert!(!bus.is_paused("responses").await);
+        
+        // Unknown groups are rejected
+        assert!(bus.pause("missing").await.is_err());
+        assert!(bus.resume("missing").await.is_err());
+        
+        bus.unsubscribe("responses").await.expect("Should stop the consumer");
+    }
+    
+    #[tokio::test]
+    async fn test_metrics_hooks_fire() {
+        let metrics = Arc::new(RecordingMetrics::default());
+        let mut config = offline_config("metrics-test");
+        config.dlq_max_payload_bytes = 0;
+        let bus = KafkaEventBus::new(config, metrics.clone())
+            .await
+            .expect("Should create event bus");
+        
+        // Oversized payloads are rejected by the producer before any broker
+        // round trip, so every send below fails fast. The DLQ cap is off so
+        // dead letters aren't truncated down to a sendable size.
+        let oversized = |attempts: u32| {
+            let mut envelope = EventEnvelope::new(MessageReceived {
+                message_id: "test-123".to_string(),
+                from_phone: "+1234567890".to_string(),
+                message_type: MessageType::Text,
+                content: MessageContent::Text {
+                    body: "x".repeat(2_000_000),
+                },
+                received_at: chrono::Utc::now(),
+                metadata: std::collections::HashMap::new(),
+            });
+            envelope.attempt_count = attempts;
+            envelope
+        };
+        let topic = MessageReceived::TOPIC;
+        let breaker = CircuitBreaker::new(0);
+        
+        assert!(bus.publish_envelope(oversized(0), None).await.is_err());
+        assert_eq!(metrics.calls(), vec![format!("publish:{}:false", topic)]);
+        metrics.calls.lock().unwrap().clear();
+        
+        // Successful handling only counts the consume
+        let timeout = Duration::from_secs(5);
+        let success = |_: EventEnvelope<MessageReceived>| std::future::ready(Ok(ProcessingResult::Success));
+        assert!(bus.process_event_envelope(oversized(0), topic, &success, timeout, None, &breaker).await.unwrap());
+        assert_eq!(metrics.calls(), vec![format!("consume:{}", topic)]);
+        metrics.calls.lock().unwrap().clear();
+        
+        // A skip commits without a retry or DLQ send, which would fail here
+        let skip = |_: EventEnvelope<MessageReceived>| std::future::ready(Ok(ProcessingResult::skip("blocked sender")));
+        assert!(bus.process_event_envelope(oversized(0), topic, &skip, timeout, None, &breaker).await.unwrap());
+        assert_eq!(metrics.calls(), vec![format!("consume:{}", topic), format!("skip:{}", topic)]);
+        metrics.calls.lock().unwrap().clear();
+        
+        // A retryable failure under the attempt limit is retried
+        let retry = |_: EventEnvelope<MessageReceived>| std::future::ready(Ok(ProcessingResult::retry("busy")));
+        assert!(bus.process_event_envelope(oversized(0), topic, &retry, timeout, None, &breaker).await.is_err());
+        assert_eq!(metrics.calls(), vec![format!("consume:{}", topic), format!("retry:{}", topic)]);
+        metrics.calls.lock().unwrap().clear();
+        
+        // ... and dead-lettered once the limit is reached
+        assert!(bus.process_event_envelope(oversized(3), topic, &retry, timeout, None, &breaker).await.is_err());
+        assert_eq!(metrics.calls(), vec![format!("consume:{}", topic), format!("dlq:{}", topic)]);
+    }    
+    #[tokio::test]
+    async fn test_publish_raw_sends_pre_serialized_envelope() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        cluster
+            .create_topic(MessageReceived::TOPIC, 1, 1)
+            .expect("Should create topic");
+        
+        let mut config = offline_config("publish-raw-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let bus = KafkaEventBus::new(config.clone(), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        // Serialized by hand, as an outbox would store it
+        let envelope = EventEnvelope::new(MessageReceived {
+            message_id: "test-123".to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Hello, world!".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        });
+        let payload = serde_json::to_vec(&envelope).expect("Should serialize");
+        
+        bus.publish_raw(MessageReceived::TOPIC, Some("+1234567890".to_string()), payload)
+            .await
+            .expect("Should publish raw payload");
+        
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &config.bootstrap_servers)
+            .set("group.id", "publish-raw-test")
+            .set("auto.offset.reset", "earliest")
+            .create()
+            .expect("Should create consumer");
+        consumer.subscribe(&[MessageReceived::TOPIC]).expect("Should subscribe");
+        
+        let message = tokio::time::timeout(Duration::from_secs(10), consumer.recv())
+            .await
+            .expect("Should receive before timeout")
+            .expect("Should receive message");
+        assert_eq!(message.key(), Some("+1234567890".as_bytes()));
+        
+        let decoded: EventEnvelope<MessageReceived> =
+            serde_json::from_slice(message.payload().expect("Should have payload"))
+                .expect("Should decode envelope");
+        assert_eq!(decoded.event_id, envelope.event_id);
+        assert_eq!(decoded.data.message_id, "test-123");
+    }
+    
+    #[tokio::test]
+    async fn test_publish_with_receipt_reports_delivery_position() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        cluster
+            .create_topic(MessageReceived::TOPIC, 1, 1)
+            .expect("Should create topic");
+        
+        let mut config = offline_config("publish-receipt-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let bus = KafkaEventBus::new(config, Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        let event = |message_id: &str| MessageReceived {
+            message_id: message_id.to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Hello, world!".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        };
+        
+        let first = bus.publish_with_receipt(event("receipt-1")).await.expect("Should publish");
+        assert_eq!(first.topic, MessageReceived::TOPIC);
+        assert_eq!(first.partition, 0);
+        assert!(first.offset >= 0);
+        
+        // Plain publish still works and advances the same partition
+        bus.publish(event("receipt-2")).await.expect("Should publish");
+        let third = bus.publish_with_receipt(event("receipt-3")).await.expect("Should publish");
+        assert_eq!(third.offset, first.offset + 2);
+    }
+    
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_slow_handler_times_out_to_retry_queue() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        let retry_topic = format!("{}.retry", MessageReceived::TOPIC);
+        cluster.create_topic(&retry_topic, 1, 1).expect("Should create retry topic");
+        
+        let mut config = offline_config("handler-timeout-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let bus = KafkaEventBus::new(config.clone(), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        let envelope = EventEnvelope::new(MessageReceived {
+            message_id: "slow-123".to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Hello, world!".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        });
+        let finished = Arc::new(AtomicBool::new(false));
+        let handler_finished = finished.clone();
+        let sleepy = move |_: EventEnvelope<MessageReceived>| {
+            let finished = handler_finished.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(500)).await;
+                finished.store(true, Ordering::SeqCst);
+                Ok(ProcessingResult::Success)
             }
+        };
+        
+        let started = std::time::Instant::now();
+        let commit = bus
+            .process_event_envelope(envelope.clone(), MessageReceived::TOPIC, &sleepy, Duration::from_millis(50), None, &CircuitBreaker::new(0))
+            .await
+            .expect("Timeout should be routed, not surfaced");
+        assert!(commit);
+        assert!(started.elapsed() < Duration::from_millis(500));
+        
+        // An async handler is cancelled rather than left running
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        assert!(!finished.load(Ordering::SeqCst));
+        
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &config.bootstrap_servers)
+            .set("group.id", "handler-timeout-test")
+            .set("auto.offset.reset", "earliest")
+            .create()
+            .expect("Should create consumer");
+        consumer.subscribe(&[retry_topic.as_str()]).expect("Should subscribe");
+        
+        let message = tokio::time::timeout(Duration::from_secs(10), consumer.recv())
+            .await
+            .expect("Should receive before timeout")
+            .expect("Should receive message");
+        let retried: EventEnvelope<MessageReceived> =
+            serde_json::from_slice(message.payload().expect("Should have payload"))
+                .expect("Should decode envelope");
+        assert_eq!(retried.event_id, envelope.event_id);
+        assert_eq!(retried.attempt_count, 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_timed_out_blocking_handler_keeps_running() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        let retry_topic = format!("{}.retry", MessageReceived::TOPIC);
+        cluster.create_topic(&retry_topic, 1, 1).expect("Should create retry topic");
+
+        let mut config = offline_config("blocking-timeout-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let metrics = Arc::new(RecordingMetrics::default());
+        let bus = KafkaEventBus::new(config, metrics.clone())
+            .await
+            .expect("Should create event bus");
+
+        let envelope = EventEnvelope::new(MessageReceived {
+            message_id: "blocking-123".to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Hello, world!".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        });
+        let finished = Arc::new(AtomicBool::new(false));
+        let handler_finished = finished.clone();
+        let blocking = Arc::new(move |_: EventEnvelope<MessageReceived>| {
+            std::thread::sleep(Duration::from_millis(500));
+            handler_finished.store(true, Ordering::SeqCst);
+            Ok(ProcessingResult::Success)
+        });
+        let handler = move |envelope| KafkaEventBus::run_blocking(blocking.clone(), envelope);
+
+        let started = std::time::Instant::now();
+        let commit = bus
+            .process_event_envelope(envelope, MessageReceived::TOPIC, &handler, Duration::from_millis(50), None, &CircuitBreaker::new(0))
+            .await
+            .expect("Timeout should be routed, not surfaced");
+        assert!(commit);
+        assert!(started.elapsed() < Duration::from_millis(500));
+        assert!(metrics.calls().contains(&format!("retry:{}", MessageReceived::TOPIC)));
+        assert!(!finished.load(Ordering::SeqCst));
+
+        // Unlike an async handler, the blocking call finishes after the retry was queued
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        assert!(finished.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_publish_delayed_is_not_delivered_early() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        let scheduled_topic = format!("{}.scheduled", MessageReceived::TOPIC);
+        cluster.create_topic(&scheduled_topic, 1, 1).expect("Should create scheduled topic");
+        cluster.create_topic(MessageReceived::TOPIC, 1, 1).expect("Should create topic");
+        
+        let mut config = offline_config("publish-delayed-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let bus = KafkaEventBus::new(config.clone(), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        bus.start_scheduler::<MessageReceived>().await.expect("Should start scheduler");
+        
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &config.bootstrap_servers)
+            .set("group.id", "publish-delayed-test-reader")
+            .set("auto.offset.reset", "earliest")
+            .create()
+            .expect("Should create consumer");
+        consumer.subscribe(&[MessageReceived::TOPIC]).expect("Should subscribe");
+        
+        let event = MessageReceived {
+            message_id: "later-123".to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Reminder".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        };
+        let started = std::time::Instant::now();
+        bus.publish_delayed(event, Duration::from_secs(1)).await.expect("Should schedule");
+        
+        let message = tokio::time::timeout(Duration::from_secs(10), consumer.recv())
+            .await
+            .expect("Should receive before timeout")
+            .expect("Should receive message");
+        // deliver-at is truncated to the millisecond
+        assert!(started.elapsed() >= Duration::from_millis(999), "delivered early: {:?}", started.elapsed());
+        
+        let headers = message.headers().expect("Should keep headers");
+        assert!(KafkaEventBus::header_value(headers, HEADER_DELIVER_AT).is_none());
+        assert_eq!(KafkaEventBus::header_value(headers, HEADER_EVENT_TYPE).as_deref(), Some("MessageReceived"));
+        assert_eq!(message.key(), Some("+1234567890".as_bytes()));
+        let delivered: EventEnvelope<MessageReceived> =
+            serde_json::from_slice(message.payload().expect("Should have payload"))
+                .expect("Should decode envelope");
+        assert_eq!(delivered.data.message_id, "later-123");
+        
+        bus.unsubscribe(&format!("{}-scheduler", MessageReceived::TOPIC)).await.expect("Should stop scheduler");
+    }
+    
+    #[tokio::test]
+    async fn test_publish_delayed_follows_the_bus_clock() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        let scheduled_topic = format!("{}.scheduled", MessageReceived::TOPIC);
+        cluster.create_topic(&scheduled_topic, 1, 1).expect("Should create scheduled topic");
+        cluster.create_topic(MessageReceived::TOPIC, 1, 1).expect("Should create topic");
+        
+        let mut config = offline_config("delayed-clock-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        // Far from the wall clock, so mixing the two clocks can't go unnoticed
+        let start = chrono::DateTime::parse_from_rfc3339("2025-06-15T12:00:00Z").unwrap().to_utc();
+        let clock = Arc::new(crate::clock::MockClock::new(start));
+        let bus = KafkaEventBus::new(config.clone(), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus")
+            .with_clock(clock.clone());
+        bus.start_scheduler::<MessageReceived>().await.expect("Should start scheduler");
+        
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &config.bootstrap_servers)
+            .set("group.id", "delayed-clock-test-reader")
+            .set("auto.offset.reset", "earliest")
+            .create()
+            .expect("Should create consumer");
+        consumer.subscribe(&[MessageReceived::TOPIC]).expect("Should subscribe");
+        
+        bus.publish_delayed(seek_test_event("in-an-hour"), Duration::from_secs(3600))
+            .await
+            .expect("Should schedule");
+        
+        // Held while the bus clock stands still, once the scheduler is reading
+        let scheduler_group = bus.consumer_group_id(&format!("{}-scheduler", MessageReceived::TOPIC));
+        let deadline = std::time::Instant::now() + Duration::from_secs(15);
+        while bus.consumers.read().await[&scheduler_group].consumer.assignment().map_or(0, |assigned| assigned.count()) == 0 {
+            assert!(std::time::Instant::now() < deadline, "scheduler never got its partition");
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        let early = tokio::time::timeout(Duration::from_secs(2), consumer.recv()).await;
+        assert!(early.is_err(), "delivered before the bus clock reached deliver-at");
+        
+        // An hour on the bus clock, not the wall clock, releases it
+        clock.advance(chrono::Duration::hours(1));
+        let message = tokio::time::timeout(Duration::from_secs(10), consumer.recv())
+            .await
+            .expect("Should receive before timeout")
+            .expect("Should receive message");
+        let delivered: EventEnvelope<MessageReceived> =
+            serde_json::from_slice(message.payload().expect("Should have payload"))
+                .expect("Should decode envelope");
+        assert_eq!(delivered.data.message_id, "in-an-hour");
+        
+        bus.unsubscribe(&format!("{}-scheduler", MessageReceived::TOPIC)).await.expect("Should stop scheduler");
+    }
+    
+    #[tokio::test]
+    async fn test_stale_event_is_dead_lettered_without_retries() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        let dlq_topic = format!("{}.dlq", MessageReceived::TOPIC);
+        cluster.create_topic(&dlq_topic, 1, 1).expect("Should create DLQ topic");
+        
+        let mut config = offline_config("max-age-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let bus = KafkaEventBus::new(config.clone(), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        // First attempt, but created ten minutes ago
+        let mut envelope = EventEnvelope::new(MessageReceived {
+            message_id: "stale-123".to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "typing...".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        });
+        envelope.timestamp -= chrono::Duration::minutes(10);
+        assert!(envelope.age() >= Duration::from_secs(600));
+        
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let handler_calls = calls.clone();
+        let handler = move |_: EventEnvelope<MessageReceived>| {
+            handler_calls.fetch_add(1, Ordering::SeqCst);
+            std::future::ready(Ok(ProcessingResult::Success))
+        };
+        
+        let commit = bus
+            .process_event_envelope(
+                envelope.clone(),
+                MessageReceived::TOPIC,
+                &handler,
+                Duration::from_secs(5),
+                Some(Duration::from_secs(60)),
+                &CircuitBreaker::new(0),
+            )
+            .await
+            .expect("Should land in the DLQ");
+        assert!(commit);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &config.bootstrap_servers)
+            .set("group.id", "max-age-test-reader")
+            .set("auto.offset.reset", "earliest")
+            .create()
+            .expect("Should create consumer");
+        consumer.subscribe(&[dlq_topic.as_str()]).expect("Should subscribe");
+        let message = tokio::time::timeout(Duration::from_secs(10), consumer.recv())
+            .await
+            .expect("Should receive before timeout")
+            .expect("Should receive message");
+        
+        let dead: EventEnvelope<MessageReceived> =
+            serde_json::from_slice(message.payload().expect("Should have payload")).expect("Should decode");
+        assert_eq!(dead.event_id, envelope.event_id);
+        assert_eq!(dead.attempt_count, 0);
+        assert_eq!(dead.metadata.get("dlq_reason").map(String::as_str), Some("max_age_exceeded"));
+        assert!(dead.metadata.contains_key("dlq_age_ms"));
+        
+        // Replay clears the age marker along with the rest of the DLQ metadata
+        let replayed = KafkaEventBus::prepare_for_replay(dead);
+        assert!(!replayed.metadata.contains_key("dlq_age_ms"));
+    }
+    
+    #[tokio::test]
+    async fn test_oversized_dead_letter_is_truncated_and_delivered() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        let dlq_topic = format!("{}.dlq", MessageReceived::TOPIC);
+        cluster.create_topic(&dlq_topic, 1, 1).expect("Should create DLQ topic");
+        
+        let mut config = offline_config("dlq-truncate-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        config.dlq_max_payload_bytes = 4096;
+        let bus = KafkaEventBus::new(config.clone(), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        // Quotes double in size once the data's JSON is embedded as a string
+        let mut envelope = EventEnvelope::new(MessageReceived {
+            message_id: "huge-123".to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "\"é".repeat(20_000),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
         });
+        envelope.attempt_count = envelope.max_attempts;
+        let event_id = envelope.event_id.clone();
+        bus.send_to_dead_letter_queue(envelope, MessageReceived::TOPIC).await.expect("Should land in the DLQ");
+        
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &config.bootstrap_servers)
+            .set("group.id", "dlq-truncate-test-reader")
+            .set("auto.offset.reset", "earliest")
+            .create()
+            .expect("Should create consumer");
+        consumer.subscribe(&[dlq_topic.as_str()]).expect("Should subscribe");
+        let message = tokio::time::timeout(Duration::from_secs(10), consumer.recv())
+            .await
+            .expect("Should receive before timeout")
+            .expect("Should receive message");
+        
+        let payload = message.payload().expect("Should have payload");
+        assert!(payload.len() <= 4096, "payload is {} bytes", payload.len());
+        assert_eq!(message.key(), Some("+1234567890".as_bytes()));
+        
+        let record: serde_json::Value = serde_json::from_slice(payload).expect("Should be valid JSON");
+        assert_eq!(record["event_id"], event_id.as_str());
+        assert_eq!(record["event_type"], "MessageReceived");
+        assert_eq!(record["metadata"]["dlq_reason"], "max_retries_exceeded");
+        assert!(record["metadata"]["dlq_original_bytes"].as_str().unwrap().parse::<usize>().unwrap() > 4096);
+        assert!(record.get("data").is_none());
+        let truncated = record["dlq_truncated"].as_str().expect("Should keep a data prefix");
+        assert!(truncated.starts_with("{\"message_id\":\"huge-123\""));
+        assert!(truncated.len() > 1000);
+    }
+    
+    #[tokio::test]
+    async fn test_failing_handler_opens_circuit_and_degrades_health() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        cluster.create_topic(MessageReceived::TOPIC, 1, 1).expect("Should create topic");
+        cluster.create_topic(&format!("{}.retry", MessageReceived::TOPIC), 1, 1).expect("Should create retry topic");
+        
+        let mut config = offline_config("circuit-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let bus = KafkaEventBus::new(config, Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        assert_eq!(bus.health().await.expect("Should reach mock cluster"), HealthStatus::Healthy);
+        
+        let handled = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = handled.clone();
+        let subscription = SubscriptionConfig::builder()
+            .consumer_group("downstream-down")
+            .circuit_breaker_threshold(3)
+            .circuit_breaker_cooldown_ms(60_000)
+            .build();
+        bus.subscribe::<MessageReceived, _>(subscription, move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(ProcessingResult::retry("downstream unavailable"))
+        })
+        .await
+        .expect("Should subscribe");
+        assert_eq!(bus.circuit_state("downstream-down").await, Some(CircuitState::Closed));
+        
+        for i in 0..5 {
+            bus.publish(MessageReceived {
+                message_id: format!("fail-{}", i),
+                from_phone: "+1234567890".to_string(),
+                message_type: MessageType::Text,
+                content: MessageContent::Text {
+                    body: "Hello".to_string(),
+                },
+                received_at: chrono::Utc::now(),
+                metadata: std::collections::HashMap::new(),
+            })
+            .await
+            .expect("Should publish");
+        }
+        
+        let deadline = std::time::Instant::now() + Duration::from_secs(15);
+        while bus.circuit_state("downstream-down").await != Some(CircuitState::Open) {
+            assert!(std::time::Instant::now() < deadline, "circuit never opened");
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        
+        // The loop stops reading during the cooldown
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(handled.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            bus.health().await.expect("Should reach mock cluster"),
+            HealthStatus::Degraded {
+                open_circuits: vec!["circuit-test-downstream-down".to_string()],
+                stale_subscriptions: vec![],
+            },
+        );
+        bus.health_check().await.expect("Degraded is still healthy enough to pass");
+        
+        bus.unsubscribe("downstream-down").await.expect("Should stop during cooldown");
+    }
+    
+    #[tokio::test]
+    async fn test_last_processed_updates_and_flags_stale_subscriptions() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        cluster.create_topic(MessageReceived::TOPIC, 1, 1).expect("Should create topic");
+        
+        let mut config = offline_config("staleness-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let clock = Arc::new(crate::clock::MockClock::new(chrono::Utc::now()));
+        let bus = KafkaEventBus::new(config, Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus")
+            .with_clock(clock.clone());
         
-        match tokio::time::timeout(Duration::from_secs(10), metadata_future).await {
-            Ok(Ok(Ok(_metadata))) => {
-                debug!("✅ Kafka health check passed");
-                Ok(())
-            }
-            Ok(Ok(Err(e))) => {
-                error!("❌ Kafka health check failed: {}", e);
-                Err(EventBusError::ConnectionError(format!("Health check failed: {}", e)))
+        let subscription = SubscriptionConfig::builder()
+            .consumer_group("tracker")
+            .stale_after_ms(60_000)
+            .build();
+        bus.subscribe::<MessageReceived, _>(subscription, |_| Ok(ProcessingResult::Success))
+            .await
+            .expect("Should subscribe");
+        assert_eq!(bus.last_processed(MessageReceived::TOPIC).await, None);
+        assert_eq!(bus.health().await.expect("Should reach mock cluster"), HealthStatus::Healthy);
+        
+        let bus = &bus;
+        let publish = |message_id: &str| bus.publish(MessageReceived {
+            message_id: message_id.to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Hello".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        });
+        let wait_for_processed_at = |at: chrono::DateTime<chrono::Utc>| async move {
+            let deadline = std::time::Instant::now() + Duration::from_secs(15);
+            while bus.last_processed(MessageReceived::TOPIC).await != Some(at) {
+                assert!(std::time::Instant::now() < deadline, "event was never handled");
+                tokio::time::sleep(Duration::from_millis(50)).await;
             }
-            Ok(Err(e)) => {
-                error!("❌ Kafka health check task failed: {}", e);
-                Err(EventBusError::ConnectionError(format!("Health check task error: {}", e)))
+        };
+        
+        publish("first").await.expect("Should publish");
+        wait_for_processed_at(clock.now()).await;
+        
+        // Nothing handled for longer than stale_after_ms
+        clock.advance(chrono::Duration::minutes(2));
+        assert_eq!(
+            bus.health().await.expect("Should reach mock cluster"),
+            HealthStatus::Degraded {
+                open_circuits: vec![],
+                stale_subscriptions: vec!["staleness-test-tracker".to_string()],
+            },
+        );
+        
+        publish("second").await.expect("Should publish");
+        wait_for_processed_at(clock.now()).await;
+        assert_eq!(bus.health().await.expect("Should reach mock cluster"), HealthStatus::Healthy);
+        
+        bus.unsubscribe("tracker").await.expect("Should unsubscribe");
+    }
+    
+    fn seek_test_event(message_id: &str) -> MessageReceived {
+        MessageReceived {
+            message_id: message_id.to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Hello".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        }
+    }
+    
+    /// Subscribe `consumer_group` and collect the message IDs it handles
+    async fn collect_handled(bus: &KafkaEventBus, consumer_group: &str) -> Arc<Mutex<Vec<String>>> {
+        let handled = Arc::new(Mutex::new(Vec::new()));
+        let seen = handled.clone();
+        let subscription = SubscriptionConfig::builder().consumer_group(consumer_group).build();
+        bus.subscribe::<MessageReceived, _>(subscription, move |envelope| {
+            seen.lock().unwrap().push(envelope.data.message_id);
+            Ok(ProcessingResult::Success)
+        })
+        .await
+        .expect("Should subscribe");
+        handled
+    }
+    
+    async fn wait_for_handled(handled: &Arc<Mutex<Vec<String>>>, count: usize) {
+        let deadline = std::time::Instant::now() + Duration::from_secs(15);
+        while handled.lock().unwrap().len() < count {
+            assert!(std::time::Instant::now() < deadline, "only handled {:?}", handled.lock().unwrap());
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_seek_to_timestamp_past_the_last_event_skips_to_the_end() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        cluster.create_topic(MessageReceived::TOPIC, 2, 1).expect("Should create topic");
+        
+        let mut config = offline_config("seek-end-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let bus = KafkaEventBus::new(config, Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        for i in 0..3 {
+            bus.publish(seek_test_event(&format!("old-{}", i))).await.expect("Should publish");
+        }
+        
+        // Nothing was written after this, so both partitions move to their end
+        let future = chrono::Utc::now() + chrono::Duration::hours(1);
+        let repositioned = bus.seek_to_timestamp("seeker", MessageReceived::TOPIC, future)
+            .await
+            .expect("Should commit offsets for the idle group");
+        assert_eq!(repositioned, 2);
+        
+        let handled = collect_handled(&bus, "seeker").await;
+        bus.publish(seek_test_event("new")).await.expect("Should publish");
+        wait_for_handled(&handled, 1).await;
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(*handled.lock().unwrap(), vec!["new".to_string()]);
+        
+        let missing = bus.seek_to_timestamp("seeker", "no-such-topic", future).await;
+        assert!(missing.is_err());
+        
+        bus.unsubscribe("seeker").await.expect("Should unsubscribe");
+    }
+    
+    #[tokio::test]
+    #[ignore = "requires a running Kafka broker"]
+    async fn test_seek_to_timestamp_replays_a_running_subscription() {
+        let run = uuid::Uuid::new_v4().to_string();
+        let mut config = offline_config("seek-test");
+        config.bootstrap_servers = std::env::var("KAFKA_BOOTSTRAP_SERVERS")
+            .unwrap_or_else(|_| "localhost:9092".to_string());
+        config.consumer_group_id = format!("seek-test-{}", run);
+        let bus = KafkaEventBus::new(config, Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        let before = format!("before-{}", run);
+        let after = format!("after-{}", run);
+        bus.publish(seek_test_event(&before)).await.expect("Should publish");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let checkpoint = chrono::Utc::now();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        bus.publish(seek_test_event(&after)).await.expect("Should publish");
+        
+        let handled = collect_handled(&bus, "replayer").await;
+        let deadline = std::time::Instant::now() + Duration::from_secs(30);
+        while !handled.lock().unwrap().contains(&after) {
+            assert!(std::time::Instant::now() < deadline, "never handled {}", after);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        handled.lock().unwrap().clear();
+        
+        bus.seek_to_timestamp("replayer", MessageReceived::TOPIC, checkpoint)
+            .await
+            .expect("Should seek the running consumer");
+        
+        // The event after the checkpoint comes around again, the one before doesn't
+        let deadline = std::time::Instant::now() + Duration::from_secs(30);
+        while !handled.lock().unwrap().contains(&after) {
+            assert!(std::time::Instant::now() < deadline, "seek didn't replay {}", after);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert!(!handled.lock().unwrap().contains(&before));
+        
+        bus.unsubscribe("replayer").await.expect("Should unsubscribe");
+    }
+    
+    #[tokio::test]
+    async fn test_tombstone_offset_is_committed() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        cluster.create_topic(MessageReceived::TOPIC, 1, 1).expect("Should create topic");
+        
+        let mut config = offline_config("tombstone-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let bus = KafkaEventBus::new(config.clone(), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        let tombstone: FutureRecord<'_, str, [u8]> = FutureRecord::to(MessageReceived::TOPIC).key("+1234567890");
+        bus.producer.send(tombstone, Duration::from_secs(5)).await.expect("Should send tombstone");
+        
+        let handled = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = handled.clone();
+        let subscription = SubscriptionConfig::builder().consumer_group("compacted").build();
+        bus.subscribe::<MessageReceived, _>(subscription, move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(ProcessingResult::Success)
+        })
+        .await
+        .expect("Should subscribe");
+        
+        // The group's committed offset moves past the tombstone
+        let mut partition = TopicPartitionList::new();
+        partition.add_partition(MessageReceived::TOPIC, 0);
+        let deadline = std::time::Instant::now() + Duration::from_secs(15);
+        loop {
+            // Errors until the group coordinator is up
+            let committed = {
+                let consumers = bus.consumers.read().await;
+                consumers["tombstone-test-compacted"].consumer
+                    .committed_offsets(partition.clone(), Duration::from_secs(1))
+                    .ok()
+                    .and_then(|committed| committed.find_partition(MessageReceived::TOPIC, 0).map(|elem| elem.offset()))
+            };
+            if committed == Some(Offset::Offset(1)) {
+                break;
             }
-            Err(_) => {
-                error!("❌ Kafka health check timed out");
-                Err(EventBusError::ConnectionError("Health check timeout".to_string()))
+            assert!(std::time::Instant::now() < deadline, "tombstone offset never committed");
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert_eq!(handled.load(Ordering::SeqCst), 0);
+        
+        bus.unsubscribe("compacted").await.expect("Should stop the consumer");
+    }
+    
+    #[tokio::test]
+    async fn test_oversized_payload_is_diverted_unparsed() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        let oversized_topic = format!("{}.oversized", MessageReceived::TOPIC);
+        cluster.create_topic(MessageReceived::TOPIC, 1, 1).expect("Should create topic");
+        cluster.create_topic(&oversized_topic, 1, 1).expect("Should create oversized topic");
+        
+        let mut config = offline_config("oversized-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let bus = KafkaEventBus::new(config.clone(), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        // Not even JSON, so parsing it would fail loudly rather than divert it
+        let huge = format!("{{\"not\": \"{}", "x".repeat(8192));
+        let record: FutureRecord<'_, str, str> = FutureRecord::to(MessageReceived::TOPIC)
+            .key("+1234567890")
+            .payload(&huge);
+        bus.producer.send(record, Duration::from_secs(5)).await.expect("Should send oversized record");
+        bus.publish(seek_test_event("small")).await.expect("Should publish");
+        
+        let handled = Arc::new(Mutex::new(Vec::new()));
+        let seen = handled.clone();
+        let subscription = SubscriptionConfig::builder()
+            .consumer_group("size-guarded")
+            .max_payload_bytes(4096)
+            .build();
+        bus.subscribe::<MessageReceived, _>(subscription, move |envelope| {
+            seen.lock().unwrap().push(envelope.data.message_id);
+            Ok(ProcessingResult::Success)
+        })
+        .await
+        .expect("Should subscribe");
+        wait_for_handled(&handled, 1).await;
+        assert_eq!(*handled.lock().unwrap(), vec!["small".to_string()]);
+        
+        // The record lands on the side topic byte for byte
+        let reader: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &config.bootstrap_servers)
+            .set("group.id", "oversized-test-reader")
+            .set("auto.offset.reset", "earliest")
+            .create()
+            .expect("Should create consumer");
+        reader.subscribe(&[oversized_topic.as_str()]).expect("Should subscribe");
+        let diverted = tokio::time::timeout(Duration::from_secs(10), reader.recv())
+            .await
+            .expect("Should receive before timeout")
+            .expect("Should receive message");
+        assert_eq!(diverted.payload(), Some(huge.as_bytes()));
+        assert_eq!(diverted.key(), Some("+1234567890".as_bytes()));
+        
+        // Both offsets are committed
+        let mut partition = TopicPartitionList::new();
+        partition.add_partition(MessageReceived::TOPIC, 0);
+        let deadline = std::time::Instant::now() + Duration::from_secs(15);
+        loop {
+            let committed = {
+                let consumers = bus.consumers.read().await;
+                consumers["oversized-test-size-guarded"].consumer
+                    .committed_offsets(partition.clone(), Duration::from_secs(1))
+                    .ok()
+                    .and_then(|committed| committed.find_partition(MessageReceived::TOPIC, 0).map(|elem| elem.offset()))
+            };
+            if committed == Some(Offset::Offset(2)) {
+                break;
             }
+            assert!(std::time::Instant::now() < deadline, "oversized offset never committed");
+            tokio::time::sleep(Duration::from_millis(100)).await;
         }
+        
+        bus.unsubscribe("size-guarded").await.expect("Should stop the consumer");
     }
     
-    /// Gracefully shutdown the event bus
-    async fn shutdown(&self) -> Result<(), Self::Error> {
-        info!("🛑 Initiating graceful shutdown of Kafka event bus");
+    #[tokio::test]
+    async fn test_oversized_payload_stays_uncommitted_until_diverted() {
+        use rdkafka::types::{RDKafkaApiKey, RDKafkaRespErr};
         
-        // Signal all consumers to stop
-        let _ = self.shutdown_signal.send(true);
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        let oversized_topic = format!("{}.oversized", MessageReceived::TOPIC);
+        cluster.create_topic(MessageReceived::TOPIC, 1, 1).expect("Should create topic");
+        cluster.create_topic(&oversized_topic, 1, 1).expect("Should create oversized topic");
         
-        // Wait for consumers to finish processing
-        tokio::time::sleep(Duration::from_secs(5)).await;
+        let mut config = offline_config("oversized-outage-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let metrics = Arc::new(RecordingMetrics::default());
+        let bus = KafkaEventBus::new(config, metrics.clone())
+            .await
+            .expect("Should create event bus");
         
-        // Clear consumer references
-        {
-            let mut consumers = self.consumers.write().await;
-            consumers.clear();
+        let huge = "x".repeat(8192);
+        let record: FutureRecord<'_, str, str> = FutureRecord::to(MessageReceived::TOPIC)
+            .key("+1234567890")
+            .payload(&huge);
+        bus.producer.send(record, Duration::from_secs(5)).await.expect("Should send oversized record");
+        bus.publish(seek_test_event("small")).await.expect("Should publish");
+        
+        // The side topic can't take writes
+        cluster.request_errors(
+            RDKafkaApiKey::Produce,
+            &[RDKafkaRespErr::RD_KAFKA_RESP_ERR_TOPIC_AUTHORIZATION_FAILED; 100],
+        );
+        
+        let handled = Arc::new(Mutex::new(Vec::new()));
+        let seen = handled.clone();
+        let subscription = SubscriptionConfig::builder()
+            .consumer_group("size-guarded")
+            .max_payload_bytes(4096)
+            .build();
+        bus.subscribe::<MessageReceived, _>(subscription, move |envelope| {
+            seen.lock().unwrap().push(envelope.data.message_id);
+            Ok(ProcessingResult::Success)
+        })
+        .await
+        .expect("Should subscribe");
+        
+        let committed_offset = async || {
+            let mut partition = TopicPartitionList::new();
+            partition.add_partition(MessageReceived::TOPIC, 0);
+            let consumers = bus.consumers.read().await;
+            consumers["oversized-outage-test-size-guarded"].consumer
+                .committed_offsets(partition, Duration::from_secs(1))
+                .ok()
+                .and_then(|committed| committed.find_partition(MessageReceived::TOPIC, 0).map(|elem| elem.offset()))
+        };
+        
+        // Nothing gets past the oversized record while it can't be diverted
+        let failed_divert = format!("publish:{}:false", oversized_topic);
+        let deadline = std::time::Instant::now() + Duration::from_secs(15);
+        while metrics.calls().iter().filter(|call| **call == failed_divert).count() < 2 {
+            assert!(std::time::Instant::now() < deadline, "diversion never retried");
+            tokio::time::sleep(Duration::from_millis(100)).await;
         }
+        assert!(
+            !matches!(committed_offset().await, Some(Offset::Offset(offset)) if offset > 0),
+            "oversized offset committed without a copy",
+        );
+        assert!(handled.lock().unwrap().is_empty());
         
-        info!("✅ Kafka event bus shutdown completed");
-        Ok(())
+        // Once the side topic is back, the retry diverts it and moves on
+        cluster.clear_request_errors(RDKafkaApiKey::Produce);
+        wait_for_handled(&handled, 1).await;
+        let deadline = std::time::Instant::now() + Duration::from_secs(15);
+        while committed_offset().await != Some(Offset::Offset(2)) {
+            assert!(std::time::Instant::now() < deadline, "oversized offset never committed");
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        
+        bus.unsubscribe("size-guarded").await.expect("Should stop the consumer");
     }
-}
-
-impl Clone for KafkaEventBus {
-    fn clone(&self) -> Self {
-        Self {
-            producer: self.producer.clone(),
-            config: self.config.clone(),
-            consumers: self.consumers.clone(),
-            shutdown_signal: self.shutdown_signal.clone(),
-            shutdown_receiver: self.shutdown_receiver.clone(),
+    
+    /// Event type routed to a sub-topic by its priority
+    #[derive(Debug, Clone, Serialize, serde::Deserialize)]
+    struct PrioritizedEvent {
+        note: String,
+        urgent: bool,
+    }
+    
+    impl Event for PrioritizedEvent {
+        const TOPIC: &'static str = "test.prioritized";
+        const VERSION: &'static str = "1.0";
+        const TOPIC_SUFFIXES: &'static [&'static str] = &["urgent", "low"];
+        
+        fn partition_key(&self) -> Option<String> {
+            Some(self.note.clone())
+        }
+        
+        fn topic_suffix(&self) -> Option<&'static str> {
+            Some(if self.urgent { "urgent" } else { "low" })
         }
     }
-}
-
-impl Drop for KafkaEventBus {
-    /// Ensure clean shutdown of Kafka producer
-    /// 
-    /// This flushes any pending messages before the producer is dropped.
-    fn drop(&mut self) {
-        debug!("🧹 Cleaning up Kafka event bus");
-        // The producer will flush automatically when dropped, but we could add
-        // explicit flush logic here if needed for more control
+    
+    #[tokio::test]
+    async fn test_prioritized_event_routed_to_subscribed_sub_topic() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        cluster.create_topic("test.prioritized.urgent", 1, 1).expect("Should create urgent topic");
+        cluster.create_topic("test.prioritized.low", 1, 1).expect("Should create low topic");
+        
+        let mut config = offline_config("priority-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let bus = KafkaEventBus::new(config, Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let subscription = SubscriptionConfig::builder()
+            .consumer_group("urgent-only")
+            .topic_suffix("urgent")
+            .build();
+        bus.subscribe::<PrioritizedEvent, _>(subscription, move |envelope| {
+            tx.send(envelope.data.note).unwrap();
+            Ok(ProcessingResult::Success)
+        })
+        .await
+        .expect("Should subscribe");
+        
+        bus.publish(PrioritizedEvent { note: "batch".to_string(), urgent: false }).await.expect("Should publish");
+        bus.publish(PrioritizedEvent { note: "page".to_string(), urgent: true }).await.expect("Should publish");
+        
+        let received = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+            .await
+            .expect("Should receive before timeout");
+        assert_eq!(received.as_deref(), Some("page"));
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert!(rx.try_recv().is_err(), "low-priority event reached the urgent subscription");
+        
+        bus.unsubscribe("urgent-only").await.expect("Should stop the consumer");
     }
-}
-
-// Add some additional error types for Kafka-specific operations
-#[derive(Debug)]
-pub enum KafkaError {
-    ConnectionFailed(String),
-    TopicNotFound(String),
-    ProducerError(String),
-    ConsumerError(String),
-}
-
-impl std::fmt::Display for KafkaError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            KafkaError::ConnectionFailed(msg) => write!(f, "Kafka connection failed: {}", msg),
-            KafkaError::TopicNotFound(msg) => write!(f, "Kafka topic not found: {}", msg),
-            KafkaError::ProducerError(msg) => write!(f, "Kafka producer error: {}", msg),
-            KafkaError::ConsumerError(msg) => write!(f, "Kafka consumer error: {}", msg),
+    
+    #[tokio::test]
+    async fn test_failed_urgent_event_keeps_its_sub_topic() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        for topic in ["test.prioritized.urgent", "test.prioritized.retry", "test.prioritized.dlq"] {
+            cluster.create_topic(topic, 1, 1).expect("Should create topic");
+        }
+        
+        let mut config = offline_config("priority-retry-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let bus = KafkaEventBus::new(config.clone(), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        let failing = |_: EventEnvelope<PrioritizedEvent>| async {
+            Ok(ProcessingResult::RetryableError("downstream unavailable".to_string()))
+        };
+        let page = || EventEnvelope::new(PrioritizedEvent { note: "page".to_string(), urgent: true });
+        
+        // First failure goes to the retry queue, the last one to the DLQ
+        let mut exhausted = page();
+        exhausted.attempt_count = exhausted.max_attempts;
+        for envelope in [page(), exhausted] {
+            bus.process_event_envelope(envelope, "test.prioritized.urgent", &failing, Duration::from_secs(5), None, &CircuitBreaker::new(0))
+                .await
+                .expect("Failure should be routed, not surfaced");
         }
+        
+        let read_one = |topic: &'static str| {
+            let consumer: StreamConsumer = ClientConfig::new()
+                .set("bootstrap.servers", &config.bootstrap_servers)
+                .set("group.id", format!("priority-retry-test-{}", topic))
+                .set("auto.offset.reset", "earliest")
+                .create()
+                .expect("Should create consumer");
+            consumer.subscribe(&[topic]).expect("Should subscribe");
+            async move {
+                let message = tokio::time::timeout(Duration::from_secs(10), consumer.recv())
+                    .await
+                    .expect("Should receive before timeout")
+                    .expect("Should receive message");
+                KafkaEventBus::decode_envelope::<PrioritizedEvent, _>(message.headers(), message.payload().expect("Should have payload"))
+                    .expect("Should decode")
+            }
+        };
+        
+        let retried = read_one("test.prioritized.retry").await;
+        assert_eq!(retried.metadata["original_topic"], "test.prioritized.urgent");
+        let dead = read_one("test.prioritized.dlq").await;
+        assert_eq!(dead.metadata["original_topic"], "test.prioritized.urgent");
+        
+        // Replay sends it back to the sub-topic it was consumed from
+        assert_eq!(bus.replay_dead_letters::<PrioritizedEvent>(10).await.expect("Should replay"), 1);
+        let replayed = read_one("test.prioritized.urgent").await;
+        assert_eq!(replayed.data.note, "page");
+        assert_eq!(replayed.attempt_count, 0);
+        
+        // A recorded topic outside the event's own falls back to its routing
+        let mut stray = page();
+        stray.add_metadata("original_topic".to_string(), "test.prioritizedx".to_string());
+        assert_eq!(KafkaEventBus::replay_topic(&stray), "test.prioritized.urgent");
+        stray.add_metadata("original_topic".to_string(), "test.prioritized".to_string());
+        assert_eq!(KafkaEventBus::replay_topic(&stray), "test.prioritized");
     }
-}
-
-impl Error for KafkaError {}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::events::{MessageReceived, MessageType, MessageContent};
     
     #[tokio::test]
-    async fn test_kafka_config_from_env() {
-        // Set test environment variables
-        unsafe {
-            std::env::remove_var("KAFKA_BOOTSTRAP_SERVERS");
-            std::env::set_var("KAFKA_BOOTSTRAP_SERVERS", "localhost:9092");
-            std::env::remove_var("KAFKA_CONSUMER_GROUP_ID");
-            std::env::set_var("KAFKA_CONSUMER_GROUP_ID", "test-group");
-            std::env::remove_var("KAFKA_TIMEOUT_MS");
-            std::env::set_var("KAFKA_TIMEOUT_MS", "3000");
+    async fn test_ensure_topics_is_noop_when_disabled() {
+        // Would fail to reach the broker if it tried to create anything
+        let bus = KafkaEventBus::new(offline_config("ensure-topics-disabled-test"), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        bus.ensure_topics::<MessageReceived>().await.expect("Disabled creation should succeed");
+    }
+    
+    /// Needs a real broker: librdkafka's mock cluster doesn't implement
+    /// CreateTopics. Run with
+    /// `KAFKA_BOOTSTRAP_SERVERS=localhost:9092 cargo test -p common -- --ignored ensure_topics`
+    #[tokio::test]
+    #[ignore = "requires a running Kafka broker"]
+    async fn test_ensure_topics_creates_topics_idempotently() {
+        let mut config = offline_config("ensure-topics-test");
+        config.bootstrap_servers = std::env::var("KAFKA_BOOTSTRAP_SERVERS")
+            .unwrap_or_else(|_| "localhost:9092".to_string());
+        config.auto_create_topics = true;
+        let bus = KafkaEventBus::new(config.clone(), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        bus.ensure_topics::<MessageReceived>().await.expect("Should create topics");
+        bus.ensure_topics::<MessageReceived>().await.expect("Existing topics should be accepted");
+        
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &config.bootstrap_servers)
+            .set("group.id", "ensure-topics-test")
+            .create()
+            .expect("Should create consumer");
+        for topic in [
+            MessageReceived::TOPIC.to_string(),
+            format!("{}.retry", MessageReceived::TOPIC),
+            format!("{}.dlq", MessageReceived::TOPIC),
+        ] {
+            let metadata = consumer.fetch_metadata(Some(&topic), Duration::from_secs(5))
+                .expect("Should fetch metadata");
+            let found = metadata.topics().iter()
+                .find(|t| t.name() == topic)
+                .expect("Topic should be listed");
+            assert!(found.error().is_none(), "{} should exist", topic);
         }
+    }
+    
+    #[tokio::test]
+    async fn test_transactions_need_a_transactional_id() {
+        let bus = KafkaEventBus::new(offline_config("no-transactions-test"), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
         
-        let config = KafkaConfig::from_env().expect("Should create config from env");
+        assert!(matches!(bus.begin_transaction(), Err(EventBusError::ConfigError(_))));
+        assert!(matches!(bus.commit_transaction().await, Err(EventBusError::ConfigError(_))));
+        let result = bus
+            .process_transactionally("payments", 10, |_: EventEnvelope<MessageReceived>| async { Ok(()) })
+            .await;
+        assert!(matches!(result, Err(EventBusError::ConfigError(_))));
         
-        assert_eq!(config.bootstrap_servers, "localhost:9092");
-        assert_eq!(config.consumer_group_id, "test-group");
-        assert_eq!(config.timeout_ms, 3000);
+        // Transactions are built on the idempotent producer
+        let mut config = offline_config("no-idempotence-test");
+        config.transactional_id = Some("payments-1".to_string());
+        config.enable_idempotence = false;
+        assert!(matches!(
+            KafkaEventBus::new(config, Arc::new(NoopMetrics)).await,
+            Err(EventBusError::ConfigError(_))
+        ));
     }
     
-    #[test]
-    fn test_event_serialization() {
-        let message = MessageReceived {
-            message_id: "test-123".to_string(),
+    /// Needs a real broker for transactions. Run with
+    /// `KAFKA_BOOTSTRAP_SERVERS=localhost:9092 cargo test -p common -- --ignored transaction`
+    #[tokio::test]
+    #[ignore = "requires a running Kafka broker"]
+    async fn test_only_committed_transactions_are_visible() {
+        let run = uuid::Uuid::new_v4().to_string();
+        let mut config = offline_config("transactions-test");
+        config.bootstrap_servers = std::env::var("KAFKA_BOOTSTRAP_SERVERS")
+            .unwrap_or_else(|_| "localhost:9092".to_string());
+        config.transactional_id = Some(format!("transactions-test-{}", run));
+        let bus = KafkaEventBus::new(config.clone(), Arc::new(NoopMetrics))
+            .await
+            .expect("Should create transactional event bus");
+        
+        let event = |message_id: String| MessageReceived {
+            message_id,
             from_phone: "+1234567890".to_string(),
             message_type: MessageType::Text,
             content: MessageContent::Text {
-                body: "Hello, world!".to_string(),
+                body: "Payment confirmed".to_string(),
             },
             received_at: chrono::Utc::now(),
             metadata: std::collections::HashMap::new(),
         };
+        let committed = format!("committed-{}", run);
+        let aborted = format!("aborted-{}", run);
         
-        let envelope = EventEnvelope::new(message);
+        bus.begin_transaction().expect("Should begin transaction");
+        bus.publish(event(committed.clone())).await.expect("Should publish in transaction");
+        bus.commit_transaction().await.expect("Should commit");
         
-        // Test that we can serialize and deserialize the envelope
-        let json = serde_json::to_string(&envelope).expect("Should serialize");
-        let deserialized: EventEnvelope<MessageReceived> = 
-            serde_json::from_str(&json).expect("Should deserialize");
+        bus.begin_transaction().expect("Should begin transaction");
+        bus.publish(event(aborted.clone())).await.expect("Should publish in transaction");
+        bus.abort_transaction().await.expect("Should abort");
         
-        assert_eq!(deserialized.data.message_id, "test-123");
-        assert_eq!(deserialized.version, "1.0");
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &config.bootstrap_servers)
+            .set("group.id", format!("transactions-test-reader-{}", run))
+            .set("auto.offset.reset", "earliest")
+            .set("isolation.level", "read_committed")
+            .create()
+            .expect("Should create consumer");
+        consumer.subscribe(&[MessageReceived::TOPIC]).expect("Should subscribe");
+        
+        let mut seen = Vec::new();
+        while let Ok(Ok(message)) = tokio::time::timeout(Duration::from_secs(5), consumer.recv()).await {
+            if let Some(Ok(envelope)) = message.payload().map(serde_json::from_slice::<EventEnvelope<MessageReceived>>) {
+                seen.push(envelope.data.message_id);
+            }
+        }
+        assert!(seen.contains(&committed), "Committed event should be visible");
+        assert!(!seen.contains(&aborted), "Aborted event should not be visible");
+    }
+    
+    #[tokio::test]
+    async fn test_publish_logs_structured_event_id() {
+        use tracing_subscriber::layer::SubscriberExt;
+        
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        cluster.create_topic(MessageReceived::TOPIC, 1, 1).expect("Should create topic");
+        
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::registry().with(CaptureLayer(logs.clone()));
+        let _guard = tracing::subscriber::set_default(subscriber);
+        
+        let mut config = offline_config("structured-logs-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        let bus = KafkaEventBus::new(config, Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        bus.publish(MessageReceived {
+            message_id: "logged-123".to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Hello, world!".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        })
+        .await
+        .expect("Should publish");
+        
+        let logs = logs.lock().unwrap();
+        let published = logs.iter()
+            .find(|fields| fields.get("message").is_some_and(|m| m.contains("Event published")))
+            .expect("Should log the publish");
+        assert!(published.get("event_id").is_some_and(|id| !id.is_empty()));
+        assert_eq!(published.get("topic").map(String::as_str), Some(MessageReceived::TOPIC));
+    }
+    
+    #[tokio::test]
+    async fn test_run_bounded_respects_max_in_flight() {
+        use std::sync::atomic::AtomicUsize;
+        
+        for (max_in_flight, expected_peak) in [(3, 3), (0, 20)] {
+            let in_flight = Arc::new(AtomicUsize::new(0));
+            let peak = Arc::new(AtomicUsize::new(0));
+            
+            let futures = (0..20).map(|i| {
+                let in_flight = in_flight.clone();
+                let peak = peak.clone();
+                async move {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    i
+                }
+            });
+            
+            let results = KafkaEventBus::run_bounded(futures, max_in_flight).await;
+            assert_eq!(results, (0..20).collect::<Vec<_>>(), "results keep input order");
+            assert_eq!(peak.load(Ordering::SeqCst), expected_peak);
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_publish_batch_reports_each_failure() {
+        let cluster = rdkafka::mocking::MockCluster::new(1).expect("Should start mock cluster");
+        cluster.create_topic(MessageReceived::TOPIC, 1, 1).expect("Should create topic");
+        
+        // Strict keys make the events with an empty phone fail
+        let mut config = offline_config("batch-errors-test");
+        config.bootstrap_servers = cluster.bootstrap_servers();
+        config.require_partition_key = true;
+        let bus = KafkaEventBus::new(config, Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        
+        let batch = || ["+1234567890", "", "+1987654321", ""]
+            .into_iter()
+            .map(|phone| MessageReceived {
+                message_id: "batch-123".to_string(),
+                from_phone: phone.to_string(),
+                message_type: MessageType::Text,
+                content: MessageContent::Text {
+                    body: "Hello, world!".to_string(),
+                },
+                received_at: chrono::Utc::now(),
+                metadata: std::collections::HashMap::new(),
+            })
+            .collect::<Vec<_>>();
+        
+        match bus.publish_batch(batch()).await {
+            Err(EventBusError::BatchPublishFailed(error)) => {
+                assert_eq!(error.succeeded, vec![0, 2]);
+                assert_eq!(error.failed_indices(), vec![1, 3]);
+                assert!(matches!(error.failures[0].1, EventBusError::PublishFailed(_)));
+            }
+            other => panic!("Expected a batch error, got {:?}", other),
+        }
+        
+        let result = bus.publish_batch_all_or_nothing(batch()).await;
+        assert!(matches!(result, Err(EventBusError::PublishFailed(_))));
     }
 }
-
@@ -1,16 +1,33 @@
+pub mod circuit_breaker;
+pub mod clock;
+pub mod commit_batch;
 pub mod errors;
 pub mod events;
+pub mod geo;
 pub mod message_bus;
 pub mod kafka_bus;
+pub mod memory_bus;
+pub mod metrics;
+pub mod redact;
+pub mod serialization;
+#[cfg(feature = "otel")]
+pub mod trace_context;
 // Keep webhook_types for now - we need these to parse incoming WhatsApp webhooks
 pub mod webhook_types;
 
 // Re-export the core types that other crates will use
+pub use circuit_breaker::*;
+pub use clock::*;
+pub use commit_batch::*;
 pub use errors::*;
 pub use events::*;
 pub use message_bus::*;
 pub use webhook_types::*;
 pub use kafka_bus::*;
+pub use memory_bus::*;
+pub use metrics::*;
+pub use redact::*;
+pub use serialization::*;
 
 #[cfg(test)]
 mod tests {
@@ -44,6 +61,25 @@ mod tests {
         assert_eq!(deserialized.data.message_id, "wamid.123");
     }
     
+    /// Test that message type tags stay stable on the wire
+    #[test]
+    fn test_message_type_serialized_tags() {
+        let cases = [
+            (MessageType::Text, "\"Text\""),
+            (MessageType::Image, "\"Image\""),
+            (MessageType::Audio, "\"Audio\""),
+            (MessageType::Video, "\"Video\""),
+            (MessageType::Document, "\"Document\""),
+            (MessageType::Sticker, "\"Sticker\""),
+        ];
+        
+        for (message_type, tag) in cases {
+            assert_eq!(serde_json::to_string(&message_type).expect("Should serialize"), tag);
+            let parsed: MessageType = serde_json::from_str(tag).expect("Should deserialize");
+            assert_eq!(parsed, message_type);
+        }
+    }
+    
     /// Test that interaction events work properly
     #[test]
     fn test_interaction_event() {
@@ -123,4 +159,139 @@ mod tests {
         envelope.increment_attempt();
         assert!(envelope.should_dead_letter());
     }
+    
+    /// Test the default schema version policy
+    #[test]
+    fn test_default_version_mismatch_policy() {
+        let policy = VersionMismatchPolicy::default();
+        
+        // Same version and forward-compatible minor bumps are processed
+        assert_eq!(policy.decide("1.0", "1.0"), VersionMismatchAction::Process);
+        assert_eq!(policy.decide("1.3", "1.0"), VersionMismatchAction::Process);
+        // Older majors are assumed to be readable by newer consumers
+        assert_eq!(policy.decide("1.0", "2.0"), VersionMismatchAction::Process);
+        // Newer majors and garbage versions are quarantined
+        assert_eq!(policy.decide("2.0", "1.0"), VersionMismatchAction::Quarantine);
+        assert_eq!(policy.decide("latest", "1.0"), VersionMismatchAction::Quarantine);
+        
+        assert_eq!(major_version("v2.3.1"), Some(2));
+        assert_eq!(major_version("1"), Some(1));
+        assert_eq!(major_version(""), None);
+    }
+    
+    /// Test that a custom version policy replaces the default
+    #[test]
+    fn test_custom_version_mismatch_policy() {
+        let config = SubscriptionConfig {
+            version_mismatch_policy: VersionMismatchPolicy::new(|_, _| VersionMismatchAction::Skip),
+            ..Default::default()
+        };
+        
+        assert_eq!(config.version_mismatch_policy.decide("2.0", "1.0"), VersionMismatchAction::Skip);
+        // Matching versions never reach the custom hook
+        assert_eq!(config.version_mismatch_policy.decide("1.0", "1.0"), VersionMismatchAction::Process);
+    }
+    
+    #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+    struct OptedOut {
+        reason: String,
+        phone: String,
+    }
+    
+    impl_event!(OptedOut {
+        topic: "test.opt-outs",
+        version: "2.0",
+        partition_by: phone,
+    });
+    
+    #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+    struct Heartbeat {
+        service: String,
+    }
+    
+    impl_event!(Heartbeat {
+        topic: "test.heartbeats",
+        version: "1.0",
+    });
+    
+    #[test]
+    fn test_impl_event_partitions_by_chosen_field() {
+        let event = OptedOut {
+            reason: "STOP".to_string(),
+            phone: "+1234567890".to_string(),
+        };
+        
+        assert_eq!(OptedOut::TOPIC, "test.opt-outs");
+        assert_eq!(OptedOut::VERSION, "2.0");
+        assert_eq!(event.partition_key(), Some("+1234567890".to_string()));
+        assert_eq!(EventEnvelope::new(event).partition_key(), Some("+1234567890".to_string()));
+    }
+    
+    #[test]
+    fn test_impl_event_without_partition_field_is_unkeyed() {
+        let event = Heartbeat { service: "webhook".to_string() };
+        
+        assert_eq!(Heartbeat::TOPIC, "test.heartbeats");
+        assert_eq!(event.partition_key(), None);
+        assert_eq!(event.event_type(), "Heartbeat");
+    }
+    
+    /// Test that the builder produces the same config as manual construction
+    #[test]
+    fn test_subscription_config_builder() {
+        // Nothing set: identical to the defaults
+        let built = SubscriptionConfig::builder().build();
+        assert_eq!(format!("{:?}", built), format!("{:?}", SubscriptionConfig::default()));
+        
+        let built = SubscriptionConfigBuilder::new()
+            .consumer_group("whatsapp-sender")
+            .max_batch_size(10)
+            .batch_timeout_ms(250)
+            .auto_commit(false)
+            .auto_commit_interval_ms(1000)
+            .version_mismatch_policy(VersionMismatchPolicy::new(|_, _| VersionMismatchAction::Skip))
+            .handler_timeout_ms(5000)
+            .circuit_breaker_threshold(3)
+            .circuit_breaker_cooldown_ms(2000)
+            .topic_suffix("urgent")
+            .max_age_ms(60_000)
+            .commit_batch_size(500)
+            .commit_interval_ms(1500)
+            .fetch_min_bytes(1)
+            .fetch_wait_max_ms(10)
+            .max_partition_fetch_bytes(4_194_304)
+            .stale_after_ms(300_000)
+            .max_payload_bytes(1_048_576)
+            .build();
+        let manual = SubscriptionConfig {
+            consumer_group: "whatsapp-sender".to_string(),
+            max_batch_size: 10,
+            batch_timeout_ms: 250,
+            auto_commit: false,
+            auto_commit_interval_ms: 1000,
+            version_mismatch_policy: VersionMismatchPolicy::new(|_, _| VersionMismatchAction::Skip),
+            handler_timeout_ms: 5000,
+            circuit_breaker_threshold: 3,
+            circuit_breaker_cooldown_ms: 2000,
+            topic_suffix: Some("urgent".to_string()),
+            max_age_ms: 60_000,
+            commit_batch_size: 500,
+            commit_interval_ms: 1500,
+            fetch_min_bytes: Some(1),
+            fetch_wait_max_ms: Some(10),
+            max_partition_fetch_bytes: Some(4_194_304),
+            stale_after_ms: 300_000,
+            max_payload_bytes: 1_048_576,
+        };
+        assert_eq!(format!("{:?}", built), format!("{:?}", manual));
+        assert_eq!(built.version_mismatch_policy.decide("2.0", "1.0"), VersionMismatchAction::Skip);
+        assert_eq!(built.topic::<OptedOut>(), "test.opt-outs.urgent");
+        assert_eq!(SubscriptionConfig::default().topic::<OptedOut>(), "test.opt-outs");
+        assert_eq!(built.max_age(), Some(std::time::Duration::from_secs(60)));
+        assert_eq!(SubscriptionConfig::default().max_age(), None);
+        assert_eq!(built.commit_interval(), Some(std::time::Duration::from_millis(1500)));
+        assert_eq!(SubscriptionConfig::default().commit_interval(), None);
+        assert_eq!(built.stale_after(), Some(std::time::Duration::from_secs(300)));
+        assert_eq!(SubscriptionConfig::default().stale_after(), None);
+    }
 }
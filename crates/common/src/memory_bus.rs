@@ -0,0 +1,681 @@
+use crate::clock::{Clock, SystemClock};
+use crate::message_bus::{
+    BatchPublishError,
+    Event,
+    EventBus,
+    EventBusError,
+    EventEnvelope,
+    ProcessingResult,
+    PublishReceipt,
+    SubscriptionConfig,
+};
+use crate::serialization::{SerializationFormat, Serializer};
+use std::{
+    collections::HashMap,
+    error::Error,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::{broadcast, watch};
+use tracing::{debug, error, info, warn};
+
+/// Number of undelivered events buffered per topic before slow subscribers lag
+const TOPIC_CHANNEL_CAPACITY: usize = 1024;
+
+/// A record published to an `InMemoryEventBus`, kept for test assertions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishedEvent {
+    /// Topic the record was routed to
+    pub topic: String,
+    /// Partition key (explicit override or `Event::partition_key()`)
+    pub key: Option<String>,
+    /// Serialized envelope, as it would be written to Kafka
+    pub payload: Vec<u8>,
+}
+
+impl PublishedEvent {
+    /// Decode the payload back into a typed envelope
+    pub fn decode<T: Event>(&self) -> Result<EventEnvelope<T>, EventBusError> {
+        SerializationFormat::Json.deserialize(&self.payload)
+    }
+}
+
+/// In-process implementation of the EventBus trait
+///
+/// This is the recommended test double for services built on `EventBus`:
+/// it needs no broker, routes events by `Event::TOPIC` like Kafka does, and
+/// records every published record so tests can assert on `published_events()`.
+///
+/// Each topic is a broadcast channel, so per-topic (and therefore per-key)
+/// ordering is preserved. Unlike Kafka there are no consumer groups or
+/// offsets: every subscriber to a topic sees every event published after it
+/// subscribed. Failed events are routed to `{topic}.retry` and `{topic}.dlq`
+/// the same way `KafkaEventBus` routes them, but nothing consumes those topics.
+#[derive(Clone)]
+pub struct InMemoryEventBus {
+    /// Every record published so far, in publish order
+    published: Arc<Mutex<Vec<PublishedEvent>>>,
+    /// Broadcast channel per topic, created on first publish or subscribe
+    topics: Arc<Mutex<HashMap<String, broadcast::Sender<PublishedEvent>>>>,
+    /// Signal used to stop subscription tasks on shutdown
+    shutdown_signal: Arc<watch::Sender<bool>>,
+    /// Time source for envelope timestamps and age checks
+    clock: Arc<dyn Clock>,
+}
+
+impl InMemoryEventBus {
+    /// Create an empty in-memory event bus
+    pub fn new() -> Self {
+        let (shutdown_signal, _) = watch::channel(false);
+
+        Self {
+            published: Arc::new(Mutex::new(Vec::new())),
+            topics: Arc::new(Mutex::new(HashMap::new())),
+            shutdown_signal: Arc::new(shutdown_signal),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Use `clock` instead of the system clock for envelope timestamps and
+    /// `max_age_ms` checks
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// All records published so far, in publish order
+    pub fn published_events(&self) -> Vec<PublishedEvent> {
+        self.published.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Records published to a single topic, in publish order
+    pub fn published_to(&self, topic: &str) -> Vec<PublishedEvent> {
+        self.published_events()
+            .into_iter()
+            .filter(|event| event.topic == topic)
+            .collect()
+    }
+
+    /// Get the broadcast sender for a topic, creating it if needed
+    fn topic_sender(&self, topic: &str) -> broadcast::Sender<PublishedEvent> {
+        let mut topics = self.topics.lock().unwrap_or_else(|e| e.into_inner());
+        topics
+            .entry(topic.to_string())
+            .or_insert_with(|| broadcast::channel(TOPIC_CHANNEL_CAPACITY).0)
+            .clone()
+    }
+
+    /// Record a published event and deliver it to the topic's subscribers
+    ///
+    /// Returns the event's offset: the number of events published to its
+    /// topic before it.
+    fn deliver(&self, event: PublishedEvent) -> i64 {
+        debug!("📤 Publishing {} bytes to in-memory topic {}", event.payload.len(), event.topic);
+
+        let offset = {
+            let mut published = self.published.lock().unwrap_or_else(|e| e.into_inner());
+            let offset = published.iter().filter(|published| published.topic == event.topic).count();
+            published.push(event.clone());
+            offset as i64
+        };
+
+        // Having no subscribers isn't an error; the event is still recorded
+        let _ = self.topic_sender(&event.topic).send(event);
+        offset
+    }
+
+    /// Serialize an envelope and publish it to its topic
+    fn publish_envelope<T>(&self, envelope: &EventEnvelope<T>, topic: &str, key_override: Option<String>) -> Result<(), EventBusError>
+    where
+        T: Event,
+    {
+        let payload = SerializationFormat::Json.serialize(envelope)?;
+
+        self.deliver(PublishedEvent {
+            topic: topic.to_string(),
+            key: key_override.or_else(|| envelope.partition_key()),
+            payload,
+        });
+        Ok(())
+    }
+
+    /// Run the handler and route failures to the retry or dead letter topic
+    ///
+    /// Events older than `max_age` skip the handler and go straight to the
+    /// dead letter topic.
+    async fn process_event_envelope<T, F, Fut>(
+        &self,
+        mut envelope: EventEnvelope<T>,
+        handler: &F,
+        max_age: Option<Duration>,
+    ) -> Result<(), EventBusError>
+    where
+        T: Event,
+        F: Fn(EventEnvelope<T>) -> Fut,
+        Fut: Future<Output = Result<ProcessingResult, Box<dyn Error + Send + Sync>>>,
+    {
+        let event_id = envelope.event_id.clone();
+        let correlation_id = envelope.correlation_id.clone();
+
+        if let Some(max_age) = max_age
+            && envelope.age_at(self.clock.now()) > max_age
+        {
+            warn!("⌛ Event {} (correlation {}) is too old to process, sending to DLQ", event_id, correlation_id);
+            envelope.add_metadata("dlq_reason".to_string(), "max_age_exceeded".to_string());
+            return self.publish_envelope(&envelope, &format!("{}.dlq", T::TOPIC), None);
+        }
+
+        match handler(envelope.clone()).await {
+            Ok(ProcessingResult::Success) => {
+                debug!("✅ Event {} (correlation {}) processed successfully", event_id, correlation_id);
+                Ok(())
+            }
+            Ok(ProcessingResult::Skip(reason)) => {
+                debug!("⏭️ Event {} (correlation {}) skipped: {}", event_id, correlation_id, reason);
+                Ok(())
+            }
+            Ok(ProcessingResult::RetryableError(error_msg)) => {
+                warn!("🔄 Event {} (correlation {}) failed with retryable error: {}", event_id, correlation_id, error_msg);
+                self.retry_or_dead_letter(envelope)
+            }
+            Ok(ProcessingResult::PermanentError(error_msg)) => {
+                error!("💀 Event {} (correlation {}) failed with permanent error: {}", event_id, correlation_id, error_msg);
+                self.publish_envelope(&envelope, &format!("{}.dlq", T::TOPIC), None)
+            }
+            Err(handler_error) => {
+                error!("❌ Handler threw exception for event {} (correlation {}): {}", event_id, correlation_id, handler_error);
+                self.retry_or_dead_letter(envelope)
+            }
+        }
+    }
+
+    /// Route a retryable failure to `{topic}.retry`, or `{topic}.dlq` once attempts run out
+    fn retry_or_dead_letter<T: Event>(&self, mut envelope: EventEnvelope<T>) -> Result<(), EventBusError> {
+        if envelope.should_dead_letter() {
+            return self.publish_envelope(&envelope, &format!("{}.dlq", T::TOPIC), None);
+        }
+
+        envelope.increment_attempt();
+        self.publish_envelope(&envelope, &format!("{}.retry", T::TOPIC), None)
+    }
+}
+
+impl Default for InMemoryEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(async_fn_in_trait)]
+impl EventBus for InMemoryEventBus {
+    type Error = EventBusError;
+
+    /// Publish a single event to its topic
+    async fn publish<T>(&self, event: T) -> Result<(), Self::Error>
+    where
+        T: Event,
+    {
+        let topic = event.routing_topic();
+        self.publish_envelope(&EventEnvelope::with_clock(event, self.clock.as_ref()), &topic, None)
+    }
+
+    /// Publish a single event to its topic, reporting its position there
+    ///
+    /// Topics have a single partition, 0, and offsets count up from 0 per topic.
+    async fn publish_with_receipt<T>(&self, event: T) -> Result<PublishReceipt, Self::Error>
+    where
+        T: Event,
+    {
+        let envelope = EventEnvelope::with_clock(event, self.clock.as_ref());
+        let topic = envelope.data.routing_topic();
+        let offset = self.deliver(PublishedEvent {
+            topic: topic.clone(),
+            key: envelope.partition_key(),
+            payload: SerializationFormat::Json.serialize(&envelope)?,
+        });
+        Ok(PublishReceipt { topic, partition: 0, offset })
+    }
+
+    /// Publish a single event, overriding its partition key
+    async fn publish_with_key<T>(&self, event: T, key: String) -> Result<(), Self::Error>
+    where
+        T: Event,
+    {
+        let topic = event.routing_topic();
+        self.publish_envelope(&EventEnvelope::with_clock(event, self.clock.as_ref()), &topic, Some(key))
+    }
+
+    /// Publish an event once `delay` has passed
+    ///
+    /// The event is serialized now and delivered by a background task, so it
+    /// only shows up in `published_events()` once it's due. Pending events
+    /// are dropped on shutdown.
+    async fn publish_delayed<T>(&self, event: T, delay: Duration) -> Result<(), Self::Error>
+    where
+        T: Event,
+    {
+        let envelope = EventEnvelope::with_clock(event, self.clock.as_ref());
+        let scheduled = PublishedEvent {
+            topic: envelope.data.routing_topic(),
+            key: envelope.partition_key(),
+            payload: SerializationFormat::Json.serialize(&envelope)?,
+        };
+        debug!("⏰ Scheduling event {} on {} in {:?}", envelope.event_id, scheduled.topic, delay);
+
+        let mut shutdown_rx = self.shutdown_signal.subscribe();
+        let event_bus = self.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = shutdown_rx.changed() => {}
+                _ = tokio::time::sleep(delay) => {
+                    event_bus.deliver(scheduled);
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Publish raw bytes to a topic
+    async fn publish_raw(&self, topic: &str, key: Option<String>, payload: Vec<u8>) -> Result<(), Self::Error> {
+        self.deliver(PublishedEvent {
+            topic: topic.to_string(),
+            key,
+            payload,
+        });
+        Ok(())
+    }
+
+    /// Publish multiple events in order
+    async fn publish_batch<T>(&self, events: Vec<T>) -> Result<(), Self::Error>
+    where
+        T: Event,
+    {
+        let mut results = Vec::with_capacity(events.len());
+        for event in events {
+            results.push(self.publish(event).await);
+        }
+        BatchPublishError::check(results)
+    }
+
+    /// Subscribe to events published after this call with a single-event handler
+    async fn subscribe<T, F>(&self, config: SubscriptionConfig, handler: F) -> Result<(), Self::Error>
+    where
+        T: Event,
+        F: Fn(EventEnvelope<T>) -> Result<ProcessingResult, Box<dyn Error + Send + Sync>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.subscribe_async(config, move |envelope| std::future::ready(handler(envelope))).await
+    }
+
+    /// Subscribe to events published after this call with an async single-event handler
+    ///
+    /// Each handler call is awaited before the next event is taken.
+    async fn subscribe_async<T, F, Fut>(&self, config: SubscriptionConfig, handler: F) -> Result<(), Self::Error>
+    where
+        T: Event,
+        F: Fn(EventEnvelope<T>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<ProcessingResult, Box<dyn Error + Send + Sync>>> + Send + 'static,
+    {
+        let topic = config.topic::<T>();
+        let max_age = config.max_age();
+        info!("🎯 Starting in-memory subscription to topic {} for {}", topic, config.consumer_group);
+
+        let mut receiver = self.topic_sender(&topic).subscribe();
+        let mut shutdown_rx = self.shutdown_signal.subscribe();
+        let event_bus = self.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let received = tokio::select! {
+                    _ = shutdown_rx.changed() => break,
+                    received = receiver.recv() => received,
+                };
+
+                let event = match received {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("⚠️ Subscriber on {} lagged, {} events dropped", topic, skipped);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+
+                let envelope: EventEnvelope<T> = match event.decode() {
+                    Ok(envelope) => envelope,
+                    Err(e) => {
+                        error!("❌ Failed to deserialize message: {}", e);
+                        continue;
+                    }
+                };
+
+                if let Err(e) = event_bus.process_event_envelope(envelope, &handler, max_age).await {
+                    error!("❌ Failed to process event: {}", e);
+                }
+            }
+
+            info!("🏁 In-memory subscription ended for topic {}", topic);
+        });
+
+        Ok(())
+    }
+
+    /// Batch subscriptions aren't supported, matching `KafkaEventBus`
+    async fn subscribe_batch<T, F>(&self, _config: SubscriptionConfig, _handler: F) -> Result<(), Self::Error>
+    where
+        T: Event,
+        F: Fn(Vec<EventEnvelope<T>>) -> Result<Vec<ProcessingResult>, Box<dyn Error + Send + Sync>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Err(EventBusError::SubscriptionFailed("Batch subscription not yet implemented".to_string()))
+    }
+
+    /// The in-memory bus is always healthy
+    async fn health_check(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Stop all subscription tasks
+    async fn shutdown(&self) -> Result<(), Self::Error> {
+        let _ = self.shutdown_signal.send(true);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{
+        MessageContent, MessageReceived, MessageType, ResponseContent, ResponsePriority,
+        ResponseReady, ResponseType,
+    };
+    use crate::clock::MockClock;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::mpsc;
+
+    fn message(from_phone: &str) -> MessageReceived {
+        MessageReceived {
+            message_id: "test-123".to_string(),
+            from_phone: from_phone.to_string(),
+            message_type: MessageType::Text,
+            content: MessageContent::Text {
+                body: "Hello, world!".to_string(),
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_reaches_matching_subscriber_only() {
+        let bus = InMemoryEventBus::new();
+        let (messages_tx, mut messages_rx) = mpsc::unbounded_channel();
+        let (responses_tx, mut responses_rx) = mpsc::unbounded_channel();
+
+        bus.subscribe(SubscriptionConfig::default(), move |envelope: EventEnvelope<MessageReceived>| {
+            let _ = messages_tx.send(envelope);
+            Ok(ProcessingResult::Success)
+        }).await.expect("Should subscribe");
+        bus.subscribe(SubscriptionConfig::default(), move |envelope: EventEnvelope<ResponseReady>| {
+            let _ = responses_tx.send(envelope);
+            Ok(ProcessingResult::Success)
+        }).await.expect("Should subscribe");
+
+        bus.publish(message("+1234567890")).await.expect("Should publish");
+
+        let received = tokio::time::timeout(Duration::from_secs(1), messages_rx.recv())
+            .await
+            .expect("Should receive before timeout")
+            .expect("Should receive envelope");
+        assert_eq!(received.data.message_id, "test-123");
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(responses_rx.try_recv().is_err());
+
+        let published = bus.published_events();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].topic, MessageReceived::TOPIC);
+        assert_eq!(published[0].key.as_deref(), Some("+1234567890"));
+    }
+
+    #[tokio::test]
+    async fn test_published_events_record_keys_and_routing() {
+        let bus = InMemoryEventBus::new();
+
+        bus.publish_with_key(message("+1234567890"), "thread-42".to_string())
+            .await
+            .expect("Should publish");
+        bus.publish(ResponseReady {
+            original_message_id: "wamid.789".to_string(),
+            to_phone: "+1987654321".to_string(),
+            response_type: ResponseType::Text,
+            content: ResponseContent::Text {
+                message: "Hi".to_string(),
+            },
+            generated_at: chrono::Utc::now(),
+            priority: ResponsePriority::Normal,
+        }).await.expect("Should publish");
+
+        let messages = bus.published_to(MessageReceived::TOPIC);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].key.as_deref(), Some("thread-42"));
+        let envelope: EventEnvelope<MessageReceived> = messages[0].decode().expect("Should decode");
+        assert_eq!(envelope.data.from_phone, "+1234567890");
+
+        let responses = bus.published_to(ResponseReady::TOPIC);
+        assert_eq!(responses[0].key.as_deref(), Some("+1987654321"));
+        assert!(bus.health_check().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_publish_with_receipt_counts_offsets_per_topic() {
+        let bus = InMemoryEventBus::new();
+
+        let first = bus.publish_with_receipt(message("+1234567890")).await.expect("Should publish");
+        bus.publish_raw("other.topic", None, b"{}".to_vec()).await.expect("Should publish");
+        let second = bus.publish_with_receipt(message("+1987654321")).await.expect("Should publish");
+
+        assert_eq!(first, PublishReceipt { topic: MessageReceived::TOPIC.to_string(), partition: 0, offset: 0 });
+        assert_eq!(second.offset, 1);
+        assert_eq!(bus.published_to(MessageReceived::TOPIC).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_events_routed_to_retry_and_dlq() {
+        let bus = InMemoryEventBus::new();
+
+        bus.subscribe(SubscriptionConfig::default(), |envelope: EventEnvelope<MessageReceived>| {
+            if envelope.data.from_phone == "+1" {
+                Ok(ProcessingResult::retry("busy"))
+            } else {
+                Ok(ProcessingResult::permanent_error("bad"))
+            }
+        }).await.expect("Should subscribe");
+
+        bus.publish(message("+1")).await.expect("Should publish");
+        bus.publish(message("+2")).await.expect("Should publish");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let retried = bus.published_to(&format!("{}.retry", MessageReceived::TOPIC));
+        assert_eq!(retried.len(), 1);
+        let envelope: EventEnvelope<MessageReceived> = retried[0].decode().expect("Should decode");
+        assert_eq!(envelope.attempt_count, 1);
+
+        let dead = bus.published_to(&format!("{}.dlq", MessageReceived::TOPIC));
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].key.as_deref(), Some("+2"));
+    }
+
+    #[tokio::test]
+    async fn test_skipped_events_are_not_routed() {
+        let bus = InMemoryEventBus::new();
+
+        bus.subscribe(SubscriptionConfig::default(), |_: EventEnvelope<MessageReceived>| {
+            Ok(ProcessingResult::skip("blocked sender"))
+        }).await.expect("Should subscribe");
+
+        bus.publish(message("+1")).await.expect("Should publish");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert!(bus.published_to(&format!("{}.retry", MessageReceived::TOPIC)).is_empty());
+        assert!(bus.published_to(&format!("{}.dlq", MessageReceived::TOPIC)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_async_handler_result_is_routed() {
+        let bus = InMemoryEventBus::new();
+        let (handled_tx, mut handled_rx) = mpsc::unbounded_channel();
+
+        bus.subscribe_async(SubscriptionConfig::default(), move |envelope: EventEnvelope<MessageReceived>| {
+            let handled_tx = handled_tx.clone();
+            async move {
+                // Stands in for an awaited API call
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                let _ = handled_tx.send(envelope.data.from_phone.clone());
+                if envelope.data.from_phone == "+1" {
+                    Ok(ProcessingResult::Success)
+                } else {
+                    Ok(ProcessingResult::retry("busy"))
+                }
+            }
+        }).await.expect("Should subscribe");
+
+        bus.publish(message("+1")).await.expect("Should publish");
+        bus.publish(message("+2")).await.expect("Should publish");
+
+        for expected in ["+1", "+2"] {
+            let handled = tokio::time::timeout(Duration::from_secs(1), handled_rx.recv())
+                .await
+                .expect("Handler should run")
+                .expect("Channel should stay open");
+            assert_eq!(handled, expected);
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let retried = bus.published_to(&format!("{}.retry", MessageReceived::TOPIC));
+        assert_eq!(retried.len(), 1);
+        assert_eq!(retried[0].key.as_deref(), Some("+2"));
+    }
+
+    #[tokio::test]
+    async fn test_correlation_id_survives_retries_and_dlq() {
+        let bus = InMemoryEventBus::new();
+        let retry_topic = format!("{}.retry", MessageReceived::TOPIC);
+        let dlq_topic = format!("{}.dlq", MessageReceived::TOPIC);
+        let busy = |_: EventEnvelope<MessageReceived>| std::future::ready(Ok(ProcessingResult::retry("busy")));
+
+        let original = EventEnvelope::with_max_attempts(message("+1"), 2);
+        bus.process_event_envelope(original.clone(), &busy, None).await.expect("Should route");
+
+        // Feed each retry back through the handler, as a retry consumer would
+        let first_retry: EventEnvelope<MessageReceived> =
+            bus.published_to(&retry_topic)[0].decode().expect("Should decode");
+        bus.process_event_envelope(first_retry, &busy, None).await.expect("Should route");
+        let second_retry: EventEnvelope<MessageReceived> =
+            bus.published_to(&retry_topic)[1].decode().expect("Should decode");
+        assert_eq!(second_retry.attempt_count, 2);
+        bus.process_event_envelope(second_retry.clone(), &busy, None).await.expect("Should route");
+
+        let dead: EventEnvelope<MessageReceived> =
+            bus.published_to(&dlq_topic)[0].decode().expect("Should decode");
+        assert_eq!(second_retry.correlation_id, original.correlation_id);
+        assert_eq!(dead.correlation_id, original.correlation_id);
+        assert_eq!(dead.event_id, original.event_id);
+    }
+
+    #[tokio::test]
+    async fn test_stale_event_skips_handler_and_goes_to_dlq() {
+        let bus = InMemoryEventBus::new();
+        let dlq_topic = format!("{}.dlq", MessageReceived::TOPIC);
+        let calls = AtomicUsize::new(0);
+        let handler = |_: EventEnvelope<MessageReceived>| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            std::future::ready(Ok(ProcessingResult::Success))
+        };
+        let max_age = Some(Duration::from_secs(60));
+
+        let mut stale = EventEnvelope::new(message("+1"));
+        stale.timestamp -= chrono::Duration::minutes(10);
+        bus.process_event_envelope(stale.clone(), &handler, max_age).await.expect("Should route");
+
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        let dead: EventEnvelope<MessageReceived> =
+            bus.published_to(&dlq_topic)[0].decode().expect("Should decode");
+        assert_eq!(dead.event_id, stale.event_id);
+        assert_eq!(dead.attempt_count, 0);
+        assert_eq!(dead.metadata.get("dlq_reason").map(String::as_str), Some("max_age_exceeded"));
+
+        // Fresh events are handled as usual
+        bus.process_event_envelope(EventEnvelope::new(message("+2")), &handler, max_age).await.expect("Should route");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(bus.published_to(&dlq_topic).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mock_clock_drives_max_age() {
+        let clock = Arc::new(MockClock::new(chrono::Utc::now()));
+        let bus = InMemoryEventBus::new().with_clock(clock.clone());
+        let dlq_topic = format!("{}.dlq", MessageReceived::TOPIC);
+        let calls = AtomicUsize::new(0);
+        let handler = |_: EventEnvelope<MessageReceived>| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            std::future::ready(Ok(ProcessingResult::Success))
+        };
+        let max_age = Some(Duration::from_secs(60));
+        let envelope = EventEnvelope::with_clock(message("+1"), clock.as_ref());
+
+        // Exactly at the limit is still fresh
+        clock.advance(chrono::Duration::seconds(60));
+        bus.process_event_envelope(envelope.clone(), &handler, max_age).await.expect("Should route");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(bus.published_to(&dlq_topic).is_empty());
+
+        // One millisecond later it's stale
+        clock.advance(chrono::Duration::milliseconds(1));
+        bus.process_event_envelope(envelope, &handler, max_age).await.expect("Should route");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(bus.published_to(&dlq_topic).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_publish_delayed_waits_for_delay() {
+        let bus = InMemoryEventBus::new();
+        let (messages_tx, mut messages_rx) = mpsc::unbounded_channel();
+        bus.subscribe(SubscriptionConfig::default(), move |envelope: EventEnvelope<MessageReceived>| {
+            let _ = messages_tx.send(envelope);
+            Ok(ProcessingResult::Success)
+        }).await.expect("Should subscribe");
+
+        let started = std::time::Instant::now();
+        bus.publish_delayed(message("+1234567890"), Duration::from_secs(1))
+            .await
+            .expect("Should schedule");
+
+        tokio::time::sleep(Duration::from_millis(800)).await;
+        assert!(bus.published_events().is_empty(), "delivered before the delay elapsed");
+        assert!(messages_rx.try_recv().is_err());
+
+        let received = tokio::time::timeout(Duration::from_secs(2), messages_rx.recv())
+            .await
+            .expect("Should receive before timeout")
+            .expect("Should receive envelope");
+        assert!(started.elapsed() >= Duration::from_secs(1));
+        assert_eq!(received.data.from_phone, "+1234567890");
+        assert_eq!(bus.published_to(MessageReceived::TOPIC).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_publish_delayed_dropped_on_shutdown() {
+        let bus = InMemoryEventBus::new();
+        bus.publish_delayed(message("+1"), Duration::from_millis(200))
+            .await
+            .expect("Should schedule");
+
+        bus.shutdown().await.expect("Should shut down");
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert!(bus.published_events().is_empty());
+    }
+}
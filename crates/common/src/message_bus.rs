@@ -1,7 +1,10 @@
+use crate::clock::{Clock, SystemClock};
 use serde::{Deserialize, Serialize};
 use std::{
     error::Error,
     fmt,
+    sync::Arc,
+    time::Duration,
 };
 
 /// Trait definin what makes a valid event in the system.
@@ -29,10 +32,34 @@ pub trait Event:
 
     /// Partition key determines which partition the event will be sent to.
     /// Events with the same key will be processed in order.
+    ///
+    /// Defaults to `None`. Events keyed by one of their fields can use
+    /// `impl_event!` with `partition_by` instead of writing this by hand.
     fn partition_key(&self) -> Option<String> {
         None 
     }
 
+    /// Sub-topics this event type can be routed to, e.g. by priority.
+    ///
+    /// Each suffix names a `{TOPIC}.{suffix}` topic, which `ensure_topics`
+    /// creates alongside the main one. Defaults to none.
+    const TOPIC_SUFFIXES: &'static [&'static str] = &[];
+
+    /// Sub-topic this event is published to, one of `TOPIC_SUFFIXES`.
+    ///
+    /// Defaults to `None`, which publishes to `TOPIC` itself.
+    fn topic_suffix(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Full topic this event is published to.
+    fn routing_topic(&self) -> String {
+        match self.topic_suffix() {
+            Some(suffix) => format!("{}.{}", Self::TOPIC, suffix),
+            None => Self::TOPIC.to_string(),
+        }
+    }
+
     fn event_type(&self) -> &'static str {
         std::any::type_name::<Self>()
             .split("::")
@@ -41,6 +68,45 @@ pub trait Event:
     }
 }
 
+/// Implement `Event` for a type, optionally partitioning by one of its fields.
+///
+/// With `partition_by`, `partition_key()` returns that field (usually the
+/// phone number) so events for the same conversation stay in order.
+///
+/// ```
+/// # use common::impl_event;
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// struct OptedOut {
+///     phone: String,
+/// }
+///
+/// impl_event!(OptedOut {
+///     topic: "conversation.opt-outs",
+///     version: "1.0",
+///     partition_by: phone,
+/// });
+/// ```
+#[macro_export]
+macro_rules! impl_event {
+    ($event:ty { topic: $topic:expr, version: $version:expr $(,)? }) => {
+        impl $crate::message_bus::Event for $event {
+            const TOPIC: &'static str = $topic;
+            const VERSION: &'static str = $version;
+        }
+    };
+    ($event:ty { topic: $topic:expr, version: $version:expr, partition_by: $field:ident $(,)? }) => {
+        impl $crate::message_bus::Event for $event {
+            const TOPIC: &'static str = $topic;
+            const VERSION: &'static str = $version;
+
+            fn partition_key(&self) -> Option<String> {
+                Some(self.$field.to_string())
+            }
+        }
+    };
+}
+
 /// Envelope that wraps evets with metadata as they flow through the system.
 /// 
 /// It can be seen as a postal envelop and the event is the letter. In this case,
@@ -69,6 +135,16 @@ where
     pub attempt_count: u32,
     /// Maximum attempts before sending to dead-letter queue.
     pub max_attempts: u32,
+    /// Stable ID shared by every hop of this event (retries, DLQ, replay),
+    /// for following it through the logs. Records written before this
+    /// field existed get a fresh ID when decoded.
+    #[serde(default = "new_correlation_id")]
+    pub correlation_id: String,
+}
+
+/// Generate a fresh correlation ID.
+fn new_correlation_id() -> String {
+    uuid::Uuid::new_v4().to_string()
 }
 
 impl<T> EventEnvelope<T>
@@ -77,15 +153,21 @@ where
 {
     /// Create a new event envelope with default retry settings.
     pub fn new(data: T) -> Self {
+        Self::with_clock(data, &SystemClock)
+    }
+
+    /// Create an envelope stamped with `clock`'s current time.
+    pub fn with_clock(data: T, clock: &dyn Clock) -> Self {
         Self {
             event_id: uuid::Uuid::new_v4().to_string(),
-            timestamp: chrono::Utc::now(),
+            timestamp: clock.now(),
             event_type: data.event_type().to_string(),
             version: T::VERSION.to_string(),
             data,
             metadata: std::collections::HashMap::new(),
             attempt_count: 0,
             max_attempts: 3, 
+            correlation_id: new_correlation_id(),
         }
     }
 
@@ -106,6 +188,19 @@ where
         self.attempt_count >= self.max_attempts
     }
 
+    /// Time since the envelope was created (`timestamp`).
+    ///
+    /// Retries keep the original timestamp, so this covers every attempt.
+    /// A timestamp in the future (clock skew between hosts) counts as zero.
+    pub fn age(&self) -> Duration {
+        self.age_at(chrono::Utc::now())
+    }
+
+    /// Like `age`, measured at `now` (usually a `Clock`'s time).
+    pub fn age_at(&self, now: chrono::DateTime<chrono::Utc>) -> Duration {
+        (now - self.timestamp).to_std().unwrap_or_default()
+    }
+
     /// Add metadata to the envelope.
     pub fn add_metadata(&mut self, key: String, value: String) {
         self.metadata.insert(key, value);
@@ -121,7 +216,8 @@ where
 /// Result type for event processing handlers.
 ///
 /// This allows handlers to indicate whether processing succeeded,
-/// failed temporarily (should retry), or failed permanently.
+/// failed temporarily (should retry), or failed permanently, or that the
+/// event was deliberately ignored.
 #[derive(Debug)]
 pub enum ProcessingResult {
     /// Event was processed successfully.
@@ -130,6 +226,11 @@ pub enum ProcessingResult {
     RetryableError(String),
     /// Processing filed permanently (sent to dead-letter queue).
     PermanentError(String),
+    /// Event was deliberately ignored, with the reason (e.g. a blocked sender).
+    ///
+    /// The offset is committed like a success, but the event is counted as
+    /// skipped rather than processed.
+    Skip(String),
 }
 
 impl ProcessingResult {
@@ -142,22 +243,107 @@ impl ProcessingResult {
     pub fn permanent_error(msg: impl Into<String>) -> Self {
         Self::PermanentError(msg.into())
     }
+
+    /// Create a skipped result.
+    pub fn skip(reason: impl Into<String>) -> Self {
+        Self::Skip(reason.into())
+    }
 }
 
 /// Configuration for event subscription behavior.
+///
+/// Start from `SubscriptionConfig::builder()` (or `Default`) and override
+/// only what you need.
 #[derive(Debug, Clone)]
 pub struct SubscriptionConfig {
-    /// Consumer group ID for this subscription.
+    /// Consumer group ID for this subscription. Default: `"default-group"`.
     pub consumer_group: String,
-    /// Maximum number of events to process in a single batch.
+    /// Maximum number of events to process in a single batch. Default: 100.
     pub max_batch_size: usize,
-    /// Maximum time to wait for a batch to fill up
+    /// Maximum time to wait for a batch to fill up. Default: 1000ms.
     pub batch_timeout_ms: u64,
-    /// Whether to enable automatic offset commits
+    /// Whether to enable automatic offset commits. Default: true.
     pub auto_commit: bool,
-    /// How often to commit offsets (if auto_commit is true)
+    /// How often to commit offsets (if auto_commit is true). Default: 5000ms.
     pub auto_commit_interval_ms: u64,
+    /// Decides what happens to events whose schema version differs from
+    /// the version this consumer was compiled against. Default:
+    /// `VersionMismatchPolicy::default()`.
+    pub version_mismatch_policy: VersionMismatchPolicy,
+    /// How long the handler may run on a single event before it's treated
+    /// as a retryable failure. Async handlers are cancelled at the timeout;
+    /// a synchronous `subscribe` handler can't be, and keeps running while
+    /// the event is retried, so it should be idempotent. Default: 30000ms.
+    pub handler_timeout_ms: u64,
+    /// Consecutive retryable failures (including handler errors and
+    /// timeouts) that open the subscription's circuit breaker; 0 disables
+    /// it. Default: 10.
+    pub circuit_breaker_threshold: u32,
+    /// How long consumption stops once the breaker opens. Keep this below
+    /// the consumer's `max.poll.interval.ms` (5 minutes). Default: 30000ms.
+    pub circuit_breaker_cooldown_ms: u64,
+    /// Consume `{TOPIC}.{suffix}` instead of `TOPIC`, for events routed with
+    /// `Event::topic_suffix`. Default: `None`.
+    pub topic_suffix: Option<String>,
+    /// Events older than this (see `EventEnvelope::age`) go straight to the
+    /// dead letter queue without reaching the handler, whatever their
+    /// attempt count. 0 disables the check. Default: 0.
+    pub max_age_ms: u64,
+    /// Commit offsets once this many events have been handled instead of
+    /// after each one; 0 disables the trigger. Default: 0.
+    pub commit_batch_size: usize,
+    /// Commit handled offsets at most this long after the previous commit;
+    /// 0 disables the trigger. With both triggers off, every event is
+    /// committed on its own. Default: 0.
+    pub commit_interval_ms: u64,
+    /// Smallest response, in bytes, a consumer fetch waits for. Default:
+    /// `None` (uses `KafkaConfig::fetch_min_bytes`).
+    pub fetch_min_bytes: Option<u32>,
+    /// Longest a consumer fetch waits for `fetch_min_bytes`. Default:
+    /// `None` (uses `KafkaConfig::fetch_wait_max_ms`).
+    pub fetch_wait_max_ms: Option<u32>,
+    /// Most data returned per partition in one fetch. Default: `None`
+    /// (uses `KafkaConfig::max_partition_fetch_bytes`).
+    pub max_partition_fetch_bytes: Option<u32>,
+    /// Report the subscription as stale in health checks once it has gone
+    /// this long without handling an event; 0 disables the check. Only set
+    /// it for topics that are never quiet for that long. Default: 0.
+    pub stale_after_ms: u64,
+    /// Records with a larger payload, in bytes, are moved to
+    /// `{topic}.oversized` without being parsed and their offset is
+    /// committed; 0 disables the check. Default: 0.
+    pub max_payload_bytes: usize,
+}
+
+impl SubscriptionConfig {
+    /// Start building a config from the defaults.
+    pub fn builder() -> SubscriptionConfigBuilder {
+        SubscriptionConfigBuilder::default()
+    }
+
+    /// Maximum event age, or `None` when stale events are handled normally.
+    pub fn max_age(&self) -> Option<Duration> {
+        (self.max_age_ms > 0).then(|| Duration::from_millis(self.max_age_ms))
+    }
+
+    /// Idle time after which the subscription counts as stale, or `None`
+    /// if it never does.
+    pub fn stale_after(&self) -> Option<Duration> {
+        (self.stale_after_ms > 0).then(|| Duration::from_millis(self.stale_after_ms))
+    }
+
+    /// Longest wait between batched commits, or `None` if there's no limit.
+    pub fn commit_interval(&self) -> Option<Duration> {
+        (self.commit_interval_ms > 0).then(|| Duration::from_millis(self.commit_interval_ms))
+    }
 
+    /// Topic this subscription consumes for event type `T`.
+    pub fn topic<T: Event>(&self) -> String {
+        match &self.topic_suffix {
+            Some(suffix) => format!("{}.{}", T::TOPIC, suffix),
+            None => T::TOPIC.to_string(),
+        }
+    }
 }
 
 impl Default for SubscriptionConfig {
@@ -168,10 +354,236 @@ impl Default for SubscriptionConfig {
             batch_timeout_ms: 1000,
             auto_commit: true,
             auto_commit_interval_ms: 5000,
+            version_mismatch_policy: VersionMismatchPolicy::default(),
+            handler_timeout_ms: 30_000,
+            circuit_breaker_threshold: 10,
+            circuit_breaker_cooldown_ms: 30_000,
+            topic_suffix: None,
+            max_age_ms: 0,
+            commit_batch_size: 0,
+            commit_interval_ms: 0,
+            fetch_min_bytes: None,
+            fetch_wait_max_ms: None,
+            max_partition_fetch_bytes: None,
+            stale_after_ms: 0,
+            max_payload_bytes: 0,
+        }
+    }
+}
+
+/// Fluent builder for `SubscriptionConfig`.
+///
+/// Every setter is optional; anything left unset keeps the value from
+/// `SubscriptionConfig::default()`.
+#[derive(Debug, Clone, Default)]
+pub struct SubscriptionConfigBuilder {
+    config: SubscriptionConfig,
+}
+
+impl SubscriptionConfigBuilder {
+    /// Create a builder starting from the defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the consumer group ID.
+    pub fn consumer_group(mut self, consumer_group: impl Into<String>) -> Self {
+        self.config.consumer_group = consumer_group.into();
+        self
+    }
+
+    /// Set the maximum number of events per batch.
+    pub fn max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.config.max_batch_size = max_batch_size;
+        self
+    }
+
+    /// Set how long to wait for a batch to fill up.
+    pub fn batch_timeout_ms(mut self, batch_timeout_ms: u64) -> Self {
+        self.config.batch_timeout_ms = batch_timeout_ms;
+        self
+    }
+
+    /// Enable or disable automatic offset commits.
+    pub fn auto_commit(mut self, auto_commit: bool) -> Self {
+        self.config.auto_commit = auto_commit;
+        self
+    }
+
+    /// Set how often offsets are committed when auto-commit is enabled.
+    pub fn auto_commit_interval_ms(mut self, auto_commit_interval_ms: u64) -> Self {
+        self.config.auto_commit_interval_ms = auto_commit_interval_ms;
+        self
+    }
+
+    /// Set the policy for events with a different schema version.
+    pub fn version_mismatch_policy(mut self, policy: VersionMismatchPolicy) -> Self {
+        self.config.version_mismatch_policy = policy;
+        self
+    }
+
+    /// Set how long the handler may run on a single event.
+    pub fn handler_timeout_ms(mut self, handler_timeout_ms: u64) -> Self {
+        self.config.handler_timeout_ms = handler_timeout_ms;
+        self
+    }
+
+    /// Set how many consecutive failures open the circuit breaker (0 disables it).
+    pub fn circuit_breaker_threshold(mut self, threshold: u32) -> Self {
+        self.config.circuit_breaker_threshold = threshold;
+        self
+    }
+
+    /// Set how long consumption pauses once the circuit breaker opens.
+    pub fn circuit_breaker_cooldown_ms(mut self, cooldown_ms: u64) -> Self {
+        self.config.circuit_breaker_cooldown_ms = cooldown_ms;
+        self
+    }
+
+    /// Consume the `{TOPIC}.{suffix}` sub-topic, e.g. `"urgent"` for
+    /// events routed there by priority.
+    pub fn topic_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.config.topic_suffix = Some(suffix.into());
+        self
+    }
+
+    /// Dead-letter events older than this instead of handling them (0 disables).
+    pub fn max_age_ms(mut self, max_age_ms: u64) -> Self {
+        self.config.max_age_ms = max_age_ms;
+        self
+    }
+
+    /// Commit offsets every `commit_batch_size` handled events (0 disables).
+    pub fn commit_batch_size(mut self, commit_batch_size: usize) -> Self {
+        self.config.commit_batch_size = commit_batch_size;
+        self
+    }
+
+    /// Commit handled offsets at least this often (0 disables).
+    pub fn commit_interval_ms(mut self, commit_interval_ms: u64) -> Self {
+        self.config.commit_interval_ms = commit_interval_ms;
+        self
+    }
+
+    /// Override the bus-wide minimum fetch size, e.g. 1 for low latency.
+    pub fn fetch_min_bytes(mut self, fetch_min_bytes: u32) -> Self {
+        self.config.fetch_min_bytes = Some(fetch_min_bytes);
+        self
+    }
+
+    /// Override how long a fetch waits for `fetch_min_bytes`.
+    pub fn fetch_wait_max_ms(mut self, fetch_wait_max_ms: u32) -> Self {
+        self.config.fetch_wait_max_ms = Some(fetch_wait_max_ms);
+        self
+    }
+
+    /// Override the per-partition fetch size, e.g. larger for bulk topics.
+    pub fn max_partition_fetch_bytes(mut self, max_partition_fetch_bytes: u32) -> Self {
+        self.config.max_partition_fetch_bytes = Some(max_partition_fetch_bytes);
+        self
+    }
+
+    /// Report the subscription stale after this long without an event (0 disables).
+    pub fn stale_after_ms(mut self, stale_after_ms: u64) -> Self {
+        self.config.stale_after_ms = stale_after_ms;
+        self
+    }
+
+    /// Divert records larger than `max_payload_bytes` unparsed (0 disables).
+    pub fn max_payload_bytes(mut self, max_payload_bytes: usize) -> Self {
+        self.config.max_payload_bytes = max_payload_bytes;
+        self
+    }
+
+    /// Finish building the config.
+    pub fn build(self) -> SubscriptionConfig {
+        self.config
+    }
+}
+
+/// What a consumer should do with an event of an unexpected schema version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionMismatchAction {
+    /// Deserialize and hand the event to the handler anyway.
+    Process,
+    /// Route the raw record to the `{topic}.version-mismatch` topic.
+    Quarantine,
+    /// Commit the offset and drop the event.
+    Skip,
+}
+
+/// Policy hook deciding how to treat events with a different schema version.
+///
+/// The closure receives the version found on the incoming event and the
+/// version the consumer expects (`Event::VERSION`). The default policy
+/// processes events from the same or an older major version, and
+/// quarantines anything with a newer (or unparseable) major version,
+/// since those may contain breaking changes the consumer can't understand.
+#[derive(Clone)]
+pub struct VersionMismatchPolicy(Arc<VersionMismatchFn>);
+
+/// Signature of a version-mismatch decision function: `(received, expected)`.
+type VersionMismatchFn = dyn Fn(&str, &str) -> VersionMismatchAction + Send + Sync;
+
+impl VersionMismatchPolicy {
+    /// Create a policy from a custom decision function.
+    pub fn new<F>(decide: F) -> Self
+    where
+        F: Fn(&str, &str) -> VersionMismatchAction + Send + Sync + 'static,
+    {
+        Self(Arc::new(decide))
+    }
+
+    /// Decide what to do with an event of `received` version when the
+    /// consumer expects `expected`.
+    pub fn decide(&self, received: &str, expected: &str) -> VersionMismatchAction {
+        if received == expected {
+            return VersionMismatchAction::Process;
         }
+        (self.0)(received, expected)
+    }
+}
+
+impl Default for VersionMismatchPolicy {
+    fn default() -> Self {
+        Self::new(|received, expected| {
+            match (major_version(received), major_version(expected)) {
+                (Some(received), Some(expected)) if received <= expected => VersionMismatchAction::Process,
+                _ => VersionMismatchAction::Quarantine,
+            }
+        })
+    }
+}
+
+impl fmt::Debug for VersionMismatchPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("VersionMismatchPolicy(..)")
     }
 }
 
+/// Extract the major component of a version string like "1.0" or "v2.3.1".
+pub fn major_version(version: &str) -> Option<u64> {
+    version
+        .trim_start_matches(['v', 'V'])
+        .split('.')
+        .next()
+        .and_then(|major| major.parse().ok())
+}
+
+/// Where a published event was durably written
+///
+/// Returned by `EventBus::publish_with_receipt`, e.g. so an outbox can
+/// record that a row made it to the broker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishReceipt {
+    /// Topic the record was written to, after any priority routing
+    pub topic: String,
+    /// Partition the record was written to
+    pub partition: i32,
+    /// Offset of the record within its partition
+    pub offset: i64,
+}
+
 /// Main event bus abstraction for publishing and subscribing to events.
 ///
 /// This trait defines the contract that all event bus implementations must follow.
@@ -186,12 +598,58 @@ pub trait EventBus: Send + Sync {
     where
         T: Event;
 
+    /// Publishes an event and reports where it was written.
+    ///
+    /// Behaves like `publish`, but returns the topic, partition and offset
+    /// from the broker's delivery report.
+    async fn publish_with_receipt<T>(&self, event: T) -> Result<PublishReceipt, Self::Error>
+    where
+        T: Event;
+
+    /// Publishes an event using an explicit partition key.
+    ///
+    /// The supplied key overrides `Event::partition_key()`, which is useful
+    /// for ordering by something other than the event's natural key (e.g. a
+    /// conversation thread). Implementations without key-based ordering can
+    /// rely on the default, which ignores the key and delegates to `publish`.
+    async fn publish_with_key<T>(&self, event: T, key: String) -> Result<(), Self::Error>
+    where
+        T: Event,
+    {
+        let _ = key;
+        self.publish(event).await
+    }
+
+    /// Publish pre-serialized bytes to an explicit topic.
+    ///
+    /// Unlike the generic methods this doesn't depend on an event type, so
+    /// events of different types can be serialized ahead of time (e.g. into
+    /// an outbox) and drained through a single call. The payload is sent
+    /// as-is; it's up to the caller to produce something consumers can decode.
+    async fn publish_raw(&self, topic: &str, key: Option<String>, payload: Vec<u8>) -> Result<(), Self::Error>;
+
+    /// Publish an event that should only reach subscribers after `delay`
+    ///
+    /// Meant for reminders and follow-ups. Delivery is never early, but it
+    /// can be late; see the implementation for how precise it is.
+    async fn publish_delayed<T>(&self, event: T, delay: Duration) -> Result<(), Self::Error>
+    where
+        T: Event;
+
     /// Publish a batch of events
+    ///
+    /// Every event is attempted even if some fail. When any do, the error
+    /// is `EventBusError::BatchPublishFailed`, listing which indices went
+    /// out and which didn't so callers can retry just the failures.
     async fn publish_batch<T>(&self, events: Vec<T>) -> Result<(), Self::Error>
     where
         T: Event;
 
     /// Subscribes to events of a specific type with a handler function.
+    ///
+    /// A blocking handler can't be interrupted. If it passes
+    /// `handler_timeout_ms` it keeps running after the event has been
+    /// handed back for retry, so its side effects may happen twice.
     async fn subscribe<T, F>(&self, config: SubscriptionConfig, handler: F) -> Result<(), Self::Error>
     where
         T: Event,
@@ -200,6 +658,17 @@ pub trait EventBus: Send + Sync {
             + Sync 
             + 'static;
 
+    /// Subscribes to events of a specific type with an async handler.
+    ///
+    /// Like `subscribe`, but the handler returns a future, so it can
+    /// `.await` I/O (HTTP calls, database writes) instead of blocking a
+    /// thread while it waits.
+    async fn subscribe_async<T, F, Fut>(&self, config: SubscriptionConfig, handler: F) -> Result<(), Self::Error>
+    where
+        T: Event,
+        F: Fn(EventEnvelope<T>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<ProcessingResult, Box<dyn Error + Send + Sync>>> + Send + 'static;
+
     /// Subscribe with batch processing for higher throughput
     ///
     /// This allows processing multiple events together, which can 
@@ -243,6 +712,8 @@ pub enum EventBusError {
     ConsumerError(String),
     /// Shutdown was requested or the system is shutting down
     ShutdownRequested,
+    /// Some events in a batch failed to publish
+    BatchPublishFailed(BatchPublishError),
 }
 
 impl fmt::Display for EventBusError {
@@ -256,8 +727,67 @@ impl fmt::Display for EventBusError {
             EventBusError::ConfigError(msg) => write!(f, "Configuration error: {}", msg),
             EventBusError::ConsumerError(msg) => write!(f, "Consumer error: {}", msg),
             EventBusError::ShutdownRequested => write!(f, "Shutdown was requested"),
+            EventBusError::BatchPublishFailed(batch) => write!(f, "{}", batch),
         }
     }
 }
 
 impl Error for EventBusError {}
+
+/// Outcome of a partially failed batch publish
+///
+/// Indices refer to positions in the batch passed to `publish_batch`.
+#[derive(Debug)]
+pub struct BatchPublishError {
+    /// Events that were published
+    pub succeeded: Vec<usize>,
+    /// Events that failed, with the reason
+    pub failures: Vec<(usize, EventBusError)>,
+}
+
+impl BatchPublishError {
+    /// Sort per-event results into a batch outcome
+    ///
+    /// Returns `Ok` only if every event was published.
+    pub fn check(results: impl IntoIterator<Item = Result<(), EventBusError>>) -> Result<(), EventBusError> {
+        let mut batch = BatchPublishError {
+            succeeded: Vec::new(),
+            failures: Vec::new(),
+        };
+        for (index, result) in results.into_iter().enumerate() {
+            match result {
+                Ok(()) => batch.succeeded.push(index),
+                Err(e) => batch.failures.push((index, e)),
+            }
+        }
+
+        if batch.failures.is_empty() {
+            Ok(())
+        } else {
+            Err(EventBusError::BatchPublishFailed(batch))
+        }
+    }
+
+    /// Indices of the events that failed
+    pub fn failed_indices(&self) -> Vec<usize> {
+        self.failures.iter().map(|(index, _)| *index).collect()
+    }
+
+    /// Error of the earliest failed event
+    pub fn into_first_failure(self) -> Option<EventBusError> {
+        self.failures.into_iter().next().map(|(_, e)| e)
+    }
+}
+
+impl fmt::Display for BatchPublishError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.succeeded.len() + self.failures.len();
+        write!(f, "Failed to publish {} of {} events in batch", self.failures.len(), total)?;
+        if let Some((index, error)) = self.failures.first() {
+            write!(f, " (first at index {}: {})", index, error)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for BatchPublishError {}
@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+/// Hooks the event bus calls to report what it is doing.
+///
+/// Implementations forward these to a metrics backend (Prometheus,
+/// StatsD, ...). Every method is called on the hot path, so they should
+/// be cheap and must never block.
+pub trait Metrics: Send + Sync {
+    /// An event was published (or failed to publish) to `topic`.
+    fn record_publish(&self, topic: &str, duration: Duration, success: bool);
+
+    /// An event from `topic` was consumed and handed to a handler.
+    fn record_consume(&self, topic: &str);
+
+    /// An event from `topic` was scheduled for another processing attempt.
+    fn record_retry(&self, topic: &str);
+
+    /// An event from `topic` was given up on and sent to the dead letter queue.
+    fn record_dlq(&self, topic: &str);
+
+    /// A handler deliberately skipped an event from `topic`.
+    fn record_skip(&self, topic: &str);
+}
+
+/// Metrics implementation that discards everything.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopMetrics;
+
+impl Metrics for NoopMetrics {
+    fn record_publish(&self, _topic: &str, _duration: Duration, _success: bool) {}
+
+    fn record_consume(&self, _topic: &str) {}
+
+    fn record_retry(&self, _topic: &str) {}
+
+    fn record_dlq(&self, _topic: &str) {}
+
+    fn record_skip(&self, _topic: &str) {}
+}
+
+#[cfg(feature = "prometheus")]
+pub use self::prometheus_metrics::PrometheusMetrics;
+
+#[cfg(feature = "prometheus")]
+mod prometheus_metrics {
+    use super::Metrics;
+    use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry};
+    use std::time::Duration;
+
+    /// Prometheus-backed event bus metrics.
+    ///
+    /// Exposes, labelled by topic:
+    /// - `event_bus_published_total` (also labelled by `status`)
+    /// - `event_bus_publish_duration_seconds`
+    /// - `event_bus_consumed_total`
+    /// - `event_bus_retries_total`
+    /// - `event_bus_dead_letters_total`
+    /// - `event_bus_skipped_total`
+    #[derive(Debug, Clone)]
+    pub struct PrometheusMetrics {
+        published: IntCounterVec,
+        publish_duration: HistogramVec,
+        consumed: IntCounterVec,
+        retries: IntCounterVec,
+        dead_letters: IntCounterVec,
+        skipped: IntCounterVec,
+    }
+
+    impl PrometheusMetrics {
+        /// Create the collectors and register them with `registry`
+        pub fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+            let metrics = Self {
+                published: IntCounterVec::new(
+                    Opts::new("event_bus_published_total", "Events published, by outcome"),
+                    &["topic", "status"],
+                )?,
+                publish_duration: HistogramVec::new(
+                    HistogramOpts::new("event_bus_publish_duration_seconds", "Time taken to publish an event"),
+                    &["topic"],
+                )?,
+                consumed: IntCounterVec::new(
+                    Opts::new("event_bus_consumed_total", "Events handed to a handler"),
+                    &["topic"],
+                )?,
+                retries: IntCounterVec::new(
+                    Opts::new("event_bus_retries_total", "Events scheduled for retry"),
+                    &["topic"],
+                )?,
+                dead_letters: IntCounterVec::new(
+                    Opts::new("event_bus_dead_letters_total", "Events sent to the dead letter queue"),
+                    &["topic"],
+                )?,
+                skipped: IntCounterVec::new(
+                    Opts::new("event_bus_skipped_total", "Events a handler deliberately skipped"),
+                    &["topic"],
+                )?,
+            };
+
+            registry.register(Box::new(metrics.published.clone()))?;
+            registry.register(Box::new(metrics.publish_duration.clone()))?;
+            registry.register(Box::new(metrics.consumed.clone()))?;
+            registry.register(Box::new(metrics.retries.clone()))?;
+            registry.register(Box::new(metrics.dead_letters.clone()))?;
+            registry.register(Box::new(metrics.skipped.clone()))?;
+
+            Ok(metrics)
+        }
+    }
+
+    impl Metrics for PrometheusMetrics {
+        fn record_publish(&self, topic: &str, duration: Duration, success: bool) {
+            let status = if success { "success" } else { "failure" };
+            self.published.with_label_values(&[topic, status]).inc();
+            self.publish_duration.with_label_values(&[topic]).observe(duration.as_secs_f64());
+        }
+
+        fn record_consume(&self, topic: &str) {
+            self.consumed.with_label_values(&[topic]).inc();
+        }
+
+        fn record_retry(&self, topic: &str) {
+            self.retries.with_label_values(&[topic]).inc();
+        }
+
+        fn record_dlq(&self, topic: &str) {
+            self.dead_letters.with_label_values(&[topic]).inc();
+        }
+
+        fn record_skip(&self, topic: &str) {
+            self.skipped.with_label_values(&[topic]).inc();
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_prometheus_metrics_export() {
+            let registry = Registry::new();
+            let metrics = PrometheusMetrics::new(&registry).expect("Should register collectors");
+
+            metrics.record_publish("conversation.messages", Duration::from_millis(12), true);
+            metrics.record_publish("conversation.messages", Duration::from_millis(40), false);
+            metrics.record_dlq("conversation.messages");
+            metrics.record_skip("conversation.messages");
+
+            assert_eq!(metrics.published.with_label_values(&["conversation.messages", "success"]).get(), 1);
+            assert_eq!(metrics.published.with_label_values(&["conversation.messages", "failure"]).get(), 1);
+            assert_eq!(metrics.dead_letters.with_label_values(&["conversation.messages"]).get(), 1);
+            assert_eq!(metrics.retries.with_label_values(&["conversation.messages"]).get(), 0);
+            assert_eq!(metrics.skipped.with_label_values(&["conversation.messages"]).get(), 1);
+
+            // Registering twice in the same registry is rejected
+            assert!(PrometheusMetrics::new(&registry).is_err());
+        }
+    }
+}
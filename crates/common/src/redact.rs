@@ -0,0 +1,109 @@
+use std::borrow::Cow;
+
+/// Whether PII is redacted from logs when not configured explicitly.
+///
+/// On in release builds, off in debug builds so local runs stay readable.
+pub const REDACT_PII_DEFAULT: bool = !cfg!(debug_assertions);
+
+/// Two-digit country calling codes; everything else starting with 1 or 7
+/// is one digit and the rest are three.
+const TWO_DIGIT_COUNTRY_CODES: &[&str] = &[
+    "20", "27", "30", "31", "32", "33", "34", "36", "39", "40", "41", "43", "44", "45", "46",
+    "47", "48", "49", "51", "52", "53", "54", "55", "56", "57", "58", "60", "61", "62", "63",
+    "64", "65", "66", "81", "82", "84", "86", "90", "91", "92", "93", "94", "95", "98",
+];
+
+/// Mask a phone number for logging.
+///
+/// Keeps the country code and the last two digits so a number can still be
+/// told apart from others in the same log, e.g. `+14155550123` becomes
+/// `+1********23`. WhatsApp IDs without the leading `+` are handled the
+/// same way. Anything that isn't a phone number keeps only its last two
+/// characters.
+///
+/// # Example
+/// ```
+/// use common::redact::redact_phone;
+///
+/// assert_eq!(redact_phone("+447911123456"), "+44********56");
+/// ```
+pub fn redact_phone(phone: &str) -> String {
+    let (plus, digits) = match phone.strip_prefix('+') {
+        Some(digits) => ("+", digits),
+        None => ("", phone),
+    };
+
+    let country_code_len = country_code_len(digits);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) || digits.len() <= country_code_len + 2 {
+        return mask_all_but_last_two(phone);
+    }
+
+    let (country_code, rest) = digits.split_at(country_code_len);
+    let (middle, last_two) = rest.split_at(rest.len() - 2);
+    format!("{}{}{}{}", plus, country_code, "*".repeat(middle.len()), last_two)
+}
+
+/// `phone` as it should appear in logs: masked when `redact_pii` is set.
+pub fn loggable_phone(phone: &str, redact_pii: bool) -> Cow<'_, str> {
+    if redact_pii {
+        Cow::Owned(redact_phone(phone))
+    } else {
+        Cow::Borrowed(phone)
+    }
+}
+
+/// Length of the country calling code at the start of `digits`.
+fn country_code_len(digits: &str) -> usize {
+    if digits.starts_with(['1', '7']) {
+        1
+    } else if digits.get(..2).is_some_and(|prefix| TWO_DIGIT_COUNTRY_CODES.contains(&prefix)) {
+        2
+    } else {
+        3
+    }
+}
+
+fn mask_all_but_last_two(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    let kept = chars.len().saturating_sub(2);
+    chars
+        .iter()
+        .enumerate()
+        .map(|(i, c)| if i < kept { '*' } else { *c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_phone_keeps_country_code_and_last_two_digits() {
+        let cases = [
+            ("+14155550123", "+1********23"),
+            ("+447911123456", "+44********56"),
+            ("+5215512345678", "+52*********78"),
+            ("+35312345678", "+353******78"),
+            ("+79161234567", "+7********67"),
+            // WhatsApp IDs in webhooks have no leading +
+            ("16505550001", "1********01"),
+        ];
+        for (phone, expected) in cases {
+            assert_eq!(redact_phone(phone), expected, "{}", phone);
+        }
+    }
+
+    #[test]
+    fn test_redact_phone_never_leaks_more_than_two_characters_of_non_numbers() {
+        assert_eq!(redact_phone("thread-42"), "*******42");
+        assert_eq!(redact_phone("+123"), "**23");
+        assert_eq!(redact_phone("7"), "7");
+        assert_eq!(redact_phone(""), "");
+    }
+
+    #[test]
+    fn test_loggable_phone_only_masks_when_enabled() {
+        assert_eq!(loggable_phone("+14155550123", true), "+1********23");
+        assert_eq!(loggable_phone("+14155550123", false), "+14155550123");
+    }
+}
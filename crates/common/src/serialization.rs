@@ -0,0 +1,177 @@
+use crate::message_bus::EventBusError;
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Pluggable wire format for event payloads.
+///
+/// The event bus uses a serializer to turn envelopes into record payloads
+/// and back. Each serializer has a short format name that is stored in a
+/// record header, so topics carrying a mix of formats can still be read.
+pub trait Serializer: Send + Sync {
+    /// Name stored in the `content-format` header (e.g. "json").
+    fn format(&self) -> &'static str;
+
+    /// Serialize a value into payload bytes.
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, EventBusError>;
+
+    /// Deserialize payload bytes into a value.
+    fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, EventBusError>;
+}
+
+/// JSON serializer - human readable, and the historical default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonSerializer;
+
+impl Serializer for JsonSerializer {
+    fn format(&self) -> &'static str {
+        "json"
+    }
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, EventBusError> {
+        serde_json::to_vec(value)
+            .map_err(|e| EventBusError::SerializationError(format!("JSON encode failed: {}", e)))
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, EventBusError> {
+        serde_json::from_slice(bytes)
+            .map_err(|e| EventBusError::SerializationError(format!("JSON decode failed: {}", e)))
+    }
+}
+
+/// MessagePack serializer - compact binary encoding for high-volume topics.
+///
+/// Structs are encoded as maps with field names so that adding optional
+/// fields stays backward compatible, just like with JSON.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MessagePackSerializer;
+
+impl Serializer for MessagePackSerializer {
+    fn format(&self) -> &'static str {
+        "msgpack"
+    }
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, EventBusError> {
+        rmp_serde::to_vec_named(value)
+            .map_err(|e| EventBusError::SerializationError(format!("MessagePack encode failed: {}", e)))
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, EventBusError> {
+        rmp_serde::from_slice(bytes)
+            .map_err(|e| EventBusError::SerializationError(format!("MessagePack decode failed: {}", e)))
+    }
+}
+
+/// Serialization formats the Kafka event bus can be configured with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SerializationFormat {
+    /// Plain JSON (default)
+    #[default]
+    Json,
+    /// MessagePack binary encoding
+    MessagePack,
+}
+
+impl SerializationFormat {
+    /// Parse a format name as found in config or a record header.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "msgpack" | "messagepack" => Some(Self::MessagePack),
+            _ => None,
+        }
+    }
+}
+
+impl Serializer for SerializationFormat {
+    fn format(&self) -> &'static str {
+        match self {
+            Self::Json => JsonSerializer.format(),
+            Self::MessagePack => MessagePackSerializer.format(),
+        }
+    }
+
+    fn serialize<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, EventBusError> {
+        match self {
+            Self::Json => JsonSerializer.serialize(value),
+            Self::MessagePack => MessagePackSerializer.serialize(value),
+        }
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, EventBusError> {
+        match self {
+            Self::Json => JsonSerializer.deserialize(bytes),
+            Self::MessagePack => MessagePackSerializer.deserialize(bytes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        events::{MessageReceived, MessageType, MessageContent},
+        message_bus::EventEnvelope,
+    };
+
+    fn sample_envelope() -> EventEnvelope<MessageReceived> {
+        let mut envelope = EventEnvelope::new(MessageReceived {
+            message_id: "wamid.123".to_string(),
+            from_phone: "+1234567890".to_string(),
+            message_type: MessageType::Location,
+            content: MessageContent::Location {
+                latitude: 4.711,
+                longitude: -74.0721,
+                name: Some("Bogotá".to_string()),
+                address: None,
+                is_live: false,
+            },
+            received_at: chrono::Utc::now(),
+            metadata: std::collections::HashMap::new(),
+        });
+        envelope.add_metadata("correlation_id".to_string(), "abc".to_string());
+        envelope
+    }
+
+    fn assert_round_trip<S: Serializer>(serializer: S) -> Vec<u8> {
+        let envelope = sample_envelope();
+        let bytes = serializer.serialize(&envelope).expect("Should serialize");
+        let decoded: EventEnvelope<MessageReceived> = serializer.deserialize(&bytes).expect("Should deserialize");
+
+        assert_eq!(decoded.event_id, envelope.event_id);
+        assert_eq!(decoded.timestamp, envelope.timestamp);
+        assert_eq!(decoded.data.from_phone, "+1234567890");
+        assert_eq!(decoded.metadata.get("correlation_id").map(String::as_str), Some("abc"));
+        match decoded.data.content {
+            MessageContent::Location { latitude, name, .. } => {
+                assert_eq!(latitude, 4.711);
+                assert_eq!(name.as_deref(), Some("Bogotá"));
+            }
+            other => panic!("Expected location content, got {:?}", other),
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let bytes = assert_round_trip(JsonSerializer);
+        assert_eq!(bytes.first(), Some(&b'{'));
+    }
+
+    #[test]
+    fn test_messagepack_round_trip() {
+        let msgpack = assert_round_trip(MessagePackSerializer);
+        let json = JsonSerializer.serialize(&sample_envelope()).unwrap();
+        assert!(msgpack.len() < json.len());
+    }
+
+    #[test]
+    fn test_format_names() {
+        assert_eq!(SerializationFormat::from_name("json"), Some(SerializationFormat::Json));
+        assert_eq!(SerializationFormat::from_name("MsgPack"), Some(SerializationFormat::MessagePack));
+        assert_eq!(SerializationFormat::from_name("avro"), None);
+        assert_eq!(SerializationFormat::MessagePack.format(), "msgpack");
+
+        // Each format decodes what it encodes, and rejects the other
+        let bytes = SerializationFormat::MessagePack.serialize(&sample_envelope()).unwrap();
+        assert!(SerializationFormat::Json.deserialize::<EventEnvelope<MessageReceived>>(&bytes).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+//! W3C trace context propagation through Kafka record headers.
+//!
+//! Producers inject the current span's `traceparent`/`tracestate` into the
+//! record headers, and the consumer loop extracts them so event handling
+//! continues the producer's trace instead of starting a new one. The
+//! globally registered OpenTelemetry text map propagator is used, as with
+//! `tracing-opentelemetry`; register a `TraceContextPropagator` at startup.
+
+use opentelemetry::{
+    global,
+    propagation::{Extractor, Injector},
+    Context,
+};
+use rdkafka::message::{Header, Headers, OwnedHeaders};
+use std::collections::HashMap;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Collects propagation fields before they're appended to the record headers.
+struct HeaderInjector(HashMap<String, String>);
+
+impl Injector for HeaderInjector {
+    fn set(&mut self, key: &str, value: String) {
+        self.0.insert(key.to_string(), value);
+    }
+}
+
+/// Reads propagation fields from incoming record headers.
+struct HeaderExtractor<'a, H: Headers>(&'a H);
+
+impl<H: Headers> Extractor for HeaderExtractor<'_, H> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|header| header.key == key)
+            .and_then(|header| header.value)
+            .and_then(|value| std::str::from_utf8(value).ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.iter().map(|header| header.key).collect()
+    }
+}
+
+/// Append the trace context of the current span to `headers`.
+pub fn inject_current_context(headers: OwnedHeaders) -> OwnedHeaders {
+    inject_context(&tracing::Span::current().context(), headers)
+}
+
+/// Append the trace context of `cx` to `headers`.
+pub fn inject_context(cx: &Context, headers: OwnedHeaders) -> OwnedHeaders {
+    let mut injector = HeaderInjector(HashMap::new());
+    global::get_text_map_propagator(|propagator| propagator.inject_context(cx, &mut injector));
+
+    injector.0.iter().fold(headers, |headers, (key, value)| {
+        headers.insert(Header { key: key.as_str(), value: Some(value.as_str()) })
+    })
+}
+
+/// Read the trace context carried by incoming record headers.
+pub fn extract_context<H: Headers>(headers: &H) -> Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)))
+}
+
+/// Span for handling one consumed record, parented to the producer's trace.
+pub fn consumer_span<H: Headers>(topic: &str, headers: Option<&H>) -> tracing::Span {
+    let span = tracing::info_span!("kafka.consume", topic = topic);
+    if let Some(headers) = headers {
+        // Fails only when no OpenTelemetry layer is installed, in which
+        // case there's nothing to continue
+        let _ = span.set_parent(extract_context(headers));
+    }
+    span
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{
+        SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+    };
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+
+    #[test]
+    fn test_traceparent_round_trips_through_headers() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+
+        let span_context = SpanContext::new(
+            TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap(),
+            SpanId::from_hex("00f067aa0ba902b7").unwrap(),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::from_key_value([("vendor", "value")]).unwrap(),
+        );
+        let cx = Context::new().with_remote_span_context(span_context.clone());
+
+        let headers = inject_context(&cx, OwnedHeaders::new());
+        let traceparent = headers
+            .iter()
+            .find(|header| header.key == "traceparent")
+            .and_then(|header| header.value)
+            .expect("Should write traceparent");
+        assert_eq!(traceparent, b"00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01");
+
+        let extracted = extract_context(&headers);
+        let extracted = extracted.span().span_context().clone();
+        assert_eq!(extracted.trace_id(), span_context.trace_id());
+        assert_eq!(extracted.span_id(), span_context.span_id());
+        assert!(extracted.is_sampled());
+        assert!(extracted.is_remote());
+        assert_eq!(extracted.trace_state().get("vendor"), Some("value"));
+    }
+}
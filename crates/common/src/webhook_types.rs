@@ -20,12 +20,39 @@ pub struct MediaMessage {
     pub caption: Option<String>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+/// A shared location, either a static pin or an update to a live location
+///
+/// The live location fields are only present on live location messages.
+#[derive(Deserialize, Debug, Clone, Default)]
 pub struct LocationMessage {
     pub latitude: f64,
     pub longitude: f64,
     pub name: Option<String>,
     pub address: Option<String>,
+    pub url: Option<String>,
+    /// Caption the sender added when starting to share their live location
+    pub caption: Option<String>,
+    /// Accuracy of the position, in meters
+    pub accuracy: Option<f64>,
+    /// Speed of the sender, in meters per second
+    pub speed: Option<f64>,
+    /// Heading of the sender, in degrees clockwise from magnetic north
+    pub degrees: Option<f64>,
+    /// Increases with every update to the same live location
+    pub sequence_number: Option<u64>,
+    /// Seconds since live location sharing started
+    pub time_offset: Option<u64>,
+}
+
+impl LocationMessage {
+    /// Whether this is a live location update rather than a static pin
+    pub fn is_live(&self) -> bool {
+        self.sequence_number.is_some()
+            || self.time_offset.is_some()
+            || self.speed.is_some()
+            || self.degrees.is_some()
+            || self.accuracy.is_some()
+    }
 }
 
 // Contact message types
@@ -98,6 +125,7 @@ pub struct InteractiveMessage {
     pub interactive_type: String,
     pub button_reply: Option<ButtonReply>,
     pub list_reply: Option<ListReply>,
+    pub nfm_reply: Option<NfmReply>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -113,6 +141,15 @@ pub struct ListReply {
     pub description: Option<String>,
 }
 
+/// WhatsApp Flow submission
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NfmReply {
+    pub name: String,
+    pub body: Option<String>,
+    /// The submitted flow fields, as a JSON-encoded string
+    pub response_json: String,
+}
+
 // Referral and error types
 #[derive(Deserialize, Debug, Clone)]
 pub struct ReferralMessage {
@@ -132,6 +169,8 @@ pub struct ReferralMessage {
 pub struct MessageError {
     pub code: u32,
     pub title: String,
+    /// Newer webhook payloads call this `message`
+    #[serde(alias = "message")]
     pub description: String,
 }
 
@@ -141,6 +180,9 @@ pub enum WebhookMessageType {
     Text(TextMessage),
     Reaction(ReactionMessage),
     Image(MediaMessage),
+    Audio(MediaMessage),
+    Video(MediaMessage),
+    Document(MediaMessage),
     Sticker(MediaMessage),
     Location(LocationMessage),
     Contact(Vec<ContactMessage>),
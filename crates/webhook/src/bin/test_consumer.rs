@@ -1,5 +1,5 @@
 use common::{
-    KafkaEventBus, KafkaConfig, EventBus, MessageReceived, 
+    KafkaEventBus, KafkaConfig, EventBus, MessageReceived, NoopMetrics,
     SubscriptionConfig, ProcessingResult, EventEnvelope,
 };
 use std::sync::Arc;
@@ -18,7 +18,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let kafka_config = KafkaConfig::from_env()
         .map_err(|e| format!("Failed to load Kafka config: {}", e))?;
     
-    let event_bus = Arc::new(KafkaEventBus::new(kafka_config).await
+    let event_bus = Arc::new(KafkaEventBus::new(kafka_config, Arc::new(NoopMetrics)).await
         .map_err(|e| format!("Failed to create event bus: {}", e))?);
 
     // Test connection
@@ -28,10 +28,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("✅ Connected to Kafka successfully");
 
     // Subscribe to message events
-    let config = SubscriptionConfig {
-        consumer_group: "test-consumer".to_string(),
-        ..Default::default()
-    };
+    let config = SubscriptionConfig::builder()
+        .consumer_group("test-consumer")
+        .build();
 
     event_bus.subscribe::<MessageReceived, _>(
         config,
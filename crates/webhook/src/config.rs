@@ -1,4 +1,7 @@
+use crate::dedup::MessageDedupCache;
+use common::REDACT_PII_DEFAULT;
 use serde::Deserialize;
+use std::time::Duration;
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct AppConfig {
@@ -9,6 +12,12 @@ pub struct AppConfig {
     pub max_file_size_mb: u64,
     pub host: String,
     pub port: u16,
+    /// How many recent message IDs to remember for deduplication (0 disables)
+    pub dedup_cache_size: usize,
+    /// How long a message ID is remembered, in seconds (0 keeps it until evicted)
+    pub dedup_ttl_secs: u64,
+    /// Mask phone numbers in logs
+    pub redact_pii: bool,
 }
 
 impl AppConfig {
@@ -28,9 +37,27 @@ impl AppConfig {
                 .unwrap_or_else(|_| "8000".to_string())
                 .parse()
                 .expect("PORT must be a valid number"),
+            dedup_cache_size: std::env::var("WEBHOOK_DEDUP_CACHE_SIZE")
+                .unwrap_or_else(|_| "10000".to_string())
+                .parse()
+                .expect("WEBHOOK_DEDUP_CACHE_SIZE must be a valid number"),
+            dedup_ttl_secs: std::env::var("WEBHOOK_DEDUP_TTL_SECS")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse()
+                .expect("WEBHOOK_DEDUP_TTL_SECS must be a valid number"),
+            redact_pii: std::env::var("WEBHOOK_REDACT_PII")
+                .map(|value| value.parse().expect("WEBHOOK_REDACT_PII must be true or false"))
+                .unwrap_or(REDACT_PII_DEFAULT),
             }
     }
 
+    /// Build the message ID dedup cache, unless it is disabled
+    pub fn dedup_cache(&self) -> Option<MessageDedupCache> {
+        (self.dedup_cache_size > 0).then(|| {
+            MessageDedupCache::new(self.dedup_cache_size, Duration::from_secs(self.dedup_ttl_secs))
+        })
+    }
+
     pub fn listen_address(&self) -> std::net::SocketAddr {
         format!("{}:{}", self.host, self.port)
             .parse()
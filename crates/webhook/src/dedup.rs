@@ -0,0 +1,173 @@
+use chrono::{DateTime, Utc};
+use common::{Clock, SystemClock};
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Bounded cache of recently seen webhook message IDs
+///
+/// WhatsApp redelivers a webhook when we don't answer fast enough, so the
+/// same `message.id` can arrive more than once. The handler records each
+/// ID here before publishing and skips the ones already present.
+///
+/// IDs are kept for `ttl` after they were first seen, and once `capacity`
+/// IDs are held the oldest is evicted to make room. A `ttl` of zero keeps
+/// IDs until they are evicted.
+pub struct MessageDedupCache {
+    capacity: usize,
+    ttl: Option<chrono::Duration>,
+    clock: Arc<dyn Clock>,
+    inner: Mutex<DedupState>,
+}
+
+#[derive(Default)]
+struct DedupState {
+    /// IDs currently cached
+    seen: HashSet<String>,
+    /// The same IDs oldest first, with when each was seen
+    order: VecDeque<(String, DateTime<Utc>)>,
+}
+
+impl MessageDedupCache {
+    /// Create a cache holding at most `capacity` IDs, each for `ttl`
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            ttl: (!ttl.is_zero()).then(|| chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::MAX)),
+            clock: Arc::new(SystemClock),
+            inner: Mutex::new(DedupState::default()),
+        }
+    }
+
+    /// Use `clock` to decide when IDs expire
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Record `message_id`, returning false if it was already seen
+    pub fn check_and_record(&self, message_id: &str) -> bool {
+        let now = self.clock.now();
+        let mut inner = self.lock();
+
+        if let Some(ttl) = self.ttl {
+            while let Some((_, seen_at)) = inner.order.front() {
+                if seen_at.checked_add_signed(ttl).is_none_or(|expires_at| expires_at > now) {
+                    break;
+                }
+                inner.pop_front();
+            }
+        }
+
+        if inner.seen.contains(message_id) {
+            return false;
+        }
+
+        while inner.seen.len() >= self.capacity {
+            inner.pop_front();
+        }
+        inner.seen.insert(message_id.to_string());
+        inner.order.push_back((message_id.to_string(), now));
+        true
+    }
+
+    /// Drop `message_id` so a redelivery of it is processed again
+    ///
+    /// Used when publishing the message failed after it was recorded.
+    pub fn forget(&self, message_id: &str) {
+        let mut inner = self.lock();
+        if inner.seen.remove(message_id) {
+            inner.order.retain(|(id, _)| id != message_id);
+        }
+    }
+
+    /// Number of IDs currently held
+    pub fn len(&self) -> usize {
+        self.lock().seen.len()
+    }
+
+    /// Whether no IDs are held
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, DedupState> {
+        // Every update leaves the maps consistent, so a poisoned lock is still usable
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl DedupState {
+    /// Remove the oldest cached ID
+    fn pop_front(&mut self) {
+        if let Some((id, _)) = self.order.pop_front() {
+            self.seen.remove(&id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::MockClock;
+
+    #[test]
+    fn test_repeated_id_is_rejected() {
+        let cache = MessageDedupCache::new(10, Duration::from_secs(60));
+        assert!(cache.check_and_record("wamid.1"));
+        assert!(!cache.check_and_record("wamid.1"));
+        assert!(cache.check_and_record("wamid.2"));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_ids_expire_after_ttl() {
+        let clock = Arc::new(MockClock::new(Utc::now()));
+        let cache = MessageDedupCache::new(10, Duration::from_secs(60)).with_clock(clock.clone());
+
+        assert!(cache.check_and_record("wamid.1"));
+        clock.advance(chrono::Duration::seconds(59));
+        assert!(!cache.check_and_record("wamid.1"));
+
+        clock.advance(chrono::Duration::seconds(1));
+        assert!(cache.check_and_record("wamid.1"));
+    }
+
+    #[test]
+    fn test_oldest_id_evicted_at_capacity() {
+        let cache = MessageDedupCache::new(2, Duration::ZERO);
+        assert!(cache.check_and_record("wamid.1"));
+        assert!(cache.check_and_record("wamid.2"));
+        assert!(cache.check_and_record("wamid.3"));
+        assert_eq!(cache.len(), 2);
+
+        assert!(!cache.check_and_record("wamid.3"));
+        assert!(cache.check_and_record("wamid.1"));
+    }
+
+    #[test]
+    fn test_forgotten_id_is_accepted_again() {
+        let cache = MessageDedupCache::new(2, Duration::ZERO);
+        assert!(cache.check_and_record("wamid.1"));
+        cache.forget("wamid.1");
+        assert!(cache.check_and_record("wamid.1"));
+
+        // Forgetting leaves nothing queued that would evict the new recording
+        assert!(cache.check_and_record("wamid.2"));
+        assert!(!cache.check_and_record("wamid.1"));
+    }
+
+    #[test]
+    fn test_repeated_forget_does_not_grow_order_without_ttl() {
+        let cache = MessageDedupCache::new(10, Duration::ZERO);
+        assert!(cache.check_and_record("wamid.ok"));
+        for _ in 0..1000 {
+            assert!(cache.check_and_record("wamid.failing"));
+            cache.forget("wamid.failing");
+        }
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.lock().order.len(), 1);
+        assert!(!cache.check_and_record("wamid.ok"));
+    }
+}
@@ -3,7 +3,7 @@ use common::{
     MessageType, MessageContent, InteractionType, InteractionSelection, FailureType,
     WebhookMessageType, ContactMessage, LocationMessage, TextMessage, MediaMessage,
     ReactionMessage, InteractiveMessage, ReferralMessage, MessageError,
-    KafkaEventBus,
+    KafkaEventBus, loggable_phone,
 };
 use std::{
     collections::HashMap,
@@ -16,20 +16,40 @@ use tracing::{debug, error, info, warn};
 /// This service acts as the bridge between WhatsApp's complex webhook format
 /// and our simplified event-driven architecture. It transforms raw webhook
 /// data into business-focused events that other services can easily consume.
-pub struct WebhookEventPublisher {
+///
+/// The event bus defaults to `KafkaEventBus`; tests can use
+/// `InMemoryEventBus` to inspect published events without a broker.
+pub struct WebhookEventPublisher<B = KafkaEventBus> {
     
     /// Event bus for publishing events
-    event_bus: Arc<KafkaEventBus>,
+    event_bus: Arc<B>,
+
+    /// Mask phone numbers in logs
+    redact_pii: bool,
 }
 
-impl WebhookEventPublisher {
+impl<B> WebhookEventPublisher<B>
+where
+    B: EventBus<Error = EventBusError>,
+{
     /// Create a new webhook event publisher with enhanced event bus
     /// 
     /// Takes an enhanced event bus implementation that provides automatic
     /// retry logic, dead letter queue support, and reliable event delivery.
-    pub fn new(event_bus: Arc<KafkaEventBus>) -> Self {
+    pub fn new(event_bus: Arc<B>) -> Self {
         info!("🔧 Initializing webhook event publisher with enhanced event bus");
-        Self { event_bus }
+        Self { event_bus, redact_pii: false }
+    }
+
+    /// Mask phone numbers in this publisher's logs (see `redact_phone`)
+    pub fn with_redact_pii(mut self, redact_pii: bool) -> Self {
+        self.redact_pii = redact_pii;
+        self
+    }
+
+    /// Whether phone numbers are masked in logs
+    pub fn redact_pii(&self) -> bool {
+        self.redact_pii
     }
     
     /// Process a WhatsApp message and publish appropriate domain events
@@ -47,10 +67,10 @@ impl WebhookEventPublisher {
         webhook_message_type: Option<WebhookMessageType>,
         context_message_id: Option<String>,
     ) -> Result<(), EventBusError> {
-        debug!("📨 Processing message {} from {} with enhanced event publishing", message_id, from_phone);
+        debug!("📨 Processing message {} from {} with enhanced event publishing", message_id, loggable_phone(&from_phone, self.redact_pii));
         
         // Parse the timestamp from WhatsApp format
-        let received_at = self.parse_timestamp(&timestamp)?;
+        let received_at = parse_timestamp(&timestamp)?;
         
         // Create metadata for additional context
         let mut metadata = HashMap::new();
@@ -71,6 +91,15 @@ impl WebhookEventPublisher {
                     WebhookMessageType::Image(media) => {
                         self.publish_media_message(message_id, from_phone, media, MessageType::Image, received_at, metadata).await
                     }
+                    WebhookMessageType::Audio(media) => {
+                        self.publish_media_message(message_id, from_phone, media, MessageType::Audio, received_at, metadata).await
+                    }
+                    WebhookMessageType::Video(media) => {
+                        self.publish_media_message(message_id, from_phone, media, MessageType::Video, received_at, metadata).await
+                    }
+                    WebhookMessageType::Document(media) => {
+                        self.publish_media_message(message_id, from_phone, media, MessageType::Document, received_at, metadata).await
+                    }
                     WebhookMessageType::Sticker(media) => {
                         self.publish_media_message(message_id, from_phone, media, MessageType::Sticker, received_at, metadata).await
                     }
@@ -107,6 +136,34 @@ impl WebhookEventPublisher {
         }
     }
     
+    /// Publish account-level errors from a webhook value
+    /// 
+    /// These aren't about a particular message, so the `MessageFailed`
+    /// event has an empty `message_id` and carries our business phone
+    /// number ID in place of a user's phone.
+    pub async fn process_account_errors(
+        &self,
+        business_phone: String,
+        errors: Vec<MessageError>,
+    ) -> Result<(), EventBusError> {
+        let error_details = errors.iter()
+            .map(|e| format!("{} ({}): {}", e.title, e.code, e.description))
+            .collect::<Vec<_>>()
+            .join("; ");
+        
+        let event = MessageFailed {
+            message_id: String::new(),
+            phone: business_phone.clone(),
+            failure_type: FailureType::AccountError,
+            error_details,
+            attempt_count: 1,
+            failed_at: chrono::Utc::now(),
+        };
+        
+        error!("📤 Publishing {} account error(s) for {}", errors.len(), business_phone);
+        self.event_bus.publish(event).await
+    }
+    
     /// Publish a text message event using the enhanced event bus
     async fn publish_text_message(
         &self,
@@ -175,6 +232,7 @@ impl WebhookEventPublisher {
             content: MessageContent::Location {
                 latitude: location.latitude,
                 longitude: location.longitude,
+                is_live: location.is_live(),
                 name: location.name,
                 address: location.address,
             },
@@ -289,6 +347,22 @@ impl WebhookEventPublisher {
                     return self.publish_interaction_failure(message_id, from_phone, received_at).await;
                 }
             }
+            "nfm_reply" => {
+                let Some(nfm_reply) = interactive.nfm_reply else {
+                    warn!("🚨 Flow reply without flow data for message {}", message_id);
+                    return self.publish_interaction_failure(message_id, from_phone, received_at).await;
+                };
+                match serde_json::from_str(&nfm_reply.response_json) {
+                    Ok(response) => (
+                        InteractionType::FlowReply,
+                        InteractionSelection::Flow { response }
+                    ),
+                    Err(e) => {
+                        warn!("🚨 Flow reply {} with invalid response_json for message {}: {}", nfm_reply.name, message_id, e);
+                        return self.publish_interaction_failure(message_id, from_phone, received_at).await;
+                    }
+                }
+            }
             _ => {
                 warn!("🚨 Unknown interaction type: {} for message {}", interactive.interactive_type, message_id);
                 return self.publish_interaction_failure(message_id, from_phone, received_at).await;
@@ -434,18 +508,336 @@ impl WebhookEventPublisher {
         warn!("📤 Publishing unknown message failure event for message {}", message_id);
         self.event_bus.publish(event).await
     }
-    
-    /// Parse WhatsApp timestamp format into chrono DateTime
-    fn parse_timestamp(&self, timestamp: &str) -> Result<chrono::DateTime<chrono::Utc>, EventBusError> {
-        // WhatsApp sends Unix timestamps as strings
-        let unix_timestamp = timestamp.parse::<i64>()
-            .map_err(|_| EventBusError::SerializationError(
-                format!("Invalid timestamp format: {}", timestamp)
-            ))?;
-        
-        chrono::DateTime::from_timestamp(unix_timestamp, 0)
+}
+
+/// Parse WhatsApp timestamp format into chrono DateTime
+/// 
+/// WhatsApp sends Unix timestamps as strings, but RFC3339 strings (as
+/// used by some test payloads) are accepted too.
+fn parse_timestamp(timestamp: &str) -> Result<chrono::DateTime<chrono::Utc>, EventBusError> {
+    if let Ok(unix_timestamp) = timestamp.parse::<i64>() {
+        return chrono::DateTime::from_timestamp(unix_timestamp, 0)
             .ok_or_else(|| EventBusError::SerializationError(
                 format!("Invalid Unix timestamp: {}", unix_timestamp)
-            ))
+            ));
+    }
+    
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|parsed| parsed.with_timezone(&chrono::Utc))
+        .map_err(|_| EventBusError::SerializationError(
+            format!("Invalid timestamp format: {}", timestamp)
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{Event, EventEnvelope, InMemoryEventBus};
+    
+    #[test]
+    fn test_parse_unix_timestamp() {
+        let parsed = parse_timestamp("1696161600").unwrap();
+        assert_eq!(parsed.to_rfc3339(), "2023-10-01T12:00:00+00:00");
+    }
+    
+    #[test]
+    fn test_parse_rfc3339_timestamp() {
+        let parsed = parse_timestamp("2023-10-01T12:00:00Z").unwrap();
+        assert_eq!(parsed.timestamp(), 1696161600);
+        
+        let offset = parse_timestamp("2023-10-01T14:00:00+02:00").unwrap();
+        assert_eq!(offset, parsed);
+    }
+    
+    #[test]
+    fn test_parse_garbage_timestamp() {
+        let error = parse_timestamp("yesterday").unwrap_err();
+        assert!(matches!(error, EventBusError::SerializationError(_)));
+        assert!(parse_timestamp("").is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_account_errors_publish_message_failed() {
+        let (event_bus, publisher) = in_memory_publisher();
+        
+        let errors = vec![MessageError {
+            code: 131042,
+            title: "Business eligibility payment issue".to_string(),
+            description: "Payment method problem".to_string(),
+        }];
+        publisher.process_account_errors("106540352242922".to_string(), errors)
+            .await
+            .expect("Should publish account errors");
+        
+        let published = event_bus.published_to(MessageFailed::TOPIC);
+        assert_eq!(published.len(), 1, "expected exactly one event");
+        let envelope: EventEnvelope<MessageFailed> = published[0].decode().expect("Should decode envelope");
+        assert!(matches!(envelope.data.failure_type, FailureType::AccountError));
+        assert_eq!(envelope.data.phone, "106540352242922");
+        assert!(envelope.data.error_details.contains("131042"));
+    }
+    
+    fn in_memory_publisher() -> (InMemoryEventBus, WebhookEventPublisher<InMemoryEventBus>) {
+        let event_bus = InMemoryEventBus::new();
+        let publisher = WebhookEventPublisher::new(Arc::new(event_bus.clone()));
+        (event_bus, publisher)
+    }
+    
+    /// Run one message through the publisher and decode the single event it published
+    async fn publish_one<T: Event>(message_type: Option<WebhookMessageType>) -> EventEnvelope<T> {
+        let (event_bus, publisher) = in_memory_publisher();
+        publisher
+            .process_message(
+                "wamid.123".to_string(),
+                "+1234567890".to_string(),
+                "1696161600".to_string(),
+                message_type,
+                Some("wamid.parent".to_string()),
+            )
+            .await
+            .expect("Should publish");
+        
+        let published = event_bus.published_events();
+        assert_eq!(published.len(), 1, "expected exactly one event");
+        assert_eq!(published[0].topic, T::TOPIC);
+        assert_eq!(published[0].key.as_deref(), Some("+1234567890"));
+        published[0].decode().expect("Should decode envelope")
+    }
+    
+    fn media(caption: Option<&str>) -> MediaMessage {
+        MediaMessage {
+            id: Some("media-1".to_string()),
+            mime_type: "image/jpeg".to_string(),
+            sha256: "abc".to_string(),
+            caption: caption.map(str::to_string),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_text_message_publishes_message_received() {
+        let envelope = publish_one::<MessageReceived>(Some(WebhookMessageType::Text(TextMessage {
+            body: "Hello".to_string(),
+        }))).await;
+        
+        assert_eq!(envelope.data.message_id, "wamid.123");
+        assert_eq!(envelope.data.message_type, MessageType::Text);
+        assert!(matches!(envelope.data.content, MessageContent::Text { ref body } if body == "Hello"));
+        assert_eq!(envelope.data.received_at.timestamp(), 1696161600);
+        assert_eq!(envelope.data.metadata.get("context_message_id").map(String::as_str), Some("wamid.parent"));
+    }
+    
+    #[tokio::test]
+    async fn test_media_messages_keep_their_type() {
+        type MediaCase = (fn(MediaMessage) -> WebhookMessageType, MessageType);
+        let cases: [MediaCase; 5] = [
+            (WebhookMessageType::Image, MessageType::Image),
+            (WebhookMessageType::Audio, MessageType::Audio),
+            (WebhookMessageType::Video, MessageType::Video),
+            (WebhookMessageType::Document, MessageType::Document),
+            (WebhookMessageType::Sticker, MessageType::Sticker),
+        ];
+        
+        for (variant, expected_type) in cases {
+            let envelope = publish_one::<MessageReceived>(Some(variant(media(Some("Look"))))).await;
+            assert_eq!(envelope.data.message_type, expected_type);
+            assert!(matches!(
+                envelope.data.content,
+                MessageContent::Media { ref media_id, ref caption, .. }
+                    if media_id == "media-1" && caption.as_deref() == Some("Look")
+            ));
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_location_message_publishes_coordinates() {
+        let envelope = publish_one::<MessageReceived>(Some(WebhookMessageType::Location(LocationMessage {
+            latitude: 4.711,
+            longitude: -74.072,
+            name: Some("Bogotá".to_string()),
+            ..Default::default()
+        }))).await;
+        
+        assert_eq!(envelope.data.message_type, MessageType::Location);
+        assert!(matches!(
+            envelope.data.content,
+            MessageContent::Location { latitude, longitude, ref name, is_live: false, .. }
+                if latitude == 4.711 && longitude == -74.072 && name.as_deref() == Some("Bogotá")
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_live_location_message_is_flagged_live() {
+        let envelope = publish_one::<MessageReceived>(Some(WebhookMessageType::Location(LocationMessage {
+            latitude: 4.711,
+            longitude: -74.072,
+            speed: Some(8.5),
+            sequence_number: Some(3),
+            ..Default::default()
+        }))).await;
+        
+        assert!(matches!(envelope.data.content, MessageContent::Location { is_live: true, .. }));
+    }
+    
+    #[tokio::test]
+    async fn test_contact_message_uses_first_contact() {
+        let contact = ContactMessage {
+            addresses: None,
+            birthday: None,
+            emails: Some(vec![common::ContactEmail {
+                email: "ana@example.com".to_string(),
+                email_type: None,
+            }]),
+            name: common::ContactName {
+                formatted_name: None,
+                first_name: Some("Ana".to_string()),
+                last_name: Some("Diaz".to_string()),
+                middle_name: None,
+                suffix: None,
+                prefix: None,
+            },
+            org: None,
+            phones: Some(vec![common::ContactPhone {
+                phone: "+15551234567".to_string(),
+                wa_id: None,
+                phone_type: None,
+            }]),
+            urls: None,
+        };
+        let envelope = publish_one::<MessageReceived>(Some(WebhookMessageType::Contact(vec![contact]))).await;
+        
+        assert!(matches!(
+            envelope.data.content,
+            MessageContent::Contact { ref name, ref phone_number, ref email }
+                if name == "Ana Diaz" && phone_number == "+15551234567" && email.as_deref() == Some("ana@example.com")
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_interactive_replies_publish_interactions() {
+        let envelope = publish_one::<InteractionReceived>(Some(WebhookMessageType::Interactive(InteractiveMessage {
+            interactive_type: "button_reply".to_string(),
+            button_reply: Some(common::ButtonReply {
+                id: "yes".to_string(),
+                title: "Yes".to_string(),
+            }),
+            list_reply: None,
+            nfm_reply: None,
+        }))).await;
+        assert!(matches!(envelope.data.interaction_type, InteractionType::ButtonReply));
+        assert!(matches!(envelope.data.selection, InteractionSelection::Button { ref id, .. } if id == "yes"));
+        
+        let envelope = publish_one::<InteractionReceived>(Some(WebhookMessageType::Interactive(InteractiveMessage {
+            interactive_type: "list_reply".to_string(),
+            button_reply: None,
+            list_reply: Some(common::ListReply {
+                id: "opt1".to_string(),
+                title: "Option 1".to_string(),
+                description: None,
+            }),
+            nfm_reply: None,
+        }))).await;
+        assert!(matches!(envelope.data.interaction_type, InteractionType::ListReply));
+        assert!(matches!(envelope.data.selection, InteractionSelection::List { ref id, .. } if id == "opt1"));
+        
+        // A reply type without its payload is published as a failure instead
+        let envelope = publish_one::<MessageFailed>(Some(WebhookMessageType::Interactive(InteractiveMessage {
+            interactive_type: "button_reply".to_string(),
+            button_reply: None,
+            list_reply: None,
+            nfm_reply: None,
+        }))).await;
+        assert!(matches!(envelope.data.failure_type, FailureType::ValidationError));
+    }
+    
+    #[tokio::test]
+    async fn test_flow_reply_publishes_flow_response() {
+        let interactive: InteractiveMessage = serde_json::from_str(r#"{
+            "type": "nfm_reply",
+            "nfm_reply": {
+                "name": "flow",
+                "body": "Sent",
+                "response_json": "{\"flow_token\": \"tok-1\", \"appointment_date\": \"2025-07-01\", \"guests\": 2}"
+            }
+        }"#).expect("Should deserialize flow reply");
+        
+        let envelope = publish_one::<InteractionReceived>(Some(WebhookMessageType::Interactive(interactive))).await;
+        assert!(matches!(envelope.data.interaction_type, InteractionType::FlowReply));
+        match envelope.data.selection {
+            InteractionSelection::Flow { response } => {
+                assert_eq!(response["flow_token"], "tok-1");
+                assert_eq!(response["appointment_date"], "2025-07-01");
+                assert_eq!(response["guests"], 2);
+            }
+            other => panic!("Expected a flow selection, got {:?}", other),
+        }
+        
+        // A response_json that isn't JSON can't be handed downstream
+        let envelope = publish_one::<MessageFailed>(Some(WebhookMessageType::Interactive(InteractiveMessage {
+            interactive_type: "nfm_reply".to_string(),
+            button_reply: None,
+            list_reply: None,
+            nfm_reply: Some(common::NfmReply {
+                name: "flow".to_string(),
+                body: None,
+                response_json: "not json".to_string(),
+            }),
+        }))).await;
+        assert!(matches!(envelope.data.failure_type, FailureType::ValidationError));
+    }
+    
+    #[tokio::test]
+    async fn test_reaction_and_referral_publish_text_events() {
+        let envelope = publish_one::<MessageReceived>(Some(WebhookMessageType::Reaction(ReactionMessage {
+            message_id: "wamid.original".to_string(),
+            emoji: "👍".to_string(),
+        }))).await;
+        assert!(matches!(envelope.data.content, MessageContent::Text { ref body } if body == "Reacted with: 👍"));
+        assert_eq!(envelope.data.metadata.get("reaction_to_message").map(String::as_str), Some("wamid.original"));
+        
+        let envelope = publish_one::<MessageReceived>(Some(WebhookMessageType::Referral(ReferralMessage {
+            source_url: "https://fb.me/ad".to_string(),
+            source_id: "ad-1".to_string(),
+            source_type: "ad".to_string(),
+            headline: Some("Sale".to_string()),
+            body: None,
+            media_type: None,
+            image_url: None,
+            video_url: None,
+            thumbnail_url: None,
+            ctwa_clid: None,
+        }))).await;
+        assert!(matches!(envelope.data.content, MessageContent::Text { ref body } if body == "User came from referral"));
+        assert_eq!(envelope.data.metadata.get("referral_source_type").map(String::as_str), Some("ad"));
+    }
+    
+    #[tokio::test]
+    async fn test_unknown_and_missing_types_publish_failures() {
+        let envelope = publish_one::<MessageFailed>(Some(WebhookMessageType::Unknown(vec![MessageError {
+            code: 131051,
+            title: "Unsupported message type".to_string(),
+            description: "Message type is not currently supported".to_string(),
+        }]))).await;
+        assert!(matches!(envelope.data.failure_type, FailureType::ValidationError));
+        assert_eq!(envelope.data.error_details, "Unsupported message type: Message type is not currently supported");
+        
+        let envelope = publish_one::<MessageFailed>(None).await;
+        assert!(matches!(envelope.data.failure_type, FailureType::UnknownError));
+    }
+    
+    #[tokio::test]
+    async fn test_bad_timestamp_publishes_nothing() {
+        let (event_bus, publisher) = in_memory_publisher();
+        let result = publisher
+            .process_message(
+                "wamid.123".to_string(),
+                "+1234567890".to_string(),
+                "yesterday".to_string(),
+                Some(WebhookMessageType::Text(TextMessage { body: "Hello".to_string() })),
+                None,
+            )
+            .await;
+        
+        assert!(matches!(result, Err(EventBusError::SerializationError(_))));
+        assert!(event_bus.published_events().is_empty());
     }
 }
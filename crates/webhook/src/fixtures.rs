@@ -0,0 +1,199 @@
+//! Ready-made incoming webhook messages for tests
+//!
+//! Each builder returns a fully populated `Message` of one type, as
+//! WhatsApp would deliver it, so tests only spell out the fields they
+//! care about. Available to this crate's tests and, with the `test-utils`
+//! feature, to other crates.
+
+use crate::types::Message;
+use common::{
+    ButtonReply, ContactMessage, ContactName, ContactPhone, InteractiveMessage, ListReply,
+    LocationMessage, MediaMessage, ReactionMessage, TextMessage,
+};
+use std::collections::HashMap;
+
+/// Timestamp given to every fixture message (2023-10-01T12:00:00Z)
+pub const TIMESTAMP: &str = "1696161600";
+
+/// A message of `message_type` with no content set
+pub fn message(id: &str, from: &str, message_type: &str) -> Message {
+    Message {
+        id: id.to_string(),
+        from: from.to_string(),
+        timestamp: TIMESTAMP.to_string(),
+        message_type: message_type.to_string(),
+        text: None,
+        reaction: None,
+        image: None,
+        audio: None,
+        video: None,
+        document: None,
+        sticker: None,
+        location: None,
+        contact: None,
+        interactive: None,
+        referral: None,
+        error: None,
+        context: None,
+        unknown_fields: HashMap::new(),
+    }
+}
+
+/// A text message
+pub fn text_message(id: &str, from: &str, body: &str) -> Message {
+    Message {
+        text: Some(TextMessage { body: body.to_string() }),
+        ..message(id, from, "text")
+    }
+}
+
+/// `count` text messages from `from`, with IDs `wamid.fixture.0` onwards
+pub fn text_messages(from: &str, count: usize) -> Vec<Message> {
+    (0..count)
+        .map(|n| text_message(&format!("wamid.fixture.{}", n), from, &format!("Message {}", n)))
+        .collect()
+}
+
+/// An image message referencing uploaded media `media_id`
+pub fn image_message(id: &str, from: &str, media_id: &str, caption: Option<&str>) -> Message {
+    Message {
+        image: Some(MediaMessage {
+            id: Some(media_id.to_string()),
+            mime_type: "image/jpeg".to_string(),
+            sha256: "fixture-sha256".to_string(),
+            caption: caption.map(str::to_string),
+        }),
+        ..message(id, from, "image")
+    }
+}
+
+/// A shared location
+pub fn location_message(id: &str, from: &str, latitude: f64, longitude: f64) -> Message {
+    Message {
+        location: Some(LocationMessage {
+            latitude,
+            longitude,
+            ..Default::default()
+        }),
+        ..message(id, from, "location")
+    }
+}
+
+/// A shared contact card with one name and phone number
+pub fn contact_message(id: &str, from: &str, name: &str, phone: &str) -> Message {
+    Message {
+        contact: Some(vec![ContactMessage {
+            addresses: None,
+            birthday: None,
+            emails: None,
+            name: ContactName {
+                formatted_name: Some(name.to_string()),
+                first_name: None,
+                last_name: None,
+                middle_name: None,
+                suffix: None,
+                prefix: None,
+            },
+            org: None,
+            phones: Some(vec![ContactPhone {
+                phone: phone.to_string(),
+                wa_id: None,
+                phone_type: None,
+            }]),
+            urls: None,
+        }]),
+        ..message(id, from, "contact")
+    }
+}
+
+/// A reaction to the message `reacted_to`
+pub fn reaction_message(id: &str, from: &str, reacted_to: &str, emoji: &str) -> Message {
+    Message {
+        reaction: Some(ReactionMessage {
+            message_id: reacted_to.to_string(),
+            emoji: emoji.to_string(),
+        }),
+        ..message(id, from, "reaction")
+    }
+}
+
+/// A tap on the reply button `button_id`
+pub fn interactive_button_reply(id: &str, from: &str, button_id: &str, title: &str) -> Message {
+    interactive(id, from, InteractiveMessage {
+        interactive_type: "button_reply".to_string(),
+        button_reply: Some(ButtonReply {
+            id: button_id.to_string(),
+            title: title.to_string(),
+        }),
+        list_reply: None,
+        nfm_reply: None,
+    })
+}
+
+/// A selection of the list row `row_id`
+pub fn interactive_list_reply(id: &str, from: &str, row_id: &str, title: &str) -> Message {
+    interactive(id, from, InteractiveMessage {
+        interactive_type: "list_reply".to_string(),
+        button_reply: None,
+        list_reply: Some(ListReply {
+            id: row_id.to_string(),
+            title: title.to_string(),
+            description: None,
+        }),
+        nfm_reply: None,
+    })
+}
+
+fn interactive(id: &str, from: &str, interactive: InteractiveMessage) -> Message {
+    Message {
+        interactive: Some(interactive),
+        ..message(id, from, "interactive")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::WebhookMessageType;
+
+    #[test]
+    fn test_fixtures_resolve_to_their_message_type() {
+        type Case = (Message, fn(&WebhookMessageType) -> bool);
+        let from = "16505551234";
+        let cases: [Case; 7] = [
+            (text_message("wamid.1", from, "Hi"), |t| matches!(t, WebhookMessageType::Text(text) if text.body == "Hi")),
+            (image_message("wamid.2", from, "media-1", Some("Look")), |t| matches!(t, WebhookMessageType::Image(_))),
+            (location_message("wamid.3", from, 4.711, -74.072), |t| matches!(t, WebhookMessageType::Location(_))),
+            (contact_message("wamid.4", from, "Ana Diaz", "+15551234567"), |t| matches!(t, WebhookMessageType::Contact(c) if c.len() == 1)),
+            (reaction_message("wamid.5", from, "wamid.1", "👍"), |t| matches!(t, WebhookMessageType::Reaction(_))),
+            (interactive_button_reply("wamid.6", from, "yes", "Yes"), |t| matches!(t, WebhookMessageType::Interactive(_))),
+            (interactive_list_reply("wamid.7", from, "tea", "Tea"), |t| matches!(t, WebhookMessageType::Interactive(_))),
+        ];
+
+        for (message, expected) in cases {
+            let message_type = message.get_message_type().expect("Fixture should have content");
+            assert!(expected(&message_type), "{} resolved to {:?}", message.id, message_type);
+        }
+    }
+
+    #[test]
+    fn test_interactive_fixtures_carry_the_selection() {
+        let message = interactive_list_reply("wamid.7", "16505551234", "tea", "Tea");
+        match message.get_message_type() {
+            Some(WebhookMessageType::Interactive(interactive)) => {
+                assert_eq!(interactive.interactive_type, "list_reply");
+                assert_eq!(interactive.list_reply.expect("Should have a list reply").id, "tea");
+                assert!(interactive.button_reply.is_none());
+            }
+            other => panic!("Expected Interactive message type, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_text_messages_have_distinct_ids() {
+        let messages = text_messages("16505551234", 3);
+        let ids: Vec<&str> = messages.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["wamid.fixture.0", "wamid.fixture.1", "wamid.fixture.2"]);
+        assert!(messages.iter().all(|m| m.timestamp == TIMESTAMP));
+    }
+}
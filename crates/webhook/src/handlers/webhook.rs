@@ -4,10 +4,13 @@ use axum::{
     Json,
 };
 use crate::{
+    dedup::MessageDedupCache,
     state::AppState,
-    types::{WebhookVerifyQuery, WebhookPayload},
+    types::{Change, LenientEntry, LenientWebhookPayload, WebhookVerifyQuery},
     event_publisher::WebhookEventPublisher,
 };
+use common::{loggable_phone, EventBus, EventBusError};
+use subtle::ConstantTimeEq;
 
 use tracing::{error, info, warn};
 
@@ -19,21 +22,36 @@ pub async fn verify_webhook(
     Query(query): Query<WebhookVerifyQuery>,
     State(state): State<AppState>,
 ) -> Result<String, StatusCode> {
-    match (query.mode.as_deref(), &query.verify_token, &query.challenge) {
-        (Some("subscribe"), Some(token), Some(challenge)) => {
-            if token == &state.config.verify_token {
-                info!("✅ Webhook verification successful");
-                Ok(challenge.clone())
-            } else {
-                warn!("❌ Invalid verify token: {}", token);
-                Err(StatusCode::FORBIDDEN)
-            }
-        }
-        _ => {
-            error!("❌ Invalid webhook verification request: {:?}", query);
-            Err(StatusCode::BAD_REQUEST)
-        }
+    verify_subscription(&query, &state.config.verify_token)
+}
+
+/// Answer a verification request, returning the challenge to echo back
+///
+/// Requires `hub.mode=subscribe`, a challenge, and a verify token matching
+/// `expected_token`. The tokens are compared in constant time so response
+/// timing doesn't reveal how much of a guess was right. Anything else is
+/// answered with 403.
+pub fn verify_subscription(query: &WebhookVerifyQuery, expected_token: &str) -> Result<String, StatusCode> {
+    let (Some(mode), Some(token), Some(challenge)) = (&query.mode, &query.verify_token, &query.challenge) else {
+        warn!(
+            "❌ Incomplete webhook verification request (mode: {}, token: {}, challenge: {})",
+            query.mode.is_some(), query.verify_token.is_some(), query.challenge.is_some(),
+        );
+        return Err(StatusCode::FORBIDDEN);
+    };
+
+    if mode != "subscribe" {
+        warn!("❌ Unsupported webhook verification mode: {}", mode);
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    if !bool::from(token.as_bytes().ct_eq(expected_token.as_bytes())) {
+        warn!("❌ Invalid verify token");
+        return Err(StatusCode::FORBIDDEN);
     }
+
+    info!("✅ Webhook verification successful");
+    Ok(challenge.clone())
 }
 
 /// Handle incoming WhatsApp webhook messages and transform them into domain events
@@ -41,62 +59,306 @@ pub async fn verify_webhook(
 /// THis is the main webhook endpoint that receives all WhatsApp messages, 
 /// interactions,and status updates. It processes each message and publishes
 /// appropriate domain events to Kafka for downstream services to consume.
+///
+/// Entries and changes are parsed one at a time, so a malformed one is
+/// logged and skipped instead of failing the whole request. The request is
+/// only rejected when nothing in it could be parsed.
 pub async fn handle_webhook(
     State(state): State<AppState>,
-    Json(payload): Json<WebhookPayload>,
+    Json(payload): Json<LenientWebhookPayload>,
 ) -> Result<StatusCode, StatusCode> {
     info!("📨 Received webhook payload with {} entries", payload.entry.len());
 
-    let event_publisher = WebhookEventPublisher::new(state.event_bus.clone());
+    let event_publisher = WebhookEventPublisher::new(state.event_bus.clone())
+        .with_redact_pii(state.config.redact_pii);
+    let outcome = process_payload(&event_publisher, payload, state.dedup.as_deref()).await;
+
+    if outcome.handled == 0 && outcome.rejected > 0 {
+        error!("❌ None of the {} changes in the webhook payload could be parsed", outcome.rejected);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // Always return 200 OK to WhatsApp to acknowledge receipt
+    // Even if some message processing failed, we don't want WhatsApp 
+    // to retry the entire webhook payload since failures are handled 
+    // by our retry mechanisms
+    Ok(StatusCode::OK)
+}
+
+/// How many changes in a payload were handled and how many were malformed
+///
+/// A malformed entry counts once, whatever number of changes it had.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadOutcome {
+    pub handled: usize,
+    pub rejected: usize,
+}
+
+/// Parse each entry and change of a webhook payload and publish its events
+///
+/// Malformed entries and changes are logged and counted in
+/// [`PayloadOutcome::rejected`]; the rest are processed as usual. When a
+/// `dedup` cache is given, messages whose ID it has already seen are
+/// skipped instead of being published again.
+pub async fn process_payload<B>(
+    event_publisher: &WebhookEventPublisher<B>,
+    payload: LenientWebhookPayload,
+    dedup: Option<&MessageDedupCache>,
+) -> PayloadOutcome
+where
+    B: EventBus<Error = EventBusError>,
+{
+    let mut outcome = PayloadOutcome::default();
+
+    for (entry_index, raw_entry) in payload.entry.into_iter().enumerate() {
+        let entry: LenientEntry = match serde_json::from_value(raw_entry) {
+            Ok(entry) => entry,
+            Err(e) => {
+                warn!("⚠️ Skipping malformed entry {}: {}", entry_index, e);
+                outcome.rejected += 1;
+                continue;
+            }
+        };
 
-    // Only process message changes (ignore status changes, etc.)
-    for entry in payload.entry {
         info!("🔄 Processing entry {} with {} changes", entry.id, entry.changes.len());
 
-        for change in entry.changes {
-            if change.field != "messages" {
-                warn!("⚠️ Unsupported field in change: {}", change.field);
+        for (change_index, raw_change) in entry.changes.into_iter().enumerate() {
+            match serde_json::from_value::<Change>(raw_change) {
+                Ok(change) => {
+                    process_change(event_publisher, &entry.id, change, dedup).await;
+                    outcome.handled += 1;
+                }
+                Err(e) => {
+                    warn!("⚠️ Skipping malformed change {} in entry {}: {}", change_index, entry.id, e);
+                    outcome.rejected += 1;
+                }
+            }
+        }
+    }
+
+    outcome
+}
+
+/// Publish the events for a single well-formed change
+async fn process_change<B>(
+    event_publisher: &WebhookEventPublisher<B>,
+    entry_id: &str,
+    change: Change,
+    dedup: Option<&MessageDedupCache>,
+)
+where
+    B: EventBus<Error = EventBusError>,
+{
+    // Only process message changes (ignore status changes, etc.)
+    if change.field != "messages" {
+        warn!("⚠️ Unsupported field in change: {}", change.field);
+        return;
+    }
+
+    if !change.value.unknown_fields.is_empty() {
+        warn!("🆕 Unrecognized fields in webhook value: {:?}", change.value.unknown_fields.keys().collect::<Vec<_>>());
+    }
+
+    if let Some(errors) = change.value.errors {
+        let business_phone = change.value.metadata
+            .as_ref()
+            .map(|metadata| metadata.phone_number_id.clone())
+            .unwrap_or_default();
+        if let Err(e) = event_publisher.process_account_errors(business_phone, errors).await {
+            error!("❌ Failed to publish account errors for entry {}: {}", entry_id, e);
+        }
+    }
+
+    if let Some(messages) = change.value.messages {
+        for message in messages {
+            let from = loggable_phone(&message.from, event_publisher.redact_pii());
+
+            if let Some(dedup) = dedup
+                && !dedup.check_and_record(&message.id)
+            {
+                info!("🔁 Skipping duplicate delivery of message {} from {}", message.id, from);
                 continue;
             }
 
-            if let Some(messages) = change.value.messages {
-                for message in messages {
-                    // Extract content message ID if present 
-                    // (for replies/interactions)
-                    let context_message_id = message.context
-                        .as_ref()
-                        .and_then(|ctx| ctx.id.clone());
-
-                    let webhook_message_type = message.get_message_type();
-
-                    // Publish message as a domain event
-                    match event_publisher.process_message(
-                        message.id.clone(),
-                        message.from.clone(),
-                        message.timestamp.clone(),
-                        webhook_message_type, 
-                        context_message_id,
-                    ).await {
-                        Ok(()) => {
-                            info!("✅ Successfully processed message {} from {}", 
-                                  message.id, message.from);
-                        }
-                        Err(e) => {
-                            error!("❌ Failed to process message {} from {}: {}", 
-                                   message.id, message.from, e);
-                            
-                            // Continue processing other messages even if one fails
-                            // The event publisher handles retries and dead letter queues
-                        }
+            if !message.unknown_fields.is_empty() {
+                warn!("🆕 Unrecognized fields in message {}: {:?}", message.id, message.unknown_fields.keys().collect::<Vec<_>>());
+            }
+
+            // Extract content message ID if present 
+            // (for replies/interactions)
+            let context_message_id = message.context
+                .as_ref()
+                .and_then(|ctx| ctx.id.clone());
+
+            let webhook_message_type = message.get_message_type();
+
+            // Publish message as a domain event
+            match event_publisher.process_message(
+                message.id.clone(),
+                message.from.clone(),
+                message.timestamp.clone(),
+                webhook_message_type, 
+                context_message_id,
+            ).await {
+                Ok(()) => {
+                    info!("✅ Successfully processed message {} from {}", 
+                          message.id, from);
+                }
+                Err(e) => {
+                    error!("❌ Failed to process message {} from {}: {}", 
+                           message.id, from, e);
+
+                    // Let a redelivery of this message try again
+                    if let Some(dedup) = dedup {
+                        dedup.forget(&message.id);
                     }
+                    
+                    // Continue processing other messages even if one fails
+                    // The event publisher handles retries and dead letter queues
                 }
             }
         }
     }
+}
 
-    // Always return 200 OK to WhatsApp to acknowledge receipt
-    // Even if some message processing failed, we don't want WhatsApp 
-    // to retry the entire webhook payload since failures are handled 
-    // by our retry mechanisms
-    Ok(StatusCode::OK)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{Event, InMemoryEventBus, MessageContent, MessageReceived};
+    use std::sync::Arc;
+
+    fn text_change(from: &str, body: &str) -> serde_json::Value {
+        serde_json::json!({
+            "field": "messages",
+            "value": {
+                "messaging_product": "whatsapp",
+                "metadata": { "phone_number_id": "106540352242922" },
+                "messages": [{
+                    "from": from,
+                    "id": format!("wamid.{}", from),
+                    "timestamp": "1749416383",
+                    "type": "text",
+                    "text": { "body": body }
+                }]
+            }
+        })
+    }
+
+    fn verify_query(mode: Option<&str>, token: Option<&str>, challenge: Option<&str>) -> WebhookVerifyQuery {
+        WebhookVerifyQuery {
+            mode: mode.map(str::to_string),
+            verify_token: token.map(str::to_string),
+            challenge: challenge.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_verify_subscription_echoes_challenge() {
+        let query = verify_query(Some("subscribe"), Some("secret-token"), Some("1158201444"));
+        assert_eq!(verify_subscription(&query, "secret-token"), Ok("1158201444".to_string()));
+    }
+
+    #[test]
+    fn test_verify_subscription_rejects_wrong_token() {
+        for token in ["wrong", "secret-toke", "secret-token-2", ""] {
+            let query = verify_query(Some("subscribe"), Some(token), Some("1158201444"));
+            assert_eq!(verify_subscription(&query, "secret-token"), Err(StatusCode::FORBIDDEN), "token {:?}", token);
+        }
+    }
+
+    #[test]
+    fn test_verify_subscription_rejects_missing_parameters() {
+        let cases = [
+            verify_query(None, Some("secret-token"), Some("1158201444")),
+            verify_query(Some("subscribe"), None, Some("1158201444")),
+            verify_query(Some("subscribe"), Some("secret-token"), None),
+            verify_query(Some("unsubscribe"), Some("secret-token"), Some("1158201444")),
+        ];
+        for query in cases {
+            assert_eq!(verify_subscription(&query, "secret-token"), Err(StatusCode::FORBIDDEN), "{:?}", query);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mixed_validity_payload_publishes_good_messages() {
+        let payload: LenientWebhookPayload = serde_json::from_value(serde_json::json!({
+            "object": "whatsapp_business_account",
+            "entry": [
+                { "id": "entry-1", "changes": [text_change("16505550001", "first")] },
+                // Missing `changes` entirely
+                { "id": "entry-2" },
+                {
+                    "id": "entry-3",
+                    "changes": [
+                        // Missing `messaging_product`
+                        { "field": "messages", "value": { "messages": [] } },
+                        text_change("16505550003", "third"),
+                    ]
+                }
+            ]
+        })).expect("Top-level structure should parse");
+
+        let event_bus = InMemoryEventBus::new();
+        let publisher = WebhookEventPublisher::new(Arc::new(event_bus.clone()));
+        let outcome = process_payload(&publisher, payload, None).await;
+
+        assert_eq!(outcome, PayloadOutcome { handled: 2, rejected: 2 });
+
+        let bodies: Vec<String> = event_bus
+            .published_to(MessageReceived::TOPIC)
+            .iter()
+            .map(|event| match event.decode::<MessageReceived>().unwrap().data.content {
+                MessageContent::Text { body } => body,
+                other => panic!("Expected text content, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(bodies, vec!["first", "third"]);
+    }
+
+    #[tokio::test]
+    async fn test_fully_malformed_payload_rejects_everything() {
+        let payload: LenientWebhookPayload = serde_json::from_value(serde_json::json!({
+            "object": "whatsapp_business_account",
+            "entry": [{ "id": 42 }, "not an entry"]
+        })).expect("Top-level structure should parse");
+
+        let event_bus = InMemoryEventBus::new();
+        let publisher = WebhookEventPublisher::new(Arc::new(event_bus.clone()));
+        let outcome = process_payload(&publisher, payload, None).await;
+
+        assert_eq!(outcome, PayloadOutcome { handled: 0, rejected: 2 });
+        assert!(event_bus.published_events().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_redelivered_message_id_published_once() {
+        let payload = |changes: Vec<serde_json::Value>| -> LenientWebhookPayload {
+            serde_json::from_value(serde_json::json!({
+                "object": "whatsapp_business_account",
+                "entry": [{ "id": "entry-1", "changes": changes }]
+            })).expect("Payload should parse")
+        };
+
+        let event_bus = InMemoryEventBus::new();
+        let publisher = WebhookEventPublisher::new(Arc::new(event_bus.clone()));
+        let dedup = MessageDedupCache::new(100, std::time::Duration::from_secs(60));
+
+        let first = process_payload(&publisher, payload(vec![text_change("16505550001", "hello")]), Some(&dedup)).await;
+        // WhatsApp retries the same delivery alongside a new message
+        let retry = process_payload(
+            &publisher,
+            payload(vec![text_change("16505550001", "hello"), text_change("16505550002", "new")]),
+            Some(&dedup),
+        ).await;
+
+        assert_eq!(first, PayloadOutcome { handled: 1, rejected: 0 });
+        assert_eq!(retry, PayloadOutcome { handled: 2, rejected: 0 });
+
+        let ids: Vec<String> = event_bus
+            .published_to(MessageReceived::TOPIC)
+            .iter()
+            .map(|event| event.decode::<MessageReceived>().unwrap().data.message_id)
+            .collect();
+        assert_eq!(ids, vec!["wamid.16505550001", "wamid.16505550002"]);
+    }
 }
@@ -1,13 +1,16 @@
 pub mod config;
+pub mod dedup;
 pub mod state;
 pub mod types;
 pub mod routes;
 pub mod handlers;
 pub mod event_publisher;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod fixtures;
 
-pub use routes::create_route;
+pub use routes::{create_route, webhook_router};
 
-use common::{KafkaEventBus, KafkaConfig, EventBus};
+use common::{KafkaEventBus, KafkaConfig, EventBus, NoopMetrics};
 use std::sync::Arc;
 
 /// Run the webhook server with enhanced event bus integration
@@ -30,7 +33,7 @@ pub async fn run_server() -> Result<(), Box<dyn std::error::Error>> {
     let kafka_config = KafkaConfig::from_env()
         .map_err(|e| format!("Failed to load Kafka configuration: {}", e))?;
     
-    let event_bus = KafkaEventBus::new(kafka_config).await
+    let event_bus = KafkaEventBus::new(kafka_config, Arc::new(NoopMetrics)).await
         .map_err(|e| format!("Failed to initialize Kafka event bus: {}", e))?;
     
     // Wrap in the trait object that our application state expects
@@ -45,7 +48,7 @@ pub async fn run_server() -> Result<(), Box<dyn std::error::Error>> {
     // Create application state with the enhanced event bus
     let state = state::AppState::new(config.clone(), event_bus);
     
-    // Create and configure the HTTP router with middleware
+    // Create the webhook routes and wrap them in tracing/CORS middleware
     let app = routes::create_route(state);
     
     // Start the HTTP server
@@ -14,13 +14,120 @@ use crate::{
     state::AppState,
 };
 
+/// Build the standalone webhook app served by `run_server`
+///
+/// This is `webhook_router` plus request tracing and CORS.
 pub fn create_route(state: AppState) -> Router {
-    Router::new()
-        .route("/webhook", get(handlers::webhook::verify_webhook).post(handlers::webhook::handle_webhook))
-        .with_state(state)
+    webhook_router(state)
         .layer(
             ServiceBuilder::new()
                 .layer(TraceLayer::new_for_http())
                 .layer(CorsLayer::new().allow_origin(tower_http::cors::Any).allow_methods([Method::GET]))
         )
 }
+
+/// Build just the webhook routes, for mounting inside a larger axum app
+///
+/// The webhook's state is applied here, so the returned router fits into
+/// an app with any state type, e.g.
+/// `Router::new().nest("/integrations/whatsapp", webhook_router(state))`
+/// serves the webhook at `/integrations/whatsapp/webhook`. No middleware
+/// is added; the host app brings its own.
+pub fn webhook_router<S>(state: AppState) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/webhook", get(handlers::webhook::verify_webhook).post(handlers::webhook::handle_webhook))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use axum::body::{to_bytes, Body};
+    use common::{KafkaConfig, KafkaEventBus, NoopMetrics};
+    use http::{Request, StatusCode};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn test_state() -> AppState {
+        let config = AppConfig {
+            verify_token: "secret-token".to_string(),
+            access_token: "access".to_string(),
+            api_version: "v23.0".to_string(),
+            phone_number_id: "106540352242922".to_string(),
+            max_file_size_mb: 25,
+            host: "127.0.0.1".to_string(),
+            port: 8000,
+            dedup_cache_size: 0,
+            dedup_ttl_secs: 0,
+            redact_pii: false,
+        };
+        let kafka_config = KafkaConfig {
+            bootstrap_servers: "127.0.0.1:1".to_string(),
+            timeout_ms: 3000,
+            consumer_group_id: "router-test".to_string(),
+            topic_partitions: 1,
+            redact_pii: false,
+            ..KafkaConfig::default()
+        };
+        let event_bus = KafkaEventBus::new(kafka_config, Arc::new(NoopMetrics))
+            .await
+            .expect("Should create event bus");
+        AppState::new(config, Arc::new(event_bus))
+    }
+
+    #[tokio::test]
+    async fn test_webhook_router_nested_under_prefix() {
+        /// State of the host app, unrelated to the webhook's `AppState`
+        #[derive(Clone)]
+        struct HostState;
+
+        let app: Router<HostState> = Router::new()
+            .route("/health", get(|| async { "ok" }))
+            .nest("/integrations/whatsapp", webhook_router(test_state().await));
+        let app = app.with_state(HostState);
+
+        let response = app.clone()
+            .oneshot(
+                Request::get("/integrations/whatsapp/webhook?hub.mode=subscribe&hub.verify_token=secret-token&hub.challenge=1158201444")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), 1024).await.unwrap();
+        assert_eq!(&body[..], b"1158201444");
+
+        let response = app.clone()
+            .oneshot(
+                Request::get("/integrations/whatsapp/webhook?hub.mode=subscribe&hub.verify_token=wrong&hub.challenge=1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app.clone()
+            .oneshot(
+                Request::post("/integrations/whatsapp/webhook")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"object":"whatsapp_business_account","entry":[]}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // The routes only exist under the prefix
+        let response = app
+            .oneshot(Request::get("/webhook").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
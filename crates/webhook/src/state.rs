@@ -1,4 +1,4 @@
-use crate::config::AppConfig;
+use crate::{config::AppConfig, dedup::MessageDedupCache};
 use common::KafkaEventBus;
 
 use reqwest::Client;
@@ -17,6 +17,8 @@ pub struct AppState {
     pub http_client: Client,
     /// Kafka event bus for publishing domain events to the cluster.
     pub event_bus: Arc<KafkaEventBus>,
+    /// Recently seen message IDs, so redelivered webhooks aren't republished.
+    pub dedup: Option<Arc<MessageDedupCache>>,
 }
 
 impl AppState {
@@ -38,10 +40,13 @@ impl AppState {
             .build()
             .expect("Failed to create HTTP client");
         
+        let dedup = config.dedup_cache().map(Arc::new);
+
         Self {
             config,
             http_client,
             event_bus,
+            dedup,
         }
     }
     
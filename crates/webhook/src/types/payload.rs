@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::collections::HashMap;
 use common::{
     WebhookMessageType,
     TextMessage,
@@ -23,6 +24,23 @@ pub struct Entry {
     pub changes: Vec<Change>,
 }
 
+/// Top-level webhook payload with entries kept as raw JSON
+///
+/// The handler parses each entry (and each of its changes) on its own, so
+/// one malformed entry doesn't cause the valid ones to be dropped.
+#[derive(Deserialize, Debug)]
+pub struct LenientWebhookPayload {
+    pub object: String,
+    pub entry: Vec<serde_json::Value>,
+}
+
+/// Entry with changes kept as raw JSON, see [`LenientWebhookPayload`]
+#[derive(Deserialize, Debug)]
+pub struct LenientEntry {
+    pub id: String,
+    pub changes: Vec<serde_json::Value>,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct Change {
     pub value: Value,
@@ -33,8 +51,25 @@ pub struct Change {
 pub struct Value {
     pub contacts: Option<Vec<Contact>>,
     pub messages: Option<Vec<Message>>,
+    pub statuses: Option<Vec<Status>>,
+    /// Account-level errors (e.g. a paused template), as opposed to errors
+    /// about a specific incoming message
+    pub errors: Option<Vec<MessageError>>,
     pub messaging_product: String,
     pub metadata: Option<Metadata>,
+    /// Fields WhatsApp sent that we don't model yet, kept so they can be
+    /// logged instead of silently dropped
+    #[serde(flatten)]
+    pub unknown_fields: HashMap<String, serde_json::Value>,
+}
+
+/// Delivery status update for a message we sent
+#[derive(Deserialize, Debug)]
+pub struct Status {
+    pub id: String,
+    pub status: String,
+    pub timestamp: String,
+    pub recipient_id: String,
 }
 
 #[derive(Deserialize, Debug)]
@@ -60,13 +95,20 @@ pub struct Message {
     pub text: Option<TextMessage>,
     pub reaction: Option<ReactionMessage>,
     pub image: Option<MediaMessage>,
+    pub audio: Option<MediaMessage>,
+    pub video: Option<MediaMessage>,
+    pub document: Option<MediaMessage>,
     pub sticker: Option<MediaMessage>,
     pub location: Option<LocationMessage>,
     pub contact: Option<Vec<ContactMessage>>,
     pub interactive: Option<InteractiveMessage>,
     pub referral: Option<ReferralMessage>,
+    #[serde(alias = "errors")]
     pub error: Option<Vec<MessageError>>,
     pub context: Option<MessageContext>,
+    /// Fields WhatsApp sent that we don't model yet (e.g. new message types)
+    #[serde(flatten)]
+    pub unknown_fields: HashMap<String, serde_json::Value>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -78,7 +120,8 @@ pub struct Metadata {
 // Message Context (used in incoming messages)
 #[derive(Deserialize, Debug)]
 pub struct MessageContext {
-    pub message_id: String,
+    /// Not sent on reply or interactive contexts, which only carry `from` and `id`
+    pub message_id: Option<String>,
     pub from: Option<String>,
     pub id: Option<String>,
 }
@@ -92,6 +135,9 @@ impl Message {
             "text" => self.text.as_ref().map(|t| WebhookMessageType::Text(t.clone())),
             "reaction" => self.reaction.as_ref().map(|r| WebhookMessageType::Reaction(r.clone())),
             "image" => self.image.as_ref().map(|i| WebhookMessageType::Image(i.clone())),
+            "audio" => self.audio.as_ref().map(|a| WebhookMessageType::Audio(a.clone())),
+            "video" => self.video.as_ref().map(|v| WebhookMessageType::Video(v.clone())),
+            "document" => self.document.as_ref().map(|d| WebhookMessageType::Document(d.clone())),
             "sticker" => self.sticker.as_ref().map(|s| WebhookMessageType::Sticker(s.clone())),
             "location" => self.location.as_ref().map(|l| WebhookMessageType::Location(l.clone())),
             "contact" => self.contact.clone().map(WebhookMessageType::Contact),
@@ -105,28 +151,14 @@ impl Message {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixtures;
     use common::{
         ContactPhone,
         ButtonReply,
     };
 
     fn create_base_message() -> Message {
-        Message {
-            id: "12345".to_string(),
-            from: "1234567890".to_string(),
-            timestamp: "2023-10-01T12:00:00Z".to_string(),
-            message_type: "text".to_string(),
-            text: None,
-            reaction: None,
-            image: None,
-            sticker: None,
-            location: None,
-            contact: None,
-            interactive: None,
-            referral: None,
-            error: None,
-            context: None,
-        }
+        fixtures::message("12345", "1234567890", "text")
     }
 
     #[test]
@@ -226,6 +258,93 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_webhook_get_message_type_audio() {
+        let message: Message = serde_json::from_str(r#"{
+            "id": "wamid.audio",
+            "from": "1234567890",
+            "timestamp": "1696161600",
+            "type": "audio",
+            "audio": {
+                "id": "audio123",
+                "mime_type": "audio/ogg; codecs=opus",
+                "sha256": "aud456",
+                "voice": true
+            }
+        }"#).expect("Should deserialize audio message");
+
+        match message.get_message_type() {
+            Some(WebhookMessageType::Audio(media)) => {
+                assert_eq!(media.id, Some("audio123".to_string()));
+                assert_eq!(media.mime_type, "audio/ogg; codecs=opus");
+                assert_eq!(media.sha256, "aud456");
+                assert_eq!(media.caption, None);
+            }
+            other => panic!("Expected Audio message type, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_webhook_get_message_type_video() {
+        let message: Message = serde_json::from_str(r#"{
+            "id": "wamid.video",
+            "from": "1234567890",
+            "timestamp": "1696161600",
+            "type": "video",
+            "video": {
+                "id": "video123",
+                "mime_type": "video/mp4",
+                "sha256": "vid456",
+                "caption": "Test video"
+            }
+        }"#).expect("Should deserialize video message");
+
+        match message.get_message_type() {
+            Some(WebhookMessageType::Video(media)) => {
+                assert_eq!(media.id, Some("video123".to_string()));
+                assert_eq!(media.mime_type, "video/mp4");
+                assert_eq!(media.caption, Some("Test video".to_string()));
+            }
+            other => panic!("Expected Video message type, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_webhook_get_message_type_document() {
+        let message: Message = serde_json::from_str(r#"{
+            "id": "wamid.document",
+            "from": "1234567890",
+            "timestamp": "1696161600",
+            "type": "document",
+            "document": {
+                "id": "doc123",
+                "mime_type": "application/pdf",
+                "sha256": "doc456",
+                "filename": "invoice.pdf",
+                "caption": "Invoice"
+            }
+        }"#).expect("Should deserialize document message");
+
+        match message.get_message_type() {
+            Some(WebhookMessageType::Document(media)) => {
+                assert_eq!(media.id, Some("doc123".to_string()));
+                assert_eq!(media.mime_type, "application/pdf");
+                assert_eq!(media.caption, Some("Invoice".to_string()));
+            }
+            other => panic!("Expected Document message type, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_webhook_get_message_type_media_missing_data() {
+        for media_type in ["audio", "video", "document"] {
+            let mut message = create_base_message();
+            message.message_type = media_type.to_string();
+
+            assert!(message.get_message_type().is_none(), "{} without payload", media_type);
+        }
+    }
+
     #[test]
     fn test_webhook_get_message_type_location() {
         let mut message = create_base_message();
@@ -235,6 +354,7 @@ mod tests {
             longitude: -122.4194,
             name: Some("San Francisco".to_string()),
             address: Some("San Francisco, CA".to_string()),
+            ..Default::default()
         });
 
         let result = message.get_message_type();
@@ -250,6 +370,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_webhook_get_message_type_live_location() {
+        let message: Message = serde_json::from_str(r#"{
+            "id": "wamid.live",
+            "from": "1234567890",
+            "timestamp": "1696161600",
+            "type": "location",
+            "location": {
+                "latitude": 4.7110,
+                "longitude": -74.0721,
+                "caption": "On my way",
+                "accuracy": 12.5,
+                "speed": 8.3,
+                "degrees": 270,
+                "sequence_number": 4,
+                "time_offset": 120
+            }
+        }"#).expect("Should deserialize live location message");
+
+        match message.get_message_type() {
+            Some(WebhookMessageType::Location(location)) => {
+                assert!(location.is_live());
+                assert_eq!(location.latitude, 4.7110);
+                assert_eq!(location.caption.as_deref(), Some("On my way"));
+                assert_eq!(location.speed, Some(8.3));
+                assert_eq!(location.degrees, Some(270.0));
+                assert_eq!(location.sequence_number, Some(4));
+                assert_eq!(location.time_offset, Some(120));
+                assert_eq!(location.name, None);
+            }
+            other => panic!("Expected Location message type, got {:?}", other),
+        }
+
+        // A static pin has none of the live fields
+        let message: Message = serde_json::from_str(r#"{
+            "id": "wamid.pin",
+            "from": "1234567890",
+            "timestamp": "1696161600",
+            "type": "location",
+            "location": {
+                "latitude": 37.7749,
+                "longitude": -122.4194,
+                "name": "San Francisco",
+                "url": "https://maps.example.com/sf"
+            }
+        }"#).expect("Should deserialize location message");
+        assert!(matches!(
+            message.get_message_type(),
+            Some(WebhookMessageType::Location(location)) if !location.is_live()
+        ));
+    }
+
     #[test]
     fn test_webhook_get_message_type_contact() {
         let mut message = create_base_message();
@@ -299,6 +471,7 @@ mod tests {
                 title: "Yes".to_string(),
             }),
             list_reply: None,
+            nfm_reply: None,
         });
 
         let result = message.get_message_type();
@@ -0,0 +1,160 @@
+//! Contract tests for the WhatsApp webhook payload types.
+//!
+//! Each fixture under `tests/fixtures/` is an anonymized payload as
+//! WhatsApp sends it. If WhatsApp changes the shape, these should fail (or
+//! surface the new fields in `unknown_fields`) before production does.
+
+use common::WebhookMessageType;
+use webhook::types::{Message, Value, WebhookPayload};
+
+fn parse(fixture: &str) -> WebhookPayload {
+    serde_json::from_str(fixture).expect("Fixture should deserialize into WebhookPayload")
+}
+
+/// The value of the only change in the only entry
+fn single_value(payload: &WebhookPayload) -> &Value {
+    assert_eq!(payload.object, "whatsapp_business_account");
+    assert_eq!(payload.entry.len(), 1);
+    assert_eq!(payload.entry[0].changes.len(), 1);
+
+    let change = &payload.entry[0].changes[0];
+    assert_eq!(change.field, "messages");
+    assert_eq!(change.value.messaging_product, "whatsapp");
+    assert_eq!(
+        change.value.metadata.as_ref().map(|m| m.phone_number_id.as_str()),
+        Some("106540352242922")
+    );
+    &change.value
+}
+
+/// The only message in a value, with nothing left unmodelled
+fn single_message(value: &Value) -> &Message {
+    assert!(value.unknown_fields.is_empty(), "Unmodelled value fields: {:?}", value.unknown_fields);
+    let messages = value.messages.as_ref().expect("Should contain messages");
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].unknown_fields.is_empty(), "Unmodelled message fields: {:?}", messages[0].unknown_fields);
+    &messages[0]
+}
+
+#[test]
+fn test_text_message_fixture() {
+    let payload = parse(include_str!("fixtures/text.json"));
+    let value = single_value(&payload);
+    let contacts = value.contacts.as_ref().expect("Should contain contacts");
+    assert_eq!(contacts[0].wa_id, "16505551234");
+    assert_eq!(contacts[0].profile.name, "Sheena Nelson");
+
+    let message = single_message(value);
+    assert_eq!(message.from, "16505551234");
+    assert_eq!(message.timestamp, "1749416383");
+    match message.get_message_type() {
+        Some(WebhookMessageType::Text(text)) => assert_eq!(text.body, "Does it come in another color?"),
+        other => panic!("Expected a text message, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_media_message_fixture() {
+    let payload = parse(include_str!("fixtures/image.json"));
+    let message = single_message(single_value(&payload));
+
+    match message.get_message_type() {
+        Some(WebhookMessageType::Image(image)) => {
+            assert_eq!(image.id.as_deref(), Some("1003383421387256"));
+            assert_eq!(image.mime_type, "image/jpeg");
+            assert_eq!(image.caption.as_deref(), Some("Black, 4/4"));
+        }
+        other => panic!("Expected an image message, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_interactive_reply_fixture() {
+    let payload = parse(include_str!("fixtures/interactive.json"));
+    let message = single_message(single_value(&payload));
+
+    let context = message.context.as_ref().expect("Replies carry context");
+    assert_eq!(context.id.as_deref(), Some("wamid.HBgLMTY1MDM4Nzk0MzkVAgARGBI3NjRFMEIxNjM1Q0Y3NkYxMzgA"));
+    match message.get_message_type() {
+        Some(WebhookMessageType::Interactive(interactive)) => {
+            assert_eq!(interactive.interactive_type, "list_reply");
+            let reply = interactive.list_reply.expect("Should contain the list reply");
+            assert_eq!(reply.id, "priority_express");
+            assert_eq!(reply.description.as_deref(), Some("Next Day to 2 Days"));
+        }
+        other => panic!("Expected an interactive message, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_flow_reply_fixture() {
+    let payload = parse(include_str!("fixtures/flow_reply.json"));
+    let message = single_message(single_value(&payload));
+
+    match message.get_message_type() {
+        Some(WebhookMessageType::Interactive(interactive)) => {
+            assert_eq!(interactive.interactive_type, "nfm_reply");
+            let reply = interactive.nfm_reply.expect("Should contain the flow reply");
+            assert_eq!(reply.name, "flow");
+            assert_eq!(reply.body.as_deref(), Some("Sent"));
+
+            let response: serde_json::Value = serde_json::from_str(&reply.response_json)
+                .expect("response_json should hold a JSON object");
+            assert_eq!(response["delivery_date"], "2025-06-20");
+            assert_eq!(response["notes"], "Leave at the door");
+        }
+        other => panic!("Expected an interactive message, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_status_update_fixture() {
+    let payload = parse(include_str!("fixtures/status.json"));
+    let value = single_value(&payload);
+    assert!(value.unknown_fields.is_empty());
+    assert!(value.messages.is_none());
+
+    let statuses = value.statuses.as_ref().expect("Should contain statuses");
+    assert_eq!(statuses[0].status, "read");
+    assert_eq!(statuses[0].recipient_id, "16505551234");
+    assert_eq!(statuses[0].timestamp, "1750263773");
+}
+
+#[test]
+fn test_unsupported_message_error_fixture() {
+    let payload = parse(include_str!("fixtures/error.json"));
+    let message = single_message(single_value(&payload));
+
+    match message.get_message_type() {
+        Some(WebhookMessageType::Unknown(errors)) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].code, 131051);
+            assert_eq!(errors[0].description, "Message type unknown");
+        }
+        other => panic!("Expected an unknown message, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_account_level_error_fixture() {
+    let payload = parse(include_str!("fixtures/account_error.json"));
+    let value = single_value(&payload);
+    assert!(value.unknown_fields.is_empty());
+    assert!(value.messages.is_none());
+
+    let errors = value.errors.as_ref().expect("Should contain value-level errors");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code, 131042);
+    assert_eq!(errors[0].title, "Business eligibility payment issue");
+}
+
+#[test]
+fn test_unmodelled_fields_are_captured() {
+    let payload = parse(include_str!("fixtures/unknown_fields.json"));
+    let value = single_value(&payload);
+    let message = &value.messages.as_ref().expect("Should contain messages")[0];
+
+    // Order messages aren't modelled yet; the content is kept, not dropped
+    assert_eq!(message.message_type, "order");
+    assert_eq!(message.unknown_fields["order"]["catalog_id"], "194836987003835");
+}
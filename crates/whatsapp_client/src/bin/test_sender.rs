@@ -1,11 +1,11 @@
-use common::{KafkaEventBus, KafkaConfig, EventBus};
+use common::{KafkaEventBus, KafkaConfig, EventBus, NoopMetrics};
 use whatsapp_client::{
     client::{
         message_types::{WhatsAppMessageSend, WhatsAppMessage, ResponsePriority},
         builders::InteractiveMessageBuilder,
     },
 };
-use std::env;
+use std::{env, sync::Arc};
 use tracing::info;
 
 #[tokio::main]
@@ -31,7 +31,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let kafka_config = KafkaConfig::from_env()
         .map_err(|e| format!("Failed to load Kafka config: {}", e))?;
     
-    let event_bus = KafkaEventBus::new(kafka_config).await
+    let event_bus = KafkaEventBus::new(kafka_config, Arc::new(NoopMetrics)).await
         .map_err(|e| format!("Failed to create event bus: {}", e))?;
 
     // Create an interactive message using the builder
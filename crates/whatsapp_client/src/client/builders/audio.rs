@@ -1,6 +1,9 @@
 use crate::{
     errors::WhatsAppResult,
-    client::message_types::AudioMessage,
+    client::{
+        message_types::AudioMessage,
+        builders::utils::{resolve_media_source, MediaSource},
+    },
 };
 
 /// Builder for creating audio messages with fluent interface
@@ -105,21 +108,9 @@ impl AudioMessageBuilder {
             )
         })?;
         
-        // Determine which creation method to use
-        match (self.media_id, self.media_url) {
-            (Some(id), _) => {
-                // Media ID takes precedence (recommended approach)
-                AudioMessage::from_media_id(&to, &id)
-            },
-            (None, Some(url)) => {
-                // Fall back to URL
-                AudioMessage::from_url(&to, &url)
-            },
-            (None, None) => {
-                Err(crate::errors::WhatsAppError::InvalidMessageContent(
-                    "Either media_id or media_url must be provided".to_string()
-                ))
-            }
+        match resolve_media_source(self.media_id, self.media_url)? {
+            MediaSource::Id(id) => AudioMessage::from_media_id(&to, &id),
+            MediaSource::Url(url) => AudioMessage::from_url(&to, &url),
         }
     }
 }
@@ -127,6 +118,7 @@ impl AudioMessageBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::message_types::Message;
     
     #[test]
     fn test_audio_message_with_media_id() {
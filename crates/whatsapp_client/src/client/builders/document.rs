@@ -1,6 +1,10 @@
 use crate::{
     errors::WhatsAppResult,
-    client::message_types::DocumentMessage,
+    client::{
+        message_types::DocumentMessage,
+        validation::{validate_caption_allowed, MediaType},
+        builders::utils::{resolve_media_source, MediaSource},
+    },
 };
 
 /// Builder for creating document messages with fluent interface
@@ -185,24 +189,14 @@ impl DocumentMessageBuilder {
         })?;
         
         // Create the base message using the appropriate method
-        let mut message = match (self.media_id, self.media_url) {
-            (Some(id), _) => {
-                // Media ID takes precedence (recommended approach)
-                DocumentMessage::from_media_id(&to, &id)?
-            },
-            (None, Some(url)) => {
-                // Fall back to URL approach
-                DocumentMessage::from_url(&to, &url)?
-            },
-            (None, None) => {
-                return Err(crate::errors::WhatsAppError::InvalidMessageContent(
-                    "Either media_id or media_url must be provided for document messages".to_string()
-                ));
-            }
+        let mut message = match resolve_media_source(self.media_id, self.media_url)? {
+            MediaSource::Id(id) => DocumentMessage::from_media_id(&to, &id)?,
+            MediaSource::Url(url) => DocumentMessage::from_url(&to, &url)?,
         };
         
         // Add caption if provided (this can fail validation)
         if let Some(caption_text) = self.caption {
+            validate_caption_allowed(MediaType::Document, &caption_text)?;
             message = message.with_caption(&caption_text)?;
         }
         
@@ -218,6 +212,7 @@ impl DocumentMessageBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::message_types::Message;
     
     #[test]
     fn test_document_message_minimal() {
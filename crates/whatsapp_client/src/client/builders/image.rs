@@ -1,6 +1,10 @@
 use crate::{
     errors::WhatsAppResult,
-    client::message_types::ImageMessage,
+    client::{
+        message_types::ImageMessage,
+        validation::{validate_caption_allowed, MediaType},
+        builders::utils::{resolve_media_source, MediaSource},
+    },
 };
 
 /// Builder for creating image messages with fluent interface
@@ -140,24 +144,14 @@ impl ImageMessageBuilder {
         })?;
         
         // Create the base message using the appropriate method
-        let mut message = match (self.media_id, self.media_url) {
-            (Some(id), _) => {
-                // Media ID takes precedence (recommended approach)
-                ImageMessage::from_media_id(&to, &id)?
-            },
-            (None, Some(url)) => {
-                // Fall back to URL approach
-                ImageMessage::from_url(&to, &url)?
-            },
-            (None, None) => {
-                return Err(crate::errors::WhatsAppError::InvalidMessageContent(
-                    "Either media_id or media_url must be provided".to_string()
-                ));
-            }
+        let mut message = match resolve_media_source(self.media_id, self.media_url)? {
+            MediaSource::Id(id) => ImageMessage::from_media_id(&to, &id)?,
+            MediaSource::Url(url) => ImageMessage::from_url(&to, &url)?,
         };
         
         // Add caption if provided
         if let Some(caption_text) = self.caption {
+            validate_caption_allowed(MediaType::Image, &caption_text)?;
             message = message.with_caption(&caption_text)?;
         }
         
@@ -168,6 +162,7 @@ impl ImageMessageBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::message_types::Message;
     
     #[test]
     fn test_image_message_with_media_id() {
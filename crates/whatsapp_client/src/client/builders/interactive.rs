@@ -1,6 +1,13 @@
 use crate::{
-    errors::WhatsAppResult,
-    client::message_types::InteractiveMessage,
+    errors::{ValidationCode, WhatsAppError, WhatsAppResult},
+    client::{
+        message_types::{InteractiveMessage, FlowAction, FlowActionPayload, ProductSectionSpec},
+        validation::{
+            validate_phone_number, validate_text_message, validate_button,
+            validate_list_section, validate_https_url, validate_header_text, validate_footer_text,
+            validate_unique_ids, validate_unique_titles, text_length,
+        },
+    },
 };
 
 /// Builder for creating interactive messages with fluent interface
@@ -14,6 +21,8 @@ use crate::{
 /// - **Reply Buttons**: Up to 3 quick-reply buttons for simple choices
 /// - **List Menus**: Organized sections with multiple options for complex choices
 /// - **Call-to-Action**: URL buttons that open external links
+/// - **Flows**: Buttons that open a multi-screen WhatsApp Flow form
+/// - **Products**: A single catalog item or a sectioned product list
 /// - **Location Requests**: Buttons that request user's location
 /// 
 /// # Design Philosophy
@@ -58,7 +67,28 @@ pub struct InteractiveMessageBuilder {
     current_section: Option<ListSectionBuilder>,
     cta_url: Option<String>,
     cta_display_text: Option<String>,
+    flow: Option<FlowBuilder>,
+    product: Option<(String, String)>, // (catalog_id, product_retailer_id)
+    product_catalog_id: Option<String>,
+    product_sections: Vec<ProductSectionSpec>,
     location_request: bool,
+    /// First list row that didn't fit, as (section title, row ID)
+    rejected_row: Option<(String, String)>,
+    /// Headers, footers and buttons that failed their check when set, oldest first
+    input_errors: Vec<WhatsAppError>,
+}
+
+/// Maximum rows WhatsApp shows in a list message, across all sections
+const MAX_LIST_ROWS: usize = 10;
+
+/// Flow parameters captured by `InteractiveMessageBuilder::flow`
+#[derive(Debug, Clone)]
+struct FlowBuilder {
+    flow_id: String,
+    flow_token: String,
+    flow_cta: String,
+    flow_action: FlowAction,
+    flow_action_payload: Option<FlowActionPayload>,
 }
 
 /// Builder for individual list sections within interactive messages
@@ -118,7 +148,11 @@ impl InteractiveMessageBuilder {
     /// - Brand identification: "Customer Service"
     /// - Message type: "Order Confirmation"
     /// - Urgency indicators: "Action Required"
+    /// 
+    /// The length is checked immediately; see [`input_error`](Self::input_error).
     pub fn header(mut self, text: &str) -> Self {
+        self.forget_input_error("header");
+        self.record_input(validate_header_text(text));
         self.header = Some(text.to_string());
         self
     }
@@ -136,7 +170,11 @@ impl InteractiveMessageBuilder {
     /// - Help text: "Reply HELP for assistance"
     /// - Disclaimers: "Standard rates may apply"
     /// - Timing info: "Expires in 24 hours"
+    /// 
+    /// The length is checked immediately; see [`input_error`](Self::input_error).
     pub fn footer(mut self, text: &str) -> Self {
+        self.forget_input_error("footer");
+        self.record_input(validate_footer_text(text));
         self.footer = Some(text.to_string());
         self
     }
@@ -157,6 +195,9 @@ impl InteractiveMessageBuilder {
     /// - Consider the most likely user responses
     /// - Ensure button IDs are meaningful for your backend processing
     /// 
+    /// The ID and title are checked immediately; see
+    /// [`input_error`](Self::input_error). Buttons beyond the third are ignored.
+    /// 
     /// # Example
     /// ```
     /// # use whatsapp_client::client::builders::InteractiveMessageBuilder;
@@ -171,11 +212,49 @@ impl InteractiveMessageBuilder {
     /// ```
     pub fn add_button(mut self, id: &str, title: &str) -> Self {
         if self.buttons.len() < 3 { // WhatsApp limit
+            self.record_input(validate_button(id, title));
             self.buttons.push((id.to_string(), title.to_string()));
         }
         self
     }
     
+    /// Earliest header, footer or button still rejected from when it was set
+    /// 
+    /// `header()`, `footer()` and `add_button()` check their input as soon
+    /// as they're called, so a bad value can be reported next to the call
+    /// that introduced it instead of surfacing later from `build()`.
+    /// Replacing a rejected header or footer, or clearing the buttons,
+    /// forgets the matching error and the next remaining one is reported.
+    /// `build()` runs the same checks again, so ignoring this never lets an
+    /// invalid message through.
+    /// 
+    /// # Example
+    /// ```
+    /// # use whatsapp_client::client::builders::InteractiveMessageBuilder;
+    /// let builder = InteractiveMessageBuilder::new()
+    ///     .header("A header that goes on and on well past the sixty character limit");
+    /// 
+    /// let error = builder.input_error().expect("header is too long");
+    /// assert_eq!(error.field(), Some("header"));
+    /// ```
+    pub fn input_error(&self) -> Option<&WhatsAppError> {
+        self.input_errors.first()
+    }
+    
+    /// Remember a failed input check
+    fn record_input(&mut self, result: WhatsAppResult<()>) {
+        if let Err(error) = result {
+            self.input_errors.push(error);
+        }
+    }
+    
+    /// Drop the stored input errors that came from `field` ("button" also matches "button.title")
+    fn forget_input_error(&mut self, field: &str) {
+        self.input_errors.retain(|error| {
+            error.field().is_none_or(|stored| stored.split('.').next() != Some(field))
+        });
+    }
+    
     /// Set the button text for list-type interactive messages
     /// 
     /// This button appears below the message body and opens the
@@ -259,15 +338,13 @@ impl InteractiveMessageBuilder {
     /// 
     /// # Note
     /// You must call `add_list_section()` before adding rows.
-    pub fn add_list_row(mut self, id: &str, title: &str, description: &str) -> Self {
-        if let Some(ref mut section) = self.current_section {
-            section.rows.push((
-                id.to_string(),
-                title.to_string(),
-                Some(description.to_string()),
-            ));
-        }
-        self
+    /// 
+    /// A list holds at most 10 rows in total. Rows past that limit are
+    /// dropped and `validate()` reports the first one straight away, naming
+    /// its section, instead of only once the message is built. Use
+    /// `can_add_row()` to check before adding.
+    pub fn add_list_row(self, id: &str, title: &str, description: &str) -> Self {
+        self.push_list_row(id, title, Some(description.to_string()))
     }
     
     /// Add a row without description to the current list section
@@ -277,13 +354,52 @@ impl InteractiveMessageBuilder {
     /// # Arguments
     /// * `id` - Unique identifier for this option
     /// * `title` - Text displayed for this option
-    pub fn add_simple_list_row(mut self, id: &str, title: &str) -> Self {
+    pub fn add_simple_list_row(self, id: &str, title: &str) -> Self {
+        self.push_list_row(id, title, None)
+    }
+    
+    /// Whether another row fits in the list
+    /// 
+    /// False when no section has been started or the list already has the
+    /// maximum of 10 rows.
+    /// 
+    /// # Example
+    /// ```
+    /// # use whatsapp_client::client::builders::InteractiveMessageBuilder;
+    /// let mut builder = InteractiveMessageBuilder::new()
+    ///     .to("+1234567890")
+    ///     .body("Pick a size")
+    ///     .list_button("Sizes")
+    ///     .add_list_section("Sizes");
+    /// 
+    /// for size in ["xs", "s", "m", "l", "xl", "xxl", "3xl", "4xl", "5xl", "6xl", "7xl"] {
+    ///     if !builder.can_add_row() {
+    ///         break;
+    ///     }
+    ///     builder = builder.add_simple_list_row(size, size);
+    /// }
+    /// assert!(builder.validate().is_ok());
+    /// ```
+    pub fn can_add_row(&self) -> bool {
+        self.current_section.is_some() && self.sections().map(|s| s.rows.len()).sum::<usize>() < MAX_LIST_ROWS
+    }
+    
+    /// Add a row to the current section, or record it as rejected if the list is full
+    fn push_list_row(mut self, id: &str, title: &str, description: Option<String>) -> Self {
+        if self.current_section.is_none() {
+            return self;
+        }
+        
+        if !self.can_add_row() {
+            if self.rejected_row.is_none() {
+                let section_title = self.current_section.as_ref().map(|s| s.title.clone()).unwrap_or_default();
+                self.rejected_row = Some((section_title, id.to_string()));
+            }
+            return self;
+        }
+        
         if let Some(ref mut section) = self.current_section {
-            section.rows.push((
-                id.to_string(),
-                title.to_string(),
-                None,
-            ));
+            section.rows.push((id.to_string(), title.to_string(), description));
         }
         self
     }
@@ -315,6 +431,82 @@ impl InteractiveMessageBuilder {
         self
     }
     
+    /// Open a WhatsApp Flow from a CTA button
+    /// 
+    /// Flows are multi-screen forms hosted by WhatsApp, useful for
+    /// onboarding, bookings or any structured data collection that
+    /// needs more than a list or a few buttons.
+    /// 
+    /// # Arguments
+    /// * `flow_id` - ID of the published flow
+    /// * `flow_token` - Token identifying this flow session
+    /// * `flow_cta` - Button text (max 30 characters)
+    /// * `flow_action` - `Navigate` or `DataExchange`
+    /// * `flow_action_payload` - First screen, required for `Navigate`
+    pub fn flow(
+        mut self,
+        flow_id: &str,
+        flow_token: &str,
+        flow_cta: &str,
+        flow_action: FlowAction,
+        flow_action_payload: Option<FlowActionPayload>,
+    ) -> Self {
+        self.flow = Some(FlowBuilder {
+            flow_id: flow_id.to_string(),
+            flow_token: flow_token.to_string(),
+            flow_cta: flow_cta.to_string(),
+            flow_action,
+            flow_action_payload,
+        });
+        self
+    }
+    
+    /// Show a single product from a catalog
+    /// 
+    /// # Arguments
+    /// * `catalog_id` - ID of the catalog connected to the business account
+    /// * `product_retailer_id` - Retailer ID of the product in that catalog
+    pub fn product(mut self, catalog_id: &str, product_retailer_id: &str) -> Self {
+        self.product = Some((catalog_id.to_string(), product_retailer_id.to_string()));
+        self
+    }
+    
+    /// Show a list of products from a catalog
+    /// 
+    /// Products are added with `add_product_section()`. WhatsApp requires
+    /// a `header()` on product lists.
+    /// 
+    /// # Example
+    /// ```
+    /// # use whatsapp_client::client::builders::InteractiveMessageBuilder;
+    /// let message = InteractiveMessageBuilder::new()
+    ///     .to("+1234567890")
+    ///     .header("Summer collection")
+    ///     .body("Our picks for this week")
+    ///     .product_list("367025965434465")
+    ///     .add_product_section("Mugs", &["sku-blue-mug", "sku-red-mug"])
+    ///     .add_product_section("Plates", &["sku-white-plate"])
+    ///     .build()?;
+    /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
+    /// ```
+    pub fn product_list(mut self, catalog_id: &str) -> Self {
+        self.product_catalog_id = Some(catalog_id.to_string());
+        self
+    }
+    
+    /// Add a section of products to the product list
+    /// 
+    /// # Arguments
+    /// * `title` - Section title (max 24 characters)
+    /// * `product_retailer_ids` - Retailer IDs of the products in this section
+    pub fn add_product_section(mut self, title: &str, product_retailer_ids: &[&str]) -> Self {
+        self.product_sections.push((
+            title.to_string(),
+            product_retailer_ids.iter().map(|id| id.to_string()).collect(),
+        ));
+        self
+    }
+    
     /// Request the user's location
     /// 
     /// This creates a button that, when tapped, prompts the user
@@ -343,6 +535,7 @@ impl InteractiveMessageBuilder {
     /// clear buttons and rebuild them based on business rules.
     pub fn clear_buttons(mut self) -> Self {
         self.buttons.clear();
+        self.forget_input_error("button");
         self
     }
     
@@ -354,14 +547,97 @@ impl InteractiveMessageBuilder {
         self.list_button_text = None;
         self.list_sections.clear();
         self.current_section = None;
+        self.rejected_row = None;
         self
     }
     
+    /// Validate the current configuration without building the message
+    /// 
+    /// Runs every check that `build()` performs - recipient and body
+    /// presence, text lengths, interaction type selection and list
+    /// structure - but borrows the builder instead of consuming it.
+    /// This makes it suitable for live feedback while a message is
+    /// still being composed.
+    /// 
+    /// # Example
+    /// ```
+    /// # use whatsapp_client::client::builders::InteractiveMessageBuilder;
+    /// let builder = InteractiveMessageBuilder::new()
+    ///     .to("+1234567890")
+    ///     .body("Pick one");
+    /// 
+    /// // No buttons yet, so validation fails but the builder is still usable
+    /// assert!(builder.validate().is_err());
+    /// let message = builder.add_button("a", "Option A").build()?;
+    /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
+    /// ```
+    pub fn validate(&self) -> WhatsAppResult<()> {
+        let to = self.to.as_deref().ok_or_else(|| {
+            crate::errors::WhatsAppError::InvalidMessageContent(
+                "Recipient phone number is required for interactive messages".to_string()
+            )
+        })?;
+        
+        let body = self.body.as_deref().ok_or_else(|| {
+            crate::errors::WhatsAppError::InvalidMessageContent(
+                "Body text is required for interactive messages".to_string()
+            )
+        })?;
+        
+        validate_phone_number(to)?;
+        validate_text_message(body)?;
+        
+        if let Some(ref header_text) = self.header {
+            validate_header_text(header_text)?;
+        }
+        
+        if let Some(ref footer_text) = self.footer {
+            validate_footer_text(footer_text)?;
+        }
+        
+        // Same priority order as build()
+        if self.location_request {
+            self.validate_location_request(body)
+        } else if self.cta_url.is_some() {
+            self.validate_cta()
+        } else if let Some(ref flow) = self.flow {
+            InteractiveMessage::validate_flow(
+                &flow.flow_id,
+                &flow.flow_token,
+                &flow.flow_cta,
+                flow.flow_action,
+                flow.flow_action_payload.as_ref(),
+            )
+        } else if let Some((ref catalog_id, ref product_retailer_id)) = self.product {
+            InteractiveMessage::validate_product(catalog_id, product_retailer_id)
+        } else if let Some(ref catalog_id) = self.product_catalog_id {
+            if self.header.is_none() {
+                return Err(crate::errors::WhatsAppError::validation(
+                    ValidationCode::HeaderRequired,
+                    "header",
+                    "required for product list messages"
+                ));
+            }
+            InteractiveMessage::validate_product_list(catalog_id, &self.product_sections)
+        } else if self.sections().next().is_some() {
+            self.validate_list()
+        } else if !self.buttons.is_empty() {
+            self.validate_buttons()
+        } else {
+            Err(crate::errors::WhatsAppError::InvalidMessageContent(
+                "Interactive messages must have at least one interaction element: \
+                 buttons, list menu, CTA URL, flow, product, or location request".to_string()
+            ))
+        }
+    }
+    
     /// Build the interactive message
     /// 
     /// This validates the complex configuration and creates the final
     /// InteractiveMessage. Interactive messages have sophisticated
     /// validation rules due to their many possible configurations.
+    /// All checks are delegated to `validate()`, so a builder that
+    /// validates cleanly will build without validation errors.
     /// 
     /// # Validation Process
     /// 1. Recipient phone number must be valid
@@ -370,6 +646,8 @@ impl InteractiveMessageBuilder {
     ///    - Reply buttons (1-3 buttons)
     ///    - List menu (sections with rows)
     ///    - CTA URL button
+    ///    - Flow
+    ///    - Single product or product list
     ///    - Location request
     /// 4. Header/footer length limits (60 characters each)
     /// 5. Button text limits (20 characters for buttons, 24 for list titles)
@@ -380,8 +658,11 @@ impl InteractiveMessageBuilder {
     /// which methods were called, prioritizing in this order:
     /// 1. Location request (if enabled)
     /// 2. CTA URL (if configured)
-    /// 3. List menu (if sections exist)
-    /// 4. Reply buttons (if buttons exist)
+    /// 3. Flow (if configured)
+    /// 4. Single product (if configured)
+    /// 5. Product list (if configured; requires a header)
+    /// 6. List menu (if sections exist)
+    /// 7. Reply buttons (if buttons exist)
     /// 
     /// # Error Scenarios
     /// - No interaction type configured
@@ -390,17 +671,11 @@ impl InteractiveMessageBuilder {
     /// - Invalid list structure (sections without rows)
     /// - Button limits exceeded (>3 buttons)
     pub fn build(mut self) -> WhatsAppResult<InteractiveMessage> {
-        let to = self.to.clone().ok_or_else(|| {
-            crate::errors::WhatsAppError::InvalidMessageContent(
-                "Recipient phone number is required for interactive messages".to_string()
-            )
-        })?;
+        self.validate()?;
         
-        let body = self.body.clone().ok_or_else(|| {
-            crate::errors::WhatsAppError::InvalidMessageContent(
-                "Body text is required for interactive messages".to_string()
-            )
-        })?;
+        // validate() guarantees both are present
+        let to = self.to.clone().unwrap_or_default();
+        let body = self.body.clone().unwrap_or_default();
         
         // Finish any pending list section
         if let Some(section) = self.current_section.take() {
@@ -410,53 +685,100 @@ impl InteractiveMessageBuilder {
         // Determine interaction type and create message
         if self.location_request {
             // Location request takes highest priority
-            self.build_location_request_message(&to, &body)
-        } else if self.cta_url.is_some() {
+            let message = InteractiveMessage::request_location(&to, &body)?;
+            self.apply_optional_elements(message)
+        } else if let (Some(display_text), Some(url)) = (&self.cta_display_text, &self.cta_url) {
             // CTA URL button
-            self.build_cta_message(&to, &body)
+            let message = InteractiveMessage::with_cta_url(&to, &body, display_text, url)?;
+            self.apply_optional_elements(message)
+        } else if let Some(flow) = self.flow.clone() {
+            // Flow
+            let message = InteractiveMessage::with_flow(
+                &to,
+                &body,
+                &flow.flow_id,
+                &flow.flow_token,
+                &flow.flow_cta,
+                flow.flow_action,
+                flow.flow_action_payload,
+            )?;
+            self.apply_optional_elements(message)
+        } else if let Some((catalog_id, product_retailer_id)) = &self.product {
+            // Single product
+            let message = InteractiveMessage::with_product(&to, &body, catalog_id, product_retailer_id)?;
+            self.apply_optional_elements(message)
+        } else if let Some(catalog_id) = &self.product_catalog_id {
+            // Product list; validate() guarantees the header
+            let header = self.header.clone().unwrap_or_default();
+            let message = InteractiveMessage::with_product_list(
+                &to,
+                &header,
+                &body,
+                catalog_id,
+                self.product_sections.clone(),
+            )?;
+            self.apply_optional_elements(message)
         } else if !self.list_sections.is_empty() {
             // List menu
             self.build_list_message(&to, &body)
-        } else if !self.buttons.is_empty() {
-            // Reply buttons
-            self.build_button_message(&to, &body)
         } else {
-            Err(crate::errors::WhatsAppError::InvalidMessageContent(
-                "Interactive messages must have at least one interaction element: \
-                 buttons, list menu, CTA URL, or location request".to_string()
-            ))
+            // Reply buttons
+            let message = InteractiveMessage::with_buttons(&to, &body, self.buttons.clone())?;
+            self.apply_optional_elements(message)
         }
     }
     
-    // Helper methods for building specific interaction types
+    // Helper methods for validating specific interaction types
+    
+    /// All list sections, including the one still being populated
+    fn sections(&self) -> impl Iterator<Item = &ListSectionBuilder> {
+        self.list_sections.iter().chain(self.current_section.iter())
+    }
     
-    fn build_button_message(&self, to: &str, body: &str) -> WhatsAppResult<InteractiveMessage> {
+    fn validate_buttons(&self) -> WhatsAppResult<()> {
         if self.buttons.len() > 3 {
             return Err(crate::errors::WhatsAppError::InvalidMessageContent(
                 "Maximum 3 reply buttons allowed".to_string()
             ));
         }
         
-        let message = InteractiveMessage::with_buttons(to, body, self.buttons.clone())?;
-        self.apply_optional_elements(message)
+        for (id, title) in &self.buttons {
+            validate_button(id, title)?;
+        }
+        validate_unique_ids("button", self.buttons.iter().map(|(id, _)| id.as_str()))?;
+        validate_unique_titles("button", self.buttons.iter().map(|(_, title)| title.as_str()))?;
+        
+        Ok(())
     }
     
-    fn build_list_message(&self, to: &str, body: &str) -> WhatsAppResult<InteractiveMessage> {
+    fn validate_list(&self) -> WhatsAppResult<()> {
         let button_text = self.list_button_text.as_ref().ok_or_else(|| {
             crate::errors::WhatsAppError::InvalidMessageContent(
                 "List button text is required when using list sections. Use list_button() method.".to_string()
             )
         })?;
+        validate_button("list_button", button_text)?;
+        
+        if let Some((ref section_title, ref row_id)) = self.rejected_row {
+            return Err(crate::errors::WhatsAppError::validation(
+                ValidationCode::TooManyRows,
+                "section.rows",
+                format!(
+                    "row '{}' in section '{}' exceeds the limit of {} rows per list",
+                    row_id, section_title, MAX_LIST_ROWS
+                ),
+            ));
+        }
         
         // Validate list structure comprehensively
-        if self.list_sections.is_empty() {
+        if self.sections().next().is_none() {
             return Err(crate::errors::WhatsAppError::InvalidMessageContent(
                 "At least one list section is required. Use add_list_section() method.".to_string()
             ));
         }
         
         // Ensure every section has rows and validate content
-        for section in &self.list_sections {
+        for section in self.sections() {
             if section.rows.is_empty() {
                 return Err(crate::errors::WhatsAppError::InvalidMessageContent(
                     format!("List section '{}' must have at least one row. Use add_list_row() method.", section.title)
@@ -472,44 +794,39 @@ impl InteractiveMessageBuilder {
         }
         
         // Total rows across all sections should not exceed WhatsApp limits
-        let total_rows: usize = self.list_sections.iter().map(|s| s.rows.len()).sum();
+        let total_rows: usize = self.sections().map(|s| s.rows.len()).sum();
         if total_rows > 10 {
             return Err(crate::errors::WhatsAppError::InvalidMessageContent(
                 format!("Total list rows ({}) exceeds WhatsApp limit of 10 rows across all sections", total_rows)
             ));
         }
         
-        // Convert our internal builder structure to the format expected by InteractiveMessage
-        // This transformation is crucial - we're converting from builder-friendly format
-        // to the wire-format that WhatsApp expects
-        let sections: Vec<_> = self.list_sections.iter().map(|section| {
-            let rows: Vec<_> = section.rows.iter().map(|(id, title, description)| {
-                (id.clone(), title.clone(), description.clone())
-            }).collect();
-            (section.title.clone(), rows)
-        }).collect();
+        for section in self.sections() {
+            validate_list_section(&section.title, &section.rows)?;
+        }
+        validate_unique_ids(
+            "row",
+            self.sections().flat_map(|section| section.rows.iter().map(|(id, _, _)| id.as_str())),
+        )?;
+        validate_unique_titles(
+            "row",
+            self.sections().flat_map(|section| section.rows.iter().map(|(_, title, _)| title.as_str())),
+        )?;
         
-        // Create the message using our assumed InteractiveMessage API
-        // In a real implementation, this would call your actual InteractiveMessage::with_list method
-        let message = InteractiveMessage::with_list(to, body, button_text, sections)?;
-        self.apply_optional_elements(message)
+        Ok(())
     }
     
-    fn build_cta_message(&self, to: &str, body: &str) -> WhatsAppResult<InteractiveMessage> {
-        let display_text = self.cta_display_text.as_ref().unwrap();
-        let url = self.cta_url.as_ref().unwrap();
+    fn validate_cta(&self) -> WhatsAppResult<()> {
+        let display_text = self.cta_display_text.as_deref().unwrap_or_default();
+        let url = self.cta_url.as_deref().unwrap_or_default();
         
         // Validate URL format - WhatsApp requires HTTPS for security
-        if !url.starts_with("https://") {
-            return Err(crate::errors::WhatsAppError::InvalidMessageContent(
-                "CTA URL must use HTTPS protocol for security. HTTP URLs are not allowed.".to_string()
-            ));
-        }
+        validate_https_url(url)?;
         
         // Validate display text length (WhatsApp has specific limits for CTA buttons)
-        if display_text.len() > 20 {
+        if text_length(display_text) > 20 {
             return Err(crate::errors::WhatsAppError::InvalidMessageContent(
-                format!("CTA button text too long: {} characters (max 20)", display_text.len())
+                format!("CTA button text too long: {} characters (max 20)", text_length(display_text))
             ));
         }
         
@@ -519,11 +836,10 @@ impl InteractiveMessageBuilder {
             ));
         }
         
-        let message = InteractiveMessage::with_cta_url(to, body, display_text, url)?;
-        self.apply_optional_elements(message)
+        Ok(())
     }
     
-    fn build_location_request_message(&self, to: &str, body: &str) -> WhatsAppResult<InteractiveMessage> {
+    fn validate_location_request(&self, body: &str) -> WhatsAppResult<()> {
         // Location requests are simpler but should still validate the context
         // The body text should clearly explain why location is needed for privacy/UX
         if body.len() < 10 {
@@ -532,7 +848,22 @@ impl InteractiveMessageBuilder {
             ));
         }
         
-        let message = InteractiveMessage::request_location(to, body)?;
+        Ok(())
+    }
+    
+    // Helper methods for building specific interaction types
+    
+    fn build_list_message(&self, to: &str, body: &str) -> WhatsAppResult<InteractiveMessage> {
+        let button_text = self.list_button_text.as_deref().unwrap_or_default();
+        
+        // Convert our internal builder structure to the format expected by InteractiveMessage
+        // This transformation is crucial - we're converting from builder-friendly format
+        // to the wire-format that WhatsApp expects
+        let sections: Vec<_> = self.list_sections.iter().map(|section| {
+            (section.title.clone(), section.rows.clone())
+        }).collect();
+        
+        let message = InteractiveMessage::with_list(to, body, button_text, sections)?;
         self.apply_optional_elements(message)
     }
     
@@ -634,6 +965,59 @@ mod tests {
         assert_eq!(builder.buttons[2].0, "btn3"); // Last button should be btn3, not btn4
     }
     
+    #[test]
+    fn test_duplicate_option_ids_rejected() {
+        let error = InteractiveMessageBuilder::new()
+            .to("+1234567890")
+            .body("Continue?")
+            .add_button("confirm", "Yes")
+            .add_button("confirm", "Sure")
+            .build()
+            .unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::DuplicateId));
+        assert_eq!(error.field(), Some("button.id"));
+        
+        let error = InteractiveMessageBuilder::new()
+            .to("+1234567890")
+            .body("Order:")
+            .list_button("Menu")
+            .add_list_section("Drinks")
+                .add_simple_list_row("small", "Small coffee")
+            .add_list_section("Snacks")
+                .add_simple_list_row("small", "Small cookie")
+            .build()
+            .unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::DuplicateId));
+        assert_eq!(error.field(), Some("row.id"));
+    }
+    
+    #[test]
+    fn test_duplicate_option_titles_rejected() {
+        let error = InteractiveMessageBuilder::new()
+            .to("+1234567890")
+            .body("Continue?")
+            .add_button("confirm", "Yes")
+            .add_button("confirm_all", "Yes")
+            .build()
+            .unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::DuplicateTitle));
+        assert_eq!(error.field(), Some("button.title"));
+        
+        // Row titles must differ across sections too
+        let error = InteractiveMessageBuilder::new()
+            .to("+1234567890")
+            .body("Order:")
+            .list_button("Menu")
+            .add_list_section("Drinks")
+                .add_simple_list_row("small_coffee", "Small")
+            .add_list_section("Snacks")
+                .add_simple_list_row("small_cookie", "Small")
+            .build()
+            .unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::DuplicateTitle));
+        assert_eq!(error.field(), Some("row.title"));
+    }
+    
     #[test]
     fn test_interaction_type_priority() {
         // Test that interaction types are prioritized correctly when multiple are set
@@ -691,6 +1075,63 @@ mod tests {
         assert!(error_msg.contains("List button text is required"));
     }
     
+    #[test]
+    fn test_list_row_limit_is_reported_early() {
+        let mut builder = InteractiveMessageBuilder::new()
+            .to("+1234567890")
+            .body("Choose a product")
+            .list_button("Catalog")
+            .add_list_section("Shirts");
+        for i in 0..6 {
+            builder = builder.add_simple_list_row(&format!("shirt_{}", i), &format!("Shirt {}", i));
+        }
+        builder = builder.add_list_section("Shoes");
+        for i in 0..4 {
+            builder = builder.add_list_row(&format!("shoe_{}", i), &format!("Shoe {}", i), "Leather");
+        }
+        assert!(builder.validate().is_ok());
+        assert!(!builder.can_add_row());
+        
+        // The 11th row is rejected as soon as it's added, long before build()
+        let builder = builder
+            .add_simple_list_row("shoe_4", "Shoe 4")
+            .add_simple_list_row("shoe_5", "Shoe 5");
+        let error = builder.validate().unwrap_err();
+        assert_eq!(error.field(), Some("section.rows"));
+        assert!(error.to_string().contains("row 'shoe_4' in section 'Shoes'"), "unexpected error: {}", error);
+        assert_eq!(builder.build().unwrap_err().to_string(), error.to_string());
+    }
+    
+    #[test]
+    fn test_can_add_row_requires_a_section() {
+        let builder = InteractiveMessageBuilder::new().list_button("Menu");
+        assert!(!builder.can_add_row());
+        
+        let builder = builder.add_list_section("Drinks");
+        assert!(builder.can_add_row());
+    }
+    
+    #[test]
+    fn test_clear_list_forgets_rejected_rows() {
+        let mut builder = InteractiveMessageBuilder::new()
+            .to("+1234567890")
+            .body("Pick one")
+            .list_button("Menu")
+            .add_list_section("Everything");
+        for i in 0..11 {
+            builder = builder.add_simple_list_row(&format!("item_{}", i), "Item");
+        }
+        assert!(builder.validate().is_err());
+        
+        let message = builder
+            .clear_list()
+            .list_button("Menu")
+            .add_list_section("Short")
+            .add_simple_list_row("only", "Only item")
+            .build();
+        assert!(message.is_ok());
+    }
+    
     #[test]
     fn test_empty_list_section_error() {
         let result = InteractiveMessageBuilder::new()
@@ -705,6 +1146,95 @@ mod tests {
         assert!(error_msg.contains("must have at least one row"));
     }
     
+    #[test]
+    fn test_validate_matches_build_without_interaction() {
+        let builder = InteractiveMessageBuilder::new()
+            .to("+1234567890")
+            .body("Test message with no interactions");
+        
+        let validate_error = builder.validate().unwrap_err().to_string();
+        let build_error = builder.build().unwrap_err().to_string();
+        assert_eq!(validate_error, build_error);
+        assert!(validate_error.contains("must have at least one interaction element"));
+    }
+    
+    #[test]
+    fn test_validate_matches_build_with_empty_section() {
+        let builder = InteractiveMessageBuilder::new()
+            .to("+1234567890")
+            .body("Choose an option:")
+            .list_button("Select")
+            .add_list_section("Empty Section");
+        
+        let validate_error = builder.validate().unwrap_err().to_string();
+        let build_error = builder.build().unwrap_err().to_string();
+        assert_eq!(validate_error, build_error);
+        assert!(validate_error.contains("must have at least one row"));
+    }
+    
+    #[test]
+    fn test_over_limit_header_is_caught_when_set() {
+        let builder = InteractiveMessageBuilder::new()
+            .to("+1234567890")
+            .header(&"x".repeat(61));
+        
+        let error = builder.input_error().expect("header should be rejected immediately");
+        assert_eq!(error.field(), Some("header"));
+        let message = error.to_string();
+        assert!(message.contains("61 characters"), "unhelpful message: {}", message);
+        assert!(message.contains("max 60"), "unhelpful message: {}", message);
+        
+        // Replacing the header forgets the error
+        let builder = builder.header("Order update");
+        assert!(builder.input_error().is_none());
+    }
+    
+    #[test]
+    fn test_first_bad_input_is_kept() {
+        let builder = InteractiveMessageBuilder::new()
+            .footer(&"f".repeat(61))
+            .add_button("ok", "A title well over twenty characters")
+            .add_button("fine", "Fine");
+        
+        // The footer came first, so it wins over the button
+        assert_eq!(builder.input_error().and_then(|e| e.field()), Some("footer"));
+        
+        // Fixing the footer reveals the button that's still too long
+        let builder = builder.footer("Short footer").body("Pick").to("+1234567890");
+        assert_eq!(builder.input_error().and_then(|e| e.field()), Some("button.title"));
+        assert!(builder.validate().is_err());
+        
+        let builder = builder.clear_buttons().add_button("ok", "OK");
+        assert!(builder.input_error().is_none());
+        assert!(builder.build().is_ok());
+    }
+    
+    #[test]
+    fn test_over_limit_button_is_caught_when_added() {
+        let builder = InteractiveMessageBuilder::new()
+            .add_button("ok", "A title well over twenty characters");
+        
+        assert_eq!(builder.input_error().and_then(|e| e.field()), Some("button.title"));
+        
+        let builder = builder.clear_buttons();
+        assert!(builder.input_error().is_none());
+    }
+    
+    #[test]
+    fn test_validate_does_not_consume_builder() {
+        let builder = InteractiveMessageBuilder::new()
+            .to("+1234567890")
+            .body("Pick one")
+            .header(&"x".repeat(61));
+        
+        assert!(builder.validate().is_err());
+        
+        // Still usable after validation, and fixing the header makes it valid
+        let builder = builder.header("Short header").add_button("a", "Option A");
+        assert!(builder.validate().is_ok());
+        assert!(builder.build().is_ok());
+    }
+    
     #[test]
     fn test_cta_url_https_validation() {
         // Test that HTTP URLs are rejected for security
@@ -913,6 +1443,26 @@ mod tests {
         assert_eq!(json_output, expected_json);
     }
 
+    #[test]
+    fn test_builder_list_message_with_header_and_footer_json_format() {
+        let message = InteractiveMessageBuilder::new()
+            .to("+16505551234")
+            .header("Order #1042")
+            .body("How would you like to collect your order?")
+            .footer("Lucky Shrub")
+            .list_button("Choose")
+            .add_list_section("Pickup")
+                .add_list_row("store", "In store", "Ready in 2 hours")
+                .add_simple_list_row("locker", "Parcel locker")
+            .build()
+            .unwrap();
+        
+        let json_output = serde_json::to_string(&message).unwrap();
+        let expected_json = r#"{"messaging_product":"whatsapp","recipient_type":"individual","to":"+16505551234","type":"interactive","interactive":{"type":"list","header":{"type":"text","text":"Order #1042"},"body":{"text":"How would you like to collect your order?"},"footer":{"text":"Lucky Shrub"},"action":{"button":"Choose","sections":[{"title":"Pickup","rows":[{"id":"store","title":"In store","description":"Ready in 2 hours"},{"id":"locker","title":"Parcel locker"}]}]}}}"#;
+        
+        assert_eq!(json_output, expected_json);
+    }
+
     #[test]
     fn test_builder_cta_url_message_json_format() {
         let message = InteractiveMessageBuilder::new()
@@ -943,6 +1493,33 @@ mod tests {
         assert_eq!(json_output, expected_json);
     }
 
+    #[test]
+    fn test_builder_flow_message_json_format() {
+        let message = InteractiveMessageBuilder::new()
+            .to("+16505551234")
+            .header("Welcome")
+            .body("Let's get you set up.")
+            .flow("1234567890123", "onboarding-42", "Start", FlowAction::Navigate, Some(FlowActionPayload::new("WELCOME")))
+            .build()
+            .unwrap();
+        
+        let json_output = serde_json::to_string(&message).unwrap();
+        let expected_json = r#"{"messaging_product":"whatsapp","recipient_type":"individual","to":"+16505551234","type":"interactive","interactive":{"type":"flow","header":{"type":"text","text":"Welcome"},"body":{"text":"Let's get you set up."},"action":{"name":"flow","parameters":{"flow_message_version":"3","flow_token":"onboarding-42","flow_id":"1234567890123","flow_cta":"Start","flow_action":"navigate","flow_action_payload":{"screen":"WELCOME"}}}}}"#;
+        
+        assert_eq!(json_output, expected_json);
+    }
+
+    #[test]
+    fn test_builder_flow_validation() {
+        // Navigate without a starting screen is caught before building
+        let builder = InteractiveMessageBuilder::new()
+            .to("+16505551234")
+            .body("Let's get you set up.")
+            .flow("1234567890123", "onboarding-42", "Start", FlowAction::Navigate, None);
+        
+        assert_eq!(builder.validate().unwrap_err().field(), Some("flow_action_payload"));
+    }
+
     #[test]
     fn test_builder_with_header_and_footer_json_format() {
         let message = InteractiveMessageBuilder::new()
@@ -960,4 +1537,78 @@ mod tests {
         
         assert_eq!(json_output, expected_json);
     }
+    
+    #[test]
+    fn test_builder_product_message_json_format() {
+        let message = InteractiveMessageBuilder::new()
+            .to("+16505551234")
+            .body("Back in stock!")
+            .product("367025965434465", "sku-blue-mug")
+            .build()
+            .unwrap();
+        
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["interactive"]["type"], "product");
+        assert_eq!(json["interactive"]["action"], serde_json::json!({
+            "catalog_id": "367025965434465",
+            "product_retailer_id": "sku-blue-mug"
+        }));
+    }
+    
+    #[test]
+    fn test_builder_product_list_message_json_format() {
+        let message = InteractiveMessageBuilder::new()
+            .to("+16505551234")
+            .header("Summer collection")
+            .body("Our picks for this week")
+            .product_list("367025965434465")
+            .add_product_section("Mugs", &["sku-blue-mug", "sku-red-mug"])
+            .add_product_section("Plates", &["sku-white-plate"])
+            .build()
+            .unwrap();
+        
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["interactive"]["type"], "product_list");
+        assert_eq!(json["interactive"]["header"]["text"], "Summer collection");
+        assert_eq!(json["interactive"]["action"], serde_json::json!({
+            "catalog_id": "367025965434465",
+            "sections": [
+                {
+                    "title": "Mugs",
+                    "product_items": [
+                        { "product_retailer_id": "sku-blue-mug" },
+                        { "product_retailer_id": "sku-red-mug" }
+                    ]
+                },
+                {
+                    "title": "Plates",
+                    "product_items": [{ "product_retailer_id": "sku-white-plate" }]
+                }
+            ]
+        }));
+    }
+    
+    #[test]
+    fn test_builder_product_list_validation() {
+        let builder = || InteractiveMessageBuilder::new()
+            .to("+16505551234")
+            .body("Our picks")
+            .product_list("367025965434465");
+        
+        let error = builder()
+            .add_product_section("Mugs", &["sku-blue-mug"])
+            .build()
+            .unwrap_err();
+        assert_eq!(error.field(), Some("header"));
+        
+        let error = builder()
+            .header("Catalog")
+            .add_product_section("Mugs", &[])
+            .validate()
+            .unwrap_err();
+        assert_eq!(error.field(), Some("section.product_items"));
+        
+        let error = builder().header("Catalog").validate().unwrap_err();
+        assert_eq!(error.field(), Some("sections"));
+    }
 }
@@ -322,6 +322,7 @@ impl LocationMessageBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::message_types::Message;
     
     #[test]
     fn test_location_message_with_coordinates_method() {
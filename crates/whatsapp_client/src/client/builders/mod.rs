@@ -5,6 +5,8 @@ pub mod document;
 pub mod video;
 pub mod location;
 pub mod interactive;
+pub mod sticker;
+pub mod utils;
 
 pub use text::TextMessageBuilder;
 pub use audio::AudioMessageBuilder;
@@ -13,4 +15,5 @@ pub use document::DocumentMessageBuilder;
 pub use video::VideoMessageBuilder;
 pub use location::LocationMessageBuilder;
 pub use interactive::InteractiveMessageBuilder;
+pub use sticker::StickerMessageBuilder;
 
@@ -0,0 +1,146 @@
+use crate::{
+    errors::WhatsAppResult,
+    client::{
+        message_types::StickerMessage,
+        builders::utils::{resolve_media_source, MediaSource},
+    },
+};
+
+/// Builder for creating sticker messages with fluent interface
+///
+/// Stickers don't support captions, so like the audio builder this only
+/// takes a recipient and either an uploaded media ID (recommended) or a
+/// hosted `.webp` URL.
+///
+/// # Example
+/// ```
+/// # use whatsapp_client::client::builders::StickerMessageBuilder;
+/// let message = StickerMessageBuilder::new()
+///     .to("+1234567890")
+///     .media_id("1013859600285441")
+///     .build()?;
+/// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct StickerMessageBuilder {
+    to: Option<String>,
+    media_id: Option<String>,
+    media_url: Option<String>,
+}
+
+impl StickerMessageBuilder {
+    /// Create a new sticker message builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the recipient phone number
+    ///
+    /// # Arguments
+    /// * `phone` - Phone number in E.164 format (+1234567890)
+    pub fn to(mut self, phone: &str) -> Self {
+        self.to = Some(phone.to_string());
+        self
+    }
+
+    /// Set the media ID for an uploaded sticker (recommended approach)
+    ///
+    /// # Note
+    /// Takes precedence over `media_url()` if both are set.
+    pub fn media_id(mut self, id: &str) -> Self {
+        self.media_id = Some(id.to_string());
+        // Clear URL if previously set - ID takes precedence
+        self.media_url = None;
+        self
+    }
+
+    /// Set the URL for a hosted .webp sticker
+    ///
+    /// # Note
+    /// Ignored if `media_id()` has already been set.
+    pub fn media_url(mut self, url: &str) -> Self {
+        // Only set URL if no media ID is already set
+        if self.media_id.is_none() {
+            self.media_url = Some(url.to_string());
+        }
+        self
+    }
+
+    /// Build the sticker message
+    ///
+    /// # Validation
+    /// - Recipient phone number must be set and valid
+    /// - Either media_id OR media_url must be set
+    pub fn build(self) -> WhatsAppResult<StickerMessage> {
+        let to = self.to.ok_or_else(|| {
+            crate::errors::WhatsAppError::InvalidMessageContent(
+                "Recipient phone number is required".to_string()
+            )
+        })?;
+
+        match resolve_media_source(self.media_id, self.media_url)? {
+            MediaSource::Id(id) => StickerMessage::from_media_id(&to, &id),
+            MediaSource::Url(url) => StickerMessage::from_url(&to, &url),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::message_types::Message;
+
+    #[test]
+    fn test_sticker_message_with_media_id() {
+        let message = StickerMessageBuilder::new()
+            .to("+1234567890")
+            .media_id("1013859600285441")
+            .build()
+            .unwrap();
+
+        assert_eq!(message.recipient(), "+1234567890");
+        assert_eq!(message.media_id(), Some("1013859600285441"));
+        assert!(message.uses_uploaded_media());
+    }
+
+    #[test]
+    fn test_sticker_message_with_media_url() {
+        let message = StickerMessageBuilder::new()
+            .to("+1234567890")
+            .media_url("https://example.com/sticker.webp")
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["type"], "sticker");
+        assert_eq!(json["sticker"]["link"], "https://example.com/sticker.webp");
+    }
+
+    #[test]
+    fn test_media_id_takes_precedence() {
+        let message = StickerMessageBuilder::new()
+            .to("+1234567890")
+            .media_url("https://example.com/sticker.webp")
+            .media_id("1013859600285441")
+            .build()
+            .unwrap();
+
+        assert_eq!(message.media_id(), Some("1013859600285441"));
+        assert_eq!(message.media_url(), None);
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        let error = StickerMessageBuilder::new()
+            .media_id("123456")
+            .build()
+            .unwrap_err();
+        assert!(error.to_string().contains("Recipient phone number is required"));
+
+        let error = StickerMessageBuilder::new()
+            .to("+1234567890")
+            .build()
+            .unwrap_err();
+        assert!(error.to_string().contains("Either media_id or media_url must be provided"));
+    }
+}
@@ -67,6 +67,16 @@ impl TextMessageBuilder {
         self
     }
     
+    /// Toggle link preview for URLs in the message
+    /// 
+    /// `true` behaves like [`with_preview`](Self::with_preview). `false`
+    /// clears any preview setting so `preview_url` is left out of the
+    /// request entirely.
+    pub fn preview_url(mut self, enabled: bool) -> Self {
+        self.preview_enabled = enabled.then_some(true);
+        self
+    }
+    
     /// Build the text message
     /// 
     /// This validates all the configuration and creates the final TextMessage.
@@ -96,6 +106,7 @@ impl TextMessageBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::message_types::Message;
     
     #[test]
     fn test_basic_text_message_builder() {
@@ -219,6 +230,32 @@ mod tests {
         assert_eq!(json_output, expected_json);
     }
     
+    #[test]
+    fn test_builder_preview_url_toggle_json_format() {
+        let message = TextMessageBuilder::new()
+            .to("+16505551234")
+            .message("Check out: https://example.com")
+            .preview_url(true)
+            .build()
+            .unwrap();
+        
+        let json_output = serde_json::to_string(&message).unwrap();
+        let expected_json = r#"{"messaging_product":"whatsapp","recipient_type":"individual","to":"+16505551234","type":"text","text":{"body":"Check out: https://example.com","preview_url":true}}"#;
+        assert_eq!(json_output, expected_json);
+        
+        let message = TextMessageBuilder::new()
+            .to("+16505551234")
+            .message("Check out: https://example.com")
+            .with_preview()
+            .preview_url(false)
+            .build()
+            .unwrap();
+        
+        let json_output = serde_json::to_string(&message).unwrap();
+        let expected_json = r#"{"messaging_product":"whatsapp","recipient_type":"individual","to":"+16505551234","type":"text","text":{"body":"Check out: https://example.com"}}"#;
+        assert_eq!(json_output, expected_json);
+    }
+    
     #[test]
     fn test_builder_and_direct_create_same_json() {
         let builder_message = TextMessageBuilder::new()
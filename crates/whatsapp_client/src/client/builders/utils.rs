@@ -0,0 +1,66 @@
+use crate::errors::{WhatsAppError, WhatsAppResult};
+
+/// Where a media message's file comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MediaSource {
+    /// Media ID returned by WhatsApp's upload API
+    Id(String),
+    /// Public HTTPS URL that WhatsApp downloads the file from
+    Url(String),
+}
+
+/// Pick the media source for a builder's `build()`
+/// 
+/// Media IDs take precedence over URLs because uploaded media is faster
+/// to deliver and isn't subject to download failures. Fails when neither
+/// was provided.
+/// 
+/// # Example
+/// ```
+/// # use whatsapp_client::client::builders::utils::{resolve_media_source, MediaSource};
+/// let source = resolve_media_source(
+///     Some("1013859600285441".to_string()),
+///     Some("https://example.com/photo.jpg".to_string()),
+/// )?;
+/// assert_eq!(source, MediaSource::Id("1013859600285441".to_string()));
+/// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
+/// ```
+pub fn resolve_media_source(id: Option<String>, url: Option<String>) -> WhatsAppResult<MediaSource> {
+    match (id, url) {
+        (Some(id), _) => Ok(MediaSource::Id(id)),
+        (None, Some(url)) => Ok(MediaSource::Url(url)),
+        (None, None) => Err(WhatsAppError::InvalidMessageContent(
+            "Either media_id or media_url must be provided".to_string()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_media_id_wins_over_url() {
+        let source = resolve_media_source(
+            Some("123456".to_string()),
+            Some("https://example.com/file.jpg".to_string()),
+        ).unwrap();
+        
+        assert_eq!(source, MediaSource::Id("123456".to_string()));
+    }
+    
+    #[test]
+    fn test_url_used_when_no_media_id() {
+        let source = resolve_media_source(None, Some("https://example.com/file.jpg".to_string())).unwrap();
+        
+        assert_eq!(source, MediaSource::Url("https://example.com/file.jpg".to_string()));
+    }
+    
+    #[test]
+    fn test_neither_provided_is_an_error() {
+        let error = resolve_media_source(None, None).unwrap_err();
+        
+        assert!(matches!(error, WhatsAppError::InvalidMessageContent(_)));
+        assert!(error.to_string().contains("Either media_id or media_url must be provided"));
+    }
+}
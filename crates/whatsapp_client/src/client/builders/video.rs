@@ -1,6 +1,10 @@
 use crate::{
     errors::WhatsAppResult,
-    client::message_types::VideoMessage,
+    client::{
+        message_types::VideoMessage,
+        validation::{validate_caption_allowed, MediaType},
+        builders::utils::{resolve_media_source, MediaSource},
+    },
 };
 
 /// Builder for creating video messages with fluent interface
@@ -180,26 +184,25 @@ impl VideoMessageBuilder {
             )
         })?;
         
-        // Create the base message using the appropriate method
-        let mut message = match (self.media_id, self.media_url) {
-            (Some(id), _) => {
-                // Media ID takes precedence (strongly recommended for videos)
-                VideoMessage::from_media_id(&to, &id)?
-            },
-            (None, Some(url)) => {
-                // Fall back to URL approach (discouraged for videos)
-                VideoMessage::from_url(&to, &url)?
-            },
-            (None, None) => {
-                return Err(crate::errors::WhatsAppError::InvalidMessageContent(
-                    "Either media_id or media_url must be provided for video messages. \
-                     Media ID is strongly recommended for videos due to file size and codec requirements.".to_string()
-                ));
+        // Create the base message, pointing out that media IDs are the reliable option for videos
+        let source = resolve_media_source(self.media_id, self.media_url).map_err(|error| match error {
+            crate::errors::WhatsAppError::InvalidMessageContent(reason) => {
+                crate::errors::WhatsAppError::InvalidMessageContent(format!(
+                    "{} for video messages. Media ID is strongly recommended for videos \
+                     due to file size and codec requirements.",
+                    reason
+                ))
             }
+            other => other,
+        })?;
+        let mut message = match source {
+            MediaSource::Id(id) => VideoMessage::from_media_id(&to, &id)?,
+            MediaSource::Url(url) => VideoMessage::from_url(&to, &url)?,
         };
         
         // Add caption if provided (this validates caption length)
         if let Some(caption_text) = self.caption {
+            validate_caption_allowed(MediaType::Video, &caption_text)?;
             message = message.with_caption(&caption_text)?;
         }
         
@@ -239,6 +242,7 @@ impl VideoMessageBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::message_types::Message;
     
     #[test]
     fn test_video_message_with_media_id() {
@@ -0,0 +1,41 @@
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// WhatsApp's customer service window
+///
+/// Free-form (non-template) messages can only be sent within 24 hours of
+/// the last message the user sent us. Outside that window WhatsApp rejects
+/// them, so checking up front saves a doomed API call.
+#[derive(Debug, Clone, Copy)]
+pub struct ConversationWindow;
+
+impl ConversationWindow {
+    /// How long the window stays open after an inbound message
+    pub const DURATION: TimeDelta = TimeDelta::hours(24);
+
+    /// Whether a free-form send at `now` falls within the window opened by `last_inbound`
+    pub fn is_open(last_inbound: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        now - last_inbound < Self::DURATION
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_open_just_before_24_hours() {
+        let last_inbound = Utc::now();
+        let now = last_inbound + TimeDelta::hours(23) + TimeDelta::minutes(59);
+
+        assert!(ConversationWindow::is_open(last_inbound, now));
+    }
+
+    #[test]
+    fn test_window_closed_just_after_24_hours() {
+        let last_inbound = Utc::now();
+        let now = last_inbound + TimeDelta::hours(24) + TimeDelta::minutes(1);
+
+        assert!(!ConversationWindow::is_open(last_inbound, now));
+        assert!(!ConversationWindow::is_open(last_inbound, last_inbound + ConversationWindow::DURATION));
+    }
+}
@@ -1,30 +1,40 @@
 use crate::{
     config::WhatsAppClientConfig,
-    errors::{WhatsAppError, WhatsAppResult, WhatsAppApiErrorResponse},
+    errors::{ValidationCode, WhatsAppError, WhatsAppResult, WhatsAppApiErrorResponse},
     client::{
-        responses::WhatsAppMessageResponse,
-        
+        responses::{WhatsAppMessageResponse, MediaUploadResponse, MediaUrlResponse},
+        idempotency::SentMessageCache,
+        conversation_window::ConversationWindow,
+        validation::{validate_file_size, MediaType},
         message_types::{
+            ContextualMessage,
             WhatsAppMessage,
-            Message,
         },
     },
 };
 use reqwest::{
     Client, 
-    header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE}
+    StatusCode,
+    header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, RETRY_AFTER},
+    multipart::{Form, Part},
 };
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
 use serde::Serialize;
-use std::time::Duration;
-use tokio::time::sleep;
+use common::clock::{Clock as TimeSource, SystemClock};
+use std::{sync::{Arc, Mutex}, time::Duration};
+use tokio::{sync::Semaphore, time::sleep};
 use tracing::{debug, error, info, warn};
 use governor::{
     Quota, 
-    clock::DefaultClock,
+    clock::{Clock, DefaultClock},
     RateLimiter, 
     state::{InMemoryState, NotKeyed}
 };
 
+/// Response header in which the Graph API reports the version that served a call
+const API_VERSION_HEADER: &str = "facebook-api-version";
+
 /// Core WhatsApp Business API client focused on HTTP communication
 /// 
 /// This client handles the low-level HTTP communication with WhatsApp's API.
@@ -40,10 +50,33 @@ pub struct WhatsAppClient {
     config: WhatsAppClientConfig,
     /// Rate limiter to prevent hitting WhatsApp's API limits
     rate_limiter: RateLimiter<NotKeyed, InMemoryState, DefaultClock>,
+    /// Per-second message throughput limiter for the number's messaging tier
+    message_rate_limiter: RateLimiter<NotKeyed, InMemoryState, DefaultClock>,
     /// Base headers that are sent with every request
     default_headers: HeaderMap,
     /// Base URL for all WhatsApp API endpoints
     base_url: String,
+    /// Idempotency keys of recently sent messages
+    sent_messages: Mutex<SentMessageCache>,
+    /// Time source for service window checks
+    time_source: Arc<dyn TimeSource>,
+}
+
+/// Per-message options for `WhatsAppClient::send_message_with`
+/// 
+/// Everything is off by default, so `SendOptions::default()` sends the
+/// message exactly like `send_message`.
+#[derive(Debug, Clone, Default)]
+pub struct SendOptions {
+    /// Send the message as a reply to this message ID, which WhatsApp
+    /// threads in the chat UI
+    pub context_message_id: Option<String>,
+    /// Skip the send and return the original response if a message with
+    /// this key was already sent within the idempotency window
+    pub idempotency_key: Option<String>,
+    /// When the recipient last wrote to us, for the 24-hour customer
+    /// service window check
+    pub last_inbound_at: Option<DateTime<Utc>>,
 }
 
 impl WhatsAppClient {
@@ -54,7 +87,8 @@ impl WhatsAppClient {
     pub fn new(config: WhatsAppClientConfig) -> WhatsAppResult<Self> {
         // Create HTTP client with optimized settings for WhatsApp API
         let http_client = Client::builder()
-            .timeout(Duration::from_secs(config.request_timeout_seconds))
+            .timeout(Duration::from_millis(config.request_timeout_ms))
+            .connect_timeout(Duration::from_millis(config.connect_timeout_ms))
             .user_agent("rust-whatsapp-client/1.0")
             .pool_idle_timeout(Duration::from_secs(90))
             .pool_max_idle_per_host(config.max_concurrent_requests)
@@ -76,6 +110,12 @@ impl WhatsAppClient {
             .allow_burst(rate_limit_burst);
         let rate_limiter = RateLimiter::direct(quota);
         
+        let messages_per_second = std::num::NonZeroU32::new(config.messages_per_second)
+            .ok_or_else(|| WhatsAppError::ConfigurationError(
+                "Messages per second must be greater than 0".to_string()
+            ))?;
+        let message_rate_limiter = RateLimiter::direct(Quota::per_second(messages_per_second));
+        
         // Prepare default headers for all requests
         let mut default_headers = HeaderMap::new();
         default_headers.insert(
@@ -91,6 +131,10 @@ impl WhatsAppClient {
         );
         
         let base_url = config.messages_url();
+        let sent_messages = Mutex::new(SentMessageCache::new(
+            config.idempotency_cache_size,
+            Duration::from_secs(config.idempotency_window_seconds),
+        ));
         
         info!(
             "WhatsApp client initialized for phone number ID: {}, rate limit: {}/min",
@@ -101,11 +145,22 @@ impl WhatsAppClient {
             http_client,
             config,
             rate_limiter,
+            message_rate_limiter,
             default_headers,
             base_url,
+            sent_messages,
+            time_source: Arc::new(SystemClock),
         })
     }
     
+    /// Use `clock` instead of the system clock for service window checks
+    /// 
+    /// Meant for tests that need to control time, e.g. with a `MockClock`.
+    pub fn with_clock(mut self, clock: Arc<dyn TimeSource>) -> Self {
+        self.time_source = clock;
+        self
+    }
+    
     /// Send any message payload to WhatsApp API
     /// 
     /// This is the core method that all message types use. It handles:
@@ -117,16 +172,225 @@ impl WhatsAppClient {
     /// 
     /// The payload should be any struct that implements Serialize and
     /// matches WhatsApp's API format for the specific message type.
+    /// 
+    /// Sends with no per-message options; see `send_message_with`.
     pub async fn send_message(&self, payload: WhatsAppMessage) -> WhatsAppResult<WhatsAppMessageResponse> {
+        self.send_message_with(payload, &SendOptions::default()).await
+    }
+    
+    /// Send a message with per-message options
+    /// 
+    /// When `options.context_message_id` is set the message is sent as a
+    /// reply to that message, which WhatsApp threads in the chat UI.
+    /// 
+    /// When `options.idempotency_key` is set and a message with the same key was
+    /// sent successfully within the idempotency window, nothing is sent and
+    /// the original response is returned instead. Concurrent sends with the
+    /// same key aren't coordinated; only completed sends are remembered.
+    /// 
+    /// When `options.last_inbound_at` is set, free-form messages are refused with
+    /// `OutsideServiceWindow` once the 24-hour customer service window has
+    /// closed, without calling the API. Every message type we support today
+    /// is free-form, so the check applies to all of them.
+    /// 
+    /// Each send takes a permit from the `messages_per_second` limiter
+    /// first. When none is free the call waits for one, or fails with
+    /// `RateLimited` if `rate_limit_fail_fast` is set. Duplicates suppressed
+    /// by the idempotency key don't take a permit.
+    /// 
+    /// The message is validated again before anything else (see
+    /// `WhatsAppMessage::validate`), since one deserialized from an event
+    /// may not have gone through the constructors.
+    pub async fn send_message_with(
+        &self,
+        payload: WhatsAppMessage,
+        options: &SendOptions,
+    ) -> WhatsAppResult<WhatsAppMessageResponse> {
+        if let Err(e) = payload.validate() {
+            warn!("Not sending invalid {} message: {}", payload.message_type_name(), e);
+            return Err(e);
+        }
+        
+        if let Some(last_inbound) = options.last_inbound_at
+            && !ConversationWindow::is_open(last_inbound, self.time_source.now())
+        {
+            warn!("Not sending free-form message: service window closed (last inbound {})", last_inbound);
+            return Err(WhatsAppError::OutsideServiceWindow { last_inbound });
+        }
+        
+        let context_message_id = options.context_message_id.as_deref();
+        let Some(key) = options.idempotency_key.as_deref() else {
+            self.acquire_message_permit().await?;
+            return self.send_contextual_message(payload, context_message_id).await;
+        };
+        
+        if let Some(response) = self.sent_messages().get(key) {
+            info!("Skipping duplicate send for idempotency key {}", key);
+            return Ok(response);
+        }
+        
+        self.acquire_message_permit().await?;
+        let response = self.send_contextual_message(payload, context_message_id).await?;
+        self.sent_messages().insert(key.to_string(), response.clone());
+        Ok(response)
+    }
+    
+    /// Send a hand-written JSON body to the messages endpoint
+    /// 
+    /// **Unstable and unsupported.** This is an escape hatch for WhatsApp
+    /// features we don't model yet, such as a new interactive subtype. The
+    /// body is posted exactly as given: nothing is validated beyond it being
+    /// a JSON object, `messaging_product` isn't filled in, and the service
+    /// window and idempotency checks of `send_message` don't apply. Auth,
+    /// both rate limiters and retries work as usual. Prefer a typed message
+    /// whenever one exists, since this may change or go away.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// # async fn example(client: &whatsapp_client::client::core::WhatsAppClient) -> whatsapp_client::errors::WhatsAppResult<()> {
+    /// let response = client.send_raw(serde_json::json!({
+    ///     "messaging_product": "whatsapp",
+    ///     "to": "+16505551234",
+    ///     "type": "text",
+    ///     "text": { "body": "Hello from a raw payload" }
+    /// })).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_raw(&self, payload: serde_json::Value) -> WhatsAppResult<WhatsAppMessageResponse> {
+        if !payload.is_object() {
+            return Err(WhatsAppError::InvalidMessageContent(
+                "Raw message payload must be a JSON object".to_string()
+            ));
+        }
+        
+        warn!("Sending raw, unvalidated message payload");
+        self.acquire_message_permit().await?;
+        self.send_message_with_retry(&payload).await
+    }
+    
+    /// Take a permit from the per-second message limiter
+    /// 
+    /// Waits for the next permit unless `rate_limit_fail_fast` is set, in
+    /// which case an empty bucket is reported as `RateLimited` along with
+    /// how long until a permit frees up.
+    async fn acquire_message_permit(&self) -> WhatsAppResult<()> {
+        if !self.config.rate_limit_fail_fast {
+            self.message_rate_limiter.until_ready().await;
+            return Ok(());
+        }
+        
+        self.message_rate_limiter.check().map_err(|not_until| {
+            let retry_after = not_until.wait_time_from(DefaultClock::default().now());
+            warn!("Message rate limit of {}/s reached, next permit in {:?}", self.config.messages_per_second, retry_after);
+            WhatsAppError::RateLimited { retry_after: Some(retry_after) }
+        })
+    }
+    
+    /// Lock the sent message cache, recovering it if a holder panicked
+    fn sent_messages(&self) -> std::sync::MutexGuard<'_, SentMessageCache> {
+        self.sent_messages.lock().unwrap_or_else(|e| e.into_inner())
+    }
+    
+    /// Wrap a message with its reply context and send it
+    async fn send_contextual_message(
+        &self,
+        payload: WhatsAppMessage,
+        context_message_id: Option<&str>,
+    ) -> WhatsAppResult<WhatsAppMessageResponse> {
         match payload {
-            WhatsAppMessage::Text(msg) => self.send_message_with_retry(&msg).await,
-            WhatsAppMessage::Audio(msg) => self.send_message_with_retry(&msg).await,
-            WhatsAppMessage::Contact(msg) => self.send_message_with_retry(&msg).await,
-            WhatsAppMessage::Document(msg) => self.send_message_with_retry(&msg).await,
-            WhatsAppMessage::Image(msg) => self.send_message_with_retry(&msg).await,
-            WhatsAppMessage::Interactive(msg) => self.send_message_with_retry(&msg).await,
-            WhatsAppMessage::Location(msg) => self.send_message_with_retry(&msg).await,
-            WhatsAppMessage::Video(msg) => self.send_message_with_retry(&msg).await,
+            WhatsAppMessage::Text(msg) => self.send_message_with_retry(&ContextualMessage::new(&msg, context_message_id)).await,
+            WhatsAppMessage::Audio(msg) => self.send_message_with_retry(&ContextualMessage::new(&msg, context_message_id)).await,
+            WhatsAppMessage::Contact(msg) => self.send_message_with_retry(&ContextualMessage::new(&msg, context_message_id)).await,
+            WhatsAppMessage::Document(msg) => self.send_message_with_retry(&ContextualMessage::new(&msg, context_message_id)).await,
+            WhatsAppMessage::Image(msg) => self.send_message_with_retry(&ContextualMessage::new(&msg, context_message_id)).await,
+            WhatsAppMessage::Interactive(msg) => self.send_message_with_retry(&ContextualMessage::new(&msg, context_message_id)).await,
+            WhatsAppMessage::Location(msg) => self.send_message_with_retry(&ContextualMessage::new(&msg, context_message_id)).await,
+            WhatsAppMessage::Video(msg) => self.send_message_with_retry(&ContextualMessage::new(&msg, context_message_id)).await,
+            WhatsAppMessage::Sticker(msg) => self.send_message_with_retry(&ContextualMessage::new(&msg, context_message_id)).await,
+        }
+    }
+    
+    /// Send several messages concurrently
+    /// 
+    /// At most `max_concurrency` sends are in flight at once (a value of 0 is
+    /// treated as 1), and every send still goes through the rate limiters and
+    /// the usual retry logic. Returns one result per message, in the same
+    /// order as `messages`, so a failure doesn't affect the rest of the batch.
+    pub async fn send_batch(
+        &self,
+        messages: Vec<WhatsAppMessage>,
+        max_concurrency: usize,
+    ) -> Vec<WhatsAppResult<WhatsAppMessageResponse>> {
+        let semaphore = Semaphore::new(max_concurrency.max(1));
+        
+        debug!("Sending batch of {} messages (max {} concurrent)", messages.len(), max_concurrency);
+        
+        let sends = messages.into_iter().map(|message| {
+            let semaphore = &semaphore;
+            async move {
+                // The semaphore is never closed, so acquiring can't fail
+                let _permit = semaphore.acquire().await
+                    .expect("batch semaphore closed unexpectedly");
+                self.send_message(message).await
+            }
+        });
+        
+        join_all(sends).await
+    }
+    
+    /// Mark an incoming message as read
+    /// 
+    /// Shows the blue check marks on the user's side, which is expected of a
+    /// bot that has picked up the message. WhatsApp also marks every earlier
+    /// message in the conversation as read.
+    pub async fn mark_as_read(&self, message_id: &str) -> WhatsAppResult<()> {
+        self.post_read_status(message_id, false).await?;
+        debug!("Marked message {} as read", message_id);
+        Ok(())
+    }
+    
+    /// Show a typing indicator while a reply to `message_id` is prepared
+    /// 
+    /// WhatsApp marks the message as read and shows "typing..." until the
+    /// reply is sent or 25 seconds pass, whichever comes first.
+    pub async fn send_typing_indicator(&self, message_id: &str) -> WhatsAppResult<()> {
+        self.post_read_status(message_id, true).await?;
+        debug!("Sent typing indicator for message {}", message_id);
+        Ok(())
+    }
+    
+    /// POST a read status for `message_id`, optionally with a typing indicator
+    async fn post_read_status(&self, message_id: &str, typing: bool) -> WhatsAppResult<()> {
+        if message_id.trim().is_empty() {
+            return Err(WhatsAppError::validation(ValidationCode::MessageIdEmpty, "message_id", "cannot be empty"));
+        }
+        
+        self.rate_limiter.until_ready().await;
+        
+        let mut payload = serde_json::json!({
+            "messaging_product": "whatsapp",
+            "status": "read",
+            "message_id": message_id,
+        });
+        if typing {
+            payload["typing_indicator"] = serde_json::json!({ "type": "text" });
+        }
+        let response = self.http_client
+            .post(&self.base_url)
+            .headers(self.default_headers.clone())
+            .json(&payload)
+            .send()
+            .await.map_err(|e| self.request_error(e))?;
+        
+        let status = response.status();
+        let retry_after = Self::retry_after_header(response.headers());
+        
+        if status.is_success() {
+            Ok(())
+        } else {
+            let response_text = response.text().await.map_err(|e| self.request_error(e))?;
+            Err(Self::parse_error_response(status, retry_after, &response_text))
         }
     }
     
@@ -136,7 +400,7 @@ impl WhatsAppClient {
     /// Different error types get different retry treatments based on
     /// whether they're likely to succeed on retry.
     async fn send_message_with_retry<T>(&self, payload: &T) -> WhatsAppResult<WhatsAppMessageResponse> 
-        where T: Message + Serialize
+        where T: Serialize
     {
         for attempt in 1..=self.config.max_retry_attempts {
             // Wait for rate limiter - this ensures we don't exceed WhatsApp's limits
@@ -181,20 +445,21 @@ impl WhatsAppClient {
     /// This method focuses purely on HTTP communication with WhatsApp's API.
     /// All retry logic is handled at a higher level.
     async fn send_message_once<T>(&self, payload: &T) -> WhatsAppResult<WhatsAppMessageResponse> 
-        where T: Message + serde::Serialize
+        where T: Serialize
     {
         // Serialize the payload to JSON
-        let json_payload = serde_json::to_value(&payload)
+        let json_payload = serde_json::to_value(payload)
             .map_err(WhatsAppError::SerializationError)?;
         let response = self.http_client
             .post(&self.base_url)
             .headers(self.default_headers.clone())
             .json(&json_payload)
             .send()
-            .await?;
+            .await.map_err(|e| self.request_error(e))?;
         
         let status = response.status();
-        let response_text = response.text().await?;
+        let retry_after = Self::retry_after_header(response.headers());
+        let response_text = response.text().await.map_err(|e| self.request_error(e))?;
         
         if status.is_success() {
             // Parse successful response
@@ -204,23 +469,239 @@ impl WhatsAppClient {
             info!("Message sent successfully: {}", message_response.messages[0].id);
             Ok(message_response)
         } else {
-            // Parse error response
-            match serde_json::from_str::<WhatsAppApiErrorResponse>(&response_text) {
-                Ok(error_response) => {
-                    Err(WhatsAppError::from_api_response(error_response))
-                }
-                Err(_) => {
-                    // Couldn't parse error response, create a generic error
-                    Err(WhatsAppError::ApiError {
-                        code: status.as_u16() as u32,
-                        message: format!("HTTP {} error: {}", status, response_text),
-                        error_data: None,
-                    })
+            Err(Self::parse_error_response(status, retry_after, &response_text))
+        }
+    }
+    
+    /// Upload media to WhatsApp and return its media ID
+    /// 
+    /// The file is sent as multipart form data to the `/{phone_number_id}/media`
+    /// endpoint. The MIME type and size are validated against WhatsApp's
+    /// per-type limits before anything is sent over the wire. For stickers
+    /// that limit is the 500 KB animated one; check a static sticker with
+    /// `StickerMessage::validate_sticker_file` first to hold it to 100 KB.
+    pub async fn upload_media(&self, bytes: Vec<u8>, mime_type: &str) -> WhatsAppResult<String> {
+        let media_type = MediaType::from_mime_type(mime_type)
+            .ok_or_else(|| WhatsAppError::validation(
+                ValidationCode::UnsupportedMimeType,
+                "mime_type",
+                format!("unsupported MIME type for upload: {}", mime_type)
+            ))?;
+        validate_file_size(bytes.len() as u64, media_type)?;
+        
+        self.rate_limiter.until_ready().await;
+        
+        debug!("Uploading {} bytes of {} media", bytes.len(), mime_type);
+        
+        let file_part = Part::bytes(bytes)
+            .file_name("upload")
+            .mime_str(mime_type)?;
+        let form = Form::new()
+            .text("messaging_product", "whatsapp")
+            .text("type", mime_type.to_string())
+            .part("file", file_part);
+        
+        // The multipart body sets its own content type, so only the
+        // authorization header is carried over from the defaults
+        let response = self.http_client
+            .post(self.config.media_url())
+            .header(AUTHORIZATION, self.config.authorization_header())
+            .multipart(form)
+            .send()
+            .await.map_err(|e| self.request_error(e))?;
+        
+        let status = response.status();
+        let retry_after = Self::retry_after_header(response.headers());
+        let response_text = response.text().await.map_err(|e| self.request_error(e))?;
+        
+        if status.is_success() {
+            let upload_response: MediaUploadResponse = serde_json::from_str(&response_text)
+                .map_err(WhatsAppError::SerializationError)?;
+            
+            info!("Media uploaded successfully: {}", upload_response.id);
+            Ok(upload_response.id)
+        } else {
+            Err(Self::parse_error_response(status, retry_after, &response_text))
+        }
+    }
+    
+    /// Resolve a media ID to its temporary download URL
+    /// 
+    /// Incoming media webhooks only carry a media ID. WhatsApp exchanges it
+    /// for a short-lived URL that must be fetched with the bearer token.
+    pub async fn get_media_url(&self, media_id: &str) -> WhatsAppResult<String> {
+        Ok(self.get_media_info(media_id).await?.url)
+    }
+    
+    /// Download the bytes of a media ID
+    /// 
+    /// This performs the full two-step flow: look up the media metadata,
+    /// then fetch the file from the returned URL. Returns the raw bytes
+    /// together with the media's MIME type.
+    pub async fn download_media(&self, media_id: &str) -> WhatsAppResult<(Vec<u8>, String)> {
+        let media_info = self.get_media_info(media_id).await?;
+        
+        self.rate_limiter.until_ready().await;
+        
+        debug!("Downloading media {} from {}", media_id, media_info.url);
+        
+        let response = self.http_client
+            .get(&media_info.url)
+            .header(AUTHORIZATION, self.config.authorization_header())
+            .send()
+            .await.map_err(|e| self.request_error(e))?;
+        
+        let status = response.status();
+        let retry_after = Self::retry_after_header(response.headers());
+        if Self::is_media_gone(status) {
+            return Err(WhatsAppError::MediaExpired { media_id: media_id.to_string() });
+        }
+        if !status.is_success() {
+            let response_text = response.text().await.map_err(|e| self.request_error(e))?;
+            return Err(Self::parse_error_response(status, retry_after, &response_text));
+        }
+        
+        let bytes = response.bytes().await.map_err(|e| self.request_error(e))?.to_vec();
+        
+        info!("Media {} downloaded successfully ({} bytes)", media_id, bytes.len());
+        Ok((bytes, media_info.mime_type))
+    }
+    
+    /// Fetch the metadata (URL, MIME type, size) for a media ID
+    async fn get_media_info(&self, media_id: &str) -> WhatsAppResult<MediaUrlResponse> {
+        self.rate_limiter.until_ready().await;
+        
+        let response = self.http_client
+            .get(self.config.media_info_url(media_id))
+            .header(AUTHORIZATION, self.config.authorization_header())
+            .send()
+            .await.map_err(|e| self.request_error(e))?;
+        
+        let status = response.status();
+        let retry_after = Self::retry_after_header(response.headers());
+        if Self::is_media_gone(status) {
+            return Err(WhatsAppError::MediaExpired { media_id: media_id.to_string() });
+        }
+        
+        let response_text = response.text().await.map_err(|e| self.request_error(e))?;
+        
+        if status.is_success() {
+            serde_json::from_str(&response_text)
+                .map_err(WhatsAppError::SerializationError)
+        } else {
+            Err(Self::parse_error_response(status, retry_after, &response_text))
+        }
+    }
+    
+    /// Ask the Graph API which version actually serves our requests
+    /// 
+    /// Every Graph response carries a `facebook-api-version` header. It
+    /// normally echoes `api_version`, but Meta answers calls to a retired
+    /// version with the oldest one still available, so a mismatch means the
+    /// configured version should be bumped. Returns `None` if the header is
+    /// missing. A version that doesn't exist at all fails like any other
+    /// API error.
+    pub async fn detect_api_version(&self) -> WhatsAppResult<Option<String>> {
+        self.rate_limiter.until_ready().await;
+        
+        let response = self.http_client
+            .get(self.config.phone_number_url())
+            .query(&[("fields", "id")])
+            .header(AUTHORIZATION, self.config.authorization_header())
+            .send()
+            .await.map_err(|e| self.request_error(e))?;
+        
+        let status = response.status();
+        let retry_after = Self::retry_after_header(response.headers());
+        let detected = response.headers()
+            .get(API_VERSION_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_string());
+        
+        if !status.is_success() {
+            let response_text = response.text().await.map_err(|e| self.request_error(e))?;
+            return Err(Self::parse_error_response(status, retry_after, &response_text));
+        }
+        
+        match &detected {
+            Some(version) if *version != self.config.api_version => warn!(
+                "Configured API version {} is being served as {}",
+                self.config.api_version, version
+            ),
+            Some(version) => debug!("API version {} confirmed", version),
+            None => debug!("API response did not report its version"),
+        }
+        Ok(detected)
+    }
+    
+    /// WhatsApp reports expired or deleted media with 404 or 410
+    fn is_media_gone(status: StatusCode) -> bool {
+        status == StatusCode::NOT_FOUND || status == StatusCode::GONE
+    }
+    
+    /// Convert a non-success API response into a `WhatsAppError`
+    /// 
+    /// The Cloud API error body is the primary source of truth. When it
+    /// doesn't map to a specific variant, the HTTP status decides whether
+    /// this was an auth failure, a rate limit or a server-side error.
+    fn parse_error_response(
+        status: StatusCode,
+        retry_after: Option<Duration>,
+        response_text: &str,
+    ) -> WhatsAppError {
+        let error = match serde_json::from_str::<WhatsAppApiErrorResponse>(response_text) {
+            Ok(error_response) => WhatsAppError::from_api_response(error_response),
+            Err(_) => {
+                // Couldn't parse error response, create a generic error
+                WhatsAppError::ApiError {
+                    code: status.as_u16() as u32,
+                    message: format!("HTTP {} error: {}", status, response_text),
+                    error_data: None,
                 }
             }
+        };
+        
+        match error {
+            WhatsAppError::RateLimited { retry_after: None } => {
+                WhatsAppError::RateLimited { retry_after }
+            }
+            WhatsAppError::ApiError { .. } if status == StatusCode::UNAUTHORIZED => {
+                WhatsAppError::AuthenticationFailed
+            }
+            WhatsAppError::ApiError { .. } if status == StatusCode::TOO_MANY_REQUESTS => {
+                WhatsAppError::RateLimited { retry_after }
+            }
+            WhatsAppError::ApiError { .. } if status.is_server_error() => {
+                WhatsAppError::ServerError(status.as_u16())
+            }
+            other => other,
         }
     }
     
+    /// Classify a transport error, surfacing timeouts as `WhatsAppError::Timeout`
+    fn request_error(&self, error: reqwest::Error) -> WhatsAppError {
+        if !error.is_timeout() {
+            return WhatsAppError::HttpError(error);
+        }
+        
+        let timeout_ms = if error.is_connect() {
+            self.config.connect_timeout_ms
+        } else {
+            self.config.request_timeout_ms
+        };
+        warn!("Request to WhatsApp API timed out after {}ms", timeout_ms);
+        WhatsAppError::Timeout { timeout_ms }
+    }
+    
+    /// Read the `Retry-After` header (in seconds) if the API sent one
+    fn retry_after_header(headers: &HeaderMap) -> Option<Duration> {
+        headers
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .map(Duration::from_secs)
+    }
+    
     /// Calculate exponential backoff delay for retries
     fn calculate_retry_delay(&self, attempt: u32, error: &WhatsAppError) -> Duration {
         // Start with error-specific delay if available
@@ -266,6 +747,17 @@ impl WhatsAppClient {
 mod tests {
     use super::*;
     use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_json, body_partial_json, header, header_regex, method, path, query_param},
+    };
+    
+    fn create_mock_config(api_base_url: &str) -> WhatsAppClientConfig {
+        WhatsAppClientConfig {
+            api_base_url: api_base_url.to_string(),
+            ..create_test_config()
+        }
+    }
     
     fn create_test_config() -> WhatsAppClientConfig {
         WhatsAppClientConfig {
@@ -275,11 +767,17 @@ mod tests {
             api_base_url: "https://graph.facebook.com".to_string(),
             rate_limit_per_minute: 800,
             rate_limit_burst: 50,
-            request_timeout_seconds: 30,
+            messages_per_second: 80,
+            rate_limit_fail_fast: false,
+            request_timeout_ms: 30000,
+            connect_timeout_ms: 10000,
             max_concurrent_requests: 20,
             max_retry_attempts: 3,
             initial_retry_delay_ms: 1000,
             max_retry_delay_ms: 30000,
+            max_send_attempts: 3,
+            idempotency_window_seconds: 600,
+            idempotency_cache_size: 10000,
             host: "0.0.0.0".to_string(),
             port: 8001,
         }
@@ -325,4 +823,677 @@ mod tests {
         let serialized = serde_json::to_value(&test_payload);
         assert!(serialized.is_ok());
     }
+    
+    #[tokio::test]
+    async fn test_upload_media_sends_multipart_and_parses_id() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v23.0/123456789/media"))
+            .and(header("authorization", "Bearer test_token"))
+            .and(header_regex("content-type", "^multipart/form-data; boundary="))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "1013859600285441" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        let media_id = client.upload_media(b"fake-png-bytes".to_vec(), "image/png").await.unwrap();
+        assert_eq!(media_id, "1013859600285441");
+        
+        let requests = server.received_requests().await.unwrap();
+        let body = String::from_utf8_lossy(&requests[0].body);
+        assert!(body.contains("name=\"messaging_product\"\r\n\r\nwhatsapp"));
+        assert!(body.contains("name=\"type\"\r\n\r\nimage/png"));
+        assert!(body.contains("name=\"file\"; filename=\"upload\""));
+        assert!(body.contains("Content-Type: image/png\r\n\r\nfake-png-bytes"));
+    }
+    
+    #[tokio::test]
+    async fn test_upload_media_rejects_invalid_input_before_sending() {
+        let server = MockServer::start().await;
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        
+        // Unsupported MIME type
+        let result = client.upload_media(vec![0; 16], "image/gif").await;
+        assert_eq!(result.unwrap_err().field(), Some("mime_type"));
+        
+        // Image over the 5 MB limit
+        let oversized = vec![0; (5 * 1024 * 1024) + 1];
+        let result = client.upload_media(oversized, "image/jpeg").await;
+        assert_eq!(result.unwrap_err().field(), Some("file_size"));
+        
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_upload_media_maps_api_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v23.0/123456789/media"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+                "error": {
+                    "message": "Invalid OAuth access token",
+                    "type": "OAuthException",
+                    "code": 190,
+                    "fbtrace_id": "abc"
+                }
+            })))
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        let result = client.upload_media(b"%PDF-1.4".to_vec(), "application/pdf").await;
+        assert!(matches!(result, Err(WhatsAppError::AuthenticationFailed)));
+    }
+    
+    #[tokio::test]
+    async fn test_get_media_url_returns_metadata_url() {
+        let server = MockServer::start().await;
+        let download_url = format!("{}/attachments/1013859600285441", server.uri());
+        Mock::given(method("GET"))
+            .and(path("/v23.0/1013859600285441"))
+            .and(header("authorization", "Bearer test_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "messaging_product": "whatsapp",
+                "url": download_url,
+                "mime_type": "image/jpeg",
+                "sha256": "abc123",
+                "file_size": 4,
+                "id": "1013859600285441"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        let url = client.get_media_url("1013859600285441").await.unwrap();
+        assert_eq!(url, download_url);
+    }
+    
+    #[tokio::test]
+    async fn test_send_raw_forwards_body_verbatim_with_retries() {
+        let server = MockServer::start().await;
+        // A shape no typed message produces
+        let payload = json!({
+            "messaging_product": "whatsapp",
+            "recipient_type": "individual",
+            "to": "+16505551234",
+            "type": "interactive",
+            "interactive": {
+                "type": "future_subtype",
+                "body": { "text": "Hello" },
+                "action": { "name": "something_new", "parameters": { "nested": [1, 2, 3] } }
+            }
+        });
+        
+        Mock::given(method("POST"))
+            .and(path("/v23.0/123456789/messages"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v23.0/123456789/messages"))
+            .and(header("authorization", "Bearer test_token"))
+            .and(body_json(&payload))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "messaging_product": "whatsapp",
+                "contacts": [{ "input": "+16505551234", "wa_id": "16505551234" }],
+                "messages": [{ "id": "wamid.raw" }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        let response = client.send_raw(payload).await.unwrap();
+        assert_eq!(response.messages[0].id, "wamid.raw");
+        
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        
+        // Anything but an object is refused without a request
+        assert!(matches!(
+            client.send_raw(json!(["not", "an", "object"])).await,
+            Err(WhatsAppError::InvalidMessageContent(_))
+        ));
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+    
+    #[tokio::test]
+    async fn test_detect_api_version_reads_version_header() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v23.0/123456789"))
+            .and(query_param("fields", "id"))
+            .and(header("authorization", "Bearer test_token"))
+            .respond_with(ResponseTemplate::new(200)
+                .insert_header("facebook-api-version", "v23.0")
+                .set_body_json(json!({ "id": "123456789" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        assert_eq!(client.detect_api_version().await.unwrap().as_deref(), Some("v23.0"));
+    }
+    
+    #[tokio::test]
+    async fn test_detect_api_version_reports_upgraded_or_missing_version() {
+        let server = MockServer::start().await;
+        let config = WhatsAppClientConfig {
+            api_version: "v16.0".to_string(),
+            ..create_mock_config(&server.uri())
+        };
+        Mock::given(method("GET"))
+            .and(path("/v16.0/123456789"))
+            .respond_with(ResponseTemplate::new(200)
+                .insert_header("facebook-api-version", "v19.0")
+                .set_body_json(json!({ "id": "123456789" })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v16.0/123456789"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "123456789" })))
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(config).unwrap();
+        assert_eq!(client.detect_api_version().await.unwrap().as_deref(), Some("v19.0"));
+        assert_eq!(client.detect_api_version().await.unwrap(), None);
+    }
+    
+    #[tokio::test]
+    async fn test_detect_api_version_surfaces_unknown_version() {
+        let server = MockServer::start().await;
+        let config = WhatsAppClientConfig {
+            api_version: "v91.0".to_string(),
+            ..create_mock_config(&server.uri())
+        };
+        Mock::given(method("GET"))
+            .and(path("/v91.0/123456789"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "error": {
+                    "message": "Unknown path components: /v91.0",
+                    "type": "OAuthException",
+                    "code": 2500,
+                    "fbtrace_id": "AbCdEf"
+                }
+            })))
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(config).unwrap();
+        match client.detect_api_version().await {
+            Err(WhatsAppError::ApiError { code, message, .. }) => {
+                assert_eq!(code, 2500);
+                assert!(message.contains("v91.0"), "{}", message);
+            }
+            other => panic!("Expected an API error, got {:?}", other),
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_download_media_fetches_bytes_with_bearer_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v23.0/1013859600285441"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "messaging_product": "whatsapp",
+                "url": format!("{}/attachments/1013859600285441", server.uri()),
+                "mime_type": "image/jpeg",
+                "id": "1013859600285441"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/attachments/1013859600285441"))
+            .and(header("authorization", "Bearer test_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"\xFF\xD8\xFF\xE0".to_vec()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        let (bytes, mime_type) = client.download_media("1013859600285441").await.unwrap();
+        assert_eq!(bytes, vec![0xFF, 0xD8, 0xFF, 0xE0]);
+        assert_eq!(mime_type, "image/jpeg");
+    }
+    
+    #[tokio::test]
+    async fn test_expired_media_maps_to_media_expired() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v23.0/404404"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v23.0/410410"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "url": format!("{}/attachments/410410", server.uri()),
+                "mime_type": "application/pdf",
+                "id": "410410"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/attachments/410410"))
+            .respond_with(ResponseTemplate::new(410))
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        
+        let result = client.get_media_url("404404").await;
+        assert!(matches!(result, Err(WhatsAppError::MediaExpired { ref media_id }) if media_id == "404404"));
+        
+        let result = client.download_media("410410").await;
+        assert!(matches!(result, Err(WhatsAppError::MediaExpired { ref media_id }) if media_id == "410410"));
+    }
+    
+    #[test]
+    fn test_parse_error_response_typed_variants() {
+        let auth = WhatsAppClient::parse_error_response(
+            StatusCode::UNAUTHORIZED,
+            None,
+            r#"{"error": {"message": "Error validating access token", "type": "OAuthException", "code": 190}}"#,
+        );
+        assert!(matches!(auth, WhatsAppError::AuthenticationFailed));
+        assert!(!auth.is_retryable());
+        
+        let rate_limited = WhatsAppClient::parse_error_response(
+            StatusCode::TOO_MANY_REQUESTS,
+            Some(Duration::from_secs(7)),
+            r#"{"error": {"message": "(#80007) Rate limit issues", "type": "OAuthException", "code": 80007}}"#,
+        );
+        assert!(matches!(rate_limited, WhatsAppError::RateLimited { retry_after: Some(d) } if d == Duration::from_secs(7)));
+        assert!(rate_limited.is_retryable());
+        
+        let server = WhatsAppClient::parse_error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            None,
+            r#"{"error": {"message": "Service temporarily unavailable", "type": "OAuthException", "code": 2}}"#,
+        );
+        assert!(matches!(server, WhatsAppError::ServerError(503)));
+        assert!(server.is_retryable());
+        
+        let unparseable = WhatsAppClient::parse_error_response(StatusCode::BAD_GATEWAY, None, "<html>Bad Gateway</html>");
+        assert!(matches!(unparseable, WhatsAppError::ServerError(502)));
+        
+        let invalid = WhatsAppClient::parse_error_response(
+            StatusCode::BAD_REQUEST,
+            None,
+            r#"{"error": {"message": "Message body too long", "type": "OAuthException", "code": 131047}}"#,
+        );
+        assert!(matches!(invalid, WhatsAppError::InvalidMessageContent(_)));
+        assert!(!invalid.is_retryable());
+    }
+    
+    #[tokio::test]
+    async fn test_rate_limit_uses_retry_after_header() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v23.0/1013859600285441"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "15"))
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        let result = client.get_media_url("1013859600285441").await;
+        assert!(matches!(result, Err(WhatsAppError::RateLimited { retry_after: Some(d) }) if d == Duration::from_secs(15)));
+    }
+    
+    #[tokio::test]
+    async fn test_send_message_threads_reply_context() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v23.0/123456789/messages"))
+            .and(body_partial_json(json!({
+                "type": "text",
+                "context": { "message_id": "wamid.original" }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "messaging_product": "whatsapp",
+                "contacts": [{ "input": "+1234567890", "wa_id": "1234567890" }],
+                "messages": [{ "id": "wamid.reply" }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        let message = WhatsAppMessage::Text(crate::client::message_types::TextMessage::new("+1234567890", "Got it!").unwrap());
+        let response = client.send_message_with(message, &SendOptions {
+            context_message_id: Some("wamid.original".to_string()),
+            ..SendOptions::default()
+        }).await.unwrap();
+        assert_eq!(response.message_id(), Some("wamid.reply"));
+    }
+    
+    #[tokio::test]
+    async fn test_send_message_suppresses_repeated_idempotency_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v23.0/123456789/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "messaging_product": "whatsapp",
+                "contacts": [{ "input": "+1234567890", "wa_id": "1234567890" }],
+                "messages": [{ "id": "wamid.sent" }]
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        let message = || WhatsAppMessage::Text(crate::client::message_types::TextMessage::new("+1234567890", "Hello!").unwrap());
+        
+        let key = |key: &str| SendOptions { idempotency_key: Some(key.to_string()), ..SendOptions::default() };
+        let (reply_1, reply_2) = (key("reply-1"), key("reply-2"));
+        
+        // The first send goes out; repeating its key returns the original response
+        let first = client.send_message_with(message(), &reply_1).await.unwrap();
+        let repeat = client.send_message_with(message(), &reply_1).await.unwrap();
+        assert_eq!(first.message_id(), Some("wamid.sent"));
+        assert_eq!(repeat.message_id(), Some("wamid.sent"));
+        
+        // A new key is sent (the mock expects exactly two requests)
+        client.send_message_with(message(), &reply_2).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_send_message_refuses_closed_service_window() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v23.0/123456789/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "messaging_product": "whatsapp",
+                "contacts": [{ "input": "+1234567890", "wa_id": "1234567890" }],
+                "messages": [{ "id": "wamid.sent" }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let last_inbound = Utc::now();
+        let clock = Arc::new(common::clock::MockClock::new(last_inbound));
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap()
+            .with_clock(clock.clone());
+        let message = || WhatsAppMessage::Text(crate::client::message_types::TextMessage::new("+1234567890", "Hello!").unwrap());
+        let options = SendOptions { last_inbound_at: Some(last_inbound), ..SendOptions::default() };
+        
+        // 23h59m is still inside the window (the mock expects exactly one request)
+        clock.advance(chrono::TimeDelta::hours(23) + chrono::TimeDelta::minutes(59));
+        client.send_message_with(message(), &options).await.unwrap();
+        
+        // 24h01m since the user last wrote: refused without hitting the API
+        clock.advance(chrono::TimeDelta::minutes(2));
+        let error = client.send_message_with(message(), &options).await.unwrap_err();
+        assert!(matches!(error, WhatsAppError::OutsideServiceWindow { last_inbound: at } if at == last_inbound));
+        assert!(!error.is_retryable());
+    }
+    
+    #[tokio::test]
+    async fn test_invalid_deserialized_message_rejected_without_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+        
+        // Built by another service without going through the constructors
+        let message: WhatsAppMessage = serde_json::from_value(json!({
+            "Text": {
+                "messaging_product": "whatsapp",
+                "recipient_type": "individual",
+                "to": "not-a-phone",
+                "type": "text",
+                "text": { "body": "Hello!" }
+            }
+        })).unwrap();
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        let error = client.send_message_with(message, &SendOptions {
+            idempotency_key: Some("reply-1".to_string()),
+            ..SendOptions::default()
+        }).await.unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::InvalidPhone));
+        assert!(!error.is_retryable());
+    }
+    
+    #[tokio::test]
+    async fn test_mark_as_read_posts_read_status() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v23.0/123456789/messages"))
+            .and(body_json(json!({
+                "messaging_product": "whatsapp",
+                "status": "read",
+                "message_id": "wamid.incoming"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "success": true })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        client.mark_as_read("wamid.incoming").await.unwrap();
+        
+        let error = client.mark_as_read("  ").await.unwrap_err();
+        assert_eq!(error.field(), Some("message_id"));
+    }
+    
+    #[tokio::test]
+    async fn test_send_typing_indicator_posts_indicator() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v23.0/123456789/messages"))
+            .and(body_json(json!({
+                "messaging_product": "whatsapp",
+                "status": "read",
+                "message_id": "wamid.incoming",
+                "typing_indicator": { "type": "text" }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "success": true })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        assert!(client.send_typing_indicator("wamid.incoming").await.is_ok());
+        
+        let error = client.send_typing_indicator("").await.unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::MessageIdEmpty));
+    }
+    
+    #[tokio::test]
+    async fn test_mark_as_read_unknown_message_id() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v23.0/123456789/messages"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "error": {
+                    "message": "(#100) Invalid parameter",
+                    "type": "OAuthException",
+                    "code": 100,
+                    "fbtrace_id": "A1"
+                }
+            })))
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        let error = client.mark_as_read("wamid.missing").await.unwrap_err();
+        assert!(matches!(error, WhatsAppError::ApiError { code: 100, .. }));
+        assert!(!error.is_retryable());
+    }
+    
+    fn batch_text(to: &str) -> WhatsAppMessage {
+        WhatsAppMessage::Text(crate::client::message_types::TextMessage::new(to, "Batch hello").unwrap())
+    }
+    
+    #[tokio::test]
+    async fn test_send_batch_respects_concurrency_cap() {
+        let server = MockServer::start().await;
+        let delay = Duration::from_millis(150);
+        Mock::given(method("POST"))
+            .and(path("/v23.0/123456789/messages"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_delay(delay)
+                .set_body_json(json!({
+                    "messaging_product": "whatsapp",
+                    "contacts": [{ "input": "+1234567890", "wa_id": "1234567890" }],
+                    "messages": [{ "id": "wamid.batch" }]
+                })))
+            .expect(6)
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        let messages = (0..6).map(|_| batch_text("+1234567890")).collect();
+        
+        let started = std::time::Instant::now();
+        let results = client.send_batch(messages, 2).await;
+        let elapsed = started.elapsed();
+        
+        assert_eq!(results.len(), 6);
+        assert!(results.iter().all(|result| result.is_ok()));
+        // With two sends in flight at a time, six sends take at least three rounds
+        assert!(elapsed >= delay * 3, "batch finished too fast for the cap: {:?}", elapsed);
+        assert!(elapsed < delay * 6, "batch didn't run concurrently: {:?}", elapsed);
+    }
+    
+    async fn mount_send_success(server: &MockServer) {
+        Mock::given(method("POST"))
+            .and(path("/v23.0/123456789/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "messaging_product": "whatsapp",
+                "contacts": [{ "input": "+1234567890", "wa_id": "1234567890" }],
+                "messages": [{ "id": "wamid.ok" }]
+            })))
+            .mount(server)
+            .await;
+    }
+    
+    #[tokio::test]
+    async fn test_send_batch_smooths_bursts_to_messages_per_second() {
+        let server = MockServer::start().await;
+        mount_send_success(&server).await;
+        
+        let config = WhatsAppClientConfig {
+            messages_per_second: 10,
+            ..create_mock_config(&server.uri())
+        };
+        let client = WhatsAppClient::new(config).unwrap();
+        let messages = (0..15).map(|_| batch_text("+1234567890")).collect();
+        
+        let started = std::time::Instant::now();
+        let results = client.send_batch(messages, 15).await;
+        let elapsed = started.elapsed();
+        
+        assert!(results.iter().all(|result| result.is_ok()));
+        // The first 10 go out as a burst, the other 5 at one per 100ms
+        assert!(elapsed >= Duration::from_millis(450), "burst wasn't smoothed: {:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(1500), "limiter too strict: {:?}", elapsed);
+    }
+    
+    #[tokio::test]
+    async fn test_send_message_fails_fast_when_over_messages_per_second() {
+        let server = MockServer::start().await;
+        mount_send_success(&server).await;
+        
+        let config = WhatsAppClientConfig {
+            messages_per_second: 2,
+            rate_limit_fail_fast: true,
+            ..create_mock_config(&server.uri())
+        };
+        let client = WhatsAppClient::new(config).unwrap();
+        
+        for _ in 0..2 {
+            client.send_message(batch_text("+1234567890")).await.unwrap();
+        }
+        let error = client.send_message(batch_text("+1234567890")).await.unwrap_err();
+        
+        match error {
+            WhatsAppError::RateLimited { retry_after: Some(wait) } => {
+                assert!(wait <= Duration::from_millis(500), "unexpected wait: {:?}", wait);
+            }
+            other => panic!("Expected RateLimited, got {:?}", other),
+        }
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+    
+    #[test]
+    fn test_zero_messages_per_second_is_rejected() {
+        let config = WhatsAppClientConfig {
+            messages_per_second: 0,
+            ..create_test_config()
+        };
+        assert!(matches!(WhatsAppClient::new(config), Err(WhatsAppError::ConfigurationError(_))));
+    }
+    
+    #[tokio::test]
+    async fn test_send_batch_reports_partial_failures_in_order() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v23.0/123456789/messages"))
+            .and(body_partial_json(json!({ "to": "+1999999999" })))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "error": {
+                    "message": "Recipient phone number not in allowed list",
+                    "type": "OAuthException",
+                    "code": 131030
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v23.0/123456789/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "messaging_product": "whatsapp",
+                "contacts": [{ "input": "+1234567890", "wa_id": "1234567890" }],
+                "messages": [{ "id": "wamid.ok" }]
+            })))
+            .mount(&server)
+            .await;
+        
+        let client = WhatsAppClient::new(create_mock_config(&server.uri())).unwrap();
+        let messages = vec![
+            batch_text("+1234567890"),
+            batch_text("+1999999999"),
+            batch_text("+1234567890"),
+        ];
+        
+        let results = client.send_batch(messages, 3).await;
+        
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().message_id(), Some("wamid.ok"));
+        assert!(matches!(results[1], Err(WhatsAppError::ApiError { code: 131030, .. })));
+        assert_eq!(results[2].as_ref().unwrap().message_id(), Some("wamid.ok"));
+    }
+    
+    #[tokio::test]
+    async fn test_slow_server_times_out_as_retryable() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v23.0/1013859600285441"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_delay(Duration::from_secs(2))
+                .set_body_json(json!({ "url": "https://example.com/media", "mime_type": "image/jpeg", "id": "1013859600285441" })))
+            .mount(&server)
+            .await;
+        
+        let config = WhatsAppClientConfig {
+            request_timeout_ms: 100,
+            ..create_mock_config(&server.uri())
+        };
+        let client = WhatsAppClient::new(config).unwrap();
+        
+        let error = client.get_media_url("1013859600285441").await.unwrap_err();
+        assert!(matches!(error, WhatsAppError::Timeout { timeout_ms: 100 }), "unexpected error: {:?}", error);
+        assert!(error.is_retryable());
+    }
 }
@@ -0,0 +1,130 @@
+use crate::client::responses::WhatsAppMessageResponse;
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// Bounded record of recently sent idempotency keys
+///
+/// Each key remembers the API response of its original send so a duplicate
+/// can be answered without calling WhatsApp again. Entries expire after
+/// `window`, and the oldest entry is evicted once `capacity` is reached.
+#[derive(Debug)]
+pub struct SentMessageCache {
+    /// Response of the original send, keyed by idempotency key
+    entries: HashMap<String, (Instant, WhatsAppMessageResponse)>,
+    /// Keys in insertion order, used for expiry and eviction
+    order: VecDeque<String>,
+    /// Maximum number of keys remembered at once
+    capacity: usize,
+    /// How long a key suppresses duplicate sends
+    window: Duration,
+}
+
+impl SentMessageCache {
+    /// Create a cache remembering at most `capacity` keys for `window`
+    pub fn new(capacity: usize, window: Duration) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            capacity,
+            window,
+        }
+    }
+
+    /// Response of an earlier send with this key, if it's still within the window
+    pub fn get(&mut self, key: &str) -> Option<WhatsAppMessageResponse> {
+        self.expire(Instant::now());
+        self.entries.get(key).map(|(_, response)| response.clone())
+    }
+
+    /// Remember a successful send
+    pub fn insert(&mut self, key: String, response: WhatsAppMessageResponse) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let now = Instant::now();
+        self.expire(now);
+
+        if self.entries.insert(key.clone(), (now, response)).is_some() {
+            self.order.retain(|existing| existing != &key);
+        }
+        self.order.push_back(key);
+
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    /// Number of keys currently remembered
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no keys are remembered
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drop keys older than the window
+    fn expire(&mut self, now: Instant) {
+        while let Some(oldest) = self.order.front() {
+            let expired = self.entries
+                .get(oldest)
+                .is_none_or(|(sent_at, _)| now.duration_since(*sent_at) >= self.window);
+            if !expired {
+                break;
+            }
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(id: &str) -> WhatsAppMessageResponse {
+        serde_json::from_value(serde_json::json!({
+            "messaging_product": "whatsapp",
+            "contacts": [{ "input": "+1234567890", "wa_id": "1234567890" }],
+            "messages": [{ "id": id }]
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_cache_returns_original_response() {
+        let mut cache = SentMessageCache::new(10, Duration::from_secs(60));
+        cache.insert("key-1".to_string(), response("wamid.1"));
+
+        assert_eq!(cache.get("key-1").unwrap().message_id(), Some("wamid.1"));
+        assert!(cache.get("key-2").is_none());
+    }
+
+    #[test]
+    fn test_cache_evicts_oldest_when_full() {
+        let mut cache = SentMessageCache::new(2, Duration::from_secs(60));
+        cache.insert("key-1".to_string(), response("wamid.1"));
+        cache.insert("key-2".to_string(), response("wamid.2"));
+        cache.insert("key-3".to_string(), response("wamid.3"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("key-1").is_none());
+        assert!(cache.get("key-3").is_some());
+    }
+
+    #[test]
+    fn test_cache_expires_after_window() {
+        let mut cache = SentMessageCache::new(10, Duration::from_millis(20));
+        cache.insert("key-1".to_string(), response("wamid.1"));
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(cache.get("key-1").is_none());
+        assert!(cache.is_empty());
+    }
+}
@@ -2,10 +2,10 @@ use crate::{
     errors::WhatsAppResult,
     client::{
         validation::{
-            validate_phone_number, validate_media_id, validate_url, 
+            validate_recipient, validate_media_reference, validate_media_id, validate_url, 
             validate_mime_type, validate_file_size, MediaType
         },
-        message_types::mtrait::Message,
+        message_types::{mtrait::Message, media::MediaReference, recipient::{Recipient, RecipientType}},
     },
 };
 use serde::{Serialize, Deserialize};
@@ -18,9 +18,9 @@ use serde::{Serialize, Deserialize};
 pub struct AudioMessage {
     /// Always "whatsapp" for WhatsApp Business API
     messaging_product: String,
-    /// Recipient type - always "individual" for direct messages
-    recipient_type: String,
-    /// Recipient's phone number in E.164 format
+    /// Whether `to` is a user's phone number or a group ID
+    recipient_type: RecipientType,
+    /// Recipient's phone number in E.164 format, or a group ID
     to: String,
     /// Message type identifier
     #[serde(rename = "type")]
@@ -47,12 +47,9 @@ impl Message for AudioMessage {
 /// The media ID approach is recommended for better performance and reliability.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct AudioContent {
-    /// Media ID for uploaded audio (recommended approach)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    id: Option<String>,
-    /// URL for hosted audio (not recommended)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    link: Option<String>,
+    /// Uploaded media ID (recommended) or hosted URL
+    #[serde(flatten)]
+    media: MediaReference,
 }
 
 impl AudioMessage {
@@ -62,7 +59,7 @@ impl AudioMessage {
     /// The audio must be uploaded to WhatsApp first using the media upload API.
     /// 
     /// # Arguments
-    /// * `to` - Recipient phone number in E.164 format
+    /// * `to` - Recipient phone number in E.164 format, or a `Recipient`
     /// * `media_id` - ID of the uploaded audio file from WhatsApp's media API
     /// 
     /// # Example
@@ -71,19 +68,19 @@ impl AudioMessage {
     /// let message = AudioMessage::from_media_id("+1234567890", "1013859600285441")?;
     /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
     /// ```
-    pub fn from_media_id(to: &str, media_id: &str) -> WhatsAppResult<Self> {
+    pub fn from_media_id(to: impl Into<Recipient>, media_id: &str) -> WhatsAppResult<Self> {
         // Validate inputs
-        validate_phone_number(to)?;
+        let to = to.into();
+        to.validate()?;
         validate_media_id(media_id)?;
         
         Ok(Self {
             messaging_product: "whatsapp".to_string(),
-            recipient_type: "individual".to_string(),
-            to: to.to_string(),
+            recipient_type: to.recipient_type(),
+            to: to.id().to_string(),
             message_type: "audio".to_string(),
             audio: AudioContent {
-                id: Some(media_id.to_string()),
-                link: None,
+                media: MediaReference::Id(media_id.to_string()),
             },
         })
     }
@@ -95,7 +92,7 @@ impl AudioMessage {
     /// adds latency and potential failure points.
     /// 
     /// # Arguments
-    /// * `to` - Recipient phone number in E.164 format
+    /// * `to` - Recipient phone number in E.164 format, or a `Recipient`
     /// * `audio_url` - URL to the hosted audio file
     /// 
     /// # Example
@@ -107,19 +104,19 @@ impl AudioMessage {
     /// )?;
     /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
     /// ```
-    pub fn from_url(to: &str, audio_url: &str) -> WhatsAppResult<Self> {
+    pub fn from_url(to: impl Into<Recipient>, audio_url: &str) -> WhatsAppResult<Self> {
         // Validate inputs
-        validate_phone_number(to)?;
+        let to = to.into();
+        to.validate()?;
         validate_url(audio_url)?;
         
         Ok(Self {
             messaging_product: "whatsapp".to_string(),
-            recipient_type: "individual".to_string(),
-            to: to.to_string(),
+            recipient_type: to.recipient_type(),
+            to: to.id().to_string(),
             message_type: "audio".to_string(),
             audio: AudioContent {
-                id: None,
-                link: Some(audio_url.to_string()),
+                media: MediaReference::Link(audio_url.to_string()),
             },
         })
     }
@@ -127,17 +124,32 @@ impl AudioMessage {
     
     /// Get the media ID if this message uses uploaded media
     pub fn media_id(&self) -> Option<&str> {
-        self.audio.id.as_deref()
+        self.audio.media.id()
     }
     
     /// Get the URL if this message uses hosted media
     pub fn media_url(&self) -> Option<&str> {
-        self.audio.link.as_deref()
+        self.audio.media.link()
     }
     
     /// Check if this message uses uploaded media (recommended)
     pub fn uses_uploaded_media(&self) -> bool {
-        self.audio.id.is_some()
+        self.audio.media.is_uploaded()
+    }
+    
+    /// Get whether the message goes to a user or a group
+    pub fn recipient_type(&self) -> RecipientType {
+        self.recipient_type
+    }
+    
+    /// Validate the message as a whole
+    /// 
+    /// Messages built with the constructors always pass. This is meant for
+    /// messages that arrived some other way, e.g. deserialized from an event.
+    pub fn validate(&self) -> WhatsAppResult<()> {
+        validate_recipient(&self.to, self.recipient_type)?;
+        validate_media_reference(self.media_id(), self.media_url())?;
+        Ok(())
     }
     
     /// Validate audio file properties
@@ -282,6 +282,39 @@ impl ContactMessage {
     pub fn contact_name(&self) -> Option<&str> {
         self.contacts.first().map(|c| c.name.formatted_name.as_str())
     }
+    
+    /// Validate the message as a whole
+    /// 
+    /// Messages built with the constructors always pass. This is meant for
+    /// messages that arrived some other way, e.g. deserialized from an event.
+    pub fn validate(&self) -> WhatsAppResult<()> {
+        validate_phone_number(&self.to)?;
+        
+        if self.contacts.is_empty() {
+            return Err(WhatsAppError::InvalidMessageContent(
+                "Contact messages must have at least one contact".to_string()
+            ));
+        }
+        
+        for contact in &self.contacts {
+            if contact.name.formatted_name.is_empty() {
+                return Err(WhatsAppError::InvalidMessageContent(
+                    "Contact formatted name cannot be empty".to_string()
+                ));
+            }
+            
+            if let Some(birthday) = &contact.birthday
+                && !birthday.is_empty()
+                && !is_valid_date_format(birthday)
+            {
+                return Err(WhatsAppError::InvalidMessageContent(
+                    "Birthday must be in YYYY-MM-DD format".to_string()
+                ));
+            }
+        }
+        
+        Ok(())
+    }
 }
 
 /// Helper function to validate date format (YYYY-MM-DD)
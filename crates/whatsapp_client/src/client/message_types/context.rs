@@ -0,0 +1,83 @@
+use crate::client::message_types::mtrait::Message;
+use serde::{Serialize, Deserialize};
+
+/// Reply context for an outbound message
+/// 
+/// Setting the context makes WhatsApp render the message as a reply to
+/// an earlier message in the conversation, quoting it in the chat UI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplyContext {
+    /// ID of the message being replied to (e.g. "wamid.HBgL...")
+    pub message_id: String,
+}
+
+/// A message payload paired with an optional reply context
+/// 
+/// The context is serialized alongside the message's own fields as
+/// `{"context": {"message_id": "..."}}`, and left out entirely when
+/// the message isn't a reply.
+#[derive(Debug, Serialize)]
+pub struct ContextualMessage<'a, T> {
+    /// The message being sent
+    #[serde(flatten)]
+    message: &'a T,
+    /// Message this one replies to, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<ReplyContext>,
+}
+
+impl<'a, T: Message> ContextualMessage<'a, T> {
+    /// Wrap a message, optionally replying to `context_message_id`
+    pub fn new(message: &'a T, context_message_id: Option<&str>) -> Self {
+        Self {
+            message,
+            context: context_message_id.map(|message_id| ReplyContext {
+                message_id: message_id.to_string(),
+            }),
+        }
+    }
+    
+    /// Get the reply context, if any
+    pub fn context(&self) -> Option<&ReplyContext> {
+        self.context.as_ref()
+    }
+}
+
+impl<T: Message> Message for ContextualMessage<'_, T> {
+    fn recipient(&self) -> &str {
+        self.message.recipient()
+    }
+
+    fn message_type(&self) -> &str {
+        self.message.message_type()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::message_types::TextMessage;
+    
+    #[test]
+    fn test_text_reply_serializes_context() {
+        let message = TextMessage::new("+16505551234", "Thanks for your order!").unwrap();
+        let reply = ContextualMessage::new(&message, Some("wamid.HBgLMTY1MDM4Nzk0MzkVAgARGBJDQjZCMzlEQUE4OTJBMTE4RTUA"));
+        
+        let json_output = serde_json::to_string(&reply).unwrap();
+        let expected_json = r#"{"messaging_product":"whatsapp","recipient_type":"individual","to":"+16505551234","type":"text","text":{"body":"Thanks for your order!"},"context":{"message_id":"wamid.HBgLMTY1MDM4Nzk0MzkVAgARGBJDQjZCMzlEQUE4OTJBMTE4RTUA"}}"#;
+        
+        assert_eq!(json_output, expected_json);
+        assert_eq!(reply.recipient(), "+16505551234");
+        assert_eq!(reply.message_type(), "text");
+    }
+    
+    #[test]
+    fn test_message_without_context_has_no_context_key() {
+        let message = TextMessage::new("+16505551234", "Hello, world!").unwrap();
+        let wrapped = ContextualMessage::new(&message, None);
+        
+        let json = serde_json::to_value(&wrapped).unwrap();
+        assert!(json.get("context").is_none());
+        assert_eq!(json, serde_json::to_value(&message).unwrap());
+    }
+}
@@ -2,10 +2,10 @@ use crate::{
     errors::WhatsAppResult,
     client::{
         validation::{
-            validate_phone_number, validate_media_id, validate_url, 
+            validate_recipient, validate_media_reference, validate_media_id, validate_url, 
             validate_mime_type, validate_file_size, validate_caption, MediaType
         },
-        message_types::mtrait::Message,
+        message_types::{mtrait::Message, media::MediaReference, recipient::{Recipient, RecipientType}},
     },
 };
 use serde::{Serialize, Deserialize};
@@ -19,9 +19,9 @@ use serde::{Serialize, Deserialize};
 pub struct DocumentMessage {
     /// Always "whatsapp" for WhatsApp Business API
     messaging_product: String,
-    /// Recipient type - always "individual" for direct messages
-    recipient_type: String,
-    /// Recipient's phone number in E.164 format
+    /// Whether `to` is a user's phone number or a group ID
+    recipient_type: RecipientType,
+    /// Recipient's phone number in E.164 format, or a group ID
     to: String,
     /// Message type identifier
     #[serde(rename = "type")]
@@ -48,12 +48,9 @@ impl Message for DocumentMessage {
 /// Documents can include an optional caption and filename.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct DocumentContent {
-    /// Media ID for uploaded document (recommended approach)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    id: Option<String>,
-    /// URL for hosted document (not recommended)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    link: Option<String>,
+    /// Uploaded media ID (recommended) or hosted URL
+    #[serde(flatten)]
+    media: MediaReference,
     /// Optional caption text (max 1024 characters)
     #[serde(skip_serializing_if = "Option::is_none")]
     caption: Option<String>,
@@ -69,7 +66,7 @@ impl DocumentMessage {
     /// The document must be uploaded to WhatsApp first using the media upload API.
     /// 
     /// # Arguments
-    /// * `to` - Recipient phone number in E.164 format
+    /// * `to` - Recipient phone number in E.164 format, or a `Recipient`
     /// * `media_id` - ID of the uploaded document file from WhatsApp's media API
     /// 
     /// # Example
@@ -78,19 +75,19 @@ impl DocumentMessage {
     /// let message = DocumentMessage::from_media_id("+1234567890", "1013859600285441")?;
     /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
     /// ```
-    pub fn from_media_id(to: &str, media_id: &str) -> WhatsAppResult<Self> {
+    pub fn from_media_id(to: impl Into<Recipient>, media_id: &str) -> WhatsAppResult<Self> {
         // Validate inputs
-        validate_phone_number(to)?;
+        let to = to.into();
+        to.validate()?;
         validate_media_id(media_id)?;
         
         Ok(Self {
             messaging_product: "whatsapp".to_string(),
-            recipient_type: "individual".to_string(),
-            to: to.to_string(),
+            recipient_type: to.recipient_type(),
+            to: to.id().to_string(),
             message_type: "document".to_string(),
             document: DocumentContent {
-                id: Some(media_id.to_string()),
-                link: None,
+                media: MediaReference::Id(media_id.to_string()),
                 caption: None,
                 filename: None,
             },
@@ -104,7 +101,7 @@ impl DocumentMessage {
     /// adds latency and potential failure points.
     /// 
     /// # Arguments
-    /// * `to` - Recipient phone number in E.164 format
+    /// * `to` - Recipient phone number in E.164 format, or a `Recipient`
     /// * `document_url` - URL to the hosted document file
     /// 
     /// # Example
@@ -116,19 +113,19 @@ impl DocumentMessage {
     /// )?;
     /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
     /// ```
-    pub fn from_url(to: &str, document_url: &str) -> WhatsAppResult<Self> {
+    pub fn from_url(to: impl Into<Recipient>, document_url: &str) -> WhatsAppResult<Self> {
         // Validate inputs
-        validate_phone_number(to)?;
+        let to = to.into();
+        to.validate()?;
         validate_url(document_url)?;
         
         Ok(Self {
             messaging_product: "whatsapp".to_string(),
-            recipient_type: "individual".to_string(),
-            to: to.to_string(),
+            recipient_type: to.recipient_type(),
+            to: to.id().to_string(),
             message_type: "document".to_string(),
             document: DocumentContent {
-                id: None,
-                link: Some(document_url.to_string()),
+                media: MediaReference::Link(document_url.to_string()),
                 caption: None,
                 filename: None,
             },
@@ -174,12 +171,12 @@ impl DocumentMessage {
     
     /// Get the media ID if this message uses uploaded media
     pub fn media_id(&self) -> Option<&str> {
-        self.document.id.as_deref()
+        self.document.media.id()
     }
     
     /// Get the URL if this message uses hosted media
     pub fn media_url(&self) -> Option<&str> {
-        self.document.link.as_deref()
+        self.document.media.link()
     }
     
     /// Get the caption text if set
@@ -194,7 +191,25 @@ impl DocumentMessage {
     
     /// Check if this message uses uploaded media (recommended)
     pub fn uses_uploaded_media(&self) -> bool {
-        self.document.id.is_some()
+        self.document.media.is_uploaded()
+    }
+    
+    /// Get whether the message goes to a user or a group
+    pub fn recipient_type(&self) -> RecipientType {
+        self.recipient_type
+    }
+    
+    /// Validate the message as a whole
+    /// 
+    /// Messages built with the constructors always pass. This is meant for
+    /// messages that arrived some other way, e.g. deserialized from an event.
+    pub fn validate(&self) -> WhatsAppResult<()> {
+        validate_recipient(&self.to, self.recipient_type)?;
+        validate_media_reference(self.media_id(), self.media_url())?;
+        if let Some(caption) = self.caption() {
+            validate_caption(caption)?;
+        }
+        Ok(())
     }
     
     /// Validate document file properties
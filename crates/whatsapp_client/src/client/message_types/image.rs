@@ -2,10 +2,10 @@ use crate::{
     errors::WhatsAppResult,
     client::{
         validation::{
-            validate_phone_number, validate_media_id, validate_url, 
+            validate_recipient, validate_media_reference, validate_media_id, validate_url, 
             validate_mime_type, validate_file_size, validate_caption, MediaType
         },
-        message_types::mtrait::Message,
+        message_types::{mtrait::Message, media::MediaReference, recipient::{Recipient, RecipientType}},
     },
 };
 use serde::{Serialize, Deserialize};
@@ -19,9 +19,9 @@ use serde::{Serialize, Deserialize};
 pub struct ImageMessage {
     /// Always "whatsapp" for WhatsApp Business API
     messaging_product: String,
-    /// Recipient type - always "individual" for direct messages
-    recipient_type: String,
-    /// Recipient's phone number in E.164 format
+    /// Whether `to` is a user's phone number or a group ID
+    recipient_type: RecipientType,
+    /// Recipient's phone number in E.164 format, or a group ID
     to: String,
     /// Message type identifier
     #[serde(rename = "type")]
@@ -49,12 +49,9 @@ impl Message for ImageMessage {
 /// Images can include an optional caption.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ImageContent {
-    /// Media ID for uploaded image (recommended approach)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    id: Option<String>,
-    /// URL for hosted image (not recommended)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    link: Option<String>,
+    /// Uploaded media ID (recommended) or hosted URL
+    #[serde(flatten)]
+    media: MediaReference,
     /// Optional caption text (max 1024 characters)
     #[serde(skip_serializing_if = "Option::is_none")]
     caption: Option<String>,
@@ -67,7 +64,7 @@ impl ImageMessage {
     /// The image must be uploaded to WhatsApp first using the media upload API.
     /// 
     /// # Arguments
-    /// * `to` - Recipient phone number in E.164 format
+    /// * `to` - Recipient phone number in E.164 format, or a `Recipient`
     /// * `media_id` - ID of the uploaded image file from WhatsApp's media API
     /// 
     /// # Example
@@ -76,19 +73,19 @@ impl ImageMessage {
     /// let message = ImageMessage::from_media_id("+1234567890", "1013859600285441")?;
     /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
     /// ```
-    pub fn from_media_id(to: &str, media_id: &str) -> WhatsAppResult<Self> {
+    pub fn from_media_id(to: impl Into<Recipient>, media_id: &str) -> WhatsAppResult<Self> {
         // Validate inputs
-        validate_phone_number(to)?;
+        let to = to.into();
+        to.validate()?;
         validate_media_id(media_id)?;
         
         Ok(Self {
             messaging_product: "whatsapp".to_string(),
-            recipient_type: "individual".to_string(),
-            to: to.to_string(),
+            recipient_type: to.recipient_type(),
+            to: to.id().to_string(),
             message_type: "image".to_string(),
             image: ImageContent {
-                id: Some(media_id.to_string()),
-                link: None,
+                media: MediaReference::Id(media_id.to_string()),
                 caption: None,
             },
         })
@@ -101,7 +98,7 @@ impl ImageMessage {
     /// adds latency and potential failure points.
     /// 
     /// # Arguments
-    /// * `to` - Recipient phone number in E.164 format
+    /// * `to` - Recipient phone number in E.164 format, or a `Recipient`
     /// * `image_url` - URL to the hosted image file
     /// 
     /// # Example
@@ -113,19 +110,19 @@ impl ImageMessage {
     /// )?;
     /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
     /// ```
-    pub fn from_url(to: &str, image_url: &str) -> WhatsAppResult<Self> {
+    pub fn from_url(to: impl Into<Recipient>, image_url: &str) -> WhatsAppResult<Self> {
         // Validate inputs
-        validate_phone_number(to)?;
+        let to = to.into();
+        to.validate()?;
         validate_url(image_url)?;
         
         Ok(Self {
             messaging_product: "whatsapp".to_string(),
-            recipient_type: "individual".to_string(),
-            to: to.to_string(),
+            recipient_type: to.recipient_type(),
+            to: to.id().to_string(),
             message_type: "image".to_string(),
             image: ImageContent {
-                id: None,
-                link: Some(image_url.to_string()),
+                media: MediaReference::Link(image_url.to_string()),
                 caption: None,
             },
         })
@@ -151,12 +148,12 @@ impl ImageMessage {
     
     /// Get the media ID if this message uses uploaded media
     pub fn media_id(&self) -> Option<&str> {
-        self.image.id.as_deref()
+        self.image.media.id()
     }
     
     /// Get the URL if this message uses hosted media
     pub fn media_url(&self) -> Option<&str> {
-        self.image.link.as_deref()
+        self.image.media.link()
     }
     
     /// Get the caption text if set
@@ -166,7 +163,25 @@ impl ImageMessage {
     
     /// Check if this message uses uploaded media (recommended)
     pub fn uses_uploaded_media(&self) -> bool {
-        self.image.id.is_some()
+        self.image.media.is_uploaded()
+    }
+    
+    /// Get whether the message goes to a user or a group
+    pub fn recipient_type(&self) -> RecipientType {
+        self.recipient_type
+    }
+    
+    /// Validate the message as a whole
+    /// 
+    /// Messages built with the constructors always pass. This is meant for
+    /// messages that arrived some other way, e.g. deserialized from an event.
+    pub fn validate(&self) -> WhatsAppResult<()> {
+        validate_recipient(&self.to, self.recipient_type)?;
+        validate_media_reference(self.media_id(), self.media_url())?;
+        if let Some(caption) = self.caption() {
+            validate_caption(caption)?;
+        }
+        Ok(())
     }
     
     /// Validate image file properties
@@ -204,6 +219,7 @@ impl ImageMessage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::errors::ValidationCode;
     use serde_json;
     
     #[test]
@@ -254,6 +270,34 @@ mod tests {
         assert!(json["image"]["caption"].is_null());
     }
     
+    #[test]
+    fn test_group_image_message_serialization() {
+        let message = ImageMessage::from_media_id(
+            Recipient::group("Y2FwaV9ncm91cDoxNzA1NTU1MDEz"),
+            "1013859600285441",
+        ).unwrap();
+        let json = serde_json::to_value(&message).unwrap();
+        
+        assert_eq!(json["recipient_type"], "group");
+        assert_eq!(json["to"], "Y2FwaV9ncm91cDoxNzA1NTU1MDEz");
+        assert_eq!(message.recipient_type(), RecipientType::Group);
+    }
+    
+    #[test]
+    fn test_image_recipient_validated_for_its_type() {
+        // A phone number is not a group, and a group ID is not a phone number
+        assert!(ImageMessage::from_url(Recipient::group("+1234567890"), "https://example.com/image.jpg").is_err());
+        assert!(ImageMessage::from_media_id("Y2FwaV9ncm91cDoxNzA1NTU1MDEz", "123456").is_err());
+        
+        // An edited group message must still carry a group ID
+        let mut json = serde_json::to_value(
+            ImageMessage::from_media_id(Recipient::group("Y2FwaV9ncm91cDoxNzA1NTU1MDEz"), "123456").unwrap()
+        ).unwrap();
+        json["to"] = serde_json::json!("+1234567890");
+        let message: ImageMessage = serde_json::from_value(json).unwrap();
+        assert_eq!(message.validate().unwrap_err().validation_code(), Some(ValidationCode::InvalidGroupId));
+    }
+    
     #[test]
     fn test_image_message_serialization_with_url_and_caption() {
         let message = ImageMessage::from_url(
@@ -324,4 +368,18 @@ mod tests {
     fn test_max_file_size() {
         assert_eq!(ImageMessage::max_file_size(), 5 * 1024 * 1024);
     }
+    
+    #[test]
+    fn test_validate_media_reference_invariant() {
+        let message = ImageMessage::from_media_id("+1234567890", "1013859600285441").unwrap();
+        assert!(message.validate().is_ok());
+        
+        // Payloads from elsewhere can't smuggle in both id and link, or neither
+        let mut json = serde_json::to_value(&message).unwrap();
+        json["image"]["link"] = serde_json::json!("https://example.com/image.jpg");
+        assert!(serde_json::from_value::<ImageMessage>(json.clone()).is_err());
+        
+        json["image"] = serde_json::json!({ "caption": "No media" });
+        assert!(serde_json::from_value::<ImageMessage>(json).is_err());
+    }
 }
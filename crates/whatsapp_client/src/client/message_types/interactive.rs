@@ -1,20 +1,83 @@
 use crate::{
-    errors::{WhatsAppError, WhatsAppResult},
+    errors::{ValidationCode, WhatsAppError, WhatsAppResult},
     client::{
         validation::{
             validate_phone_number, validate_button, validate_list_section,
-            validate_header_text, validate_footer_text, validate_text_message, validate_url
+            validate_header_text, validate_footer_text, validate_text_message, validate_https_url,
+            validate_media_reference,
+            validate_unique_ids, validate_unique_titles, text_length, MAX_FLOW_CTA_LENGTH, MAX_LIST_TITLE_LENGTH,
         },
-        message_types::mtrait::Message,
+        message_types::{mtrait::Message, media::MediaReference},
     },
 };
 use serde::{Serialize, Deserialize};
 
+/// A list section as `(title, rows)`, where each row is `(id, title, description)`
+pub type ListSectionSpec = (String, Vec<(String, String, Option<String>)>);
+
+/// A product list section as `(title, product_retailer_ids)`
+pub type ProductSectionSpec = (String, Vec<String>);
+
+/// Maximum sections in a product list message
+const MAX_PRODUCT_SECTIONS: usize = 10;
+
+/// Maximum products across all sections of a product list message
+const MAX_PRODUCT_LIST_ITEMS: usize = 30;
+
+/// Maximum rows across all sections of a list message
+const MAX_LIST_ROWS: usize = 10;
+
+/// Prefix of the row ID that `paginate_list` adds to every page but the last
+/// 
+/// The full ID is the prefix followed by the 1-based number of the page the
+/// row leads to, e.g. `next_page:2`.
+pub const NEXT_PAGE_ROW_ID_PREFIX: &str = "next_page:";
+
+/// Flow message version sent with every flow action
+const FLOW_MESSAGE_VERSION: &str = "3";
+
+/// What happens when the user taps a flow's CTA button
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowAction {
+    /// Open the flow at the screen given in the action payload
+    Navigate,
+    /// Ask the flow's data endpoint for the first screen
+    DataExchange,
+}
+
+/// Initial screen (and optional data) for a `navigate` flow action
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlowActionPayload {
+    /// ID of the first screen to show
+    pub screen: String,
+    /// Input data for the first screen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl FlowActionPayload {
+    /// Open the flow at `screen` without initial data
+    pub fn new(screen: &str) -> Self {
+        Self {
+            screen: screen.to_string(),
+            data: None,
+        }
+    }
+
+    /// Pass input data to the first screen
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+}
+
 /// An interactive message that can be sent via WhatsApp
 /// 
 /// Interactive messages provide structured ways for users to respond,
-/// including buttons, lists, call-to-action URLs, and location requests.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// including buttons, lists, call-to-action URLs, location requests and
+/// products from a commerce catalog.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InteractiveMessage {
     /// Always "whatsapp" for WhatsApp Business API
     messaging_product: String,
@@ -44,8 +107,11 @@ impl Message for InteractiveMessage {
 /// Interactive message content structure
 /// 
 /// This contains the type of interaction and all associated content.
-/// Different interaction types have different required fields.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Different interaction types have different required fields. The action
+/// is deserialized as the variant that `interactive_type` names, so a
+/// payload can't come back as a different kind of action than was sent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawInteractiveContent")]
 struct InteractiveContent {
     /// Type of interactive message
     #[serde(rename = "type")]
@@ -62,8 +128,35 @@ struct InteractiveContent {
     action: InteractiveAction,
 }
 
+/// Wire shape of interactive content, before the action is read as the
+/// variant its type names
+#[derive(Deserialize)]
+struct RawInteractiveContent {
+    #[serde(rename = "type")]
+    interactive_type: String,
+    header: Option<InteractiveHeader>,
+    body: InteractiveBody,
+    footer: Option<InteractiveFooter>,
+    action: serde_json::Value,
+}
+
+impl TryFrom<RawInteractiveContent> for InteractiveContent {
+    type Error = WhatsAppError;
+
+    fn try_from(raw: RawInteractiveContent) -> Result<Self, Self::Error> {
+        let action = InteractiveAction::from_value(&raw.interactive_type, raw.action)?;
+        Ok(InteractiveContent {
+            interactive_type: raw.interactive_type,
+            header: raw.header,
+            body: raw.body,
+            footer: raw.footer,
+            action,
+        })
+    }
+}
+
 /// Header for interactive messages
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct InteractiveHeader {
     /// Header type (text, image, video, document)
     #[serde(rename = "type")]
@@ -82,34 +175,27 @@ struct InteractiveHeader {
     document: Option<MediaReference>,
 }
 
-/// Media reference for headers
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct MediaReference {
-    /// Media ID for uploaded media
-    #[serde(skip_serializing_if = "Option::is_none")]
-    id: Option<String>,
-    /// URL for hosted media
-    #[serde(skip_serializing_if = "Option::is_none")]
-    link: Option<String>,
-}
-
 /// Body text for interactive messages
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct InteractiveBody {
     /// Body text content
     text: String,
 }
 
 /// Footer text for interactive messages
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct InteractiveFooter {
     /// Footer text content
     text: String,
 }
 
 /// Action configuration for interactive messages
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
+///
+/// Serialized untagged, as WhatsApp expects. Deserializing rejects unknown
+/// fields so each action only matches its own variant, and
+/// `InteractiveContent` checks the result against its `interactive_type`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
 enum InteractiveAction {
     /// Button actions (up to 3 buttons)
     Buttons {
@@ -125,14 +211,29 @@ enum InteractiveAction {
         name: String,
         parameters: CtaUrlParameters,
     },
+    /// WhatsApp Flow launched from a CTA button
+    Flow {
+        name: String,
+        parameters: FlowParameters,
+    },
     /// Location request
     LocationRequest {
         name: String,
     },
+    /// Single product from a catalog
+    Product {
+        catalog_id: String,
+        product_retailer_id: String,
+    },
+    /// Products from a catalog, grouped into sections
+    ProductList {
+        catalog_id: String,
+        sections: Vec<ProductSection>,
+    },
 }
 
 /// Individual button for button-type interactive messages
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct InteractiveButton {
     /// Always "reply" for reply buttons
     #[serde(rename = "type")]
@@ -142,7 +243,7 @@ struct InteractiveButton {
 }
 
 /// Button reply configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct ButtonReply {
     /// Unique button identifier
     id: String,
@@ -151,7 +252,7 @@ struct ButtonReply {
 }
 
 /// Section for list-type interactive messages
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct InteractiveListSection {
     /// Section title
     title: String,
@@ -160,7 +261,7 @@ struct InteractiveListSection {
 }
 
 /// Row in a list section
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct InteractiveListRow {
     /// Unique row identifier
     id: String,
@@ -171,8 +272,24 @@ struct InteractiveListRow {
     description: Option<String>,
 }
 
+/// Section for product list messages
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ProductSection {
+    /// Section title
+    title: String,
+    /// Products in this section
+    product_items: Vec<ProductItem>,
+}
+
+/// Product in a product list section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ProductItem {
+    /// Retailer ID of the product in the catalog
+    product_retailer_id: String,
+}
+
 /// Parameters for call-to-action URL buttons
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct CtaUrlParameters {
     /// Button display text
     display_text: String,
@@ -180,6 +297,55 @@ struct CtaUrlParameters {
     url: String,
 }
 
+/// Parameters for flow actions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct FlowParameters {
+    /// Flow message version (always "3")
+    flow_message_version: String,
+    /// Token identifying this flow session to the business
+    flow_token: String,
+    /// ID of the published flow
+    flow_id: String,
+    /// Text on the button that opens the flow
+    flow_cta: String,
+    /// Whether to navigate to a screen or exchange data first
+    flow_action: FlowAction,
+    /// First screen for `navigate` actions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    flow_action_payload: Option<FlowActionPayload>,
+}
+
+impl InteractiveAction {
+    /// The `interactive_type` this action is sent with
+    fn interactive_type(&self) -> &'static str {
+        match self {
+            InteractiveAction::Buttons { .. } => "button",
+            InteractiveAction::List { .. } => "list",
+            InteractiveAction::CtaUrl { .. } => "cta_url",
+            InteractiveAction::Flow { .. } => "flow",
+            InteractiveAction::LocationRequest { .. } => "location_request_message",
+            InteractiveAction::Product { .. } => "product",
+            InteractiveAction::ProductList { .. } => "product_list",
+        }
+    }
+
+    /// Read the action of an `interactive_type` message from its JSON
+    fn from_value(interactive_type: &str, value: serde_json::Value) -> WhatsAppResult<Self> {
+        let action: InteractiveAction = serde_json::from_value(value).map_err(|e| {
+            WhatsAppError::InvalidMessageContent(format!(
+                "invalid {} action: {}", interactive_type, e
+            ))
+        })?;
+        if action.interactive_type() != interactive_type {
+            return Err(WhatsAppError::InvalidMessageContent(format!(
+                "{} action in a {} interactive message",
+                action.interactive_type(), interactive_type
+            )));
+        }
+        Ok(action)
+    }
+}
+
 impl InteractiveMessage {
     /// Create a new interactive message with reply buttons
     /// 
@@ -219,6 +385,8 @@ impl InteractiveMessage {
                     "Interactive messages must have 1-3 buttons".to_string()
             ));
         }
+        validate_unique_ids("button", buttons.iter().map(|(id, _)| id.as_str()))?;
+        validate_unique_titles("button", buttons.iter().map(|(_, title)| title.as_str()))?;
 
         // Validate and convert buttons
         let interactive_buttons: Result<Vec<InteractiveButton>, WhatsAppError> = buttons
@@ -268,7 +436,7 @@ impl InteractiveMessage {
         to: &str,
         body_text: &str,
         button_text: &str,
-        sections: Vec<(String, Vec<(String, String, Option<String>)>)>,
+        sections: Vec<ListSectionSpec>,
     ) -> WhatsAppResult<Self> {
         // Validate inputs
         validate_phone_number(to)?;
@@ -288,6 +456,14 @@ impl InteractiveMessage {
                     format!("List messages can have at most 10 total rows, got {}", total_rows)
             ));
         }
+        validate_unique_ids(
+            "row",
+            sections.iter().flat_map(|(_, rows)| rows.iter().map(|(id, _, _)| id.as_str())),
+        )?;
+        validate_unique_titles(
+            "row",
+            sections.iter().flat_map(|(_, rows)| rows.iter().map(|(_, title, _)| title.as_str())),
+        )?;
 
         // Validate and convert sections
         let interactive_sections: Result<Vec<InteractiveListSection>, WhatsAppError> = sections
@@ -333,6 +509,117 @@ impl InteractiveMessage {
         })
     }
 
+    /// Split a long list into several list messages of at most 10 rows each
+    /// 
+    /// WhatsApp caps list messages at 10 rows, so larger menus (a product
+    /// catalog, say) have to be sent as pages. Rows keep their order and stay
+    /// under their section's title; a section that doesn't fit on one page
+    /// continues on the next under the same title. Every page but the last
+    /// ends with a "Next page" row whose ID is [`NEXT_PAGE_ROW_ID_PREFIX`]
+    /// followed by the next page's number, so the webhook handler can send
+    /// that page when it's tapped. Each page's footer shows "Page N of M".
+    /// 
+    /// A list that already fits in one message comes back as a single
+    /// message with no "Next page" row.
+    /// 
+    /// # Arguments
+    /// * `to` - Recipient phone number in E.164 format
+    /// * `body_text` - Main message text, repeated on every page
+    /// * `button_text` - Text for the button that opens the list
+    /// * `all_sections` - Every section to show, with any number of rows
+    /// 
+    /// # Example
+    /// ```
+    /// # use whatsapp_client::client::message_types::InteractiveMessage;
+    /// let rows = (1..=25)
+    ///     .map(|n| (format!("item_{}", n), format!("Item {}", n), None))
+    ///     .collect();
+    /// let pages = InteractiveMessage::paginate_list(
+    ///     "+1234567890",
+    ///     "Browse our catalog:",
+    ///     "View items",
+    ///     vec![("Catalog".to_string(), rows)],
+    /// )?;
+    /// assert_eq!(pages.len(), 3);
+    /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
+    /// ```
+    pub fn paginate_list(
+        to: &str,
+        body_text: &str,
+        button_text: &str,
+        all_sections: Vec<ListSectionSpec>,
+    ) -> WhatsAppResult<Vec<Self>> {
+        // Sections with no rows would vanish silently, and IDs must stay
+        // unique across pages because they all reach the same handler
+        for (title, rows) in &all_sections {
+            if rows.is_empty() {
+                validate_list_section(title, rows)?;
+            }
+        }
+        validate_unique_ids(
+            "row",
+            all_sections.iter().flat_map(|(_, rows)| rows.iter().map(|(id, _, _)| id.as_str())),
+        )?;
+        
+        let mut rows_left: usize = all_sections.iter().map(|(_, rows)| rows.len()).sum();
+        if rows_left == 0 {
+            return Err(WhatsAppError::InvalidMessageContent(
+                "List messages must have 1-10 sections".to_string()
+            ));
+        }
+        
+        let mut pages: Vec<Vec<ListSectionSpec>> = Vec::new();
+        let mut page: Vec<ListSectionSpec> = Vec::new();
+        let mut page_rows = 0;
+        
+        for (title, rows) in all_sections {
+            let mut rows = rows.into_iter().peekable();
+            while rows.peek().is_some() {
+                // The last page doesn't need a slot for the "Next page" row
+                let capacity = if page_rows + rows_left <= MAX_LIST_ROWS {
+                    MAX_LIST_ROWS
+                } else {
+                    MAX_LIST_ROWS - 1
+                };
+                let chunk: Vec<_> = rows.by_ref().take(capacity - page_rows).collect();
+                page_rows += chunk.len();
+                rows_left -= chunk.len();
+                page.push((title.clone(), chunk));
+                
+                if page_rows == capacity && rows_left > 0 {
+                    pages.push(std::mem::take(&mut page));
+                    page_rows = 0;
+                }
+            }
+        }
+        if !page.is_empty() {
+            pages.push(page);
+        }
+        
+        let page_count = pages.len();
+        pages
+            .into_iter()
+            .enumerate()
+            .map(|(index, mut sections)| {
+                let page_number = index + 1;
+                if page_number < page_count {
+                    sections.push(("More".to_string(), vec![(
+                        format!("{}{}", NEXT_PAGE_ROW_ID_PREFIX, page_number + 1),
+                        "Next page".to_string(),
+                        Some(format!("Show page {} of {}", page_number + 1, page_count)),
+                    )]));
+                }
+                
+                let message = Self::with_list(to, body_text, button_text, sections)?;
+                if page_count > 1 {
+                    message.with_footer(&format!("Page {} of {}", page_number, page_count))
+                } else {
+                    Ok(message)
+                }
+            })
+            .collect()
+    }
+
     /// Create a call-to-action URL button message
     /// 
     /// CTA URL buttons allow users to visit a website by tapping a button.
@@ -348,7 +635,7 @@ impl InteractiveMessage {
         validate_phone_number(to)?;
         validate_text_message(body_text)?;
         validate_button("cta_button", button_text)?;
-        validate_url(url)?;
+        validate_https_url(url)?;
 
         Ok(Self {
             messaging_product: "whatsapp".to_string(),
@@ -373,6 +660,128 @@ impl InteractiveMessage {
         })
     }
 
+    /// Create a WhatsApp Flow message
+    /// 
+    /// Flows embed multi-screen forms in the chat. The user opens the flow
+    /// by tapping a CTA button; `navigate` actions open it at the screen in
+    /// `flow_action_payload`, while `data_exchange` actions ask the flow's
+    /// endpoint for the first screen and must not carry a payload.
+    /// 
+    /// # Arguments
+    /// * `to` - Recipient phone number in E.164 format
+    /// * `body_text` - Main message text
+    /// * `flow_id` - ID of the published flow
+    /// * `flow_token` - Token identifying this flow session
+    /// * `flow_cta` - Button text (max 30 characters)
+    /// * `flow_action` - `Navigate` or `DataExchange`
+    /// * `flow_action_payload` - First screen, required for `Navigate`
+    /// 
+    /// # Example
+    /// ```
+    /// # use whatsapp_client::client::message_types::{InteractiveMessage, FlowAction, FlowActionPayload};
+    /// let message = InteractiveMessage::with_flow(
+    ///     "+1234567890",
+    ///     "Let's get you set up",
+    ///     "1234567890123",
+    ///     "onboarding-42",
+    ///     "Start",
+    ///     FlowAction::Navigate,
+    ///     Some(FlowActionPayload::new("WELCOME")),
+    /// )?;
+    /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
+    /// ```
+    pub fn with_flow(
+        to: &str,
+        body_text: &str,
+        flow_id: &str,
+        flow_token: &str,
+        flow_cta: &str,
+        flow_action: FlowAction,
+        flow_action_payload: Option<FlowActionPayload>,
+    ) -> WhatsAppResult<Self> {
+        // Validate inputs
+        validate_phone_number(to)?;
+        validate_text_message(body_text)?;
+        Self::validate_flow(flow_id, flow_token, flow_cta, flow_action, flow_action_payload.as_ref())?;
+
+        Ok(Self {
+            messaging_product: "whatsapp".to_string(),
+            recipient_type: "individual".to_string(),
+            to: to.to_string(),
+            message_type: "interactive".to_string(),
+            interactive: InteractiveContent {
+                interactive_type: "flow".to_string(),
+                header: None,
+                body: InteractiveBody {
+                    text: body_text.to_string(),
+                },
+                footer: None,
+                action: InteractiveAction::Flow {
+                    name: "flow".to_string(),
+                    parameters: FlowParameters {
+                        flow_message_version: FLOW_MESSAGE_VERSION.to_string(),
+                        flow_token: flow_token.to_string(),
+                        flow_id: flow_id.to_string(),
+                        flow_cta: flow_cta.to_string(),
+                        flow_action,
+                        flow_action_payload,
+                    },
+                },
+            },
+        })
+    }
+
+    /// Validate flow parameters
+    /// 
+    /// Shared with the builder so it can report problems before building.
+    pub(crate) fn validate_flow(
+        flow_id: &str,
+        flow_token: &str,
+        flow_cta: &str,
+        flow_action: FlowAction,
+        flow_action_payload: Option<&FlowActionPayload>,
+    ) -> WhatsAppResult<()> {
+        if flow_id.is_empty() {
+            return Err(WhatsAppError::validation(ValidationCode::FlowIdEmpty, "flow_id", "cannot be empty"));
+        }
+
+        if flow_token.is_empty() {
+            return Err(WhatsAppError::validation(ValidationCode::FlowTokenEmpty, "flow_token", "cannot be empty"));
+        }
+
+        if flow_cta.is_empty() {
+            return Err(WhatsAppError::validation(ValidationCode::FlowCtaEmpty, "flow_cta", "cannot be empty"));
+        }
+
+        if text_length(flow_cta) > MAX_FLOW_CTA_LENGTH {
+            return Err(WhatsAppError::validation(
+                ValidationCode::FlowCtaTooLong,
+                "flow_cta",
+                format!("too long: {} characters (max {})",
+                       text_length(flow_cta), MAX_FLOW_CTA_LENGTH)
+            ));
+        }
+
+        match (flow_action, flow_action_payload) {
+            (FlowAction::Navigate, None) => Err(WhatsAppError::validation(
+                ValidationCode::FlowPayloadRequired,
+                "flow_action_payload",
+                "required for navigate actions"
+            )),
+            (FlowAction::Navigate, Some(payload)) if payload.screen.is_empty() => Err(WhatsAppError::validation(
+                ValidationCode::FlowScreenEmpty,
+                "flow_action_payload.screen",
+                "cannot be empty"
+            )),
+            (FlowAction::DataExchange, Some(_)) => Err(WhatsAppError::validation(
+                ValidationCode::FlowPayloadNotAllowed,
+                "flow_action_payload",
+                "must be omitted for data_exchange actions"
+            )),
+            _ => Ok(()),
+        }
+    }
+
     /// Create a location request message
     /// 
     /// Location request messages prompt users to share their current location.
@@ -401,8 +810,310 @@ impl InteractiveMessage {
         })
     }
 
+    /// Create a single product message
+    /// 
+    /// Shows one item from a commerce catalog connected to the business
+    /// account. The product's name, image and price come from the catalog.
+    /// 
+    /// # Arguments
+    /// * `to` - Recipient phone number in E.164 format
+    /// * `body_text` - Main message text
+    /// * `catalog_id` - ID of the catalog connected to the business account
+    /// * `product_retailer_id` - Retailer ID of the product in that catalog
+    /// 
+    /// # Example
+    /// ```
+    /// # use whatsapp_client::client::message_types::InteractiveMessage;
+    /// let message = InteractiveMessage::with_product(
+    ///     "+1234567890",
+    ///     "Back in stock!",
+    ///     "367025965434465",
+    ///     "sku-blue-mug",
+    /// )?;
+    /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
+    /// ```
+    pub fn with_product(
+        to: &str,
+        body_text: &str,
+        catalog_id: &str,
+        product_retailer_id: &str,
+    ) -> WhatsAppResult<Self> {
+        // Validate inputs
+        validate_phone_number(to)?;
+        validate_text_message(body_text)?;
+        Self::validate_product(catalog_id, product_retailer_id)?;
+
+        Ok(Self {
+            messaging_product: "whatsapp".to_string(),
+            recipient_type: "individual".to_string(),
+            to: to.to_string(),
+            message_type: "interactive".to_string(),
+            interactive: InteractiveContent {
+                interactive_type: "product".to_string(),
+                header: None,
+                body: InteractiveBody {
+                    text: body_text.to_string(),
+                },
+                footer: None,
+                action: InteractiveAction::Product {
+                    catalog_id: catalog_id.to_string(),
+                    product_retailer_id: product_retailer_id.to_string(),
+                },
+            },
+        })
+    }
+
+    /// Create a product list message
+    /// 
+    /// Shows up to 30 catalog items grouped into up to 10 sections. WhatsApp
+    /// requires a text header on product lists, so it's taken up front.
+    /// 
+    /// # Arguments
+    /// * `to` - Recipient phone number in E.164 format
+    /// * `header_text` - Header text (max 60 characters)
+    /// * `body_text` - Main message text
+    /// * `catalog_id` - ID of the catalog connected to the business account
+    /// * `sections` - Sections as (title, product_retailer_ids)
+    /// 
+    /// # Example
+    /// ```
+    /// # use whatsapp_client::client::message_types::InteractiveMessage;
+    /// let message = InteractiveMessage::with_product_list(
+    ///     "+1234567890",
+    ///     "Summer collection",
+    ///     "Our picks for this week",
+    ///     "367025965434465",
+    ///     vec![("Mugs".to_string(), vec!["sku-blue-mug".to_string(), "sku-red-mug".to_string()])],
+    /// )?;
+    /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
+    /// ```
+    pub fn with_product_list(
+        to: &str,
+        header_text: &str,
+        body_text: &str,
+        catalog_id: &str,
+        sections: Vec<ProductSectionSpec>,
+    ) -> WhatsAppResult<Self> {
+        // Validate inputs
+        validate_phone_number(to)?;
+        validate_header_text(header_text)?;
+        validate_text_message(body_text)?;
+        Self::validate_product_list(catalog_id, &sections)?;
+
+        let product_sections = sections
+            .into_iter()
+            .map(|(title, product_ids)| ProductSection {
+                title,
+                product_items: product_ids
+                    .into_iter()
+                    .map(|product_retailer_id| ProductItem { product_retailer_id })
+                    .collect(),
+            })
+            .collect();
+
+        Ok(Self {
+            messaging_product: "whatsapp".to_string(),
+            recipient_type: "individual".to_string(),
+            to: to.to_string(),
+            message_type: "interactive".to_string(),
+            interactive: InteractiveContent {
+                interactive_type: "product_list".to_string(),
+                header: Some(InteractiveHeader {
+                    header_type: "text".to_string(),
+                    text: Some(header_text.to_string()),
+                    image: None,
+                    video: None,
+                    document: None,
+                }),
+                body: InteractiveBody {
+                    text: body_text.to_string(),
+                },
+                footer: None,
+                action: InteractiveAction::ProductList {
+                    catalog_id: catalog_id.to_string(),
+                    sections: product_sections,
+                },
+            },
+        })
+    }
+
+    /// Validate single product parameters
+    pub(crate) fn validate_product(catalog_id: &str, product_retailer_id: &str) -> WhatsAppResult<()> {
+        if catalog_id.is_empty() {
+            return Err(WhatsAppError::validation(ValidationCode::CatalogIdEmpty, "catalog_id", "cannot be empty"));
+        }
+
+        if product_retailer_id.is_empty() {
+            return Err(WhatsAppError::validation(ValidationCode::ProductIdEmpty, "product_retailer_id", "cannot be empty"));
+        }
+
+        Ok(())
+    }
+
+    /// Validate product list parameters
+    /// 
+    /// Shared with the builder so it can report problems before building.
+    pub(crate) fn validate_product_list(catalog_id: &str, sections: &[ProductSectionSpec]) -> WhatsAppResult<()> {
+        if catalog_id.is_empty() {
+            return Err(WhatsAppError::validation(ValidationCode::CatalogIdEmpty, "catalog_id", "cannot be empty"));
+        }
+
+        if sections.is_empty() || sections.len() > MAX_PRODUCT_SECTIONS {
+            return Err(WhatsAppError::validation(
+                ValidationCode::InvalidSectionCount,
+                "sections",
+                format!("must have 1-{} sections, got {}", MAX_PRODUCT_SECTIONS, sections.len())
+            ));
+        }
+
+        for (title, product_ids) in sections {
+            if title.is_empty() {
+                return Err(WhatsAppError::validation(ValidationCode::SectionTitleEmpty, "section.title", "cannot be empty"));
+            }
+
+            if text_length(title) > MAX_LIST_TITLE_LENGTH {
+                return Err(WhatsAppError::validation(
+                    ValidationCode::SectionTitleTooLong,
+                    "section.title",
+                    format!("too long: {} characters (max {})",
+                           text_length(title), MAX_LIST_TITLE_LENGTH)
+                ));
+            }
+
+            if product_ids.is_empty() {
+                return Err(WhatsAppError::validation(
+                    ValidationCode::SectionEmpty,
+                    "section.product_items",
+                    format!("section '{}' must have at least one product", title)
+                ));
+            }
+
+            if product_ids.iter().any(|id| id.is_empty()) {
+                return Err(WhatsAppError::validation(ValidationCode::ProductIdEmpty, "product_retailer_id", "cannot be empty"));
+            }
+        }
+
+        let total_products: usize = sections.iter().map(|(_, product_ids)| product_ids.len()).sum();
+        if total_products > MAX_PRODUCT_LIST_ITEMS {
+            return Err(WhatsAppError::validation(
+                ValidationCode::TooManyProducts,
+                "section.product_items",
+                format!("too many products: {} (max {})", total_products, MAX_PRODUCT_LIST_ITEMS)
+            ));
+        }
+
+        validate_unique_ids(
+            "product",
+            sections.iter().flat_map(|(_, product_ids)| product_ids.iter().map(String::as_str)),
+        )
+    }
+
+    /// Validate the message as a whole
+    /// 
+    /// Messages built with the constructors always pass. This is meant for
+    /// messages that arrived some other way, e.g. deserialized from an event.
+    pub fn validate(&self) -> WhatsAppResult<()> {
+        validate_phone_number(&self.to)?;
+        validate_text_message(&self.interactive.body.text)?;
+
+        if let Some(header) = &self.interactive.header {
+            self.check_header_type(&header.header_type)?;
+            if let Some(text) = &header.text {
+                validate_header_text(text)?;
+            }
+            for media in [&header.image, &header.video, &header.document].into_iter().flatten() {
+                validate_media_reference(media.id(), media.link())?;
+            }
+        }
+
+        if let Some(footer) = &self.interactive.footer {
+            validate_footer_text(&footer.text)?;
+        }
+
+        match &self.interactive.action {
+            InteractiveAction::Buttons { buttons } => {
+                if buttons.is_empty() || buttons.len() > 3 {
+                    return Err(WhatsAppError::InvalidMessageContent(
+                        "Interactive messages must have 1-3 buttons".to_string()
+                    ));
+                }
+                validate_unique_ids("button", buttons.iter().map(|button| button.reply.id.as_str()))?;
+                validate_unique_titles("button", buttons.iter().map(|button| button.reply.title.as_str()))?;
+                for button in buttons {
+                    validate_button(&button.reply.id, &button.reply.title)?;
+                }
+            }
+            InteractiveAction::List { button, sections } => {
+                validate_button("list_button", button)?;
+                if sections.is_empty() || sections.len() > 10 {
+                    return Err(WhatsAppError::InvalidMessageContent(
+                        "List messages must have 1-10 sections".to_string()
+                    ));
+                }
+                let total_rows: usize = sections.iter().map(|section| section.rows.len()).sum();
+                if total_rows > MAX_LIST_ROWS {
+                    return Err(WhatsAppError::InvalidMessageContent(
+                        format!("List messages can have at most 10 total rows, got {}", total_rows)
+                    ));
+                }
+                validate_unique_ids(
+                    "row",
+                    sections.iter().flat_map(|section| section.rows.iter().map(|row| row.id.as_str())),
+                )?;
+                validate_unique_titles(
+                    "row",
+                    sections.iter().flat_map(|section| section.rows.iter().map(|row| row.title.as_str())),
+                )?;
+                for section in sections {
+                    let rows: Vec<_> = section.rows
+                        .iter()
+                        .map(|row| (row.id.clone(), row.title.clone(), row.description.clone()))
+                        .collect();
+                    validate_list_section(&section.title, &rows)?;
+                }
+            }
+            InteractiveAction::CtaUrl { parameters, .. } => {
+                validate_button("cta_button", &parameters.display_text)?;
+                validate_https_url(&parameters.url)?;
+            }
+            InteractiveAction::Flow { parameters, .. } => {
+                Self::validate_flow(
+                    &parameters.flow_id,
+                    &parameters.flow_token,
+                    &parameters.flow_cta,
+                    parameters.flow_action,
+                    parameters.flow_action_payload.as_ref(),
+                )?;
+            }
+            InteractiveAction::LocationRequest { .. } => {}
+            InteractiveAction::Product { catalog_id, product_retailer_id } => {
+                Self::validate_product(catalog_id, product_retailer_id)?;
+            }
+            InteractiveAction::ProductList { catalog_id, sections } => {
+                if self.interactive.header.is_none() {
+                    return Err(WhatsAppError::validation(
+                        ValidationCode::HeaderRequired,
+                        "header",
+                        "required for product list messages",
+                    ));
+                }
+                let sections: Vec<ProductSectionSpec> = sections
+                    .iter()
+                    .map(|section| (
+                        section.title.clone(),
+                        section.product_items.iter().map(|item| item.product_retailer_id.clone()).collect(),
+                    ))
+                    .collect();
+                Self::validate_product_list(catalog_id, &sections)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Add a text header to the message
     pub fn with_text_header(mut self, header_text: &str) -> WhatsAppResult<Self> {
+        self.check_header_type("text")?;
         validate_header_text(header_text)?;
 
         self.interactive.header = Some(InteractiveHeader {
@@ -416,6 +1127,79 @@ impl InteractiveMessage {
         Ok(self)
     }
 
+    /// Add an image header to the message
+    ///
+    /// Only reply button, CTA URL and flow messages can have a media header.
+    ///
+    /// # Example
+    /// ```
+    /// # use whatsapp_client::client::message_types::{InteractiveMessage, MediaReference};
+    /// let message = InteractiveMessage::with_cta_url(
+    ///     "+1234567890",
+    ///     "Our annual sale is now live!",
+    ///     "Shop Now",
+    ///     "https://shop.example.com/sale",
+    /// )?
+    /// .with_image_header(MediaReference::Link("https://shop.example.com/hero.jpg".to_string()))?;
+    /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
+    /// ```
+    pub fn with_image_header(mut self, image: MediaReference) -> WhatsAppResult<Self> {
+        self.check_header_type("image")?;
+        validate_media_reference(image.id(), image.link())?;
+
+        self.interactive.header = Some(InteractiveHeader {
+            header_type: "image".to_string(),
+            text: None,
+            image: Some(image),
+            video: None,
+            document: None,
+        });
+
+        Ok(self)
+    }
+
+    /// Add a video header to the message
+    ///
+    /// Only reply button, CTA URL and flow messages can have a media header.
+    pub fn with_video_header(mut self, video: MediaReference) -> WhatsAppResult<Self> {
+        self.check_header_type("video")?;
+        validate_media_reference(video.id(), video.link())?;
+
+        self.interactive.header = Some(InteractiveHeader {
+            header_type: "video".to_string(),
+            text: None,
+            image: None,
+            video: Some(video),
+            document: None,
+        });
+
+        Ok(self)
+    }
+
+    /// Reject a header type WhatsApp doesn't accept on this kind of message
+    ///
+    /// Lists and product lists take text headers only, and location
+    /// requests and single products take no header at all.
+    fn check_header_type(&self, header_type: &str) -> WhatsAppResult<()> {
+        let allowed: &[&str] = match self.interactive.action {
+            InteractiveAction::Buttons { .. }
+            | InteractiveAction::CtaUrl { .. }
+            | InteractiveAction::Flow { .. } => &["text", "image", "video", "document"],
+            InteractiveAction::List { .. } | InteractiveAction::ProductList { .. } => &["text"],
+            InteractiveAction::LocationRequest { .. } | InteractiveAction::Product { .. } => &[],
+        };
+
+        if !allowed.contains(&header_type) {
+            return Err(WhatsAppError::validation(
+                ValidationCode::HeaderNotSupported,
+                "header",
+                format!("{} messages can't have a {} header", self.interactive.interactive_type, header_type),
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Add a footer to the message
     pub fn with_footer(mut self, footer_text: &str) -> WhatsAppResult<Self> {
         validate_footer_text(footer_text)?;
@@ -480,6 +1264,26 @@ mod tests {
         assert_eq!(message.interaction_type(), "list");
     }
 
+    #[test]
+    fn test_duplicate_option_ids_rejected() {
+        let buttons = vec![
+            ("confirm".to_string(), "Yes".to_string()),
+            ("confirm".to_string(), "Sure".to_string()),
+        ];
+        let error = InteractiveMessage::with_buttons("+1234567890", "Continue?", buttons).unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::DuplicateId));
+        assert_eq!(error.field(), Some("button.id"));
+
+        // Row IDs must be unique across sections, not just within one
+        let sections = vec![
+            ("Drinks".to_string(), vec![("small".to_string(), "Small coffee".to_string(), None)]),
+            ("Snacks".to_string(), vec![("small".to_string(), "Small cookie".to_string(), None)]),
+        ];
+        let error = InteractiveMessage::with_list("+1234567890", "Order:", "Menu", sections).unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::DuplicateId));
+        assert_eq!(error.field(), Some("row.id"));
+    }
+
     #[test]
     fn test_interactive_message_with_cta_url() {
         let message = InteractiveMessage::with_cta_url(
@@ -535,6 +1339,26 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_duplicate_titles_rejected() {
+        let buttons = vec![
+            ("ship_standard".to_string(), "Ship it".to_string()),
+            ("ship_express".to_string(), "Ship it".to_string()),
+        ];
+        let error = InteractiveMessage::with_buttons("+1234567890", "Delivery?", buttons).unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::DuplicateTitle));
+        assert_eq!(error.field(), Some("button.title"));
+
+        // Rows in different sections still share one list
+        let sections = vec![
+            ("Drinks".to_string(), vec![("drink_small".to_string(), "Small".to_string(), None)]),
+            ("Snacks".to_string(), vec![("snack_small".to_string(), "Small".to_string(), Some("Cookie".to_string()))]),
+        ];
+        let error = InteractiveMessage::with_list("+1234567890", "Order:", "Menu", sections).unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::DuplicateTitle));
+        assert_eq!(error.field(), Some("row.title"));
+    }
+
     #[test]
     fn test_interactive_message_serialization() {
         let buttons = vec![("yes".to_string(), "Yes".to_string())];
@@ -614,7 +1438,7 @@ mod tests {
 
     #[test]
     fn test_list_message_single_section_json_format() {
-        let sections: Vec<(String, Vec<(String, String, Option<String>)>)> = vec![
+        let sections: Vec<ListSectionSpec> = vec![
             (
                 "Options".to_string(),
                 vec![
@@ -639,7 +1463,7 @@ mod tests {
 
     #[test]
     fn test_list_message_multiple_sections_json_format() {
-        let sections: Vec<(String, Vec<(String, String, Option<String>)>)> = vec![
+        let sections: Vec<ListSectionSpec> = vec![
             (
                 "Electronics".to_string(),
                 vec![
@@ -670,7 +1494,7 @@ mod tests {
 
     #[test]
     fn test_list_message_rows_without_description_json_format() {
-        let sections: Vec<(String, Vec<(String, String, Option<String>)>)> = vec![
+        let sections: Vec<ListSectionSpec> = vec![
             (
                 "Quick Options".to_string(),
                 vec![
@@ -693,6 +1517,59 @@ mod tests {
         assert_eq!(json_output, expected_json);
     }
 
+    #[test]
+    fn test_list_message_with_header_and_footer_json_format() {
+        let sections: Vec<ListSectionSpec> = vec![
+            (
+                "Pickup".to_string(),
+                vec![
+                ("store".to_string(), "In store".to_string(), Some("Ready in 2 hours".to_string())),
+                ("locker".to_string(), "Parcel locker".to_string(), None),
+                ],
+            ),
+        ];
+
+        let message = InteractiveMessage::with_list(
+            "+16505551234",
+            "How would you like to collect your order?",
+            "Choose",
+            sections
+        ).unwrap()
+        .with_text_header("Order #1042").unwrap()
+        .with_footer("Lucky Shrub").unwrap();
+
+        let json_output = serde_json::to_string(&message).unwrap();
+        let expected_json = r#"{"messaging_product":"whatsapp","recipient_type":"individual","to":"+16505551234","type":"interactive","interactive":{"type":"list","header":{"type":"text","text":"Order #1042"},"body":{"text":"How would you like to collect your order?"},"footer":{"text":"Lucky Shrub"},"action":{"button":"Choose","sections":[{"title":"Pickup","rows":[{"id":"store","title":"In store","description":"Ready in 2 hours"},{"id":"locker","title":"Parcel locker"}]}]}}}"#;
+
+        assert_eq!(json_output, expected_json);
+
+        let parsed: InteractiveMessage = serde_json::from_str(&json_output).unwrap();
+        assert!(parsed.validate().is_ok());
+    }
+
+    #[test]
+    fn test_list_message_with_media_header_fails_validation() {
+        let list = InteractiveMessage::with_list(
+            "+16505551234",
+            "Pick one",
+            "Menu",
+            vec![("Drinks".to_string(), vec![("tea".to_string(), "Tea".to_string(), None)])],
+        ).unwrap();
+        let media = MediaReference::Id("1013859600285441".to_string());
+        for header_type in ["image", "video", "document"] {
+            let mut json = serde_json::to_value(&list).unwrap();
+            json["interactive"]["header"] = serde_json::json!({"type": header_type, header_type: &media});
+
+            // Deserializing accepts it, validate() doesn't
+            let parsed: InteractiveMessage = serde_json::from_value(json).unwrap();
+            let error = parsed.validate().unwrap_err();
+            assert_eq!(error.validation_code(), Some(ValidationCode::HeaderNotSupported), "{}", header_type);
+        }
+
+        let error = list.with_video_header(media).unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::HeaderNotSupported));
+    }
+
     #[test]
     fn test_cta_url_message_json_format() {
         let message = InteractiveMessage::with_cta_url(
@@ -729,6 +1606,57 @@ mod tests {
         assert_eq!(json_output, expected_json);
     }
 
+    #[test]
+    fn test_cta_url_message_with_image_header_json_format() {
+        let message = InteractiveMessage::with_cta_url(
+            "+16505551234",
+            "Our annual sale is now live! Get up to 50% off on all products.",
+            "Shop Now",
+            "https://shop.example.com/sale",
+        )
+            .unwrap()
+            .with_image_header(MediaReference::Link("https://shop.example.com/hero.jpg".to_string()))
+            .unwrap();
+
+        let json_output = serde_json::to_string(&message).unwrap();
+        let expected_json = r#"{"messaging_product":"whatsapp","recipient_type":"individual","to":"+16505551234","type":"interactive","interactive":{"type":"cta_url","header":{"type":"image","image":{"link":"https://shop.example.com/hero.jpg"}},"body":{"text":"Our annual sale is now live! Get up to 50% off on all products."},"action":{"name":"cta_url","parameters":{"display_text":"Shop Now","url":"https://shop.example.com/sale"}}}}"#;
+
+        assert_eq!(json_output, expected_json);
+        let parsed: InteractiveMessage = serde_json::from_str(&json_output).unwrap();
+        assert_eq!(parsed, message);
+    }
+
+    #[test]
+    fn test_media_headers_only_where_whatsapp_allows_them() {
+        let video = || MediaReference::Id("1013859600285441".to_string());
+        let buttons = InteractiveMessage::with_buttons(
+            "+16505551234",
+            "Watch and reply",
+            vec![("ok".to_string(), "OK".to_string())],
+        ).unwrap();
+        let json = serde_json::to_value(buttons.with_video_header(video()).unwrap()).unwrap();
+        assert_eq!(json["interactive"]["header"], serde_json::json!({"type": "video", "video": {"id": "1013859600285441"}}));
+
+        let list = InteractiveMessage::with_list(
+            "+16505551234",
+            "Pick one",
+            "Menu",
+            vec![("Drinks".to_string(), vec![("tea".to_string(), "Tea".to_string(), None)])],
+        ).unwrap();
+        let error = list.with_image_header(video()).unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::HeaderNotSupported));
+        assert_eq!(error.field(), Some("header"));
+
+        let location = InteractiveMessage::request_location("+16505551234", "Share your location").unwrap();
+        let error = location.with_text_header("Pickup").unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::HeaderNotSupported));
+
+        // The media itself is still checked
+        let cta = InteractiveMessage::with_cta_url("+16505551234", "Visit", "Visit", "https://example.com").unwrap();
+        let error = cta.with_image_header(MediaReference::Link(String::new())).unwrap_err();
+        assert_eq!(error.field(), Some("url"));
+    }
+
     #[test]
     fn test_location_request_message_json_format() {
         let message = InteractiveMessage::request_location(
@@ -741,4 +1669,317 @@ mod tests {
         
         assert_eq!(json_output, expected_json);
     }
+
+    #[test]
+    fn test_flow_message_navigate_json_format() {
+        let message = InteractiveMessage::with_flow(
+            "+16505551234",
+            "Book your next appointment in a few taps.",
+            "1234567890123",
+            "AQAAAAACS5FpgQ_cAAAAAD0QI3s.",
+            "Book!",
+            FlowAction::Navigate,
+            Some(FlowActionPayload::new("APPOINTMENT").with_data(serde_json::json!({ "product_name": "name" }))),
+        ).unwrap();
+
+        let json_output = serde_json::to_string(&message).unwrap();
+        let expected_json = r#"{"messaging_product":"whatsapp","recipient_type":"individual","to":"+16505551234","type":"interactive","interactive":{"type":"flow","body":{"text":"Book your next appointment in a few taps."},"action":{"name":"flow","parameters":{"flow_message_version":"3","flow_token":"AQAAAAACS5FpgQ_cAAAAAD0QI3s.","flow_id":"1234567890123","flow_cta":"Book!","flow_action":"navigate","flow_action_payload":{"screen":"APPOINTMENT","data":{"product_name":"name"}}}}}}"#;
+
+        assert_eq!(json_output, expected_json);
+        assert_eq!(message.interaction_type(), "flow");
+    }
+
+    #[test]
+    fn test_flow_message_data_exchange_json_format() {
+        let message = InteractiveMessage::with_flow(
+            "+16505551234",
+            "Tell us about yourself.",
+            "1234567890123",
+            "onboarding-42",
+            "Start",
+            FlowAction::DataExchange,
+            None,
+        ).unwrap();
+
+        let json = serde_json::to_value(&message).unwrap();
+        let parameters = &json["interactive"]["action"]["parameters"];
+        assert_eq!(parameters["flow_action"], "data_exchange");
+        assert!(parameters.get("flow_action_payload").is_none());
+    }
+
+    #[test]
+    fn test_untagged_actions_round_trip_to_the_same_variant() {
+        let flow = InteractiveMessage::with_flow(
+            "+16505551234",
+            "Book now",
+            "1234567890123",
+            "token",
+            "Book!",
+            FlowAction::Navigate,
+            Some(FlowActionPayload::new("APPOINTMENT")),
+        ).unwrap();
+        let location = InteractiveMessage::request_location("+16505551234", "Share your location").unwrap();
+        let cta = InteractiveMessage::with_cta_url("+16505551234", "Visit", "Visit", "https://example.com").unwrap();
+
+        // A flow action also has a `name`, so it must not fall through to LocationRequest
+        for message in [flow, location, cta] {
+            let json = serde_json::to_string(&message).unwrap();
+            let parsed: InteractiveMessage = serde_json::from_str(&json).unwrap();
+            assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+        }
+    }
+
+    #[test]
+    fn test_each_action_round_trips_to_an_equal_message() {
+        let to = "+16505551234";
+        let buttons = InteractiveMessage::with_buttons(
+            to,
+            "Continue?",
+            vec![("yes".to_string(), "Yes".to_string()), ("no".to_string(), "No".to_string())],
+        ).unwrap().with_text_header("Checkout").unwrap().with_footer("Reply any time").unwrap();
+        let list = InteractiveMessage::with_list(
+            to,
+            "Pick one",
+            "Menu",
+            vec![("Drinks".to_string(), vec![
+                ("tea".to_string(), "Tea".to_string(), Some("Hot".to_string())),
+                ("juice".to_string(), "Juice".to_string(), None),
+            ])],
+        ).unwrap();
+        let cta = InteractiveMessage::with_cta_url(to, "Visit", "Visit", "https://example.com").unwrap();
+        let location = InteractiveMessage::request_location(to, "Share your location").unwrap();
+        let flow = InteractiveMessage::with_flow(
+            to,
+            "Book now",
+            "1234567890123",
+            "token",
+            "Book!",
+            FlowAction::Navigate,
+            Some(FlowActionPayload::new("APPOINTMENT").with_data(serde_json::json!({"slot": 3}))),
+        ).unwrap();
+        let product = InteractiveMessage::with_product(to, "Look", "123", "sku-1").unwrap();
+
+        for message in [buttons, list, cta, location, flow, product] {
+            let json = serde_json::to_string(&message).unwrap();
+            let parsed: InteractiveMessage = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, message, "{}", json);
+        }
+    }
+
+    #[test]
+    fn test_action_not_matching_its_type_is_rejected() {
+        let message = InteractiveMessage::request_location("+16505551234", "Share your location").unwrap();
+        let mut json = serde_json::to_value(&message).unwrap();
+
+        // A location request action under a flow type is an error, not a location request
+        json["interactive"]["type"] = "flow".into();
+        assert!(serde_json::from_value::<InteractiveMessage>(json.clone()).is_err());
+
+        json["interactive"]["type"] = "carousel".into();
+        assert!(serde_json::from_value::<InteractiveMessage>(json.clone()).is_err());
+
+        // Fields from another kind of action don't fall through to a looser variant
+        json["interactive"]["type"] = "location_request_message".into();
+        json["interactive"]["action"]["parameters"] = serde_json::json!({"display_text": "Go", "url": "https://example.com", "extra": 1});
+        assert!(serde_json::from_value::<InteractiveMessage>(json).is_err());
+    }
+
+    #[test]
+    fn test_flow_validation() {
+        let flow = |cta: &str, action: FlowAction, payload: Option<FlowActionPayload>| {
+            InteractiveMessage::with_flow("+16505551234", "Body", "123", "token", cta, action, payload)
+        };
+
+        let error = flow("Start", FlowAction::Navigate, None).unwrap_err();
+        assert_eq!(error.field(), Some("flow_action_payload"));
+
+        let error = flow("Start", FlowAction::DataExchange, Some(FlowActionPayload::new("WELCOME"))).unwrap_err();
+        assert_eq!(error.field(), Some("flow_action_payload"));
+
+        let error = flow(&"x".repeat(31), FlowAction::DataExchange, None).unwrap_err();
+        assert_eq!(error.field(), Some("flow_cta"));
+        assert!(flow(&"x".repeat(30), FlowAction::DataExchange, None).is_ok());
+
+        let error = InteractiveMessage::with_flow("+16505551234", "Body", "", "token", "Start", FlowAction::DataExchange, None).unwrap_err();
+        assert_eq!(error.field(), Some("flow_id"));
+    }
+
+    #[test]
+    fn test_product_message_json_format() {
+        let message = InteractiveMessage::with_product(
+            "+16505551234",
+            "Back in stock!",
+            "367025965434465",
+            "sku-blue-mug",
+        ).unwrap();
+
+        let json_output = serde_json::to_string(&message).unwrap();
+        let expected_json = r#"{"messaging_product":"whatsapp","recipient_type":"individual","to":"+16505551234","type":"interactive","interactive":{"type":"product","body":{"text":"Back in stock!"},"action":{"catalog_id":"367025965434465","product_retailer_id":"sku-blue-mug"}}}"#;
+
+        assert_eq!(json_output, expected_json);
+        assert_eq!(message.interaction_type(), "product");
+    }
+
+    #[test]
+    fn test_product_list_message_json_format() {
+        let message = InteractiveMessage::with_product_list(
+            "+16505551234",
+            "Summer collection",
+            "Our picks for this week",
+            "367025965434465",
+            vec![
+                ("Mugs".to_string(), vec!["sku-blue-mug".to_string(), "sku-red-mug".to_string()]),
+                ("Plates".to_string(), vec!["sku-white-plate".to_string()]),
+            ],
+        ).unwrap()
+            .with_footer("Free shipping over $50")
+            .unwrap();
+
+        let json_output = serde_json::to_string(&message).unwrap();
+        let expected_json = r#"{"messaging_product":"whatsapp","recipient_type":"individual","to":"+16505551234","type":"interactive","interactive":{"type":"product_list","header":{"type":"text","text":"Summer collection"},"body":{"text":"Our picks for this week"},"footer":{"text":"Free shipping over $50"},"action":{"catalog_id":"367025965434465","sections":[{"title":"Mugs","product_items":[{"product_retailer_id":"sku-blue-mug"},{"product_retailer_id":"sku-red-mug"}]},{"title":"Plates","product_items":[{"product_retailer_id":"sku-white-plate"}]}]}}}"#;
+
+        assert_eq!(json_output, expected_json);
+        assert_eq!(message.interaction_type(), "product_list");
+    }
+
+    #[test]
+    fn test_product_actions_round_trip_to_the_same_variant() {
+        let product = InteractiveMessage::with_product("+16505551234", "Look", "123", "sku-1").unwrap();
+        let product_list = InteractiveMessage::with_product_list(
+            "+16505551234",
+            "Catalog",
+            "Look",
+            "123",
+            vec![("Picks".to_string(), vec!["sku-1".to_string()])],
+        ).unwrap();
+
+        for message in [product, product_list] {
+            let json = serde_json::to_string(&message).unwrap();
+            let parsed: InteractiveMessage = serde_json::from_str(&json).unwrap();
+            assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+        }
+    }
+
+    #[test]
+    fn test_product_validation() {
+        let error = InteractiveMessage::with_product("+16505551234", "Look", "", "sku-1").unwrap_err();
+        assert_eq!(error.field(), Some("catalog_id"));
+
+        let error = InteractiveMessage::with_product("+16505551234", "Look", "123", "").unwrap_err();
+        assert_eq!(error.field(), Some("product_retailer_id"));
+
+        let product_list = |sections: Vec<ProductSectionSpec>| {
+            InteractiveMessage::with_product_list("+16505551234", "Catalog", "Look", "123", sections)
+        };
+
+        let error = product_list(vec![]).unwrap_err();
+        assert_eq!(error.field(), Some("sections"));
+
+        let error = product_list(vec![("Empty".to_string(), vec![])]).unwrap_err();
+        assert_eq!(error.field(), Some("section.product_items"));
+
+        let too_many = (0..31).map(|i| format!("sku-{}", i)).collect();
+        let error = product_list(vec![("All".to_string(), too_many)]).unwrap_err();
+        assert_eq!(error.field(), Some("section.product_items"));
+
+        let error = product_list(vec![
+            ("Mugs".to_string(), vec!["sku-1".to_string()]),
+            ("Sale".to_string(), vec!["sku-1".to_string()]),
+        ]).unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::DuplicateId));
+        assert_eq!(error.field(), Some("product.id"));
+    }
+
+    /// (section title, row IDs) for every section of a list message
+    fn list_layout(message: &InteractiveMessage) -> Vec<(String, Vec<String>)> {
+        match &message.interactive.action {
+            InteractiveAction::List { sections, .. } => sections
+                .iter()
+                .map(|section| {
+                    (section.title.clone(), section.rows.iter().map(|row| row.id.clone()).collect())
+                })
+                .collect(),
+            other => panic!("expected a list action, got {:?}", other),
+        }
+    }
+
+    fn catalog_rows(prefix: &str, count: usize) -> Vec<(String, String, Option<String>)> {
+        (1..=count)
+            .map(|n| (format!("{}_{}", prefix, n), format!("Item {}", n), None))
+            .collect()
+    }
+
+    #[test]
+    fn test_paginate_list_splits_25_rows_into_3_pages() {
+        let sections: Vec<ListSectionSpec> = vec![
+            ("Phones".to_string(), catalog_rows("phone", 12)),
+            ("Laptops".to_string(), catalog_rows("laptop", 8)),
+            ("Tablets".to_string(), catalog_rows("tablet", 5)),
+        ];
+
+        let pages = InteractiveMessage::paginate_list("+1234567890", "Browse:", "View", sections).unwrap();
+        assert_eq!(pages.len(), 3);
+
+        let mut content_ids = Vec::new();
+        for (index, page) in pages.iter().enumerate() {
+            let layout = list_layout(page);
+            let total_rows: usize = layout.iter().map(|(_, ids)| ids.len()).sum();
+            assert!(total_rows <= 10, "page {} has {} rows", index + 1, total_rows);
+            assert_eq!(page.body_text(), "Browse:");
+
+            let footer = page.interactive.footer.as_ref().unwrap();
+            assert_eq!(footer.text, format!("Page {} of 3", index + 1));
+
+            for (title, ids) in layout {
+                if title == "More" {
+                    assert_eq!(ids, vec![format!("{}{}", NEXT_PAGE_ROW_ID_PREFIX, index + 2)]);
+                } else {
+                    content_ids.extend(ids);
+                }
+            }
+        }
+
+        // Every row shows up exactly once, in order
+        let expected: Vec<String> = catalog_rows("phone", 12).into_iter()
+            .chain(catalog_rows("laptop", 8))
+            .chain(catalog_rows("tablet", 5))
+            .map(|(id, _, _)| id)
+            .collect();
+        assert_eq!(content_ids, expected);
+
+        // Only the last page goes without a "Next page" row
+        assert!(list_layout(&pages[2]).iter().all(|(title, _)| title != "More"));
+        // A section split across pages keeps its title on both
+        assert_eq!(list_layout(&pages[0])[0].0, "Phones");
+        assert_eq!(list_layout(&pages[1])[0].0, "Phones");
+    }
+
+    #[test]
+    fn test_paginate_list_short_list_is_a_single_page() {
+        let sections: Vec<ListSectionSpec> = vec![("Menu".to_string(), catalog_rows("dish", 10))];
+
+        let pages = InteractiveMessage::paginate_list("+1234567890", "Order:", "Menu", sections).unwrap();
+
+        assert_eq!(pages.len(), 1);
+        assert_eq!(list_layout(&pages[0]), vec![(
+            "Menu".to_string(),
+            catalog_rows("dish", 10).into_iter().map(|(id, _, _)| id).collect(),
+        )]);
+        assert!(pages[0].interactive.footer.is_none());
+    }
+
+    #[test]
+    fn test_paginate_list_rejects_duplicates_across_pages() {
+        let sections: Vec<ListSectionSpec> = vec![
+            ("First".to_string(), catalog_rows("item", 9)),
+            ("Second".to_string(), catalog_rows("item", 3)),
+        ];
+
+        let error = InteractiveMessage::paginate_list("+1234567890", "Pick:", "View", sections).unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::DuplicateId));
+        assert_eq!(error.field(), Some("row.id"));
+
+        let empty = InteractiveMessage::paginate_list("+1234567890", "Pick:", "View", Vec::new());
+        assert!(empty.is_err());
+    }
 }
@@ -227,6 +227,16 @@ impl LocationMessage {
         self.location.name.is_some() || self.location.address.is_some()
     }
     
+    /// Validate the message as a whole
+    /// 
+    /// Messages built with the constructors always pass. This is meant for
+    /// messages that arrived some other way, e.g. deserialized from an event.
+    pub fn validate(&self) -> WhatsAppResult<()> {
+        validate_phone_number(&self.to)?;
+        validate_coordinates(self.location.latitude, self.location.longitude)?;
+        Ok(())
+    }
+    
     /// Validate coordinate values
     /// 
     /// This can be used to validate coordinates before creating a location message.
@@ -247,7 +257,7 @@ impl LocationMessage {
     
     /// Calculate approximate distance to another location in kilometers
     /// 
-    /// Uses the Haversine formula to calculate the great-circle distance
+    /// Uses `common::geo::haversine_km` to calculate the great-circle distance
     /// between two points on Earth. This is useful for determining proximity
     /// or for display purposes.
     /// 
@@ -260,18 +270,12 @@ impl LocationMessage {
     /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
     /// ```
     pub fn distance_to(&self, other_latitude: f64, other_longitude: f64) -> f64 {
-        const EARTH_RADIUS_KM: f64 = 6371.0;
-        
-        let lat1_rad = self.location.latitude.to_radians();
-        let lat2_rad = other_latitude.to_radians();
-        let delta_lat = (other_latitude - self.location.latitude).to_radians();
-        let delta_lon = (other_longitude - self.location.longitude).to_radians();
-        
-        let a = (delta_lat / 2.0).sin().powi(2) +
-               lat1_rad.cos() * lat2_rad.cos() * (delta_lon / 2.0).sin().powi(2);
-        let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
-        
-        EARTH_RADIUS_KM * c
+        common::geo::haversine_km(
+            self.location.latitude,
+            self.location.longitude,
+            other_latitude,
+            other_longitude,
+        )
     }
 }
 
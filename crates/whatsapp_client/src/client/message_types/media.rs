@@ -0,0 +1,114 @@
+use crate::{
+    errors::WhatsAppError,
+    client::validation::validate_media_reference,
+};
+use serde::{Serialize, Deserialize};
+
+/// Where WhatsApp should get a message's media from
+///
+/// Media is either uploaded ahead of time and referenced by ID (recommended)
+/// or fetched by WhatsApp from a hosted URL. Being an enum, exactly one of
+/// `id` or `link` is ever serialized. Flatten it into a content struct to
+/// get WhatsApp's `{"id": ...}` / `{"link": ...}` shape. Deserializing
+/// input that has both fields, or neither, is an error.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase", try_from = "RawMediaReference")]
+pub enum MediaReference {
+    /// Media ID returned by WhatsApp's media upload API
+    Id(String),
+    /// URL of hosted media
+    Link(String),
+}
+
+/// Wire shape of a media reference, before checking exactly one field is set
+#[derive(Deserialize)]
+struct RawMediaReference {
+    id: Option<String>,
+    link: Option<String>,
+}
+
+impl TryFrom<RawMediaReference> for MediaReference {
+    type Error = WhatsAppError;
+
+    fn try_from(raw: RawMediaReference) -> Result<Self, Self::Error> {
+        validate_media_reference(raw.id.as_deref(), raw.link.as_deref())?;
+        // Exactly one of the two is set past this point
+        Ok(match (raw.id, raw.link) {
+            (Some(id), _) => MediaReference::Id(id),
+            (None, link) => MediaReference::Link(link.unwrap_or_default()),
+        })
+    }
+}
+
+impl MediaReference {
+    /// Get the media ID if this references uploaded media
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            MediaReference::Id(id) => Some(id),
+            MediaReference::Link(_) => None,
+        }
+    }
+
+    /// Get the URL if this references hosted media
+    pub fn link(&self) -> Option<&str> {
+        match self {
+            MediaReference::Id(_) => None,
+            MediaReference::Link(link) => Some(link),
+        }
+    }
+
+    /// Check if this references uploaded media (recommended)
+    pub fn is_uploaded(&self) -> bool {
+        matches!(self, MediaReference::Id(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Content {
+        #[serde(flatten)]
+        media: MediaReference,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+    }
+
+    #[test]
+    fn test_only_one_field_serializes() {
+        let by_id = Content { media: MediaReference::Id("1013859600285441".to_string()), caption: None };
+        assert_eq!(serde_json::to_value(&by_id).unwrap(), json!({ "id": "1013859600285441" }));
+
+        let by_link = Content {
+            media: MediaReference::Link("https://example.com/a.jpg".to_string()),
+            caption: Some("Hi".to_string()),
+        };
+        assert_eq!(
+            serde_json::to_value(&by_link).unwrap(),
+            json!({ "link": "https://example.com/a.jpg", "caption": "Hi" })
+        );
+    }
+
+    #[test]
+    fn test_round_trips_through_flattened_content() {
+        let content: Content = serde_json::from_value(json!({ "link": "https://example.com/a.jpg" })).unwrap();
+        assert_eq!(content.media.link(), Some("https://example.com/a.jpg"));
+        assert_eq!(content.media.id(), None);
+        assert!(!content.media.is_uploaded());
+
+        let content: Content = serde_json::from_value(json!({ "id": "123", "caption": "Hi" })).unwrap();
+        assert!(content.media.is_uploaded());
+        assert_eq!(content.caption.as_deref(), Some("Hi"));
+    }
+
+    #[test]
+    fn test_deserializing_both_or_neither_fails() {
+        let both = json!({ "id": "123", "link": "https://example.com/a.jpg" });
+        assert!(serde_json::from_value::<Content>(both).is_err());
+
+        let neither = json!({ "caption": "Hi" });
+        assert!(serde_json::from_value::<Content>(neither).is_err());
+    }
+}
@@ -3,24 +3,36 @@ pub mod mtrait;
 pub mod text;
 pub mod audio;
 pub mod contacts;
+pub mod context;
 pub mod document;
 pub mod image;
 pub mod interactive;
 pub mod location;
+pub mod media;
+pub mod recipient;
+pub mod sticker;
 pub mod video;
 
 pub use mtrait::Message;
 pub use text::TextMessage;
 pub use audio::AudioMessage;
 pub use contacts::ContactMessage;
+pub use context::{ContextualMessage, ReplyContext};
 pub use document::DocumentMessage;
 pub use image::ImageMessage;
-pub use interactive::InteractiveMessage;
+pub use interactive::{InteractiveMessage, ListSectionSpec, ProductSectionSpec, FlowAction, FlowActionPayload, NEXT_PAGE_ROW_ID_PREFIX};
 pub use location::LocationMessage;
+pub use media::MediaReference;
+pub use recipient::{Recipient, RecipientType};
+pub use sticker::StickerMessage;
 pub use video::VideoMessage;
 
 use serde::{Deserialize, Serialize};
-use common::message_bus::Event;
+use crate::errors::{ValidationCode, WhatsAppError, WhatsAppResult};
+use common::{
+    clock::{Clock, SystemClock},
+    message_bus::Event,
+};
 
 /// A response message to be sent via WhatsApp
 /// 
@@ -37,25 +49,42 @@ pub struct WhatsAppMessageSend {
     pub generated_at: chrono::DateTime<chrono::Utc>,
     /// Priority level for message delivery (Low, Normal, Urgent)
     pub priority: ResponsePriority,
+    /// ID of the message this one replies to, threading it in the chat UI
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_message_id: Option<String>,
+    /// Key used to suppress duplicate sends when this event is redelivered
+    ///
+    /// When unset, `original_message_id` is used (see `dedup_key`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+    /// When the recipient last messaged us, used to enforce the 24-hour
+    /// customer service window before sending
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_inbound_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Seconds after which an undelivered message should be dropped
+    ///
+    /// Only honoured for message types whose `supports_ttl` is true; see
+    /// `validate_ttl`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_seconds: Option<u32>,
 }
 
 impl Event for WhatsAppMessageSend {
     const TOPIC: &'static str = "conversation.responses";
     const VERSION: &'static str = "1.0";
+    /// One sub-topic per `ResponsePriority`, so urgent responses don't
+    /// queue behind low-priority batches.
+    const TOPIC_SUFFIXES: &'static [&'static str] = &["urgent", "normal", "low"];
+
     /// Partitioning by `to_phone` allows us to group responses
     /// to the same recipient together.
     fn partition_key(&self) -> Option<String> {
-        let to_phone = match &self.message {
-            WhatsAppMessage::Text(msg) => msg.recipient(),
-            WhatsAppMessage::Audio(msg) => msg.recipient(),
-            WhatsAppMessage::Contact(msg) => msg.recipient(),
-            WhatsAppMessage::Document(msg) => msg.recipient(),
-            WhatsAppMessage::Image(msg) => msg.recipient(),
-            WhatsAppMessage::Interactive(msg) => msg.recipient(),
-            WhatsAppMessage::Location(msg) => msg.recipient(),
-            WhatsAppMessage::Video(msg) => msg.recipient(),
-        };
-        Some(to_phone.to_string())
+        Some(self.message.recipient().to_string())
+    }
+
+    /// Route to `conversation.responses.{urgent,normal,low}` by priority
+    fn topic_suffix(&self) -> Option<&'static str> {
+        Some(self.priority.topic_suffix())
     }
 }
 
@@ -73,7 +102,7 @@ impl WhatsAppMessageSend {
     /// ```
     /// # use whatsapp_client::client::message_types::*;
     /// let text_msg = TextMessage::new("+1234567890", "Hello!")?;
-    /// let response = WhatsAppMessageResponse::new(
+    /// let response = WhatsAppMessageSend::new(
     ///     "msg_12345".to_string(),
     ///     WhatsAppMessage::Text(text_msg),
     ///     ResponsePriority::Normal
@@ -85,16 +114,170 @@ impl WhatsAppMessageSend {
         message: WhatsAppMessage,
         priority: ResponsePriority,
     ) -> Self {
+        Self::new_with_clock(original_message_id, message, priority, &SystemClock)
+    }
  
+    /// Like `new`, with `generated_at` taken from `clock`
+    pub fn new_with_clock(
+        original_message_id: String,
+        message: WhatsAppMessage,
+        priority: ResponsePriority,
+        clock: &dyn Clock,
+    ) -> Self {
         Self { 
             original_message_id,
             message, 
-            generated_at: chrono::Utc::now(),
-            priority
+            generated_at: clock.now(),
+            priority,
+            context_message_id: None,
+            idempotency_key: None,
+            last_inbound_at: None,
+            ttl_seconds: None,
         }
      }
+ 
+    /// Start building a response, with the priority picked from the message type
+    /// 
+    /// See [`WhatsAppMessage::default_priority`] for the defaults; call
+    /// `priority()` on the builder to override them.
+    /// 
+    /// # Example
+    /// ```
+    /// # use whatsapp_client::client::message_types::*;
+    /// let image = ImageMessage::from_media_id("+1234567890", "1013859600285441")?;
+    /// let response = WhatsAppMessageSend::builder()
+    ///     .original_message_id("msg_12345")
+    ///     .message(WhatsAppMessage::Image(image))
+    ///     .build()?;
+    /// assert_eq!(response.priority, ResponsePriority::Low);
+    /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
+    /// ```
+    pub fn builder() -> WhatsAppMessageSendBuilder {
+        WhatsAppMessageSendBuilder::default()
+    }
+ 
+    /// Send this message as a reply to `message_id`
+    /// 
+    /// WhatsApp quotes the referenced message above the reply.
+    pub fn in_reply_to(mut self, message_id: impl Into<String>) -> Self {
+        self.context_message_id = Some(message_id.into());
+        self
+    }
+ 
+    /// Use an explicit idempotency key instead of `original_message_id`
+    /// 
+    /// Needed when several distinct responses go out for the same
+    /// original message, so they aren't suppressed as duplicates.
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+ 
+    /// Record when the recipient last messaged us
+    /// 
+    /// The send is refused if the 24-hour service window has closed by the
+    /// time the message is delivered.
+    pub fn with_last_inbound_at(mut self, last_inbound_at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.last_inbound_at = Some(last_inbound_at);
+        self
+    }
+ 
+    /// Mark this message as ephemeral, expiring after `ttl_seconds`
+    pub fn with_ttl_seconds(mut self, ttl_seconds: u32) -> Self {
+        self.ttl_seconds = Some(ttl_seconds);
+        self
+    }
+ 
+    /// Check that `ttl_seconds`, if set, can be honoured
+    /// 
+    /// Fails with `TtlZero` when the TTL is zero and `TtlNotSupported` when
+    /// the message type doesn't accept one, so the send is rejected up front
+    /// instead of going out without the expiry the caller asked for.
+    pub fn validate_ttl(&self) -> WhatsAppResult<()> {
+        let Some(ttl) = self.ttl_seconds else {
+            return Ok(());
+        };
+        if ttl == 0 {
+            return Err(WhatsAppError::validation(
+                ValidationCode::TtlZero,
+                "ttl_seconds",
+                "must be greater than zero",
+            ));
+        }
+        if !self.message.supports_ttl() {
+            return Err(WhatsAppError::validation(
+                ValidationCode::TtlNotSupported,
+                "ttl_seconds",
+                format!("not supported for {} messages", self.message.message_type_name()),
+            ));
+        }
+        Ok(())
+    }
+ 
+    /// Key identifying this send for duplicate suppression
+    pub fn dedup_key(&self) -> &str {
+        self.idempotency_key.as_deref().unwrap_or(&self.original_message_id)
+    }
  }
  
+ /// Builder for `WhatsAppMessageSend`
+ /// 
+ /// Only the message is required besides `original_message_id`. Without an
+ /// explicit `priority()`, the message's
+ /// [`default_priority`](WhatsAppMessage::default_priority) is used.
+ #[derive(Debug, Default)]
+ pub struct WhatsAppMessageSendBuilder {
+     original_message_id: Option<String>,
+     message: Option<WhatsAppMessage>,
+     priority: Option<ResponsePriority>,
+ }
+ 
+ impl WhatsAppMessageSendBuilder {
+     /// Set the ID of the message this response relates to
+     pub fn original_message_id(mut self, id: impl Into<String>) -> Self {
+         self.original_message_id = Some(id.into());
+         self
+     }
+ 
+     /// Set the message to send
+     pub fn message(mut self, message: WhatsAppMessage) -> Self {
+         self.message = Some(message);
+         self
+     }
+ 
+     /// Override the priority derived from the message type
+     pub fn priority(mut self, priority: ResponsePriority) -> Self {
+         self.priority = Some(priority);
+         self
+     }
+ 
+     /// Build the response
+     /// 
+     /// Fails with `MessageIdEmpty` when `original_message_id` is unset or
+     /// blank, and with `MessageMissing` when no message was set.
+     pub fn build(self) -> WhatsAppResult<WhatsAppMessageSend> {
+         self.build_with_clock(&SystemClock)
+     }
+ 
+     /// Like `build`, with `generated_at` taken from `clock`
+     pub fn build_with_clock(self, clock: &dyn Clock) -> WhatsAppResult<WhatsAppMessageSend> {
+         let original_message_id = self.original_message_id.unwrap_or_default();
+         if original_message_id.trim().is_empty() {
+             return Err(WhatsAppError::validation(
+                 ValidationCode::MessageIdEmpty,
+                 "original_message_id",
+                 "cannot be empty",
+             ));
+         }
+ 
+         let message = self.message.ok_or_else(|| {
+             WhatsAppError::validation(ValidationCode::MessageMissing, "message", "is required")
+         })?;
+         let priority = self.priority.unwrap_or_else(|| message.default_priority());
+ 
+         Ok(WhatsAppMessageSend::new_with_clock(original_message_id, message, priority, clock))
+     }
+ }
  
  /// Union type for all supported WhatsApp message types
  /// 
@@ -102,6 +285,7 @@ impl WhatsAppMessageSend {
  /// through the WhatsApp Business API. Each variant contains the specific
  /// message data and formatting for that message type.
  #[derive(Debug, Clone, Serialize, Deserialize)]
+ #[allow(clippy::large_enum_variant)]
  pub enum WhatsAppMessage {
      /// Plain text message with optional link preview
      Text(TextMessage),
@@ -119,13 +303,107 @@ impl WhatsAppMessageSend {
      Location(LocationMessage),
      /// Video message with optional caption
      Video(VideoMessage),
+     /// WebP sticker without caption
+     Sticker(StickerMessage),
+ }
+ 
+ impl WhatsAppMessage {
+     /// The wrapped message, whatever its type
+     /// 
+     /// This is the one place that matches on every variant; a new variant
+     /// fails to compile here until it's added, and everything built on
+     /// `as_message` picks it up.
+     fn as_message(&self) -> &dyn Message {
+         match self {
+             WhatsAppMessage::Text(msg) => msg,
+             WhatsAppMessage::Audio(msg) => msg,
+             WhatsAppMessage::Contact(msg) => msg,
+             WhatsAppMessage::Document(msg) => msg,
+             WhatsAppMessage::Image(msg) => msg,
+             WhatsAppMessage::Interactive(msg) => msg,
+             WhatsAppMessage::Location(msg) => msg,
+             WhatsAppMessage::Video(msg) => msg,
+             WhatsAppMessage::Sticker(msg) => msg,
+         }
+     }
+ 
+     /// Recipient phone number of the wrapped message
+     pub fn recipient(&self) -> &str {
+         self.as_message().recipient()
+     }
+ 
+     /// Re-run the checks the wrapped message's constructors apply
+     /// 
+     /// Builders and constructors only produce valid messages, but one
+     /// deserialized from an event may have been built elsewhere. Fails with
+     /// the same error the constructor would have returned.
+     pub fn validate(&self) -> WhatsAppResult<()> {
+         match self {
+             WhatsAppMessage::Text(msg) => msg.validate(),
+             WhatsAppMessage::Audio(msg) => msg.validate(),
+             WhatsAppMessage::Contact(msg) => msg.validate(),
+             WhatsAppMessage::Document(msg) => msg.validate(),
+             WhatsAppMessage::Image(msg) => msg.validate(),
+             WhatsAppMessage::Interactive(msg) => msg.validate(),
+             WhatsAppMessage::Location(msg) => msg.validate(),
+             WhatsAppMessage::Video(msg) => msg.validate(),
+             WhatsAppMessage::Sticker(msg) => msg.validate(),
+         }
+     }
+ 
+     /// Whether WhatsApp accepts a time-to-live for this message type
+     /// 
+     /// The Cloud API only applies a TTL to template messages, where it is
+     /// configured on the template itself. None of the free-form types
+     /// sent here accept one.
+     pub fn supports_ttl(&self) -> bool {
+         false
+     }
+ 
+     /// Short name of the message type, for logs and metrics
+     /// 
+     /// Matches the variant name rather than the API's `type` field, so a
+     /// contact card is `"contact"` even though the API calls it `"contacts"`.
+     pub fn message_type_name(&self) -> &'static str {
+         match self {
+             WhatsAppMessage::Text(_) => "text",
+             WhatsAppMessage::Audio(_) => "audio",
+             WhatsAppMessage::Contact(_) => "contact",
+             WhatsAppMessage::Document(_) => "document",
+             WhatsAppMessage::Image(_) => "image",
+             WhatsAppMessage::Interactive(_) => "interactive",
+             WhatsAppMessage::Location(_) => "location",
+             WhatsAppMessage::Video(_) => "video",
+             WhatsAppMessage::Sticker(_) => "sticker",
+         }
+     }
+ 
+     /// Priority a response of this type gets unless told otherwise
+     /// 
+     /// Interactive messages usually wait on the user (a confirmation, a
+     /// one-time code), so they're urgent. Media can take its time and is
+     /// low. Everything else is normal.
+     pub fn default_priority(&self) -> ResponsePriority {
+         match self {
+             WhatsAppMessage::Interactive(_) => ResponsePriority::Urgent,
+             WhatsAppMessage::Text(_)
+             | WhatsAppMessage::Contact(_)
+             | WhatsAppMessage::Location(_) => ResponsePriority::Normal,
+             WhatsAppMessage::Audio(_)
+             | WhatsAppMessage::Document(_)
+             | WhatsAppMessage::Image(_)
+             | WhatsAppMessage::Video(_)
+             | WhatsAppMessage::Sticker(_) => ResponsePriority::Low,
+         }
+     }
  }
  
  /// Priority level for message delivery
  /// 
- /// This enum defines the urgency level for message responses, which can
- /// be used by the message processing system to prioritize delivery.
- #[derive(Debug, Clone, Serialize, Deserialize)]
+ /// This enum defines the urgency level for message responses. Each level
+ /// is published to its own sub-topic, so the sender can consume urgent
+ /// responses without waiting on low-priority ones.
+ #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
  pub enum ResponsePriority {
      /// Low priority - can be delayed for batch processing
      Low,
@@ -134,3 +412,248 @@ impl WhatsAppMessageSend {
      /// Urgent priority - should be processed immediately
      Urgent,
  }
+ 
+ impl ResponsePriority {
+     /// Every priority, most urgent first
+     pub const ALL: [ResponsePriority; 3] = [Self::Urgent, Self::Normal, Self::Low];
+ 
+     /// Sub-topic of `conversation.responses` this priority is routed to
+     pub fn topic_suffix(self) -> &'static str {
+         match self {
+             Self::Urgent => "urgent",
+             Self::Normal => "normal",
+             Self::Low => "low",
+         }
+     }
+ }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TO: &str = "+1234567890";
+
+    /// One message of every variant, with the expected type name
+    fn every_variant() -> Vec<(WhatsAppMessage, &'static str)> {
+        vec![
+            (WhatsAppMessage::Text(TextMessage::new(TO, "Hello").unwrap()), "text"),
+            (WhatsAppMessage::Audio(AudioMessage::from_media_id(TO, "1013859600285441").unwrap()), "audio"),
+            (WhatsAppMessage::Contact(ContactMessage::new(TO, "Ana Diaz").unwrap()), "contact"),
+            (WhatsAppMessage::Document(DocumentMessage::from_media_id(TO, "1013859600285442").unwrap()), "document"),
+            (WhatsAppMessage::Image(ImageMessage::from_media_id(TO, "1013859600285443").unwrap()), "image"),
+            (
+                WhatsAppMessage::Interactive(InteractiveMessage::with_buttons(
+                    TO,
+                    "Continue?",
+                    vec![("yes".to_string(), "Yes".to_string())],
+                ).unwrap()),
+                "interactive",
+            ),
+            (WhatsAppMessage::Location(LocationMessage::new(TO, 4.711, -74.072).unwrap()), "location"),
+            (WhatsAppMessage::Video(VideoMessage::from_media_id(TO, "1013859600285444").unwrap()), "video"),
+            (WhatsAppMessage::Sticker(StickerMessage::from_media_id(TO, "1013859600285445").unwrap()), "sticker"),
+        ]
+    }
+
+    #[test]
+    fn test_recipient_for_every_variant() {
+        for (message, name) in every_variant() {
+            assert_eq!(message.recipient(), TO, "{} recipient", name);
+        }
+    }
+
+    #[test]
+    fn test_validate_passes_for_every_variant() {
+        for (message, name) in every_variant() {
+            assert!(message.validate().is_ok(), "{} should validate", name);
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_deserialized_invalid_messages() {
+        let interactive = WhatsAppMessage::Interactive(InteractiveMessage::with_buttons(
+            TO,
+            "Continue?",
+            vec![("yes".to_string(), "Yes".to_string())],
+        ).unwrap());
+        let mut json = serde_json::to_value(&interactive).unwrap();
+        let button = json["Interactive"]["interactive"]["action"]["buttons"][0].clone();
+        json["Interactive"]["interactive"]["action"]["buttons"] = serde_json::json!([button.clone(), button]);
+
+        let decoded: WhatsAppMessage = serde_json::from_value(json).unwrap();
+        let error = decoded.validate().unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::DuplicateId), "{}", error);
+
+        let location = WhatsAppMessage::Location(LocationMessage::new(TO, 4.711, -74.072).unwrap());
+        let mut json = serde_json::to_value(&location).unwrap();
+        json["Location"]["location"]["latitude"] = 91.0.into();
+
+        let decoded: WhatsAppMessage = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.validate().unwrap_err().field(), Some("latitude"));
+    }
+
+    #[test]
+    fn test_message_type_name_for_every_variant() {
+        for (message, name) in every_variant() {
+            assert_eq!(message.message_type_name(), name);
+        }
+    }
+
+    #[test]
+    fn test_partition_key_uses_recipient() {
+        for (message, name) in every_variant() {
+            let send = WhatsAppMessageSend::new("wamid.1".to_string(), message, ResponsePriority::Normal);
+            assert_eq!(send.partition_key().as_deref(), Some(TO), "{} partition key", name);
+        }
+    }
+
+    #[test]
+    fn test_new_with_clock_stamps_clock_time() {
+        let at = chrono::DateTime::parse_from_rfc3339("2025-06-15T12:00:00Z").unwrap().to_utc();
+        let clock = common::clock::MockClock::new(at);
+        let message = WhatsAppMessage::Text(TextMessage::new(TO, "Hello!").unwrap());
+
+        let send = WhatsAppMessageSend::new_with_clock("wamid.1".to_string(), message, ResponsePriority::Low, &clock);
+        assert_eq!(send.generated_at, at);
+    }
+
+    #[test]
+    fn test_ttl_seconds_round_trips_through_json() {
+        let message = WhatsAppMessage::Text(TextMessage::new(TO, "Hello!").unwrap());
+        let send = WhatsAppMessageSend::new("wamid.1".to_string(), message, ResponsePriority::Normal)
+            .with_ttl_seconds(300);
+
+        let json = serde_json::to_value(&send).unwrap();
+        assert_eq!(json["ttl_seconds"], 300);
+
+        let decoded: WhatsAppMessageSend = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.ttl_seconds, Some(300));
+    }
+
+    #[test]
+    fn test_ttl_seconds_omitted_when_unset() {
+        let message = WhatsAppMessage::Text(TextMessage::new(TO, "Hello!").unwrap());
+        let send = WhatsAppMessageSend::new("wamid.1".to_string(), message, ResponsePriority::Normal);
+
+        let mut json = serde_json::to_value(&send).unwrap();
+        assert!(json.get("ttl_seconds").is_none());
+
+        // Events published before the field existed still decode
+        json.as_object_mut().unwrap().remove("ttl_seconds");
+        let decoded: WhatsAppMessageSend = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.ttl_seconds, None);
+        assert!(decoded.validate_ttl().is_ok());
+    }
+
+    #[test]
+    fn test_ttl_rejected_for_unsupported_type() {
+        for (message, name) in every_variant() {
+            assert!(!message.supports_ttl(), "{} should not accept a TTL", name);
+            let send = WhatsAppMessageSend::new("wamid.1".to_string(), message, ResponsePriority::Normal)
+                .with_ttl_seconds(60);
+            let error = send.validate_ttl().unwrap_err();
+            assert_eq!(error.validation_code(), Some(ValidationCode::TtlNotSupported), "{}", name);
+            assert_eq!(error.field(), Some("ttl_seconds"));
+        }
+    }
+
+    #[test]
+    fn test_zero_ttl_rejected() {
+        let message = WhatsAppMessage::Text(TextMessage::new(TO, "Hello!").unwrap());
+        let send = WhatsAppMessageSend::new("wamid.1".to_string(), message, ResponsePriority::Normal)
+            .with_ttl_seconds(0);
+
+        let error = send.validate_ttl().unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::TtlZero));
+        assert_eq!(error.field(), Some("ttl_seconds"));
+    }
+
+    #[test]
+    fn test_builder_derives_priority_from_message_type() {
+        let priority_of = |message: WhatsAppMessage| {
+            WhatsAppMessageSend::builder()
+                .original_message_id("wamid.1")
+                .message(message)
+                .build()
+                .unwrap()
+                .priority
+        };
+
+        let mut priorities = std::collections::HashMap::new();
+        for (message, name) in every_variant() {
+            assert_eq!(priority_of(message.clone()), message.default_priority(), "{}", name);
+            priorities.insert(name, message.default_priority());
+        }
+        assert_eq!(priorities["interactive"], ResponsePriority::Urgent);
+        assert_eq!(priorities["text"], ResponsePriority::Normal);
+        for media in ["audio", "document", "image", "video", "sticker"] {
+            assert_eq!(priorities[media], ResponsePriority::Low, "{}", media);
+        }
+    }
+
+    #[test]
+    fn test_builder_explicit_priority_overrides_default() {
+        let (image, _) = every_variant().remove(4);
+        let send = WhatsAppMessageSend::builder()
+            .original_message_id("wamid.1")
+            .message(image)
+            .priority(ResponsePriority::Urgent)
+            .build()
+            .unwrap();
+        assert_eq!(send.priority, ResponsePriority::Urgent);
+        assert_eq!(send.topic_suffix(), Some("urgent"));
+    }
+
+    #[test]
+    fn test_builder_requires_original_message_id_and_message() {
+        let text = || WhatsAppMessage::Text(TextMessage::new(TO, "Hello!").unwrap());
+
+        for id in [None, Some(""), Some("   ")] {
+            let mut builder = WhatsAppMessageSend::builder().message(text());
+            if let Some(id) = id {
+                builder = builder.original_message_id(id);
+            }
+            let error = builder.build().unwrap_err();
+            assert_eq!(error.validation_code(), Some(ValidationCode::MessageIdEmpty), "{:?}", id);
+            assert_eq!(error.field(), Some("original_message_id"));
+        }
+
+        let error = WhatsAppMessageSend::builder().original_message_id("wamid.1").build().unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::MessageMissing));
+        assert_eq!(error.field(), Some("message"));
+
+        let at = chrono::DateTime::parse_from_rfc3339("2025-06-15T12:00:00Z").unwrap().to_utc();
+        let send = WhatsAppMessageSend::builder()
+            .original_message_id("wamid.1")
+            .message(text())
+            .build_with_clock(&common::clock::MockClock::new(at))
+            .unwrap();
+        assert_eq!(send.generated_at, at);
+        assert_eq!(send.original_message_id, "wamid.1");
+    }
+
+    #[tokio::test]
+    async fn test_urgent_message_lands_on_urgent_topic() {
+        use common::{EventBus, EventEnvelope, InMemoryEventBus};
+
+        let bus = InMemoryEventBus::new();
+        for priority in ResponsePriority::ALL {
+            let message = WhatsAppMessage::Text(TextMessage::new(TO, "Your code is 123456").unwrap());
+            bus.publish(WhatsAppMessageSend::new("wamid.1".to_string(), message, priority))
+                .await
+                .expect("Should publish");
+        }
+
+        let urgent = bus.published_to("conversation.responses.urgent");
+        assert_eq!(urgent.len(), 1);
+        let envelope: EventEnvelope<WhatsAppMessageSend> = urgent[0].decode().expect("Should decode");
+        assert_eq!(envelope.data.priority, ResponsePriority::Urgent);
+        assert_eq!(bus.published_to("conversation.responses.normal").len(), 1);
+        assert_eq!(bus.published_to("conversation.responses.low").len(), 1);
+        assert!(bus.published_to(WhatsAppMessageSend::TOPIC).is_empty());
+
+        for priority in ResponsePriority::ALL {
+            assert!(WhatsAppMessageSend::TOPIC_SUFFIXES.contains(&priority.topic_suffix()));
+        }
+    }
+}
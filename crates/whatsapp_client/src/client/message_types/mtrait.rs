@@ -1,5 +1,5 @@
 pub trait Message {
-    /// Get the recipient's phone number in E.164 format
+    /// Get the recipient's phone number in E.164 format, or group ID
     fn recipient(&self) -> &str;
 
     /// Get the message type identifier
@@ -0,0 +1,122 @@
+use crate::{
+    errors::WhatsAppResult,
+    client::validation::validate_recipient,
+};
+use serde::{Serialize, Deserialize};
+
+/// Whether a message goes to a single user or to a group
+///
+/// Serialized as WhatsApp's `recipient_type` field.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecipientType {
+    /// A single user, addressed by E.164 phone number
+    #[default]
+    Individual,
+    /// A group, addressed by the group ID from WhatsApp's groups API
+    Group,
+}
+
+/// Who a message is sent to
+///
+/// Message constructors take `impl Into<Recipient>`, so a plain phone
+/// number still works; use `Recipient::group` to target a group.
+///
+/// # Example
+/// ```
+/// # use whatsapp_client::client::message_types::{Recipient, RecipientType, TextMessage};
+/// let message = TextMessage::new(Recipient::group("Y2FwaV9ncm91cDoxNzA1NTU1MDEz"), "Hi all!")?;
+/// assert_eq!(message.recipient_type(), RecipientType::Group);
+/// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recipient {
+    id: String,
+    recipient_type: RecipientType,
+}
+
+impl Recipient {
+    /// A single user, by phone number in E.164 format
+    pub fn individual(phone: &str) -> Self {
+        Self {
+            id: phone.to_string(),
+            recipient_type: RecipientType::Individual,
+        }
+    }
+
+    /// A group, by its group ID
+    pub fn group(group_id: &str) -> Self {
+        Self {
+            id: group_id.to_string(),
+            recipient_type: RecipientType::Group,
+        }
+    }
+
+    /// Get the phone number or group ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get whether this is a user or a group
+    pub fn recipient_type(&self) -> RecipientType {
+        self.recipient_type
+    }
+
+    /// Check the ID has the right shape for the recipient type
+    pub fn validate(&self) -> WhatsAppResult<()> {
+        validate_recipient(&self.id, self.recipient_type)
+    }
+}
+
+impl From<&str> for Recipient {
+    fn from(phone: &str) -> Self {
+        Self::individual(phone)
+    }
+}
+
+impl From<&String> for Recipient {
+    fn from(phone: &String) -> Self {
+        Self::individual(phone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::{ValidationCode, WhatsAppError};
+
+    #[test]
+    fn test_phone_numbers_convert_to_individual_recipients() {
+        let recipient = Recipient::from("+16505551234");
+        assert_eq!(recipient, Recipient::individual("+16505551234"));
+        assert_eq!(recipient.recipient_type(), RecipientType::Individual);
+        assert!(recipient.validate().is_ok());
+    }
+
+    #[test]
+    fn test_recipient_type_serializes_lowercase() {
+        assert_eq!(serde_json::to_string(&RecipientType::Group).unwrap(), r#""group""#);
+        assert_eq!(serde_json::to_string(&RecipientType::default()).unwrap(), r#""individual""#);
+        assert!(serde_json::from_str::<RecipientType>(r#""broadcast""#).is_err());
+    }
+
+    #[test]
+    fn test_ids_must_match_the_recipient_type() {
+        assert!(Recipient::group("Y2FwaV9ncm91cDoxNzA1NTU1MDEz").validate().is_ok());
+
+        // Each kind of ID is rejected as the other kind
+        for recipient in [Recipient::group("+16505551234"), Recipient::individual("Y2FwaV9ncm91cDoxNzA1NTU1MDEz")] {
+            match recipient.validate() {
+                Err(WhatsAppError::Validation { code, field, .. }) => {
+                    assert_eq!(field, "to");
+                    let expected = match recipient.recipient_type() {
+                        RecipientType::Group => ValidationCode::InvalidGroupId,
+                        RecipientType::Individual => ValidationCode::InvalidPhone,
+                    };
+                    assert_eq!(code, expected);
+                }
+                other => panic!("Expected validation error for {:?}, got {:?}", recipient, other),
+            }
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use crate::{
+    errors::{ValidationCode, WhatsAppResult},
+    client::{
+        validation::{
+            validate_recipient, validate_media_reference, validate_media_id, validate_url,
+            validate_mime_type, MediaType,
+            MAX_STICKER_SIZE, MAX_ANIMATED_STICKER_SIZE,
+        },
+        message_types::{mtrait::Message, media::MediaReference, recipient::{Recipient, RecipientType}},
+    },
+};
+use serde::{Serialize, Deserialize};
+
+/// A sticker message that can be sent via WhatsApp
+///
+/// Stickers are WebP images displayed without a bubble. They can be static
+/// or animated, and unlike images they can't carry a caption.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StickerMessage {
+    /// Always "whatsapp" for WhatsApp Business API
+    messaging_product: String,
+    /// Whether `to` is a user's phone number or a group ID
+    recipient_type: RecipientType,
+    /// Recipient's phone number in E.164 format, or a group ID
+    to: String,
+    /// Message type identifier
+    #[serde(rename = "type")]
+    message_type: String,
+    /// Sticker content configuration
+    sticker: StickerContent,
+}
+
+impl Message for StickerMessage {
+    /// Get the recipient phone number
+    fn recipient(&self) -> &str {
+        &self.to
+    }
+
+    /// Get the message type identifier
+    fn message_type(&self) -> &str {
+        "sticker"
+    }
+}
+
+/// Sticker message content structure
+///
+/// This contains either a media ID (for uploaded stickers) or a URL (for hosted stickers).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StickerContent {
+    /// Uploaded media ID (recommended) or hosted URL
+    #[serde(flatten)]
+    media: MediaReference,
+}
+
+impl StickerMessage {
+    /// Create a new sticker message using uploaded media ID
+    ///
+    /// # Arguments
+    /// * `to` - Recipient phone number in E.164 format, or a `Recipient`
+    /// * `media_id` - ID of the uploaded sticker from WhatsApp's media API
+    ///
+    /// # Example
+    /// ```
+    /// # use whatsapp_client::client::message_types::StickerMessage;
+    /// let message = StickerMessage::from_media_id("+1234567890", "1013859600285441")?;
+    /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
+    /// ```
+    pub fn from_media_id(to: impl Into<Recipient>, media_id: &str) -> WhatsAppResult<Self> {
+        // Validate inputs
+        let to = to.into();
+        to.validate()?;
+        validate_media_id(media_id)?;
+
+        Ok(Self {
+            messaging_product: "whatsapp".to_string(),
+            recipient_type: to.recipient_type(),
+            to: to.id().to_string(),
+            message_type: "sticker".to_string(),
+            sticker: StickerContent {
+                media: MediaReference::Id(media_id.to_string()),
+            },
+        })
+    }
+
+    /// Create a new sticker message using a hosted URL
+    ///
+    /// WhatsApp downloads the sticker from your server, so the URL must point
+    /// at a publicly reachable `.webp` file.
+    ///
+    /// # Arguments
+    /// * `to` - Recipient phone number in E.164 format, or a `Recipient`
+    /// * `sticker_url` - URL to the hosted .webp sticker
+    ///
+    /// # Example
+    /// ```
+    /// # use whatsapp_client::client::message_types::StickerMessage;
+    /// let message = StickerMessage::from_url(
+    ///     "+1234567890",
+    ///     "https://example.com/sticker.webp"
+    /// )?;
+    /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
+    /// ```
+    pub fn from_url(to: impl Into<Recipient>, sticker_url: &str) -> WhatsAppResult<Self> {
+        // Validate inputs
+        let to = to.into();
+        to.validate()?;
+        validate_url(sticker_url)?;
+
+        Ok(Self {
+            messaging_product: "whatsapp".to_string(),
+            recipient_type: to.recipient_type(),
+            to: to.id().to_string(),
+            message_type: "sticker".to_string(),
+            sticker: StickerContent {
+                media: MediaReference::Link(sticker_url.to_string()),
+            },
+        })
+    }
+
+    /// Get the media ID if this message uses uploaded media
+    pub fn media_id(&self) -> Option<&str> {
+        self.sticker.media.id()
+    }
+
+    /// Get the URL if this message uses hosted media
+    pub fn media_url(&self) -> Option<&str> {
+        self.sticker.media.link()
+    }
+
+    /// Check if this message uses uploaded media (recommended)
+    pub fn uses_uploaded_media(&self) -> bool {
+        self.sticker.media.is_uploaded()
+    }
+    
+    /// Get whether the message goes to a user or a group
+    pub fn recipient_type(&self) -> RecipientType {
+        self.recipient_type
+    }
+    
+    /// Validate the message as a whole
+    /// 
+    /// Messages built with the constructors always pass. This is meant for
+    /// messages that arrived some other way, e.g. deserialized from an event.
+    pub fn validate(&self) -> WhatsAppResult<()> {
+        validate_recipient(&self.to, self.recipient_type)?;
+        validate_media_reference(self.media_id(), self.media_url())?;
+        Ok(())
+    }
+
+    /// Validate sticker file properties
+    ///
+    /// Stickers must be WebP. Static stickers may be up to 100 KB and
+    /// animated ones up to 500 KB.
+    pub fn validate_sticker_file(
+        mime_type: &str,
+        file_size_bytes: u64,
+        animated: bool,
+    ) -> WhatsAppResult<()> {
+        validate_mime_type(mime_type, MediaType::Sticker)?;
+        let max_size = Self::max_file_size(animated);
+        if file_size_bytes > max_size {
+            return Err(crate::errors::WhatsAppError::validation(
+                ValidationCode::FileTooLarge,
+                "file_size",
+                format!("too large: {} bytes (max {} for {} stickers)",
+                       file_size_bytes, max_size, if animated { "animated" } else { "static" })
+            ));
+        }
+        Ok(())
+    }
+
+    /// Get supported sticker formats
+    pub fn supported_formats() -> &'static [&'static str] {
+        &["image/webp"]
+    }
+
+    /// Get maximum file size for sticker messages
+    ///
+    /// Returns the maximum file size in bytes (100 KB static, 500 KB animated).
+    pub fn max_file_size(animated: bool) -> u64 {
+        if animated {
+            MAX_ANIMATED_STICKER_SIZE
+        } else {
+            MAX_STICKER_SIZE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::validation::validate_file_size;
+
+    #[test]
+    fn test_sticker_message_serialization_with_media_id() {
+        let message = StickerMessage::from_media_id("+1234567890", "1013859600285441").unwrap();
+        let json = serde_json::to_value(&message).unwrap();
+
+        assert_eq!(json, serde_json::json!({
+            "messaging_product": "whatsapp",
+            "recipient_type": "individual",
+            "to": "+1234567890",
+            "type": "sticker",
+            "sticker": { "id": "1013859600285441" }
+        }));
+        assert!(message.uses_uploaded_media());
+    }
+
+    #[test]
+    fn test_sticker_message_serialization_with_url() {
+        let message = StickerMessage::from_url(
+            "+1234567890",
+            "https://example.com/sticker.webp"
+        ).unwrap();
+        let json = serde_json::to_value(&message).unwrap();
+
+        assert_eq!(json["type"], "sticker");
+        assert_eq!(json["sticker"]["link"], "https://example.com/sticker.webp");
+        assert!(json["sticker"].get("id").is_none());
+        assert!(json["sticker"].get("caption").is_none());
+        assert_eq!(message.message_type(), "sticker");
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(StickerMessage::from_media_id("invalid", "123456").is_err());
+        assert!(StickerMessage::from_media_id("+1234567890", "invalid_id").is_err());
+        assert!(StickerMessage::from_url("+1234567890", "not-a-url").is_err());
+    }
+
+    #[test]
+    fn test_sticker_file_validation() {
+        // Only WebP is accepted
+        assert!(StickerMessage::validate_sticker_file("image/webp", 100 * 1024, false).is_ok());
+        let error = StickerMessage::validate_sticker_file("image/png", 1024, false).unwrap_err();
+        assert_eq!(error.field(), Some("mime_type"));
+
+        // Static stickers may be up to 100 KB, animated ones up to 500 KB
+        let error = StickerMessage::validate_sticker_file("image/webp", 510 * 1024, false).unwrap_err();
+        assert_eq!(error.field(), Some("file_size"));
+        assert!(StickerMessage::validate_sticker_file("image/webp", 101 * 1024, false).is_err());
+        assert!(StickerMessage::validate_sticker_file("image/webp", 400 * 1024, true).is_ok());
+        let error = StickerMessage::validate_sticker_file("image/webp", 510 * 1024, true).unwrap_err();
+        assert_eq!(error.field(), Some("file_size"));
+
+        assert_eq!(StickerMessage::max_file_size(false), 100 * 1024);
+        assert_eq!(StickerMessage::max_file_size(true), 500 * 1024);
+
+        // The generic size check (used by uploads) only knows the upper bound
+        assert!(validate_file_size(400 * 1024, MediaType::Sticker).is_ok());
+        assert!(StickerMessage::validate_sticker_file("image/webp", 400 * 1024, false).is_err());
+        assert!(validate_file_size(MAX_ANIMATED_STICKER_SIZE + 1, MediaType::Sticker).is_err());
+    }
+}
@@ -1,8 +1,8 @@
 use crate::{
     errors::WhatsAppResult,
     client::{
-        validation::{validate_phone_number, validate_text_message},
-        message_types::mtrait::Message,
+        validation::{text_length, validate_recipient, validate_text_message},
+        message_types::{mtrait::Message, recipient::{Recipient, RecipientType}},
     },
 };
 use serde::{Serialize, Deserialize};
@@ -16,9 +16,9 @@ use serde::{Serialize, Deserialize};
 pub struct TextMessage {
     /// Always "whatsapp" for WhatsApp Business API
     messaging_product: String,
-    /// Recipient type - always "individual" for direct messages
-    recipient_type: String,
-    /// Recipient's phone number in E.164 format
+    /// Whether `to` is a user's phone number or a group ID
+    recipient_type: RecipientType,
+    /// Recipient's phone number in E.164 format, or a group ID
     to: String,
     /// Message type identifier
     #[serde(rename = "type")]
@@ -56,7 +56,7 @@ impl TextMessage {
     /// Create a new text message
     /// 
     /// # Arguments
-    /// * `to` - Recipient phone number in E.164 format (+1234567890)
+    /// * `to` - Recipient phone number in E.164 format (+1234567890), or a `Recipient`
     /// * `message` - Text content (up to 4096 characters)
     /// 
     /// # Example
@@ -65,15 +65,16 @@ impl TextMessage {
     /// let message = TextMessage::new("+1234567890", "Hello, world!")?;
     /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
     /// ```
-    pub fn new(to: &str, message: &str) -> WhatsAppResult<Self> {
+    pub fn new(to: impl Into<Recipient>, message: &str) -> WhatsAppResult<Self> {
         // Validate inputs
-        validate_phone_number(to)?;
+        let to = to.into();
+        to.validate()?;
         validate_text_message(message)?;
         
         Ok(Self {
             messaging_product: "whatsapp".to_string(),
-            recipient_type: "individual".to_string(),
-            to: to.to_string(),
+            recipient_type: to.recipient_type(),
+            to: to.id().to_string(),
             message_type: "text".to_string(),
             text: TextContent {
                 body: message.to_string(),
@@ -96,7 +97,7 @@ impl TextMessage {
     /// )?;
     /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
     /// ```
-    pub fn with_preview(to: &str, message: &str) -> WhatsAppResult<Self> {
+    pub fn with_preview(to: impl Into<Recipient>, message: &str) -> WhatsAppResult<Self> {
         let mut text_message = Self::new(to, message)?;
         text_message.text.preview_url = Some(true);
         Ok(text_message)
@@ -105,12 +106,61 @@ impl TextMessage {
     /// Create a new text message with link preview explicitly disabled
     /// 
     /// This ensures that no link previews are shown even if URLs are present.
-    pub fn without_preview(to: &str, message: &str) -> WhatsAppResult<Self> {
+    pub fn without_preview(to: impl Into<Recipient>, message: &str) -> WhatsAppResult<Self> {
         let mut text_message = Self::new(to, message)?;
         text_message.text.preview_url = Some(false);
         Ok(text_message)
     }
     
+    /// Toggle the link preview for URLs in the message
+    /// 
+    /// `preview_url` is only sent when enabled; disabling it leaves the
+    /// field out and WhatsApp falls back to its default of no preview.
+    /// 
+    /// # Example
+    /// ```
+    /// # use whatsapp_client::client::message_types::TextMessage;
+    /// let body = "Our menu: https://example.com/menu";
+    /// let message = TextMessage::new("+1234567890", body)?;
+    /// let message = message.clone().with_preview_url(message.contains_url());
+    /// assert_eq!(message.has_preview_enabled(), Some(true));
+    /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
+    /// ```
+    pub fn with_preview_url(mut self, enabled: bool) -> Self {
+        self.text.preview_url = enabled.then_some(true);
+        self
+    }
+    
+    /// Get whether the message goes to a user or a group
+    pub fn recipient_type(&self) -> RecipientType {
+        self.recipient_type
+    }
+    
+    /// Validate the message as a whole
+    /// 
+    /// Messages built with the constructors always pass. This is meant for
+    /// messages that arrived some other way, e.g. deserialized from an event.
+    pub fn validate(&self) -> WhatsAppResult<()> {
+        validate_recipient(&self.to, self.recipient_type)?;
+        validate_text_message(&self.text.body)?;
+        Ok(())
+    }
+    
+    /// Check whether the message text contains an http(s) link
+    /// 
+    /// Useful for deciding whether enabling a link preview makes sense.
+    pub fn contains_url(&self) -> bool {
+        let body = self.text.body.to_ascii_lowercase();
+        ["http://", "https://"].iter().any(|scheme| {
+            body.match_indices(scheme).any(|(start, _)| {
+                body[start + scheme.len()..]
+                    .chars()
+                    .next()
+                    .is_some_and(|c| !c.is_whitespace())
+            })
+        })
+    }
+    
     /// Get the message text
     pub fn message(&self) -> &str {
         &self.text.body
@@ -121,15 +171,19 @@ impl TextMessage {
         self.text.preview_url
     }
     
-    /// Get the length of the message text
+    /// Get the length of the message text in characters
+    /// 
+    /// Counted the same way as the 4096 character limit, see
+    /// [`text_length`](crate::client::validation::text_length).
     pub fn message_length(&self) -> usize {
-        self.text.body.len()
+        text_length(&self.text.body)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::errors::ValidationCode;
     use serde_json;
     
     #[test]
@@ -179,6 +233,41 @@ mod tests {
         assert_eq!(json["text"]["preview_url"], true);
     }
     
+    #[test]
+    fn test_with_preview_url_serialization() {
+        let message = TextMessage::new("+16505551234", "Check out: https://example.com")
+            .unwrap()
+            .with_preview_url(true);
+        let json_output = serde_json::to_string(&message).unwrap();
+        let expected_json = r#"{"messaging_product":"whatsapp","recipient_type":"individual","to":"+16505551234","type":"text","text":{"body":"Check out: https://example.com","preview_url":true}}"#;
+        assert_eq!(json_output, expected_json);
+        
+        // Turning the preview off omits the field entirely
+        let message = message.with_preview_url(false);
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(message.has_preview_enabled(), None);
+        assert!(json["text"].get("preview_url").is_none());
+    }
+    
+    #[test]
+    fn test_contains_url() {
+        let cases = [
+            ("Visit https://example.com today", true),
+            ("Plain http://example.com/path?q=1", true),
+            ("Shouting HTTPS://EXAMPLE.COM", true),
+            ("(see https://example.com)", true),
+            ("No links here", false),
+            ("Just the scheme https:// alone", false),
+            ("ftp://example.com is not a web link", false),
+            ("example.com without a scheme", false),
+        ];
+        
+        for (body, expected) in cases {
+            let message = TextMessage::new("+1234567890", body).unwrap();
+            assert_eq!(message.contains_url(), expected, "body: {}", body);
+        }
+    }
+    
     #[test]
     fn test_invalid_phone_number() {
         let result = TextMessage::new("invalid", "Hello");
@@ -237,4 +326,26 @@ mod tests {
         
         assert_eq!(json_output, expected_json);
     }
+    
+    #[test]
+    fn test_group_text_message_json_format() {
+        let group = Recipient::group("Y2FwaV9ncm91cDoxNzA1NTU1MDEz");
+        let message = TextMessage::new(group, "Meeting moved to 3pm").unwrap();
+        let json_output = serde_json::to_string(&message).unwrap();
+        
+        let expected_json = r#"{"messaging_product":"whatsapp","recipient_type":"group","to":"Y2FwaV9ncm91cDoxNzA1NTU1MDEz","type":"text","text":{"body":"Meeting moved to 3pm"}}"#;
+        
+        assert_eq!(json_output, expected_json);
+        assert_eq!(message.recipient_type(), RecipientType::Group);
+        
+        // Deserialized group messages still validate as groups
+        let round_trip: TextMessage = serde_json::from_str(&json_output).unwrap();
+        assert!(round_trip.validate().is_ok());
+    }
+    
+    #[test]
+    fn test_group_text_message_requires_group_id() {
+        let result = TextMessage::new(Recipient::group("+16505551234"), "Hello");
+        assert_eq!(result.unwrap_err().validation_code(), Some(ValidationCode::InvalidGroupId));
+    }
 }
@@ -2,10 +2,10 @@ use crate::{
     errors::WhatsAppResult,
     client::{
         validation::{
-            validate_phone_number, validate_media_id, validate_url, 
+            validate_recipient, validate_media_reference, validate_media_id, validate_url, 
             validate_mime_type, validate_file_size, validate_caption, MediaType
         },
-        message_types::mtrait::Message,
+        message_types::{mtrait::Message, media::MediaReference, recipient::{Recipient, RecipientType}},
     },
 };
 use serde::{Serialize, Deserialize};
@@ -19,9 +19,9 @@ use serde::{Serialize, Deserialize};
 pub struct VideoMessage {
     /// Always "whatsapp" for WhatsApp Business API
     messaging_product: String,
-    /// Recipient type - always "individual" for direct messages
-    recipient_type: String,
-    /// Recipient's phone number in E.164 format
+    /// Whether `to` is a user's phone number or a group ID
+    recipient_type: RecipientType,
+    /// Recipient's phone number in E.164 format, or a group ID
     to: String,
     /// Message type identifier
     #[serde(rename = "type")]
@@ -49,12 +49,9 @@ impl Message for VideoMessage {
 /// Videos can include an optional caption.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct VideoContent {
-    /// Media ID for uploaded video (recommended approach)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    id: Option<String>,
-    /// URL for hosted video (not recommended)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    link: Option<String>,
+    /// Uploaded media ID (recommended) or hosted URL
+    #[serde(flatten)]
+    media: MediaReference,
     /// Optional caption text (max 1024 characters)
     #[serde(skip_serializing_if = "Option::is_none")]
     caption: Option<String>,
@@ -67,7 +64,7 @@ impl VideoMessage {
     /// The video must be uploaded to WhatsApp first using the media upload API.
     /// 
     /// # Arguments
-    /// * `to` - Recipient phone number in E.164 format
+    /// * `to` - Recipient phone number in E.164 format, or a `Recipient`
     /// * `media_id` - ID of the uploaded video file from WhatsApp's media API
     /// 
     /// # Example
@@ -76,19 +73,19 @@ impl VideoMessage {
     /// let message = VideoMessage::from_media_id("+1234567890", "1013859600285441")?;
     /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
     /// ```
-    pub fn from_media_id(to: &str, media_id: &str) -> WhatsAppResult<Self> {
+    pub fn from_media_id(to: impl Into<Recipient>, media_id: &str) -> WhatsAppResult<Self> {
         // Validate inputs
-        validate_phone_number(to)?;
+        let to = to.into();
+        to.validate()?;
         validate_media_id(media_id)?;
         
         Ok(Self {
             messaging_product: "whatsapp".to_string(),
-            recipient_type: "individual".to_string(),
-            to: to.to_string(),
+            recipient_type: to.recipient_type(),
+            to: to.id().to_string(),
             message_type: "video".to_string(),
             video: VideoContent {
-                id: Some(media_id.to_string()),
-                link: None,
+                media: MediaReference::Id(media_id.to_string()),
                 caption: None,
             },
         })
@@ -101,7 +98,7 @@ impl VideoMessage {
     /// adds latency and potential failure points. Videos are typically large files.
     /// 
     /// # Arguments
-    /// * `to` - Recipient phone number in E.164 format
+    /// * `to` - Recipient phone number in E.164 format, or a `Recipient`
     /// * `video_url` - URL to the hosted video file
     /// 
     /// # Example
@@ -113,19 +110,19 @@ impl VideoMessage {
     /// )?;
     /// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
     /// ```
-    pub fn from_url(to: &str, video_url: &str) -> WhatsAppResult<Self> {
+    pub fn from_url(to: impl Into<Recipient>, video_url: &str) -> WhatsAppResult<Self> {
         // Validate inputs
-        validate_phone_number(to)?;
+        let to = to.into();
+        to.validate()?;
         validate_url(video_url)?;
         
         Ok(Self {
             messaging_product: "whatsapp".to_string(),
-            recipient_type: "individual".to_string(),
-            to: to.to_string(),
+            recipient_type: to.recipient_type(),
+            to: to.id().to_string(),
             message_type: "video".to_string(),
             video: VideoContent {
-                id: None,
-                link: Some(video_url.to_string()),
+                media: MediaReference::Link(video_url.to_string()),
                 caption: None,
             },
         })
@@ -151,12 +148,12 @@ impl VideoMessage {
     
     /// Get the media ID if this message uses uploaded media
     pub fn media_id(&self) -> Option<&str> {
-        self.video.id.as_deref()
+        self.video.media.id()
     }
     
     /// Get the URL if this message uses hosted media
     pub fn media_url(&self) -> Option<&str> {
-        self.video.link.as_deref()
+        self.video.media.link()
     }
     
     /// Get the caption text if set
@@ -166,7 +163,25 @@ impl VideoMessage {
     
     /// Check if this message uses uploaded media (recommended)
     pub fn uses_uploaded_media(&self) -> bool {
-        self.video.id.is_some()
+        self.video.media.is_uploaded()
+    }
+    
+    /// Get whether the message goes to a user or a group
+    pub fn recipient_type(&self) -> RecipientType {
+        self.recipient_type
+    }
+    
+    /// Validate the message as a whole
+    /// 
+    /// Messages built with the constructors always pass. This is meant for
+    /// messages that arrived some other way, e.g. deserialized from an event.
+    pub fn validate(&self) -> WhatsAppResult<()> {
+        validate_recipient(&self.to, self.recipient_type)?;
+        validate_media_reference(self.media_id(), self.media_url())?;
+        if let Some(caption) = self.caption() {
+            validate_caption(caption)?;
+        }
+        Ok(())
     }
     
     /// Validate video file properties
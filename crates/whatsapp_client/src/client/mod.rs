@@ -6,9 +6,13 @@
 //! - `builders/`: Builder patterns for constructing complex messages
 //! - `responses.rs`: Response types and parsing
 //! - `validation.rs`: Input validation utilities
+//! - `idempotency.rs`: Recently sent message keys for duplicate suppression
+//! - `conversation_window.rs`: 24-hour customer service window checks
 
 pub mod core;
 pub mod message_types;
 pub mod builders;
 pub mod responses;
 pub mod validation;
+pub mod idempotency;
+pub mod conversation_window;
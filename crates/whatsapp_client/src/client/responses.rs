@@ -73,6 +73,25 @@ pub struct MediaUploadResponse {
     pub id: String,
 }
 
+/// Response from media metadata lookups
+/// 
+/// Looking up a media ID returns a short-lived download URL along with
+/// the file's MIME type. The URL requires the same bearer token as every
+/// other API call.
+#[derive(Deserialize, Debug, Clone)]
+pub struct MediaUrlResponse {
+    /// Temporary URL the media bytes can be downloaded from
+    pub url: String,
+    /// MIME type of the media file
+    pub mime_type: String,
+    /// SHA-256 hash of the media file
+    pub sha256: Option<String>,
+    /// Size of the media file in bytes
+    pub file_size: Option<u64>,
+    /// The media ID that was looked up
+    pub id: String,
+}
+
 /// Response for webhook verification
 /// 
 /// This is used during the webhook setup process when WhatsApp verifies
@@ -139,6 +158,23 @@ mod tests {
         assert_eq!(response.id, "1013859600285441");
     }
     
+    #[test]
+    fn test_media_url_response_parsing() {
+        let response_json = json!({
+            "messaging_product": "whatsapp",
+            "url": "https://lookaside.fbsbx.com/whatsapp_business/attachments/?mid=1013859600285441",
+            "mime_type": "image/jpeg",
+            "sha256": "abc123",
+            "file_size": 303833,
+            "id": "1013859600285441"
+        });
+        
+        let response: MediaUrlResponse = serde_json::from_value(response_json).unwrap();
+        assert_eq!(response.mime_type, "image/jpeg");
+        assert_eq!(response.file_size, Some(303833));
+        assert_eq!(response.id, "1013859600285441");
+    }
+    
     #[test]
     fn test_empty_response_handling() {
         let response_json = json!({
@@ -1,14 +1,25 @@
-use crate::errors::{WhatsAppError, WhatsAppResult};
+use crate::{
+    errors::{ValidationCode, WhatsAppError, WhatsAppResult},
+    client::message_types::RecipientType,
+};
 use regex::Regex;
 use std::sync::OnceLock;
+use url::Url;
 
 /// Maximum file sizes for different media types (in bytes)
 pub const MAX_AUDIO_SIZE: u64 = 16 * 1024 * 1024; // 16 MB
 pub const MAX_DOCUMENT_SIZE: u64 = 100 * 1024 * 1024; // 100 MB
 pub const MAX_IMAGE_SIZE: u64 = 5 * 1024 * 1024; // 5 MB
 pub const MAX_VIDEO_SIZE: u64 = 16 * 1024 * 1024; // 16 MB
+/// Sticker limits are from the Cloud API's supported media types table
+/// (https://developers.facebook.com/docs/whatsapp/cloud-api/reference/media#supported-media-types):
+/// 100 KB for static stickers and 500 KB for animated ones.
+pub const MAX_STICKER_SIZE: u64 = 100 * 1024; // 100 KB (static)
+pub const MAX_ANIMATED_STICKER_SIZE: u64 = 500 * 1024; // 500 KB
 
 /// Maximum text lengths for various fields
+///
+/// These are measured in characters as counted by [`text_length`], not bytes.
 pub const MAX_TEXT_MESSAGE_LENGTH: usize = 4096;
 pub const MAX_CAPTION_LENGTH: usize = 1024;
 pub const MAX_BUTTON_TITLE_LENGTH: usize = 20;
@@ -17,8 +28,20 @@ pub const MAX_LIST_TITLE_LENGTH: usize = 24;
 pub const MAX_LIST_DESCRIPTION_LENGTH: usize = 72;
 pub const MAX_HEADER_TEXT_LENGTH: usize = 60;
 pub const MAX_FOOTER_TEXT_LENGTH: usize = 60;
+pub const MAX_FLOW_CTA_LENGTH: usize = 30;
 pub const MAX_URL_LENGTH: usize = 2048;
 
+/// Length of a text field as WhatsApp's character limits count it
+/// 
+/// Limits are counted in Unicode scalar values (`char`s) rather than UTF-8
+/// bytes, so Cyrillic, CJK or emoji text isn't rejected early for taking
+/// several bytes per character. Emoji built from several code points
+/// (flags, skin tones, ZWJ sequences like 👨‍👩‍👧) count once per code
+/// point, which errs on the strict side of WhatsApp's own counting.
+pub fn text_length(text: &str) -> usize {
+    text.chars().count()
+}
+
 /// Validate phone number format (E.164)
 /// 
 /// WhatsApp requires phone numbers to be in E.164 format: +[country code][number]
@@ -30,29 +53,96 @@ pub fn validate_phone_number(phone: &str) -> WhatsAppResult<()> {
     });
     
     if !regex.is_match(phone) {
-        return Err(WhatsAppError::InvalidPhoneNumber(
-            format!("Phone number must be in E.164 format (+1234567890): {}", phone)
+        return Err(WhatsAppError::validation(
+            ValidationCode::InvalidPhone,
+            "to",
+            format!("must be in E.164 format (+1234567890): {}", phone)
         ));
     }
     
     Ok(())
 }
 
+/// Validate a group ID from WhatsApp's groups API
+/// 
+/// Group IDs are opaque base64-style tokens, e.g.
+/// `Y2FwaV9ncm91cDoxNzA1NTU1MDEz`. A phone number is rejected here so
+/// that sending to a group can't silently message a single user instead.
+pub fn validate_group_id(group_id: &str) -> WhatsAppResult<()> {
+    static GROUP_ID_REGEX: OnceLock<Regex> = OnceLock::new();
+    let regex = GROUP_ID_REGEX.get_or_init(|| {
+        Regex::new(r"^[A-Za-z0-9_=-]{16,256}$").expect("Invalid group ID regex")
+    });
+    
+    if !regex.is_match(group_id) || group_id.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(WhatsAppError::validation(
+            ValidationCode::InvalidGroupId,
+            "to",
+            format!("must be a WhatsApp group ID: {}", group_id)
+        ));
+    }
+    
+    Ok(())
+}
+
+/// Validate a recipient ID for its recipient type
+/// 
+/// Individuals are addressed by E.164 phone number, groups by group ID.
+pub fn validate_recipient(to: &str, recipient_type: RecipientType) -> WhatsAppResult<()> {
+    match recipient_type {
+        RecipientType::Individual => validate_phone_number(to),
+        RecipientType::Group => validate_group_id(to),
+    }
+}
+
+/// Extract the ISO country and dialing code from an E.164 phone number
+/// 
+/// Returns e.g. `("GB", "+44")`. The country comes from libphonenumber's
+/// metadata rather than a prefix table, so numbers sharing a dialing code
+/// are told apart: `+1 242 ...` is the Bahamas, not the US, even though
+/// both use `+1`.
+/// 
+/// # Example
+/// ```
+/// # use whatsapp_client::client::validation::extract_country_code;
+/// let (country, dialing_code) = extract_country_code("+919876543210")?;
+/// assert_eq!(country, "IN");
+/// assert_eq!(dialing_code, "+91");
+/// # Ok::<(), whatsapp_client::errors::WhatsAppError>(())
+/// ```
+pub fn extract_country_code(phone: &str) -> WhatsAppResult<(String, String)> {
+    validate_phone_number(phone)?;
+    
+    let number = phonenumber::parse(None, phone).map_err(|e| WhatsAppError::validation(
+        ValidationCode::InvalidPhone,
+        "to",
+        format!("not a valid phone number: {}", e)
+    ))?;
+    
+    let country = number.country().id().ok_or_else(|| WhatsAppError::validation(
+        ValidationCode::UnknownCountry,
+        "to",
+        format!("no country is assigned to {}", phone)
+    ))?;
+    
+    Ok((country.as_ref().to_string(), format!("+{}", number.code().value())))
+}
+
 /// Validate text message content
 /// 
 /// Checks message length and ensures it's not empty.
 /// WhatsApp supports up to 4096 characters for text messages.
 pub fn validate_text_message(message: &str) -> WhatsAppResult<()> {
     if message.is_empty() {
-        return Err(WhatsAppError::InvalidMessageContent(
-            "Message cannot be empty".to_string()
-        ));
+        return Err(WhatsAppError::validation(ValidationCode::BodyEmpty, "body", "cannot be empty"));
     }
     
-    if message.len() > MAX_TEXT_MESSAGE_LENGTH {
-        return Err(WhatsAppError::InvalidMessageContent(
-            format!("Message too long: {} characters (max {})", 
-                   message.len(), MAX_TEXT_MESSAGE_LENGTH)
+    if text_length(message) > MAX_TEXT_MESSAGE_LENGTH {
+        return Err(WhatsAppError::validation(
+            ValidationCode::BodyTooLong,
+            "body",
+            format!("too long: {} characters (max {})", 
+                   text_length(message), MAX_TEXT_MESSAGE_LENGTH)
         ));
     }
     
@@ -63,10 +153,50 @@ pub fn validate_text_message(message: &str) -> WhatsAppResult<()> {
 /// 
 /// Captions are optional but when provided must be within WhatsApp's limits.
 pub fn validate_caption(caption: &str) -> WhatsAppResult<()> {
-    if caption.len() > MAX_CAPTION_LENGTH {
-        return Err(WhatsAppError::InvalidMessageContent(
-            format!("Caption too long: {} characters (max {})", 
-                   caption.len(), MAX_CAPTION_LENGTH)
+    if text_length(caption) > MAX_CAPTION_LENGTH {
+        return Err(WhatsAppError::validation(
+            ValidationCode::CaptionTooLong,
+            "caption",
+            format!("too long: {} characters (max {})", 
+                   text_length(caption), MAX_CAPTION_LENGTH)
+        ));
+    }
+    
+    Ok(())
+}
+
+/// Validate a caption for a given media type
+/// 
+/// Only images, videos and documents can carry a caption; WhatsApp rejects
+/// captions on audio and stickers. Supported captions are length-checked
+/// as in `validate_caption`.
+pub fn validate_caption_allowed(media_type: MediaType, caption: &str) -> WhatsAppResult<()> {
+    if !media_type.supports_caption() {
+        return Err(WhatsAppError::validation(
+            ValidationCode::CaptionNotSupported,
+            "caption",
+            format!("not supported for {:?} messages", media_type)
+        ));
+    }
+    
+    validate_caption(caption)
+}
+
+/// Check that a button or row ID only uses characters safe to round-trip
+/// 
+/// WhatsApp echoes the ID back in the reply webhook, and IDs with
+/// whitespace or non-ASCII characters can come back mangled, breaking reply
+/// routing. ASCII letters and digits plus `_`, `-`, `.` and `:` are allowed;
+/// `:` is kept for namespaced IDs like [`NEXT_PAGE_ROW_ID_PREFIX`].
+/// 
+/// [`NEXT_PAGE_ROW_ID_PREFIX`]: crate::client::message_types::NEXT_PAGE_ROW_ID_PREFIX
+pub fn validate_button_id_charset(id: &str) -> WhatsAppResult<()> {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':');
+    if let Some(bad) = id.chars().find(|c| !is_safe(*c)) {
+        return Err(WhatsAppError::validation(
+            ValidationCode::InvalidButtonId,
+            "button.id",
+            format!("contains {:?}; only letters, digits, '_', '-', '.' and ':' are allowed", bad)
         ));
     }
     
@@ -76,30 +206,34 @@ pub fn validate_caption(caption: &str) -> WhatsAppResult<()> {
 /// Validate interactive button
 /// 
 /// Buttons must have valid IDs and titles within WhatsApp's character limits.
+/// IDs are also restricted to a safe character set, see
+/// `validate_button_id_charset`.
 pub fn validate_button(id: &str, title: &str) -> WhatsAppResult<()> {
     if id.is_empty() {
-        return Err(WhatsAppError::InvalidMessageContent(
-            "Button ID cannot be empty".to_string()
-        ));
+        return Err(WhatsAppError::validation(ValidationCode::ButtonIdEmpty, "button.id", "cannot be empty"));
     }
     
-    if id.len() > MAX_BUTTON_ID_LENGTH {
-        return Err(WhatsAppError::InvalidMessageContent(
-            format!("Button ID too long: {} characters (max {})", 
-                   id.len(), MAX_BUTTON_ID_LENGTH)
+    if text_length(id) > MAX_BUTTON_ID_LENGTH {
+        return Err(WhatsAppError::validation(
+            ValidationCode::ButtonIdTooLong,
+            "button.id",
+            format!("too long: {} characters (max {})", 
+                   text_length(id), MAX_BUTTON_ID_LENGTH)
         ));
     }
     
+    validate_button_id_charset(id)?;
+    
     if title.is_empty() {
-        return Err(WhatsAppError::InvalidMessageContent(
-            "Button title cannot be empty".to_string()
-        ));
+        return Err(WhatsAppError::validation(ValidationCode::ButtonTitleEmpty, "button.title", "cannot be empty"));
     }
     
-    if title.len() > MAX_BUTTON_TITLE_LENGTH {
-        return Err(WhatsAppError::InvalidMessageContent(
-            format!("Button title too long: {} characters (max {})", 
-                   title.len(), MAX_BUTTON_TITLE_LENGTH)
+    if text_length(title) > MAX_BUTTON_TITLE_LENGTH {
+        return Err(WhatsAppError::validation(
+            ValidationCode::ButtonTitleTooLong,
+            "button.title",
+            format!("too long: {} characters (max {})", 
+                   text_length(title), MAX_BUTTON_TITLE_LENGTH)
         ));
     }
     
@@ -111,40 +245,84 @@ pub fn validate_button(id: &str, title: &str) -> WhatsAppResult<()> {
 /// List messages have specific limits on section titles and row content.
 pub fn validate_list_section(title: &str, rows: &[(String, String, Option<String>)]) -> WhatsAppResult<()> {
     if title.is_empty() {
-        return Err(WhatsAppError::InvalidMessageContent(
-            "List section title cannot be empty".to_string()
-        ));
+        return Err(WhatsAppError::validation(ValidationCode::SectionTitleEmpty, "section.title", "cannot be empty"));
     }
     
-    if title.len() > MAX_LIST_TITLE_LENGTH {
-        return Err(WhatsAppError::InvalidMessageContent(
-            format!("List section title too long: {} characters (max {})", 
-                   title.len(), MAX_LIST_TITLE_LENGTH)
+    if text_length(title) > MAX_LIST_TITLE_LENGTH {
+        return Err(WhatsAppError::validation(
+            ValidationCode::SectionTitleTooLong,
+            "section.title",
+            format!("too long: {} characters (max {})", 
+                   text_length(title), MAX_LIST_TITLE_LENGTH)
         ));
     }
     
     if rows.is_empty() {
-        return Err(WhatsAppError::InvalidMessageContent(
-            "List section must have at least one row".to_string()
-        ));
+        return Err(WhatsAppError::validation(ValidationCode::SectionEmpty, "section.rows", "must have at least one row"));
     }
     
     if rows.len() > 10 {
-        return Err(WhatsAppError::InvalidMessageContent(
-            format!("List section has too many rows: {} (max 10)", rows.len())
+        return Err(WhatsAppError::validation(
+            ValidationCode::TooManyRows,
+            "section.rows",
+            format!("too many rows: {} (max 10)", rows.len())
         ));
     }
     
     for (id, title, description) in rows {
         validate_button(id, title)?;
         
-        if let Some(desc) = description {
-            if desc.len() > MAX_LIST_DESCRIPTION_LENGTH {
-                return Err(WhatsAppError::InvalidMessageContent(
-                    format!("List row description too long: {} characters (max {})", 
-                           desc.len(), MAX_LIST_DESCRIPTION_LENGTH)
-                ));
-            }
+        if let Some(desc) = description
+            && text_length(desc) > MAX_LIST_DESCRIPTION_LENGTH
+        {
+            return Err(WhatsAppError::validation(
+                ValidationCode::RowDescriptionTooLong,
+                "row.description",
+                format!("too long: {} characters (max {})", 
+                       text_length(desc), MAX_LIST_DESCRIPTION_LENGTH)
+            ));
+        }
+    }
+    
+    Ok(())
+}
+
+/// Validate that interactive option IDs are unique
+/// 
+/// Button and list row IDs are how a reply tells us which option was
+/// picked, so a duplicate makes the choice ambiguous. `kind` names the
+/// option type ("button", "row" or "product"), so the error's field is
+/// e.g. `row.id`.
+pub fn validate_unique_ids<'a>(kind: &str, ids: impl IntoIterator<Item = &'a str>) -> WhatsAppResult<()> {
+    let mut seen = std::collections::HashSet::new();
+    for id in ids {
+        if !seen.insert(id) {
+            return Err(WhatsAppError::validation(
+                ValidationCode::DuplicateId,
+                format!("{}.id", kind),
+                format!("duplicate: {}", id)
+            ));
+        }
+    }
+    
+    Ok(())
+}
+
+/// Validate that interactive option titles are unique
+/// 
+/// WhatsApp rejects a button set or list where two options show the same
+/// title, even with different IDs, since users couldn't tell them apart.
+/// `kind` names the option type ("button" or "row"), so the error's field
+/// is e.g. `button.title`.
+pub fn validate_unique_titles<'a>(kind: &str, titles: impl IntoIterator<Item = &'a str>) -> WhatsAppResult<()> {
+    let mut seen = std::collections::HashSet::new();
+    for title in titles {
+        if !seen.insert(title) {
+            return Err(WhatsAppError::validation(
+                ValidationCode::DuplicateTitle,
+                format!("{}.title", kind),
+                format!("duplicate: {}", title)
+            ));
         }
     }
     
@@ -153,24 +331,71 @@ pub fn validate_list_section(title: &str, rows: &[(String, String, Option<String
 
 /// Validate URL format
 /// 
-/// URLs must be properly formatted and within length limits.
+/// URLs must be properly formatted and within length limits. The URL is
+/// parsed to ensure it has an http(s) scheme and a non-empty host, and
+/// any whitespace or control characters are rejected outright since the
+/// parser would otherwise silently strip or percent-encode them.
 pub fn validate_url(url: &str) -> WhatsAppResult<()> {
     if url.is_empty() {
-        return Err(WhatsAppError::InvalidMessageContent(
-            "URL cannot be empty".to_string()
-        ));
+        return Err(WhatsAppError::validation(ValidationCode::UrlEmpty, "url", "cannot be empty"));
     }
 
     if url.len() > MAX_URL_LENGTH {
-        return Err(WhatsAppError::InvalidMessageContent(
-            format!("URL too long: {} characters (max {})", 
+        return Err(WhatsAppError::validation(
+            ValidationCode::UrlTooLong,
+            "url",
+            format!("too long: {} characters (max {})", 
                    url.len(), MAX_URL_LENGTH)
         ));
     }
     
-    if !url.starts_with("http://") && !url.starts_with("https://") {
-        return Err(WhatsAppError::InvalidMessageContent(
-            "URL must start with http:// or https://".to_string()
+    if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(WhatsAppError::validation(
+            ValidationCode::InvalidUrl,
+            "url",
+            format!("must not contain whitespace or control characters: {:?}", url)
+        ));
+    }
+    
+    let parsed = Url::parse(url).map_err(|e| WhatsAppError::validation(
+        ValidationCode::InvalidUrl,
+        "url",
+        format!("invalid URL '{}': {}", url, e)
+    ))?;
+    
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return Err(WhatsAppError::validation(ValidationCode::InvalidUrl, "url", "must start with http:// or https://"));
+    }
+    
+    // The parser treats `https:///path` as host "path", so check the raw
+    // authority as well as the parsed host
+    let authority_missing = url
+        .split_once("://")
+        .is_none_or(|(_, rest)| rest.starts_with('/'));
+    
+    if authority_missing || parsed.host_str().is_none_or(str::is_empty) {
+        return Err(WhatsAppError::validation(
+            ValidationCode::InvalidUrl,
+            "url",
+            format!("must include a host: {}", url)
+        ));
+    }
+    
+    Ok(())
+}
+
+/// Validate a URL that must be served over HTTPS
+/// 
+/// Applies all of `validate_url`'s checks and additionally rejects plain
+/// HTTP. Used for call-to-action buttons, which WhatsApp requires to be HTTPS.
+pub fn validate_https_url(url: &str) -> WhatsAppResult<()> {
+    validate_url(url)?;
+    
+    if !url.starts_with("https://") {
+        return Err(WhatsAppError::validation(
+            ValidationCode::UrlNotHttps,
+            "url",
+            format!("must use HTTPS protocol: {}", url)
         ));
     }
     
@@ -182,14 +407,18 @@ pub fn validate_url(url: &str) -> WhatsAppResult<()> {
 /// Latitude must be between -90 and 90, longitude between -180 and 180.
 pub fn validate_coordinates(latitude: f64, longitude: f64) -> WhatsAppResult<()> {
     if !(-90.0..=90.0).contains(&latitude) {
-        return Err(WhatsAppError::InvalidMessageContent(
-            format!("Invalid latitude: {} (must be between -90 and 90)", latitude)
+        return Err(WhatsAppError::validation(
+            ValidationCode::LatitudeOutOfRange,
+            "latitude",
+            format!("{} must be between -90 and 90", latitude)
         ));
     }
     
     if !(-180.0..=180.0).contains(&longitude) {
-        return Err(WhatsAppError::InvalidMessageContent(
-            format!("Invalid longitude: {} (must be between -180 and 180)", longitude)
+        return Err(WhatsAppError::validation(
+            ValidationCode::LongitudeOutOfRange,
+            "longitude",
+            format!("{} must be between -180 and 180", longitude)
         ));
     }
     
@@ -201,35 +430,54 @@ pub fn validate_coordinates(latitude: f64, longitude: f64) -> WhatsAppResult<()>
 /// Media IDs should be non-empty strings, typically numeric.
 pub fn validate_media_id(media_id: &str) -> WhatsAppResult<()> {
     if media_id.is_empty() {
-        return Err(WhatsAppError::InvalidMessageContent(
-            "Media ID cannot be empty".to_string()
-        ));
+        return Err(WhatsAppError::validation(ValidationCode::MediaIdEmpty, "media_id", "cannot be empty"));
     }
     
     // WhatsApp media IDs are typically numeric strings
     if !media_id.chars().all(|c| c.is_ascii_digit()) {
-        return Err(WhatsAppError::InvalidMessageContent(
-            format!("Invalid media ID format: {} (should be numeric)", media_id)
+        return Err(WhatsAppError::validation(
+            ValidationCode::InvalidMediaId,
+            "media_id",
+            format!("invalid format: {} (should be numeric)", media_id)
         ));
     }
     
     Ok(())
 }
 
+/// Validate a media reference
+/// 
+/// Media messages must reference exactly one of an uploaded media ID or a
+/// hosted URL. WhatsApp rejects messages carrying both, or neither.
+pub fn validate_media_reference(id: Option<&str>, link: Option<&str>) -> WhatsAppResult<()> {
+    match (id, link) {
+        (Some(id), None) => validate_media_id(id),
+        (None, Some(link)) => validate_url(link),
+        (Some(_), Some(_)) => Err(WhatsAppError::validation(ValidationCode::MediaSourceConflict, "media", "cannot have both an id and a link")),
+        (None, None) => Err(WhatsAppError::validation(ValidationCode::MediaSourceMissing, "media", "must have either an id or a link")),
+    }
+}
+
 /// Validate file size for media type
 /// 
-/// Different media types have different size limits.
+/// Different media types have different size limits. Stickers are only
+/// checked against the animated limit, an upper bound for both kinds,
+/// since whether one is animated isn't known from its type alone; use
+/// `StickerMessage::validate_sticker_file` for the exact limit.
 pub fn validate_file_size(size_bytes: u64, media_type: MediaType) -> WhatsAppResult<()> {
     let max_size = match media_type {
         MediaType::Audio => MAX_AUDIO_SIZE,
         MediaType::Document => MAX_DOCUMENT_SIZE,
         MediaType::Image => MAX_IMAGE_SIZE,
         MediaType::Video => MAX_VIDEO_SIZE,
+        MediaType::Sticker => MAX_ANIMATED_STICKER_SIZE,
     };
     
     if size_bytes > max_size {
-        return Err(WhatsAppError::InvalidMessageContent(
-            format!("File too large: {} bytes (max {} for {:?})", 
+        return Err(WhatsAppError::validation(
+            ValidationCode::FileTooLarge,
+            "file_size",
+            format!("too large: {} bytes (max {} for {:?})", 
                    size_bytes, max_size, media_type)
         ));
     }
@@ -261,11 +509,16 @@ pub fn validate_mime_type(mime_type: &str, media_type: MediaType) -> WhatsAppRes
         MediaType::Video => &[
             "video/3gpp", "video/mp4"
         ],
+        MediaType::Sticker => &[
+            "image/webp"
+        ],
     };
     
     if !valid_mime_types.contains(&mime_type) {
-        return Err(WhatsAppError::InvalidMessageContent(
-            format!("Unsupported MIME type '{}' for {:?}. Supported types: {:?}", 
+        return Err(WhatsAppError::validation(
+            ValidationCode::UnsupportedMimeType,
+            "mime_type",
+            format!("unsupported MIME type '{}' for {:?}. Supported types: {:?}", 
                    mime_type, media_type, valid_mime_types)
         ));
     }
@@ -275,10 +528,12 @@ pub fn validate_mime_type(mime_type: &str, media_type: MediaType) -> WhatsAppRes
 
 /// Validate header text (for interactive messages)
 pub fn validate_header_text(header: &str) -> WhatsAppResult<()> {
-    if header.len() > MAX_HEADER_TEXT_LENGTH {
-        return Err(WhatsAppError::InvalidMessageContent(
-            format!("Header text too long: {} characters (max {})", 
-                   header.len(), MAX_HEADER_TEXT_LENGTH)
+    if text_length(header) > MAX_HEADER_TEXT_LENGTH {
+        return Err(WhatsAppError::validation(
+            ValidationCode::HeaderTooLong,
+            "header",
+            format!("too long: {} characters (max {})", 
+                   text_length(header), MAX_HEADER_TEXT_LENGTH)
         ));
     }
     
@@ -287,10 +542,12 @@ pub fn validate_header_text(header: &str) -> WhatsAppResult<()> {
 
 /// Validate footer text (for interactive messages)
 pub fn validate_footer_text(footer: &str) -> WhatsAppResult<()> {
-    if footer.len() > MAX_FOOTER_TEXT_LENGTH {
-        return Err(WhatsAppError::InvalidMessageContent(
-            format!("Footer text too long: {} characters (max {})", 
-                   footer.len(), MAX_FOOTER_TEXT_LENGTH)
+    if text_length(footer) > MAX_FOOTER_TEXT_LENGTH {
+        return Err(WhatsAppError::validation(
+            ValidationCode::FooterTooLong,
+            "footer",
+            format!("too long: {} characters (max {})", 
+                   text_length(footer), MAX_FOOTER_TEXT_LENGTH)
         ));
     }
     
@@ -298,12 +555,32 @@ pub fn validate_footer_text(footer: &str) -> WhatsAppResult<()> {
 }
 
 /// Media types supported by WhatsApp
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MediaType {
     Audio,
     Document,
     Image,
     Video,
+    Sticker,
+}
+
+impl MediaType {
+    /// Infer the media category from a MIME type
+    ///
+    /// Returns `None` when the MIME type isn't supported by any category.
+    pub fn from_mime_type(mime_type: &str) -> Option<Self> {
+        [MediaType::Audio, MediaType::Document, MediaType::Image, MediaType::Video, MediaType::Sticker]
+            .into_iter()
+            .find(|media_type| validate_mime_type(mime_type, *media_type).is_ok())
+    }
+
+    /// Whether messages of this media type can carry a caption
+    pub fn supports_caption(self) -> bool {
+        match self {
+            MediaType::Document | MediaType::Image | MediaType::Video => true,
+            MediaType::Audio | MediaType::Sticker => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -326,6 +603,57 @@ mod tests {
         assert!(validate_phone_number("").is_err()); // Empty
     }
     
+    #[test]
+    fn test_caption_allowed_by_media_type() {
+        for media_type in [MediaType::Image, MediaType::Video, MediaType::Document] {
+            assert!(media_type.supports_caption());
+            assert!(validate_caption_allowed(media_type, "Look at this").is_ok());
+        }
+        
+        for media_type in [MediaType::Audio, MediaType::Sticker] {
+            assert!(!media_type.supports_caption());
+            let error = validate_caption_allowed(media_type, "Listen to this").unwrap_err();
+            assert_eq!(error.field(), Some("caption"));
+        }
+        
+        // Supported captions still respect the length limit
+        let long_caption = "a".repeat(MAX_CAPTION_LENGTH + 1);
+        assert!(validate_caption_allowed(MediaType::Image, &long_caption).is_err());
+    }
+    
+    #[test]
+    fn test_extract_country_code() {
+        let country = |phone: &str| extract_country_code(phone).unwrap();
+        
+        assert_eq!(country("+12015550123"), ("US".to_string(), "+1".to_string()));
+        assert_eq!(country("+442079460958"), ("GB".to_string(), "+44".to_string()));
+        assert_eq!(country("+919876543210"), ("IN".to_string(), "+91".to_string()));
+        
+        // Caribbean NANP numbers share +1 with the US but resolve to their own country
+        assert_eq!(country("+12423570000"), ("BS".to_string(), "+1".to_string()));
+        
+        // Malformed numbers are rejected before lookup
+        let error = extract_country_code("12015550123").unwrap_err();
+        assert_eq!(error.field(), Some("to"));
+    }
+    
+    #[test]
+    fn test_group_id_validation() {
+        // Valid group IDs
+        assert!(validate_group_id("Y2FwaV9ncm91cDoxNzA1NTU1MDEz").is_ok());
+        assert!(validate_group_id("Y2FwaV9ncm91cDoxNzA1NTU1MDEzOToxMjAz-_=").is_ok());
+        
+        // Invalid group IDs
+        assert!(validate_group_id("").is_err()); // Empty
+        assert!(validate_group_id("+16505551234").is_err()); // Phone number
+        assert!(validate_group_id("1650555123456789").is_err()); // Phone number without +
+        assert!(validate_group_id("Y2FwaV9ncm91cDox NzA1").is_err()); // Whitespace
+        assert!(validate_group_id("group").is_err()); // Too short
+        
+        assert!(validate_recipient("+16505551234", RecipientType::Individual).is_ok());
+        assert!(validate_recipient("+16505551234", RecipientType::Group).is_err());
+    }
+    
     #[test]
     fn test_text_message_validation() {
         // Valid messages
@@ -337,6 +665,115 @@ mod tests {
         assert!(validate_text_message(&"x".repeat(4097)).is_err()); // Too long
     }
     
+    #[test]
+    fn test_text_length_counts_characters_not_bytes() {
+        assert_eq!(text_length("hello"), 5);
+        assert_eq!(text_length("привет"), 6);
+        assert_eq!("привет".len(), 12);
+        assert_eq!(text_length("😀"), 1);
+        // Multi-codepoint emoji count once per code point
+        assert_eq!(text_length("🇨🇴"), 2);
+    }
+    
+    #[test]
+    fn test_multibyte_text_at_length_boundary() {
+        // Cyrillic is 2 bytes per character, emoji 4
+        assert!(validate_text_message(&"я".repeat(MAX_TEXT_MESSAGE_LENGTH)).is_ok());
+        assert!(validate_text_message(&"я".repeat(MAX_TEXT_MESSAGE_LENGTH + 1)).is_err());
+        assert!(validate_text_message(&"😀".repeat(MAX_TEXT_MESSAGE_LENGTH)).is_ok());
+        assert!(validate_text_message(&"😀".repeat(MAX_TEXT_MESSAGE_LENGTH + 1)).is_err());
+        
+        assert!(validate_caption(&"ж".repeat(MAX_CAPTION_LENGTH)).is_ok());
+        assert!(validate_caption(&"ж".repeat(MAX_CAPTION_LENGTH + 1)).is_err());
+        
+        assert!(validate_button("btn", &"🔥".repeat(MAX_BUTTON_TITLE_LENGTH)).is_ok());
+        assert!(validate_button("btn", &"🔥".repeat(MAX_BUTTON_TITLE_LENGTH + 1)).is_err());
+        
+        assert!(validate_header_text(&"Ш".repeat(MAX_HEADER_TEXT_LENGTH)).is_ok());
+        assert!(validate_header_text(&"Ш".repeat(MAX_HEADER_TEXT_LENGTH + 1)).is_err());
+        assert!(validate_footer_text(&"🙂".repeat(MAX_FOOTER_TEXT_LENGTH)).is_ok());
+        assert!(validate_footer_text(&"🙂".repeat(MAX_FOOTER_TEXT_LENGTH + 1)).is_err());
+        
+        let rows = vec![("row_1".to_string(), "Опция".to_string(), Some("д".repeat(MAX_LIST_DESCRIPTION_LENGTH)))];
+        assert!(validate_list_section(&"м".repeat(MAX_LIST_TITLE_LENGTH), &rows).is_ok());
+        assert!(validate_list_section(&"м".repeat(MAX_LIST_TITLE_LENGTH + 1), &rows).is_err());
+    }
+    
+    #[test]
+    fn test_validation_errors_name_the_field() {
+        let error = validate_caption(&"x".repeat(MAX_CAPTION_LENGTH + 1)).unwrap_err();
+        match &error {
+            WhatsAppError::Validation { code, field, reason } => {
+                assert_eq!(*code, ValidationCode::CaptionTooLong);
+                assert_eq!(field, "caption");
+                assert_eq!(reason, "too long: 1025 characters (max 1024)");
+            }
+            other => panic!("Expected a validation error, got {:?}", other),
+        }
+        assert_eq!(error.to_string(), "caption: too long: 1025 characters (max 1024)");
+        assert_eq!(error.field(), Some("caption"));
+        assert!(!error.is_retryable());
+        
+        assert_eq!(validate_phone_number("123").unwrap_err().field(), Some("to"));
+        assert_eq!(validate_text_message("").unwrap_err().field(), Some("body"));
+        assert_eq!(validate_coordinates(91.0, 0.0).unwrap_err().field(), Some("latitude"));
+        assert_eq!(validate_coordinates(0.0, 181.0).unwrap_err().field(), Some("longitude"));
+        assert_eq!(validate_url("ftp://example.com").unwrap_err().field(), Some("url"));
+        assert_eq!(validate_footer_text(&"x".repeat(61)).unwrap_err().field(), Some("footer"));
+    }
+    
+    #[test]
+    fn test_each_validator_reports_its_code() {
+        let code = |result: WhatsAppResult<()>| result.unwrap_err().validation_code();
+        let long = |limit: usize| "x".repeat(limit + 1);
+        
+        assert_eq!(code(validate_phone_number("12345")), Some(ValidationCode::InvalidPhone));
+        assert_eq!(code(validate_text_message("")), Some(ValidationCode::BodyEmpty));
+        assert_eq!(code(validate_text_message(&long(MAX_TEXT_MESSAGE_LENGTH))), Some(ValidationCode::BodyTooLong));
+        assert_eq!(code(validate_caption(&long(MAX_CAPTION_LENGTH))), Some(ValidationCode::CaptionTooLong));
+        assert_eq!(code(validate_caption_allowed(MediaType::Audio, "hi")), Some(ValidationCode::CaptionNotSupported));
+        
+        assert_eq!(code(validate_button("", "Yes")), Some(ValidationCode::ButtonIdEmpty));
+        assert_eq!(code(validate_button(&long(MAX_BUTTON_ID_LENGTH), "Yes")), Some(ValidationCode::ButtonIdTooLong));
+        assert_eq!(code(validate_button("yes", "")), Some(ValidationCode::ButtonTitleEmpty));
+        assert_eq!(code(validate_button("yes", &long(MAX_BUTTON_TITLE_LENGTH))), Some(ValidationCode::ButtonTitleTooLong));
+        
+        let row = |description: Option<String>| vec![("a".to_string(), "A".to_string(), description)];
+        let rows: Vec<_> = (0..11).map(|n| (n.to_string(), "Row".to_string(), None)).collect();
+        assert_eq!(code(validate_list_section("", &row(None))), Some(ValidationCode::SectionTitleEmpty));
+        assert_eq!(code(validate_list_section(&long(MAX_LIST_TITLE_LENGTH), &row(None))), Some(ValidationCode::SectionTitleTooLong));
+        assert_eq!(code(validate_list_section("Menu", &[])), Some(ValidationCode::SectionEmpty));
+        assert_eq!(code(validate_list_section("Menu", &rows)), Some(ValidationCode::TooManyRows));
+        assert_eq!(
+            code(validate_list_section("Menu", &row(Some(long(MAX_LIST_DESCRIPTION_LENGTH))))),
+            Some(ValidationCode::RowDescriptionTooLong)
+        );
+        
+        assert_eq!(code(validate_url("")), Some(ValidationCode::UrlEmpty));
+        assert_eq!(code(validate_url(&format!("https://{}", long(MAX_URL_LENGTH)))), Some(ValidationCode::UrlTooLong));
+        assert_eq!(code(validate_url("ftp://example.com")), Some(ValidationCode::InvalidUrl));
+        assert_eq!(code(validate_url("https:///path")), Some(ValidationCode::InvalidUrl));
+        assert_eq!(code(validate_https_url("http://example.com")), Some(ValidationCode::UrlNotHttps));
+        
+        assert_eq!(code(validate_coordinates(91.0, 0.0)), Some(ValidationCode::LatitudeOutOfRange));
+        assert_eq!(code(validate_coordinates(0.0, 181.0)), Some(ValidationCode::LongitudeOutOfRange));
+        
+        assert_eq!(code(validate_media_id("")), Some(ValidationCode::MediaIdEmpty));
+        assert_eq!(code(validate_media_id("abc")), Some(ValidationCode::InvalidMediaId));
+        assert_eq!(code(validate_media_reference(Some("1"), Some("https://a.com"))), Some(ValidationCode::MediaSourceConflict));
+        assert_eq!(code(validate_media_reference(None, None)), Some(ValidationCode::MediaSourceMissing));
+        assert_eq!(code(validate_file_size(MAX_IMAGE_SIZE + 1, MediaType::Image)), Some(ValidationCode::FileTooLarge));
+        assert_eq!(code(validate_mime_type("image/gif", MediaType::Image)), Some(ValidationCode::UnsupportedMimeType));
+        
+        assert_eq!(code(validate_header_text(&long(MAX_HEADER_TEXT_LENGTH))), Some(ValidationCode::HeaderTooLong));
+        assert_eq!(code(validate_footer_text(&long(MAX_FOOTER_TEXT_LENGTH))), Some(ValidationCode::FooterTooLong));
+        
+        // Display stays English
+        let error = validate_phone_number("12345").unwrap_err();
+        assert_eq!(error.to_string(), "to: must be in E.164 format (+1234567890): 12345");
+        assert_eq!(error.validation_code().map(ValidationCode::as_str), Some("invalid_phone"));
+    }
+    
     #[test]
     fn test_button_validation() {
         // Valid button
@@ -350,6 +787,56 @@ mod tests {
         assert!(validate_button(&"x".repeat(257), "title").is_err()); // ID too long
     }
     
+    #[test]
+    fn test_button_id_charset_validation() {
+        for id in ["help_button.v2", "order-42", "next_page:2", "ABC.def_1-2:3"] {
+            assert!(validate_button(id, "Help").is_ok(), "{} should be allowed", id);
+        }
+        
+        for id in ["help button", "help\tbutton", "café", "ボタン", "opt/1", "a,b", "emoji🔥"] {
+            let error = validate_button(id, "Help").unwrap_err();
+            assert_eq!(error.validation_code(), Some(ValidationCode::InvalidButtonId), "{:?}", id);
+            assert_eq!(error.field(), Some("button.id"));
+        }
+        
+        // List rows go through the same check
+        let rows = vec![("row one".to_string(), "Row one".to_string(), None)];
+        let error = validate_list_section("Menu", &rows).unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::InvalidButtonId));
+    }
+    
+    #[test]
+    fn test_unique_id_validation() {
+        assert!(validate_unique_ids("button", ["yes", "no"]).is_ok());
+        
+        let error = validate_unique_ids("row", ["a", "b", "a"]).unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::DuplicateId));
+        assert_eq!(error.field(), Some("row.id"));
+    }
+    
+    #[test]
+    fn test_unique_title_validation() {
+        assert!(validate_unique_titles("button", ["Yes", "No", "yes"]).is_ok());
+        
+        let error = validate_unique_titles("button", ["Yes", "No", "Yes"]).unwrap_err();
+        assert_eq!(error.validation_code(), Some(ValidationCode::DuplicateTitle));
+        assert_eq!(error.field(), Some("button.title"));
+    }
+    
+    #[test]
+    fn test_media_reference_validation() {
+        assert!(validate_media_reference(Some("1013859600285441"), None).is_ok());
+        assert!(validate_media_reference(None, Some("https://example.com/a.jpg")).is_ok());
+        
+        let both = validate_media_reference(Some("1013859600285441"), Some("https://example.com/a.jpg")).unwrap_err();
+        assert_eq!(both.field(), Some("media"));
+        let neither = validate_media_reference(None, None).unwrap_err();
+        assert_eq!(neither.field(), Some("media"));
+        
+        // The chosen reference is still checked
+        assert_eq!(validate_media_reference(Some("abc"), None).unwrap_err().field(), Some("media_id"));
+    }
+    
     #[test]
     fn test_coordinate_validation() {
         // Valid coordinates
@@ -377,6 +864,38 @@ mod tests {
         assert!(validate_url(&format!("https://{}.com", "x".repeat(3000))).is_err()); // Too long
     }
     
+    #[test]
+    fn test_url_validation_requires_host() {
+        assert!(validate_url("https://").is_err());
+        assert!(validate_url("http://").is_err());
+        assert!(validate_url("https:///path-only").is_err());
+        assert!(validate_url("https://?query=1").is_err());
+    }
+    
+    #[test]
+    fn test_url_validation_rejects_whitespace_and_control_chars() {
+        assert!(validate_url("https:// spaces.com").is_err());
+        assert!(validate_url("https://example.com/a path").is_err());
+        assert!(validate_url("https://example.com/\tpath").is_err());
+        assert!(validate_url("https://example.com/\u{0007}").is_err());
+        assert!(validate_url(" https://example.com").is_err());
+    }
+    
+    #[test]
+    fn test_url_validation_accepts_query_strings() {
+        assert!(validate_url("https://shop.example.com/sale?utm_source=whatsapp&utm_medium=cta").is_ok());
+        assert!(validate_url("https://example.com:8443/path/to?x=1&y=%20#frag").is_ok());
+        assert!(validate_url("http://localhost:8080/callback?code=abc").is_ok());
+    }
+    
+    #[test]
+    fn test_https_url_validation() {
+        assert!(validate_https_url("https://example.com/checkout?id=42").is_ok());
+        assert!(validate_https_url("http://example.com").is_err());
+        assert!(validate_https_url("https://").is_err());
+        assert!(validate_https_url("HTTPS://example.com").is_err());
+    }
+    
     #[test]
     fn test_mime_type_validation() {
         // Valid MIME types
@@ -391,4 +910,14 @@ mod tests {
         assert!(validate_mime_type("audio/wav", MediaType::Audio).is_err()); // Not supported
         assert!(validate_mime_type("application/zip", MediaType::Document).is_err()); // Not supported
     }
+    
+    #[test]
+    fn test_media_type_from_mime_type() {
+        assert_eq!(MediaType::from_mime_type("image/png"), Some(MediaType::Image));
+        assert_eq!(MediaType::from_mime_type("audio/ogg"), Some(MediaType::Audio));
+        assert_eq!(MediaType::from_mime_type("video/mp4"), Some(MediaType::Video));
+        assert_eq!(MediaType::from_mime_type("application/pdf"), Some(MediaType::Document));
+        assert_eq!(MediaType::from_mime_type("image/webp"), Some(MediaType::Sticker));
+        assert_eq!(MediaType::from_mime_type("image/gif"), None);
+    }
 }
@@ -1,4 +1,12 @@
+use crate::errors::{WhatsAppError, WhatsAppResult};
 use serde::Deserialize;
+use tracing::warn;
+
+/// Oldest Graph API version still served when this client was last updated
+///
+/// Meta retires each version about two years after release. Configuring an
+/// older one only logs a warning, since calls may still work for a while.
+pub const MIN_API_VERSION: (u32, u32) = (19, 0);
 
 /// Configuration for the WhatsApp client service
 ///
@@ -22,10 +30,16 @@ pub struct WhatsAppClientConfig {
     pub rate_limit_per_minute: u32,
     /// Burst capacity for rate limiter
     pub rate_limit_burst: u32,
+    /// Maximum messages sent per second, matching the number's throughput tier
+    pub messages_per_second: u32,
+    /// Fail sends with `RateLimited` instead of waiting when over `messages_per_second`
+    pub rate_limit_fail_fast: bool,
     
     // HTTP client configuration
-    /// Timeout for individual API calls in seconds
-    pub request_timeout_seconds: u64,
+    /// Timeout for a whole API call (connect, send and read) in milliseconds
+    pub request_timeout_ms: u64,
+    /// Timeout for establishing a connection to the API in milliseconds
+    pub connect_timeout_ms: u64,
     /// Maximum number of concurrent API calls
     pub max_concurrent_requests: usize,
     
@@ -36,6 +50,15 @@ pub struct WhatsAppClientConfig {
     pub initial_retry_delay_ms: u64,
     /// Maximum retry delay in milliseconds (for exponential backoff)
     pub max_retry_delay_ms: u64,
+    /// Deliveries of a send event after which transient failures count as
+    /// permanent, so a message that keeps failing stops using quota (0 disables)
+    pub max_send_attempts: u32,
+    
+    // Duplicate suppression configuration
+    /// How long a sent idempotency key suppresses duplicate sends, in seconds
+    pub idempotency_window_seconds: u64,
+    /// Maximum number of idempotency keys remembered at once
+    pub idempotency_cache_size: usize,
     
     // Service configuration
     /// Host to bind the service to
@@ -48,61 +71,94 @@ impl WhatsAppClientConfig {
     ///
     /// This follows the same pattern as your webhook service, making it
     /// easy to configure consistently across your infrastructure.
-    pub fn from_env() -> Self {
+    ///
+    /// `WHATSAPP_ACCESS_TOKEN` and `WHATSAPP_PHONE_NUMBER_ID` must be set, and
+    /// `WHATSAPP_API_VERSION` (default "v23.0") must look like `v<major>.<minor>`.
+    /// Missing or unparseable values return a `ConfigurationError` naming the
+    /// variable, so a bad deployment fails at startup instead of at the first
+    /// send. A version older than `MIN_API_VERSION` is accepted with a warning.
+    pub fn from_env() -> WhatsAppResult<Self> {
         dotenv::dotenv().ok();
 
-        Self {
+        Ok(Self {
             // WhatsApp API credentials - these must be set
-            access_token: std::env::var("WHATSAPP_ACCESS_TOKEN")
-                .expect("WHATSAPP_ACCESS_TOKEN environment variable must be set"),
-            api_version: std::env::var("WHATSAPP_API_VERSION")
-                .unwrap_or_else(|_| "v23.0".to_string()),
-            phone_number_id: std::env::var("WHATSAPP_PHONE_NUMBER_ID")
-                .expect("WHATSAPP_PHONE_NUMBER_ID environment variable must be set"),
+            access_token: Self::required_env("WHATSAPP_ACCESS_TOKEN")?,
+            api_version: Self::api_version_env(
+                "WHATSAPP_API_VERSION",
+                std::env::var("WHATSAPP_API_VERSION").unwrap_or_else(|_| "v23.0".to_string()),
+            )?,
+            phone_number_id: Self::required_env("WHATSAPP_PHONE_NUMBER_ID")?,
             api_base_url: std::env::var("WHATSAPP_API_BASE_URL")
                 .unwrap_or_else(|_| "https://graph.facebook.com".to_string()),
             
             // Rate limiting - conservative defaults to avoid hitting WhatsApp limits
-            rate_limit_per_minute: std::env::var("WHATSAPP_RATE_LIMIT_PER_MINUTE")
-                .unwrap_or_else(|_| "800".to_string()) // 80% of WhatsApp's 1000/min limit
-                .parse()
-                .expect("WHATSAPP_RATE_LIMIT_PER_MINUTE must be a valid number"),
-            rate_limit_burst: std::env::var("WHATSAPP_RATE_LIMIT_BURST")
-                .unwrap_or_else(|_| "50".to_string())
-                .parse()
-                .expect("WHATSAPP_RATE_LIMIT_BURST must be a valid number"),
+            rate_limit_per_minute: Self::env_or("WHATSAPP_RATE_LIMIT_PER_MINUTE", 800)?, // 80% of WhatsApp's 1000/min limit
+            rate_limit_burst: Self::env_or("WHATSAPP_RATE_LIMIT_BURST", 50)?,
+            messages_per_second: Self::env_or("WHATSAPP_MESSAGES_PER_SECOND", 80)?, // Cloud API default throughput
+            rate_limit_fail_fast: Self::env_or("WHATSAPP_RATE_LIMIT_FAIL_FAST", false)?,
             
             // HTTP client settings - optimized for reliability
-            request_timeout_seconds: std::env::var("WHATSAPP_REQUEST_TIMEOUT_SECONDS")
-                .unwrap_or_else(|_| "30".to_string())
-                .parse()
-                .expect("WHATSAPP_REQUEST_TIMEOUT_SECONDS must be a valid number"),
-            max_concurrent_requests: std::env::var("WHATSAPP_MAX_CONCURRENT_REQUESTS")
-                .unwrap_or_else(|_| "20".to_string())
-                .parse()
-                .expect("WHATSAPP_MAX_CONCURRENT_REQUESTS must be a valid number"),
+            request_timeout_ms: Self::env_or("WHATSAPP_REQUEST_TIMEOUT_MS", 30000)?,
+            connect_timeout_ms: Self::env_or("WHATSAPP_CONNECT_TIMEOUT_MS", 10000)?,
+            max_concurrent_requests: Self::env_or("WHATSAPP_MAX_CONCURRENT_REQUESTS", 20)?,
             
             // Retry configuration - aggressive retries for reliability
-            max_retry_attempts: std::env::var("WHATSAPP_MAX_RETRY_ATTEMPTS")
-                .unwrap_or_else(|_| "3".to_string())
-                .parse()
-                .expect("WHATSAPP_MAX_RETRY_ATTEMPTS must be a valid number"),
-            initial_retry_delay_ms: std::env::var("WHATSAPP_INITIAL_RETRY_DELAY_MS")
-                .unwrap_or_else(|_| "1000".to_string())
-                .parse()
-                .expect("WHATSAPP_INITIAL_RETRY_DELAY_MS must be a valid number"),
-            max_retry_delay_ms: std::env::var("WHATSAPP_MAX_RETRY_DELAY_MS")
-                .unwrap_or_else(|_| "30000".to_string())
-                .parse()
-                .expect("WHATSAPP_MAX_RETRY_DELAY_MS must be a valid number"),
+            max_retry_attempts: Self::env_or("WHATSAPP_MAX_RETRY_ATTEMPTS", 3)?,
+            initial_retry_delay_ms: Self::env_or("WHATSAPP_INITIAL_RETRY_DELAY_MS", 1000)?,
+            max_retry_delay_ms: Self::env_or("WHATSAPP_MAX_RETRY_DELAY_MS", 30000)?,
+            max_send_attempts: Self::env_or("WHATSAPP_MAX_SEND_ATTEMPTS", 3)?,
+            
+            // Duplicate suppression - long enough to cover Kafka redeliveries
+            idempotency_window_seconds: Self::env_or("WHATSAPP_IDEMPOTENCY_WINDOW_SECONDS", 600)?,
+            idempotency_cache_size: Self::env_or("WHATSAPP_IDEMPOTENCY_CACHE_SIZE", 10000)?,
             
             // Service configuration
             host: std::env::var("WHATSAPP_CLIENT_HOST")
                 .unwrap_or_else(|_| "0.0.0.0".to_string()),
-            port: std::env::var("WHATSAPP_CLIENT_PORT")
-                .unwrap_or_else(|_| "8001".to_string())
-                .parse()
-                .expect("WHATSAPP_CLIENT_PORT must be a valid number"),
+            port: Self::env_or("WHATSAPP_CLIENT_PORT", 8001)?,
+        })
+    }
+
+    /// Read an environment variable that has no default
+    fn required_env(name: &str) -> WhatsAppResult<String> {
+        let value = std::env::var(name).map_err(|_| WhatsAppError::ConfigurationError(
+            format!("{} environment variable must be set", name)
+        ))?;
+        Self::non_blank(name, value)
+    }
+
+    /// Reject values that are set but empty
+    fn non_blank(name: &str, value: String) -> WhatsAppResult<String> {
+        if value.trim().is_empty() {
+            return Err(WhatsAppError::ConfigurationError(
+                format!("{} environment variable must not be empty", name)
+            ));
+        }
+        Ok(value)
+    }
+
+    /// Check an API version setting is a well-formed Graph API version
+    fn api_version_env(name: &str, value: String) -> WhatsAppResult<String> {
+        let value = Self::non_blank(name, value)?;
+        let version = parse_api_version(&value).ok_or_else(|| WhatsAppError::ConfigurationError(
+            format!("{} must look like v<major>.<minor> (e.g. v23.0), got: {}", name, value)
+        ))?;
+        if version < MIN_API_VERSION {
+            warn!(
+                "{} is {}, older than v{}.{}; Meta may have retired it",
+                name, value, MIN_API_VERSION.0, MIN_API_VERSION.1
+            );
+        }
+        Ok(value)
+    }
+
+    /// Parse an optional environment variable, falling back to `default`
+    fn env_or<T: std::str::FromStr>(name: &str, default: T) -> WhatsAppResult<T> {
+        match std::env::var(name) {
+            Ok(value) => value.parse().map_err(|_| WhatsAppError::ConfigurationError(
+                format!("{} has an invalid value: {}", name, value)
+            )),
+            Err(_) => Ok(default),
         }
     }
 
@@ -119,12 +175,64 @@ impl WhatsAppClientConfig {
         )
     }
 
+    /// Get the complete URL for uploading media via WhatsApp API
+    ///
+    /// Uploaded media is scoped to the phone number and returns a media ID
+    /// that can be referenced when sending media messages.
+    pub fn media_url(&self) -> String {
+        format!(
+            "{}/{}/{}/media",
+            self.api_base_url,
+            self.api_version,
+            self.phone_number_id,
+        )
+    }
+
+    /// Get the URL for retrieving metadata about an uploaded or received media ID
+    ///
+    /// Media lookups are not scoped to the phone number, so the media ID
+    /// sits directly under the API version.
+    pub fn media_info_url(&self, media_id: &str) -> String {
+        format!(
+            "{}/{}/{}",
+            self.api_base_url,
+            self.api_version,
+            media_id,
+        )
+    }
+
+    /// Get the URL of the phone number node itself
+    ///
+    /// Reading it is a cheap authenticated call, used to check which API
+    /// version actually serves requests.
+    pub fn phone_number_url(&self) -> String {
+        format!(
+            "{}/{}/{}",
+            self.api_base_url,
+            self.api_version,
+            self.phone_number_id,
+        )
+    }
+
     /// Get the authorization header value for WhatsApp API requests
     pub fn authorization_header(&self) -> String {
         format!("Bearer {}", self.access_token)
     }
 }
 
+/// Parse a Graph API version like "v23.0" into `(major, minor)`
+///
+/// Returns `None` for anything else, including a missing `v`, extra
+/// components or whitespace.
+pub fn parse_api_version(version: &str) -> Option<(u32, u32)> {
+    let (major, minor) = version.strip_prefix('v')?.split_once('.')?;
+    let is_number = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    if !is_number(major) || !is_number(minor) {
+        return None;
+    }
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,17 +247,25 @@ mod tests {
             access_token: "test_token".to_string(),
             rate_limit_per_minute: 800,
             rate_limit_burst: 50,
-            request_timeout_seconds: 30,
+            messages_per_second: 80,
+            rate_limit_fail_fast: false,
+            request_timeout_ms: 30000,
+            connect_timeout_ms: 10000,
             max_concurrent_requests: 20,
             max_retry_attempts: 3,
             initial_retry_delay_ms: 1000,
             max_retry_delay_ms: 30000,
+            max_send_attempts: 3,
+            idempotency_window_seconds: 600,
+            idempotency_cache_size: 10000,
             host: "0.0.0.0".to_string(),
             port: 8001,
         };
         
         let expected_url = "https://graph.facebook.com/v23.0/123456789/messages";
         assert_eq!(config.messages_url(), expected_url);
+        assert_eq!(config.media_url(), "https://graph.facebook.com/v23.0/123456789/media");
+        assert_eq!(config.media_info_url("1013859600285441"), "https://graph.facebook.com/v23.0/1013859600285441");
     }
     
     #[test]
@@ -162,15 +278,111 @@ mod tests {
             phone_number_id: "123456789".to_string(),
             rate_limit_per_minute: 800,
             rate_limit_burst: 50,
-            request_timeout_seconds: 30,
+            messages_per_second: 80,
+            rate_limit_fail_fast: false,
+            request_timeout_ms: 30000,
+            connect_timeout_ms: 10000,
             max_concurrent_requests: 20,
             max_retry_attempts: 3,
             initial_retry_delay_ms: 1000,
             max_retry_delay_ms: 30000,
+            max_send_attempts: 3,
+            idempotency_window_seconds: 600,
+            idempotency_cache_size: 10000,
             host: "0.0.0.0".to_string(),
             port: 8001,
         };
         
         assert_eq!(config.authorization_header(), "Bearer test_token_123");
     }
+
+    #[test]
+    fn test_parse_api_version() {
+        assert_eq!(parse_api_version("v23.0"), Some((23, 0)));
+        assert_eq!(parse_api_version("v19.0"), Some((19, 0)));
+        assert_eq!(parse_api_version("v2.12"), Some((2, 12)));
+
+        for invalid in ["23.0", "V23.0", "v23", "v23.", "v.0", "v23.0.1", "v23.x", "v+23.0", " v23.0", "v23.0 ", "latest"] {
+            assert_eq!(parse_api_version(invalid), None, "{:?} should be rejected", invalid);
+        }
+        assert!(parse_api_version("v18.0").unwrap() < MIN_API_VERSION);
+    }
+
+    /// Serializes tests that modify the process environment
+    static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    /// Set the required variables and clear the optional ones under test
+    fn set_required_env() {
+        unsafe {
+            std::env::set_var("WHATSAPP_ACCESS_TOKEN", "EAAG-test-token");
+            std::env::set_var("WHATSAPP_PHONE_NUMBER_ID", "106540352242922");
+            std::env::remove_var("WHATSAPP_API_VERSION");
+            std::env::remove_var("WHATSAPP_MESSAGES_PER_SECOND");
+        }
+    }
+
+    #[test]
+    fn test_from_env_with_complete_config() {
+        let _env = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        set_required_env();
+
+        let config = WhatsAppClientConfig::from_env().expect("Should load config");
+        assert_eq!(config.access_token, "EAAG-test-token");
+        assert_eq!(config.phone_number_id, "106540352242922");
+        assert_eq!(config.api_version, "v23.0");
+        assert_eq!(config.messages_per_second, 80);
+    }
+
+    #[test]
+    fn test_from_env_missing_token_names_variable() {
+        let _env = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        set_required_env();
+        unsafe { std::env::remove_var("WHATSAPP_ACCESS_TOKEN") };
+
+        match WhatsAppClientConfig::from_env() {
+            Err(WhatsAppError::ConfigurationError(message)) => {
+                assert!(message.contains("WHATSAPP_ACCESS_TOKEN"), "{}", message);
+            }
+            other => panic!("expected a configuration error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_from_env_rejects_blank_and_invalid_values() {
+        let _env = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        set_required_env();
+        unsafe { std::env::set_var("WHATSAPP_PHONE_NUMBER_ID", "  ") };
+        assert!(matches!(
+            WhatsAppClientConfig::from_env(),
+            Err(WhatsAppError::ConfigurationError(message)) if message.contains("WHATSAPP_PHONE_NUMBER_ID")
+        ));
+
+        set_required_env();
+        unsafe { std::env::set_var("WHATSAPP_API_VERSION", "") };
+        assert!(matches!(
+            WhatsAppClientConfig::from_env(),
+            Err(WhatsAppError::ConfigurationError(message)) if message.contains("WHATSAPP_API_VERSION")
+        ));
+
+        set_required_env();
+        unsafe { std::env::set_var("WHATSAPP_API_VERSION", "23.0") };
+        assert!(matches!(
+            WhatsAppClientConfig::from_env(),
+            Err(WhatsAppError::ConfigurationError(message)) if message.contains("WHATSAPP_API_VERSION")
+        ));
+
+        // Old but well-formed versions only warn
+        set_required_env();
+        unsafe { std::env::set_var("WHATSAPP_API_VERSION", "v17.0") };
+        assert_eq!(WhatsAppClientConfig::from_env().unwrap().api_version, "v17.0");
+
+        set_required_env();
+        unsafe { std::env::set_var("WHATSAPP_MESSAGES_PER_SECOND", "fast") };
+        assert!(matches!(
+            WhatsAppClientConfig::from_env(),
+            Err(WhatsAppError::ConfigurationError(message)) if message.contains("WHATSAPP_MESSAGES_PER_SECOND")
+        ));
+        unsafe { std::env::remove_var("WHATSAPP_MESSAGES_PER_SECOND") };
+    }
 }
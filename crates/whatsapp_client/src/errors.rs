@@ -1,3 +1,5 @@
+use chrono::{DateTime, Utc};
+use std::time::Duration;
 use thiserror::Error;
 
 /// Comprehensive error types for WhatsApp API client operations
@@ -19,15 +21,18 @@ pub enum WhatsAppError {
     },
     
     /// Rate limit exceeded - we hit WhatsApp's rate limits
-    #[error("Rate limit exceeded: {message}")]
-    RateLimitExceeded {
-        message: String,
-        retry_after_seconds: Option<u64>,
+    #[error("Rate limit exceeded{}", .retry_after.map(|d| format!(", retry after {}s", d.as_secs())).unwrap_or_default())]
+    RateLimited {
+        retry_after: Option<Duration>,
     },
     
-    /// Authentication failed - invalid access token
-    #[error("Authentication failed: {0}")]
-    AuthenticationError(String),
+    /// WhatsApp's servers failed to handle the request (HTTP 5xx)
+    #[error("WhatsApp API server error: HTTP {0}")]
+    ServerError(u16),
+    
+    /// Authentication failed - invalid or expired access token
+    #[error("Authentication failed: invalid or expired access token")]
+    AuthenticationFailed,
     
     /// Request serialization failed - our data couldn't be converted to JSON
     #[error("Failed to serialize request: {0}")]
@@ -41,13 +46,28 @@ pub enum WhatsAppError {
     #[error("Invalid message content: {0}")]
     InvalidMessageContent(String),
     
+    /// A specific field failed validation before anything was sent
+    /// 
+    /// `reason` is English; `code` identifies the failure for callers
+    /// that show their own, translated message.
+    #[error("{field}: {reason}")]
+    Validation { code: ValidationCode, field: String, reason: String },
+    
+    /// Media ID has expired or no longer exists on WhatsApp's servers
+    #[error("Media {media_id} has expired or is no longer available")]
+    MediaExpired { media_id: String },
+    
+    /// Free-form message refused because the 24-hour service window has closed
+    #[error("Customer service window closed: last inbound message was at {last_inbound}")]
+    OutsideServiceWindow { last_inbound: DateTime<Utc> },
+    
     /// Service configuration is invalid
     #[error("Configuration error: {0}")]
     ConfigurationError(String),
     
-    /// Operation timed out
-    #[error("Operation timed out after {seconds} seconds")]
-    TimeoutError { seconds: u64 },
+    /// Request to the WhatsApp API timed out (connecting or waiting for a response)
+    #[error("Request timed out after {timeout_ms}ms")]
+    Timeout { timeout_ms: u64 },
     
     /// Too many retry attempts exhausted
     #[error("Maximum retry attempts ({attempts}) exceeded for operation: {operation}")]
@@ -58,6 +78,173 @@ pub enum WhatsAppError {
     InternalError(String),
 }
 
+/// What went wrong in a `WhatsAppError::Validation`
+/// 
+/// The error's message is always English. Localized UIs should key their
+/// own text on this code instead; `as_str` gives a stable snake_case name
+/// for lookups in translation files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValidationCode {
+    /// Phone number isn't in E.164 format or can't be parsed
+    InvalidPhone,
+    /// Phone number is valid but belongs to no country
+    UnknownCountry,
+    /// Group recipient ID isn't a WhatsApp group ID
+    InvalidGroupId,
+    /// Message body is empty
+    BodyEmpty,
+    /// Message body exceeds 4096 characters
+    BodyTooLong,
+    /// Caption exceeds 1024 characters
+    CaptionTooLong,
+    /// Media type can't carry a caption (audio, stickers)
+    CaptionNotSupported,
+    /// Button or row ID is empty
+    ButtonIdEmpty,
+    /// Button or row ID exceeds 256 characters
+    ButtonIdTooLong,
+    /// Button or row ID has characters outside `A-Z a-z 0-9 _ - . :`
+    InvalidButtonId,
+    /// Two buttons, rows or products share an ID
+    DuplicateId,
+    /// Button or row title is empty
+    ButtonTitleEmpty,
+    /// Button or row title exceeds 20 characters
+    ButtonTitleTooLong,
+    /// Two buttons or rows share a title
+    DuplicateTitle,
+    /// List or product section title is empty
+    SectionTitleEmpty,
+    /// List or product section title exceeds 24 characters
+    SectionTitleTooLong,
+    /// Section has no rows or products
+    SectionEmpty,
+    /// List message has more than 10 rows
+    TooManyRows,
+    /// List row description exceeds 72 characters
+    RowDescriptionTooLong,
+    /// Product list has too few or too many sections
+    InvalidSectionCount,
+    /// Product list has more than 30 products
+    TooManyProducts,
+    /// URL is empty
+    UrlEmpty,
+    /// URL exceeds 2048 characters
+    UrlTooLong,
+    /// URL is malformed, has no host or isn't http(s)
+    InvalidUrl,
+    /// URL must use HTTPS
+    UrlNotHttps,
+    /// Latitude is outside -90..=90
+    LatitudeOutOfRange,
+    /// Longitude is outside -180..=180
+    LongitudeOutOfRange,
+    /// Media ID is empty
+    MediaIdEmpty,
+    /// Media ID isn't numeric
+    InvalidMediaId,
+    /// Media has both an ID and a link
+    MediaSourceConflict,
+    /// Media has neither an ID nor a link
+    MediaSourceMissing,
+    /// File exceeds the size limit for its media type
+    FileTooLarge,
+    /// MIME type isn't supported for the media type
+    UnsupportedMimeType,
+    /// Header exceeds 60 characters
+    HeaderTooLong,
+    /// Message type needs a header but none was set
+    HeaderRequired,
+    /// Header type isn't allowed on this kind of interactive message
+    HeaderNotSupported,
+    /// Footer exceeds 60 characters
+    FooterTooLong,
+    /// Flow ID is empty
+    FlowIdEmpty,
+    /// Flow token is empty
+    FlowTokenEmpty,
+    /// Flow CTA text is empty
+    FlowCtaEmpty,
+    /// Flow CTA text exceeds 30 characters
+    FlowCtaTooLong,
+    /// Navigate flow has no action payload
+    FlowPayloadRequired,
+    /// Flow action payload names no screen
+    FlowScreenEmpty,
+    /// Data exchange flow has an action payload
+    FlowPayloadNotAllowed,
+    /// Catalog ID is empty
+    CatalogIdEmpty,
+    /// Product retailer ID is empty
+    ProductIdEmpty,
+    /// Message ID is empty
+    MessageIdEmpty,
+    /// Response has no message to send
+    MessageMissing,
+    /// Time-to-live is zero
+    TtlZero,
+    /// Message type doesn't accept a time-to-live
+    TtlNotSupported,
+}
+
+impl ValidationCode {
+    /// Stable snake_case name, e.g. `"caption_too_long"`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ValidationCode::InvalidPhone => "invalid_phone",
+            ValidationCode::UnknownCountry => "unknown_country",
+            ValidationCode::InvalidGroupId => "invalid_group_id",
+            ValidationCode::BodyEmpty => "body_empty",
+            ValidationCode::BodyTooLong => "body_too_long",
+            ValidationCode::CaptionTooLong => "caption_too_long",
+            ValidationCode::CaptionNotSupported => "caption_not_supported",
+            ValidationCode::ButtonIdEmpty => "button_id_empty",
+            ValidationCode::ButtonIdTooLong => "button_id_too_long",
+            ValidationCode::InvalidButtonId => "invalid_button_id",
+            ValidationCode::DuplicateId => "duplicate_id",
+            ValidationCode::ButtonTitleEmpty => "button_title_empty",
+            ValidationCode::ButtonTitleTooLong => "button_title_too_long",
+            ValidationCode::DuplicateTitle => "duplicate_title",
+            ValidationCode::SectionTitleEmpty => "section_title_empty",
+            ValidationCode::SectionTitleTooLong => "section_title_too_long",
+            ValidationCode::SectionEmpty => "section_empty",
+            ValidationCode::TooManyRows => "too_many_rows",
+            ValidationCode::RowDescriptionTooLong => "row_description_too_long",
+            ValidationCode::InvalidSectionCount => "invalid_section_count",
+            ValidationCode::TooManyProducts => "too_many_products",
+            ValidationCode::UrlEmpty => "url_empty",
+            ValidationCode::UrlTooLong => "url_too_long",
+            ValidationCode::InvalidUrl => "invalid_url",
+            ValidationCode::UrlNotHttps => "url_not_https",
+            ValidationCode::LatitudeOutOfRange => "latitude_out_of_range",
+            ValidationCode::LongitudeOutOfRange => "longitude_out_of_range",
+            ValidationCode::MediaIdEmpty => "media_id_empty",
+            ValidationCode::InvalidMediaId => "invalid_media_id",
+            ValidationCode::MediaSourceConflict => "media_source_conflict",
+            ValidationCode::MediaSourceMissing => "media_source_missing",
+            ValidationCode::FileTooLarge => "file_too_large",
+            ValidationCode::UnsupportedMimeType => "unsupported_mime_type",
+            ValidationCode::HeaderTooLong => "header_too_long",
+            ValidationCode::HeaderRequired => "header_required",
+            ValidationCode::HeaderNotSupported => "header_not_supported",
+            ValidationCode::FooterTooLong => "footer_too_long",
+            ValidationCode::FlowIdEmpty => "flow_id_empty",
+            ValidationCode::FlowTokenEmpty => "flow_token_empty",
+            ValidationCode::FlowCtaEmpty => "flow_cta_empty",
+            ValidationCode::FlowCtaTooLong => "flow_cta_too_long",
+            ValidationCode::FlowPayloadRequired => "flow_payload_required",
+            ValidationCode::FlowScreenEmpty => "flow_screen_empty",
+            ValidationCode::FlowPayloadNotAllowed => "flow_payload_not_allowed",
+            ValidationCode::CatalogIdEmpty => "catalog_id_empty",
+            ValidationCode::ProductIdEmpty => "product_id_empty",
+            ValidationCode::MessageIdEmpty => "message_id_empty",
+            ValidationCode::MessageMissing => "message_missing",
+            ValidationCode::TtlZero => "ttl_zero",
+            ValidationCode::TtlNotSupported => "ttl_not_supported",
+        }
+    }
+}
+
 /// WhatsApp API error response structure
 ///
 /// This matches the error format that WhatsApp's Business API returns.
@@ -95,29 +282,27 @@ impl WhatsAppError {
         // Check for specific error types that need special handling
         match api_error.code {
             // Authentication errors (4xx range)
-            190 | 401 => WhatsAppError::AuthenticationError(api_error.message),
+            190 | 401 => WhatsAppError::AuthenticationFailed,
             
-            // Rate limiting errors
-            429 | 80007 => {
+            // Rate limiting errors (app, account and pair-level throughput)
+            4 | 429 | 80007 | 130429 | 131056 => {
                 // Try to extract retry-after from error_data if available
                 let retry_after = api_error.error_data
                     .as_ref()
                     .and_then(|data| data.get("retry_after"))
-                    .and_then(|val| val.as_u64());
+                    .and_then(|val| val.as_u64())
+                    .map(Duration::from_secs);
                 
-                WhatsAppError::RateLimitExceeded {
-                    message: api_error.message,
-                    retry_after_seconds: retry_after,
-                }
+                WhatsAppError::RateLimited { retry_after }
             },
             
             // Invalid phone number errors
-            131051 | 131052 | 131053 => {
+            131051..=131053 => {
                 WhatsAppError::InvalidPhoneNumber(api_error.message)
             },
             
             // Invalid message content errors
-            131047 | 131048 | 131049 => {
+            131047..=131049 => {
                 WhatsAppError::InvalidMessageContent(api_error.message)
             },
             
@@ -143,17 +328,21 @@ impl WhatsAppError {
                     || reqwest_error.is_connect()
                     || reqwest_error.is_request()
             },
-            WhatsAppError::RateLimitExceeded { .. } => true,
-            WhatsAppError::TimeoutError { .. } => true,
+            WhatsAppError::RateLimited { .. } => true,
+            WhatsAppError::ServerError(_) => true,
+            WhatsAppError::Timeout { .. } => true,
             WhatsAppError::ApiError { code, .. } => {
                 // Only retry server errors (5xx), not client errors (4xx)
                 *code >= 500 && *code < 600
             },
             
             // These errors are permanent and should not be retried
-            WhatsAppError::AuthenticationError(_) => false,
+            WhatsAppError::AuthenticationFailed => false,
             WhatsAppError::InvalidPhoneNumber(_) => false,
             WhatsAppError::InvalidMessageContent(_) => false,
+            WhatsAppError::Validation { .. } => false,
+            WhatsAppError::MediaExpired { .. } => false,
+            WhatsAppError::OutsideServiceWindow { .. } => false,
             WhatsAppError::ConfigurationError(_) => false,
             WhatsAppError::SerializationError(_) => false,
             WhatsAppError::MaxRetriesExceeded { .. } => false,
@@ -161,18 +350,44 @@ impl WhatsAppError {
         }
     }
     
+    /// Create a validation error for `field`
+    pub fn validation(code: ValidationCode, field: impl Into<String>, reason: impl Into<String>) -> Self {
+        WhatsAppError::Validation {
+            code,
+            field: field.into(),
+            reason: reason.into(),
+        }
+    }
+    
+    /// Name of the field that failed validation, if this is a validation error
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            WhatsAppError::Validation { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+    
+    /// Machine-readable code of a validation error, for translating it
+    pub fn validation_code(&self) -> Option<ValidationCode> {
+        match self {
+            WhatsAppError::Validation { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+    
     /// Get the suggested delay before retrying (in seconds)
     /// 
     /// This implements intelligent retry delays based on the error type.
     /// Rate limit errors get longer delays, while network errors get shorter ones.
     pub fn retry_delay_seconds(&self) -> Option<u64> {
         match self {
-            WhatsAppError::RateLimitExceeded { retry_after_seconds, .. } => {
+            WhatsAppError::RateLimited { retry_after } => {
                 // Use WhatsApp's suggested delay, or default to 60 seconds
-                Some(retry_after_seconds.unwrap_or(60))
+                Some(retry_after.map(|d| d.as_secs()).unwrap_or(60))
             },
+            WhatsAppError::ServerError(_) => Some(30),
             WhatsAppError::HttpError(_) => Some(5), // Quick retry for network issues
-            WhatsAppError::TimeoutError { .. } => Some(10),
+            WhatsAppError::Timeout { .. } => Some(10),
             WhatsAppError::ApiError { code, .. } => {
                 if *code >= 500 { Some(30) } else { None } // Only retry server errors
             },
@@ -193,9 +408,8 @@ mod tests {
     
     #[test]
     fn test_rate_limit_error_is_retryable() {
-        let error = WhatsAppError::RateLimitExceeded {
-            message: "Rate limit hit".to_string(),
-            retry_after_seconds: Some(60),
+        let error = WhatsAppError::RateLimited {
+            retry_after: Some(Duration::from_secs(60)),
         };
         
         assert!(error.is_retryable());
@@ -204,7 +418,50 @@ mod tests {
     
     #[test]
     fn test_auth_error_not_retryable() {
-        let error = WhatsAppError::AuthenticationError("Invalid token".to_string());
+        let error = WhatsAppError::AuthenticationFailed;
+        
+        assert!(!error.is_retryable());
+        assert_eq!(error.retry_delay_seconds(), None);
+    }
+    
+    #[test]
+    fn test_server_error_is_retryable() {
+        let error = WhatsAppError::ServerError(503);
+        
+        assert!(error.is_retryable());
+        assert_eq!(error.retry_delay_seconds(), Some(30));
+    }
+    
+    #[test]
+    fn test_invalid_content_not_retryable() {
+        let error = WhatsAppError::InvalidMessageContent("Message cannot be empty".to_string());
+        
+        assert!(!error.is_retryable());
+    }
+    
+    #[test]
+    fn test_api_response_maps_to_typed_variants() {
+        let parse = |body: &str| {
+            let response: WhatsAppApiErrorResponse = serde_json::from_str(body).unwrap();
+            WhatsAppError::from_api_response(response)
+        };
+        
+        let error = parse(r#"{"error": {"message": "Invalid OAuth access token.", "type": "OAuthException", "code": 190, "fbtrace_id": "A1"}}"#);
+        assert!(matches!(error, WhatsAppError::AuthenticationFailed));
+        
+        let error = parse(r#"{"error": {"message": "Rate limit hit", "type": "OAuthException", "code": 130429, "error_data": {"retry_after": 12}}}"#);
+        assert!(matches!(error, WhatsAppError::RateLimited { retry_after: Some(d) } if d == Duration::from_secs(12)));
+        
+        let error = parse(r#"{"error": {"message": "Too many calls", "type": "OAuthException", "code": 80007}}"#);
+        assert!(matches!(error, WhatsAppError::RateLimited { retry_after: None }));
+        
+        let error = parse(r#"{"error": {"message": "Parameter value is not valid", "type": "OAuthException", "code": 131047}}"#);
+        assert!(matches!(error, WhatsAppError::InvalidMessageContent(_)));
+    }
+    
+    #[test]
+    fn test_media_expired_not_retryable() {
+        let error = WhatsAppError::MediaExpired { media_id: "1013859600285441".to_string() };
         
         assert!(!error.is_retryable());
         assert_eq!(error.retry_delay_seconds(), None);
@@ -1,21 +1,17 @@
 use common::{
-    KafkaEventBus, KafkaConfig, EventBus, 
+    KafkaEventBus, KafkaConfig, EventBus, NoopMetrics,
     SubscriptionConfig, ProcessingResult, EventEnvelope,
 };
 use whatsapp_client::{
     client::{
-        core::WhatsAppClient,
-        message_types::{
-            WhatsAppMessageSend, 
-            WhatsAppMessage,
-            Message,
-        },
+        core::{SendOptions, WhatsAppClient},
+        message_types::{ResponsePriority, WhatsAppMessageSend},
     },
     config::WhatsAppClientConfig,
-    errors::WhatsAppResult,
+    errors::{WhatsAppError, WhatsAppResult},
 };
 use std::sync::Arc;
-use tracing::{info, error};
+use tracing::{info, warn, error};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -27,7 +23,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("📱 Starting WhatsApp message sender service...");
 
     // Initialize WhatsApp client
-    let whatsapp_config = WhatsAppClientConfig::from_env();
+    let whatsapp_config = WhatsAppClientConfig::from_env()?;
     let whatsapp_client = Arc::new(WhatsAppClient::new(whatsapp_config)
         .map_err(|e| format!("Failed to create WhatsApp client: {}", e))?);
 
@@ -37,7 +33,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let kafka_config = KafkaConfig::from_env()
         .map_err(|e| format!("Failed to load Kafka config: {}", e))?;
     
-    let event_bus = Arc::new(KafkaEventBus::new(kafka_config).await
+    let event_bus = Arc::new(KafkaEventBus::new(kafka_config, Arc::new(NoopMetrics)).await
         .map_err(|e| format!("Failed to create event bus: {}", e))?);
 
     // Test connection
@@ -46,49 +42,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     info!("✅ Connected to Kafka successfully");
 
-    // Subscribe to WhatsApp message send events
-    let config = SubscriptionConfig {
-        consumer_group: "whatsapp-sender".to_string(),
-        ..Default::default()
-    };
-
-    let client_clone = whatsapp_client.clone();
-    event_bus.subscribe::<WhatsAppMessageSend, _>(
-        config,
-        move |envelope: EventEnvelope<WhatsAppMessageSend>| {
-            let client = client_clone.clone();
-            let message_send = &envelope.data;
-            
-            info!("📨 Processing WhatsApp message send event (original: {})", 
-                  message_send.original_message_id);
-            
-            // Handle the message sending in a blocking context
-            let result = tokio::task::block_in_place(|| {
-                let rt = tokio::runtime::Handle::current();
-                rt.block_on(async {
-                    process_whatsapp_message_send(client, message_send).await
-                })
-            });
-            
-            match result {
-                Ok(response) => {
-                    info!("✅ Message sent successfully. WhatsApp ID: {}", 
-                          response.messages.first().map(|m| &m.id).unwrap_or(&"unknown".to_string()));
-                    Ok(ProcessingResult::Success)
-                }
-                Err(e) => {
-                    error!("❌ Failed to send WhatsApp message: {}", e);
-                    if e.is_retryable() {
-                        Ok(ProcessingResult::RetryableError(e.to_string()))
-                    } else {
-                        Ok(ProcessingResult::PermanentError(e.to_string()))
-                    }
-                }
-            }
-        }
-    ).await?;
+    // Subscribe to each priority's topic, urgent first, so urgent responses
+    // have their own consumer and never wait behind low-priority ones
+    for priority in ResponsePriority::ALL {
+        let suffix = priority.topic_suffix();
+        let config = SubscriptionConfig::builder()
+            .consumer_group(format!("whatsapp-sender-{}", suffix))
+            .topic_suffix(suffix)
+            .build();
+        let client = whatsapp_client.clone();
+        event_bus.subscribe_async(config, move |envelope| handle_send_event(client.clone(), envelope)).await?;
+        info!("🎯 Subscribed to conversation.responses.{} topic", suffix);
+    }
 
+    // Keep draining the unrouted topic for events from older producers
+    let config = SubscriptionConfig::builder()
+        .consumer_group("whatsapp-sender")
+        .build();
+    let client = whatsapp_client.clone();
+    event_bus.subscribe_async(config, move |envelope| handle_send_event(client.clone(), envelope)).await?;
     info!("🎯 Subscribed to conversation.responses topic");
+
     info!("📞 Waiting for WhatsApp message send events...");
     info!("🛑 Press Ctrl+C to stop");
 
@@ -99,44 +73,121 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Handle a single `WhatsAppMessageSend` event
+async fn handle_send_event(
+    client: Arc<WhatsAppClient>,
+    envelope: EventEnvelope<WhatsAppMessageSend>,
+) -> Result<ProcessingResult, Box<dyn std::error::Error + Send + Sync>> {
+    let message_send = &envelope.data;
+    
+    info!("📨 Processing WhatsApp message send event (original: {})", 
+          message_send.original_message_id);
+    
+    match process_whatsapp_message_send(client.clone(), message_send).await {
+        Ok(response) => {
+            info!("✅ Message sent successfully. WhatsApp ID: {}", 
+                  response.messages.first().map(|m| &m.id).unwrap_or(&"unknown".to_string()));
+            Ok(ProcessingResult::Success)
+        }
+        Err(e) => {
+            error!("❌ Failed to send WhatsApp message: {}", e);
+            let max_send_attempts = client.config().max_send_attempts;
+            Ok(send_failure_result(&e, envelope.attempt_count, max_send_attempts))
+        }
+    }
+}
+
+/// Decide whether a failed send is worth another delivery
+/// 
+/// `attempt_count` is the envelope's, so 0 on the first delivery. Once
+/// `max_send_attempts` deliveries have failed, even a retryable error is
+/// reported as permanent: an error that keeps coming back is rarely
+/// transient, and every retry spends quota. 0 never gives up early, leaving
+/// it to the bus's own retry limit.
+fn send_failure_result(error: &WhatsAppError, attempt_count: u32, max_send_attempts: u32) -> ProcessingResult {
+    if !error.is_retryable() {
+        return ProcessingResult::PermanentError(error.to_string());
+    }
+
+    let deliveries = attempt_count.saturating_add(1);
+    if max_send_attempts > 0 && deliveries >= max_send_attempts {
+        warn!("🛑 Giving up after {} failed sends: {}", deliveries, error);
+        return ProcessingResult::PermanentError(format!(
+            "{} (gave up after {} attempts)",
+            error, deliveries
+        ));
+    }
+
+    ProcessingResult::RetryableError(error.to_string())
+}
+
 async fn process_whatsapp_message_send(
     client: Arc<WhatsAppClient>,
     message_send: &WhatsAppMessageSend,
 ) -> WhatsAppResult<whatsapp_client::client::responses::WhatsAppMessageResponse> {
-    let recipient = get_recipient_from_message(&message_send.message);
-    
     info!("🚀 Sending {} message to {} (priority: {:?})",
-          get_message_type_name(&message_send.message),
-          recipient,
+          message_send.message.message_type_name(),
+          message_send.message.recipient(),
           message_send.priority);
 
+    // Refuse a TTL the API can't honour rather than sending it without one
+    message_send.validate_ttl()?;
+
     // Send the message using the WhatsApp client
     // The message is already in the correct format for the WhatsApp API
-    client.send_message(message_send.message.clone()).await
+    let options = SendOptions {
+        context_message_id: message_send.context_message_id.clone(),
+        idempotency_key: Some(message_send.dedup_key().to_string()),
+        last_inbound_at: message_send.last_inbound_at,
+    };
+    client.send_message_with(message_send.message.clone(), &options).await
 }
 
-fn get_recipient_from_message(message: &WhatsAppMessage) -> &str {
-    match message {
-        WhatsAppMessage::Text(msg) => msg.recipient(),
-        WhatsAppMessage::Audio(msg) => msg.recipient(),
-        WhatsAppMessage::Contact(msg) => msg.recipient(),
-        WhatsAppMessage::Document(msg) => msg.recipient(),
-        WhatsAppMessage::Image(msg) => msg.recipient(),
-        WhatsAppMessage::Interactive(msg) => msg.recipient(),
-        WhatsAppMessage::Location(msg) => msg.recipient(),
-        WhatsAppMessage::Video(msg) => msg.recipient(),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transient() -> WhatsAppError {
+        WhatsAppError::ServerError(503)
+    }
+
+    #[test]
+    fn test_transient_failure_retried_below_threshold() {
+        for attempt_count in [0, 1] {
+            let result = send_failure_result(&transient(), attempt_count, 3);
+            assert!(matches!(result, ProcessingResult::RetryableError(_)), "attempt {}: {:?}", attempt_count, result);
+        }
     }
-}
 
-fn get_message_type_name(message: &WhatsAppMessage) -> &'static str {
-    match message {
-        WhatsAppMessage::Text(_) => "text",
-        WhatsAppMessage::Audio(_) => "audio",
-        WhatsAppMessage::Contact(_) => "contact",
-        WhatsAppMessage::Document(_) => "document",
-        WhatsAppMessage::Image(_) => "image",
-        WhatsAppMessage::Interactive(_) => "interactive",
-        WhatsAppMessage::Location(_) => "location",
-        WhatsAppMessage::Video(_) => "video",
+    #[test]
+    fn test_transient_failure_downgraded_at_threshold() {
+        // The third delivery is the last one, and so is anything after it
+        for attempt_count in [2, 3, 10] {
+            match send_failure_result(&transient(), attempt_count, 3) {
+                ProcessingResult::PermanentError(reason) => {
+                    assert!(reason.contains("HTTP 503"), "{}", reason);
+                    assert!(reason.contains(&format!("after {} attempts", attempt_count + 1)), "{}", reason);
+                }
+                other => panic!("attempt {} should be permanent, got {:?}", attempt_count, other),
+            }
+        }
+
+        let rate_limited = WhatsAppError::RateLimited { retry_after: Some(std::time::Duration::from_secs(1)) };
+        assert!(matches!(send_failure_result(&rate_limited, 0, 1), ProcessingResult::PermanentError(_)));
+    }
+
+    #[test]
+    fn test_zero_threshold_never_downgrades() {
+        let result = send_failure_result(&transient(), u32::MAX, 0);
+        assert!(matches!(result, ProcessingResult::RetryableError(_)), "{:?}", result);
+    }
+
+    #[test]
+    fn test_permanent_failure_stays_permanent() {
+        let error = WhatsAppError::AuthenticationFailed;
+        for attempt_count in [0, 5] {
+            let result = send_failure_result(&error, attempt_count, 3);
+            assert!(matches!(result, ProcessingResult::PermanentError(_)), "{:?}", result);
+        }
     }
 }